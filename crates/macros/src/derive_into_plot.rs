@@ -18,7 +18,7 @@ pub fn derive_into_plot(input: TokenStream) -> TokenStream {
 
         impl #impl_generics gpui::Element for #type_name #type_generics #where_clause {
             type RequestLayoutState = ();
-            type PrepaintState = ();
+            type PrepaintState = (gpui::Hitbox, Option<gpui::AnyElement>);
 
             fn id(&self) -> Option<gpui::ElementId> {
                 None
@@ -47,11 +47,13 @@ pub fn derive_into_plot(input: TokenStream) -> TokenStream {
                 &mut self,
                 _: Option<&gpui::GlobalElementId>,
                 _: Option<&gpui::InspectorElementId>,
-                _: gpui::Bounds<gpui::Pixels>,
+                bounds: gpui::Bounds<gpui::Pixels>,
                 _: &mut Self::RequestLayoutState,
-                _: &mut gpui::Window,
-                _: &mut gpui::App,
+                window: &mut gpui::Window,
+                cx: &mut gpui::App,
             ) -> Self::PrepaintState {
+                let hitbox = window.insert_hitbox(bounds, gpui::HitboxBehavior::Normal);
+                (hitbox, <Self as gpui_component::plot::Plot>::prepaint(self, bounds, window, cx))
             }
 
             fn paint(
@@ -60,11 +62,15 @@ pub fn derive_into_plot(input: TokenStream) -> TokenStream {
                 _: Option<&gpui::InspectorElementId>,
                 bounds: gpui::Bounds<gpui::Pixels>,
                 _: &mut Self::RequestLayoutState,
-                _: &mut Self::PrepaintState,
+                (hitbox, overlay): &mut Self::PrepaintState,
                 window: &mut gpui::Window,
                 cx: &mut gpui::App,
             ) {
-                <Self as Plot>::paint(self, bounds, window, cx)
+                let mut canvas = gpui_component::plot::Canvas::Window(window, Some(&*hitbox));
+                <Self as gpui_component::plot::Plot>::paint_canvas(self, bounds, &mut canvas, cx);
+                if let Some(overlay) = overlay {
+                    overlay.paint(window, cx);
+                }
             }
         }
     };
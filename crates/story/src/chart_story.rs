@@ -1,13 +1,21 @@
+use chrono::{DateTime, Duration, TimeZone as _, Utc};
 use gpui::{
     div, linear_color_stop, linear_gradient, prelude::FluentBuilder, px, App, AppContext, Context,
     Entity, FocusHandle, Focusable, Hsla, IntoElement, ParentElement, Render, SharedString, Styled,
     Window,
 };
 use gpui_component::{
-    chart::{AreaChart, BarChart, LineChart, PieChart},
+    chart::{
+        AreaChart, BarChart, BulletChart, CandlestickChart, ChartState, FunnelChart, GaugeChart,
+        HeatmapChart, HistogramChart, LineChart, PieChart, RadarChart, ScatterChart, Series,
+        Sparkline,
+    },
     divider::Divider,
     dock::PanelControl,
-    h_flex, v_flex, ActiveTheme, StyledExt,
+    h_flex,
+    list::ListItem,
+    plot::NumberFormat,
+    v_flex, ActiveTheme, StyledExt,
 };
 use serde::Deserialize;
 
@@ -25,10 +33,51 @@ struct DailyDevice {
     pub mobile: f64,
 }
 
+#[derive(Clone)]
+struct Latency {
+    pub time: DateTime<Utc>,
+    pub p50: f64,
+    pub p99: f64,
+}
+
+#[derive(Clone)]
+struct Candle {
+    pub time: DateTime<Utc>,
+    pub open: f64,
+    pub high: f64,
+    pub low: f64,
+    pub close: f64,
+}
+
+#[derive(Clone)]
+struct Activity {
+    pub week: SharedString,
+    pub weekday: SharedString,
+    pub count: f64,
+}
+
 pub struct ChartStory {
     focus_handle: FocusHandle,
     daily_devices: Vec<DailyDevice>,
     monthly_devices: Vec<MonthlyDevice>,
+    latencies: Vec<Latency>,
+    stacked_area_state: ChartState,
+    grouped_bar_state: ChartState,
+    stacked_bar_state: ChartState,
+    latency_state: ChartState,
+    pie_state: ChartState,
+    selected_month: Option<SharedString>,
+    time_state: ChartState,
+    log_state: ChartState,
+    brushed_time: Option<(DateTime<Utc>, DateTime<Utc>)>,
+    candles: Vec<Candle>,
+    activities: Vec<Activity>,
+    scatter_state: ChartState,
+    heatmap_state: ChartState,
+    candle_state: ChartState,
+    histogram_state: ChartState,
+    radar_state: ChartState,
+    funnel_state: ChartState,
 }
 
 impl ChartStory {
@@ -41,9 +90,70 @@ impl ChartStory {
         ))
         .unwrap();
 
+        // The latencies every 10 minutes in a day, range in several decades.
+        let start = Utc.with_ymd_and_hms(2025, 6, 1, 0, 0, 0).unwrap();
+        let latencies = (0..144)
+            .map(|i| {
+                let wave = (i as f64 / 12.).sin();
+                Latency {
+                    time: start + Duration::minutes(i * 10),
+                    p50: 10f64.powf(1. + wave * 0.5),
+                    p99: 10f64.powf(2.5 + wave + (i % 7) as f64 * 0.1),
+                }
+            })
+            .collect();
+
+        // The daily prices of 60 days, a random walk from 100.
+        let mut close = 100.;
+        let candles = (0..60)
+            .map(|i| {
+                let open = close;
+                close = open + (i as f64 * 1.7).sin() * 4. + (i as f64 * 0.3).cos() * 2.;
+                Candle {
+                    time: start + Duration::days(i),
+                    open,
+                    high: open.max(close) + (i % 5) as f64 * 0.8 + 0.5,
+                    low: open.min(close) - (i % 3) as f64 * 0.9 - 0.5,
+                    close,
+                }
+            })
+            .collect();
+
+        // The activities of every day in 16 weeks.
+        let weekdays = ["Mon", "Tue", "Wed", "Thu", "Fri", "Sat", "Sun"];
+        let activities = (0..16 * 7)
+            .map(|i| {
+                let (week, day) = (i / 7, i % 7);
+                let weekend = if day >= 5 { 0.3 } else { 1. };
+                Activity {
+                    week: format!("W{}", week + 1).into(),
+                    weekday: weekdays[day].into(),
+                    count: ((i as f64 * 0.7).sin().abs() * 10. * weekend).round(),
+                }
+            })
+            .collect();
+
         Self {
             daily_devices,
             monthly_devices,
+            latencies,
+            stacked_area_state: ChartState::default(),
+            grouped_bar_state: ChartState::default(),
+            stacked_bar_state: ChartState::default(),
+            latency_state: ChartState::default(),
+            pie_state: ChartState::default(),
+            selected_month: None,
+            time_state: ChartState::default(),
+            log_state: ChartState::default(),
+            brushed_time: None,
+            candles,
+            activities,
+            scatter_state: ChartState::default(),
+            heatmap_state: ChartState::default(),
+            candle_state: ChartState::default(),
+            histogram_state: ChartState::default(),
+            radar_state: ChartState::default(),
+            funnel_state: ChartState::default(),
             focus_handle: cx.focus_handle(),
         }
     }
@@ -120,8 +230,40 @@ fn chart_container(
         )
 }
 
+/// The tooltip of the charts, a title with the rows of the name and the value.
+fn chart_tooltip(
+    title: impl Into<SharedString>,
+    rows: Vec<(&'static str, String)>,
+    cx: &App,
+) -> impl IntoElement {
+    v_flex()
+        .gap_1()
+        .text_xs()
+        .child(div().font_semibold().child(title.into()))
+        .children(rows.into_iter().map(|(name, value)| {
+            h_flex()
+                .justify_between()
+                .child(div().text_color(cx.theme().muted_foreground).child(name))
+                .child(value)
+        }))
+}
+
 impl Render for ChartStory {
     fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let view = cx.entity().downgrade();
+        let pie_title = match self.selected_month.as_ref() {
+            Some(month) => format!("Pie Chart - Clicked {}", month),
+            None => "Pie Chart - Click a slice".to_string(),
+        };
+        let log_title = match self.brushed_time {
+            Some((start, end)) => format!(
+                "Area Chart - Brushed {} - {}",
+                start.format("%H:%M"),
+                end.format("%H:%M")
+            ),
+            None => "Area Chart - Log Scale, drag to brush".to_string(),
+        };
+
         v_flex()
             .size_full()
             .gap_y_4()
@@ -131,20 +273,33 @@ impl Render for ChartStory {
                     "Area Chart - Stacked",
                     AreaChart::new(self.daily_devices.clone())
                         .x(|d| d.date.clone())
-                        .y(|d| d.desktop)
+                        .series(Series::new("Desktop", |d: &DailyDevice| d.desktop))
                         .stroke(cx.theme().chart_1)
                         .fill(linear_gradient(
                             0.,
                             linear_color_stop(cx.theme().chart_1.opacity(0.4), 1.),
                             linear_color_stop(cx.theme().background.opacity(0.3), 0.),
                         ))
-                        .y(|d| d.mobile)
+                        .series(Series::new("Mobile", |d: &DailyDevice| d.mobile))
                         .stroke(cx.theme().chart_2)
                         .fill(linear_gradient(
                             0.,
                             linear_color_stop(cx.theme().chart_2.opacity(0.4), 1.),
                             linear_color_stop(cx.theme().background.opacity(0.3), 0.),
                         ))
+                        .stacked()
+                        .legend()
+                        .state(&self.stacked_area_state)
+                        .tooltip(|d, _, cx| {
+                            chart_tooltip(
+                                d.date.clone(),
+                                vec![
+                                    ("Desktop", d.desktop.to_string()),
+                                    ("Mobile", d.mobile.to_string()),
+                                ],
+                                cx,
+                            )
+                        })
                         .tick_margin(8),
                     false,
                     cx,
@@ -155,11 +310,30 @@ impl Render for ChartStory {
                     .gap_x_8()
                     .h(px(450.))
                     .child(chart_container(
-                        "Pie Chart",
+                        &pie_title,
                         PieChart::new(self.monthly_devices.clone())
                             .value(|d| d.desktop)
                             .outer_radius(100.)
-                            .color(|d| d.color),
+                            .color(|d| d.color)
+                            .key(|d| d.month.clone())
+                            .animate()
+                            .state(&self.pie_state)
+                            .tooltip(|d, _, cx| {
+                                chart_tooltip(
+                                    d.month.clone(),
+                                    vec![("Desktop", d.desktop.to_string())],
+                                    cx,
+                                )
+                            })
+                            .on_click({
+                                let view = view.clone();
+                                move |d, _, cx| {
+                                    _ = view.update(cx, |this, cx| {
+                                        this.selected_month = Some(d.month.clone());
+                                        cx.notify();
+                                    });
+                                }
+                            }),
                         true,
                         cx,
                     ))
@@ -218,6 +392,70 @@ impl Render for ChartStory {
                     )),
             )
             .child(Divider::horizontal())
+            .child(
+                h_flex()
+                    .gap_x_8()
+                    .h(px(400.))
+                    .child(chart_container(
+                        "Bar Chart - Grouped",
+                        BarChart::new(self.daily_devices.iter().take(8).cloned())
+                            .x(|d| d.date.clone())
+                            .series(Series::new("Desktop", |d: &DailyDevice| d.desktop))
+                            .series(Series::new("Mobile", |d: &DailyDevice| d.mobile))
+                            .legend()
+                            .animate()
+                            .state(&self.grouped_bar_state)
+                            .tooltip(|d, _, cx| {
+                                chart_tooltip(
+                                    d.date.clone(),
+                                    vec![
+                                        ("Desktop", d.desktop.to_string()),
+                                        ("Mobile", d.mobile.to_string()),
+                                    ],
+                                    cx,
+                                )
+                            }),
+                        false,
+                        cx,
+                    ))
+                    .child(chart_container(
+                        "Bar Chart - Stacked",
+                        BarChart::new(self.daily_devices.iter().take(8).cloned())
+                            .x(|d| d.date.clone())
+                            .series(Series::new("Desktop", |d: &DailyDevice| d.desktop))
+                            .series(Series::new("Mobile", |d: &DailyDevice| d.mobile))
+                            .stacked()
+                            .legend()
+                            .state(&self.stacked_bar_state),
+                        false,
+                        cx,
+                    ))
+                    .child(chart_container(
+                        "Line Chart - Secondary Axis",
+                        LineChart::new(self.latencies.clone())
+                            .x(|d| d.time)
+                            .series(Series::new("p50", |d: &Latency| d.p50))
+                            .series(Series::new("p99", |d: &Latency| d.p99).secondary())
+                            .linear()
+                            .y_format(NumberFormat::custom(|v| format!("{}ms", v).into()))
+                            .y2_format(NumberFormat::Si)
+                            .legend()
+                            .state(&self.latency_state)
+                            .tooltip(|d, _, cx| {
+                                chart_tooltip(
+                                    d.time.format("%H:%M").to_string(),
+                                    vec![
+                                        ("p50", format!("{:.1}ms", d.p50)),
+                                        ("p99", format!("{:.1}ms", d.p99)),
+                                    ],
+                                    cx,
+                                )
+                            }),
+                        false,
+                        cx,
+                    )),
+            )
+            .child(Divider::horizontal())
             .child(
                 h_flex()
                     .gap_x_8()
@@ -250,6 +488,55 @@ impl Render for ChartStory {
                     )),
             )
             .child(Divider::horizontal())
+            .child(
+                h_flex()
+                    .gap_x_8()
+                    .h(px(400.))
+                    .child(chart_container(
+                        "Line Chart - Time Scale, ctrl + scroll to zoom",
+                        LineChart::new(self.latencies.clone())
+                            .x(|d| d.time)
+                            .y(|d| d.p50)
+                            .linear()
+                            .state(&self.time_state)
+                            .zoomable()
+                            .overview(),
+                        false,
+                        cx,
+                    ))
+                    .child(chart_container(
+                        &log_title,
+                        AreaChart::new(self.latencies.clone())
+                            .x(|d| d.time)
+                            .y(|d| d.p99)
+                            .y(|d| d.p50)
+                            .stroke(cx.theme().chart_1)
+                            .fill(cx.theme().chart_1.opacity(0.2))
+                            .stroke(cx.theme().chart_2)
+                            .fill(cx.theme().chart_2.opacity(0.4))
+                            .log_scale()
+                            .y_format(NumberFormat::custom(|v| format!("{}ms", v).into()))
+                            .state(&self.log_state)
+                            .on_brush(move |range, _, cx| {
+                                _ = view.update(cx, |this, cx| {
+                                    this.brushed_time = Some((*range.start(), *range.end()));
+                                    cx.notify();
+                                });
+                            }),
+                        false,
+                        cx,
+                    ))
+                    .child(chart_container(
+                        "Bar Chart - Currency Format",
+                        BarChart::new(self.monthly_devices.clone())
+                            .x(|d| d.month.clone())
+                            .y(|d| d.desktop * 1000.)
+                            .y_format(NumberFormat::currency("$")),
+                        false,
+                        cx,
+                    )),
+            )
+            .child(Divider::horizontal())
             .child(
                 h_flex()
                     .gap_x_8()
@@ -285,5 +572,189 @@ impl Render for ChartStory {
                         cx,
                     )),
             )
+            .child(Divider::horizontal())
+            .child(
+                h_flex()
+                    .gap_x_8()
+                    .h(px(400.))
+                    .child(chart_container(
+                        "Scatter Chart - Bubble",
+                        ScatterChart::new(self.daily_devices.clone())
+                            .x(|d| d.desktop)
+                            .y(|d| d.mobile)
+                            .size(|d| d.desktop + d.mobile)
+                            .state(&self.scatter_state)
+                            .tooltip(|d, _, cx| {
+                                chart_tooltip(
+                                    d.date.clone(),
+                                    vec![
+                                        ("Desktop", d.desktop.to_string()),
+                                        ("Mobile", d.mobile.to_string()),
+                                    ],
+                                    cx,
+                                )
+                            }),
+                        false,
+                        cx,
+                    ))
+                    .child(chart_container(
+                        "Heatmap Chart - Calendar",
+                        HeatmapChart::new(self.activities.clone())
+                            .x(|d| d.week.clone())
+                            .y(|d| d.weekday.clone())
+                            .value(|d| d.count)
+                            .tick_margin(4)
+                            .state(&self.heatmap_state)
+                            .tooltip(|d, _, cx| {
+                                chart_tooltip(
+                                    format!("{} {}", d.week, d.weekday),
+                                    vec![("Count", d.count.to_string())],
+                                    cx,
+                                )
+                            }),
+                        false,
+                        cx,
+                    )),
+            )
+            .child(Divider::horizontal())
+            .child(
+                h_flex()
+                    .gap_x_8()
+                    .h(px(400.))
+                    .child(chart_container(
+                        "Candlestick Chart",
+                        CandlestickChart::new(self.candles.clone())
+                            .x(|d| d.time)
+                            .open(|d| d.open)
+                            .high(|d| d.high)
+                            .low(|d| d.low)
+                            .close(|d| d.close)
+                            .y_format(NumberFormat::currency("$"))
+                            .state(&self.candle_state)
+                            .tooltip(|d, _, cx| {
+                                chart_tooltip(
+                                    d.time.format("%b %d").to_string(),
+                                    vec![
+                                        ("Open", format!("{:.2}", d.open)),
+                                        ("High", format!("{:.2}", d.high)),
+                                        ("Low", format!("{:.2}", d.low)),
+                                        ("Close", format!("{:.2}", d.close)),
+                                    ],
+                                    cx,
+                                )
+                            }),
+                        false,
+                        cx,
+                    ))
+                    .child(chart_container(
+                        "Histogram Chart - p99 Latency",
+                        HistogramChart::new(self.latencies.clone())
+                            .value(|d| d.p99)
+                            .x_format(NumberFormat::custom(|v| format!("{}ms", v).into()))
+                            .state(&self.histogram_state)
+                            .tooltip(|bin, _, cx| {
+                                chart_tooltip(
+                                    format!("{}ms - {}ms", bin.x0, bin.x1),
+                                    vec![("Count", bin.len().to_string())],
+                                    cx,
+                                )
+                            }),
+                        false,
+                        cx,
+                    )),
+            )
+            .child(Divider::horizontal())
+            .child(
+                h_flex()
+                    .gap_x_8()
+                    .h(px(400.))
+                    .child(chart_container(
+                        "Gauge Chart",
+                        GaugeChart::new(72.)
+                            .band(50., cx.theme().success)
+                            .band(80., cx.theme().warning)
+                            .band(100., cx.theme().danger)
+                            .label("CPU Usage")
+                            .format(NumberFormat::custom(|v| format!("{}%", v).into())),
+                        true,
+                        cx,
+                    ))
+                    .child(chart_container(
+                        "Radar Chart",
+                        RadarChart::new(self.daily_devices.iter().take(6).cloned())
+                            .axis(|d| d.date.clone())
+                            .series(Series::new("Desktop", |d: &DailyDevice| d.desktop))
+                            .series(Series::new("Mobile", |d: &DailyDevice| d.mobile))
+                            .legend()
+                            .state(&self.radar_state),
+                        true,
+                        cx,
+                    ))
+                    .child(chart_container(
+                        "Funnel Chart",
+                        FunnelChart::new([
+                            ("Visits", 5200.),
+                            ("Sign ups", 2100.),
+                            ("Trials", 1200.),
+                            ("Purchases", 430.),
+                        ])
+                        .label(|d| d.0)
+                        .value(|d| d.1)
+                        .state(&self.funnel_state)
+                        .tooltip(|d, _, cx| {
+                            chart_tooltip(d.0, vec![("Count", d.1.to_string())], cx)
+                        }),
+                        true,
+                        cx,
+                    )),
+            )
+            .child(Divider::horizontal())
+            .child(
+                v_flex()
+                    .gap_y_1()
+                    .child(div().font_semibold().child("Sparklines"))
+                    .child(
+                        ListItem::new("sparkline-close")
+                            .child(div().w(px(120.)).child("Close"))
+                            .child(
+                                Sparkline::new(self.candles.iter().map(|d| d.close))
+                                    .width(px(160.))
+                                    .min_marker()
+                                    .max_marker()
+                                    .last_marker(),
+                            ),
+                    )
+                    .child(
+                        ListItem::new("sparkline-desktop")
+                            .child(div().w(px(120.)).child("Desktop"))
+                            .child(
+                                Sparkline::new(
+                                    self.daily_devices.iter().take(30).map(|d| d.desktop),
+                                )
+                                .width(px(160.))
+                                .bar()
+                                .max_marker(),
+                            ),
+                    )
+                    .child(
+                        ListItem::new("sparkline-change")
+                            .child(div().w(px(120.)).child("Daily change"))
+                            .child(
+                                Sparkline::new(self.candles.iter().map(|d| d.close - d.open))
+                                    .width(px(160.))
+                                    .win_loss(),
+                            ),
+                    )
+                    .child(
+                        ListItem::new("sparkline-bullet")
+                            .child(div().w(px(120.)).child("Revenue"))
+                            .child(
+                                BulletChart::new(270.)
+                                    .target(250.)
+                                    .ranges([150., 225., 300.])
+                                    .width(px(160.)),
+                            ),
+                    ),
+            )
     }
 }
@@ -18,6 +18,7 @@ mod input_story;
 mod kbd_story;
 mod label_story;
 mod list_story;
+mod measured_list_story;
 mod menu_story;
 mod modal_story;
 mod notification_story;
@@ -38,6 +39,7 @@ mod textarea_story;
 mod title_bar;
 mod toggle_story;
 mod tooltip_story;
+mod tree_story;
 mod webview_story;
 mod welcome_story;
 
@@ -69,6 +71,7 @@ pub use input_story::InputStory;
 pub use kbd_story::KbdStory;
 pub use label_story::LabelStory;
 pub use list_story::ListStory;
+pub use measured_list_story::MeasuredListStory;
 pub use menu_story::MenuStory;
 pub use modal_story::ModalStory;
 pub use notification_story::NotificationStory;
@@ -91,6 +94,7 @@ pub use title_bar::AppTitleBar;
 pub use toggle_story::ToggleStory;
 pub use tooltip_story::TooltipStory;
 use tracing_subscriber::{layer::SubscriberExt as _, util::SubscriberInitExt as _};
+pub use tree_story::TreeStory;
 pub use webview_story::WebViewStory;
 pub use welcome_story::WelcomeStory;
 
@@ -299,6 +299,9 @@ impl ListStory {
                     ListEvent::Cancel => {
                         println!("List Cancelled");
                     }
+                    ListEvent::SelectionChange(indices) => {
+                        println!("List Selection Changed: {:?}", indices);
+                    }
                 }),
             ];
 
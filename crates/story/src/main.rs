@@ -56,6 +56,7 @@ impl Gallery {
                     StoryContainer::panel::<KbdStory>(window, cx),
                     StoryContainer::panel::<LabelStory>(window, cx),
                     StoryContainer::panel::<ListStory>(window, cx),
+                    StoryContainer::panel::<MeasuredListStory>(window, cx),
                     StoryContainer::panel::<MenuStory>(window, cx),
                     StoryContainer::panel::<ModalStory>(window, cx),
                     StoryContainer::panel::<NotificationStory>(window, cx),
@@ -74,6 +75,7 @@ impl Gallery {
                     StoryContainer::panel::<TagStory>(window, cx),
                     StoryContainer::panel::<TextareaStory>(window, cx),
                     StoryContainer::panel::<TooltipStory>(window, cx),
+                    StoryContainer::panel::<TreeStory>(window, cx),
                 ],
             ),
         ];
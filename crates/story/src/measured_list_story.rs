@@ -0,0 +1,209 @@
+use gpui::{
+    div, App, AppContext, Context, Entity, Focusable, IntoElement, ParentElement, Render,
+    SharedString, Styled, Window,
+};
+
+use gpui_component::{
+    button::{Button, ButtonVariants as _},
+    checkbox::Checkbox,
+    h_flex,
+    scroll::{Scrollbar, ScrollbarState},
+    v_flex, v_measured_list, ActiveTheme as _, MeasuredListState,
+};
+
+use crate::section;
+
+const WORDS: &[&str] = &[
+    "Lorem",
+    "ipsum",
+    "dolor",
+    "sit",
+    "amet",
+    "consectetur",
+    "adipiscing",
+    "elit",
+    "sed",
+    "do",
+    "eiusmod",
+    "tempor",
+    "incididunt",
+    "ut",
+    "labore",
+    "et",
+    "dolore",
+    "magna",
+    "aliqua",
+];
+
+fn message(id: isize) -> SharedString {
+    // Make the messages have different lengths.
+    let ix = id.unsigned_abs();
+    let len = 3 + (ix * 7919) % 60;
+    (0..len)
+        .map(|i| WORDS[(ix + i * 31) % WORDS.len()])
+        .collect::<Vec<_>>()
+        .join(" ")
+        .into()
+}
+
+pub struct MeasuredListStory {
+    focus_handle: gpui::FocusHandle,
+    messages: Vec<(isize, SharedString)>,
+    next_id: isize,
+    first_id: isize,
+    list_state: MeasuredListState,
+    scroll_state: ScrollbarState,
+}
+
+impl super::Story for MeasuredListStory {
+    fn title() -> &'static str {
+        "MeasuredList"
+    }
+
+    fn description() -> &'static str {
+        "A virtual list with variable height items, keeps the scroll position when items are prepended, and can follow the tail."
+    }
+
+    fn new_view(window: &mut Window, cx: &mut App) -> Entity<impl Render + Focusable> {
+        Self::view(window, cx)
+    }
+}
+
+impl MeasuredListStory {
+    pub(crate) fn new(_: &mut Window, cx: &mut Context<Self>) -> Self {
+        let messages = (0..10_000).map(|id| (id, message(id))).collect::<Vec<_>>();
+        let list_state = MeasuredListState::new(messages.len()).follow_tail(true);
+
+        Self {
+            focus_handle: cx.focus_handle(),
+            next_id: messages.len() as isize,
+            first_id: 0,
+            messages,
+            list_state,
+            scroll_state: ScrollbarState::default(),
+        }
+    }
+
+    pub fn view(window: &mut Window, cx: &mut App) -> Entity<Self> {
+        cx.new(|cx| Self::new(window, cx))
+    }
+
+    fn append(&mut self, count: usize, cx: &mut Context<Self>) {
+        let start = self.messages.len();
+        for _ in 0..count {
+            self.messages.push((self.next_id, message(self.next_id)));
+            self.next_id += 1;
+        }
+        self.list_state.splice(start..start, count);
+        cx.notify();
+    }
+
+    fn prepend(&mut self, count: usize, cx: &mut Context<Self>) {
+        let first_id = self.first_id - count as isize;
+        self.messages
+            .splice(0..0, (first_id..self.first_id).map(|id| (id, message(id))));
+        self.first_id = first_id;
+        self.list_state.splice(0..0, count);
+        cx.notify();
+    }
+}
+
+impl Focusable for MeasuredListStory {
+    fn focus_handle(&self, _: &gpui::App) -> gpui::FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl Render for MeasuredListStory {
+    fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let view = cx.entity();
+        let follow_tail = self.list_state.is_following_tail();
+
+        v_flex()
+            .size_full()
+            .gap_6()
+            .child(
+                h_flex()
+                    .gap_3()
+                    .child(
+                        Button::new("append")
+                            .primary()
+                            .label("Append 5")
+                            .on_click(cx.listener(|this, _, _, cx| this.append(5, cx))),
+                    )
+                    .child(
+                        Button::new("prepend")
+                            .label("Prepend 20")
+                            .on_click(cx.listener(|this, _, _, cx| this.prepend(20, cx))),
+                    )
+                    .child(
+                        Button::new("scroll-to-bottom")
+                            .label("Scroll to Bottom")
+                            .on_click(cx.listener(|this, _, _, cx| {
+                                this.list_state.scroll_to_bottom();
+                                cx.notify();
+                            })),
+                    )
+                    .child(
+                        Checkbox::new("follow-tail")
+                            .label("Follow Tail")
+                            .checked(follow_tail)
+                            .on_click(cx.listener(|this, checked: &bool, _, cx| {
+                                this.list_state.set_follow_tail(*checked);
+                                if *checked {
+                                    this.list_state.scroll_to_bottom();
+                                }
+                                cx.notify();
+                            })),
+                    ),
+            )
+            .child(
+                section(format!("{} messages", self.messages.len())).child(
+                    div()
+                        .relative()
+                        .w_full()
+                        .h_96()
+                        .border_1()
+                        .border_color(cx.theme().border)
+                        .rounded(cx.theme().radius)
+                        .child(v_measured_list(
+                            view,
+                            "messages",
+                            &self.list_state,
+                            |this, range, _, cx| {
+                                range
+                                    .map(|ix| {
+                                        let (id, text) = &this.messages[ix];
+                                        v_flex()
+                                            .px_3()
+                                            .py_2()
+                                            .gap_1()
+                                            .border_b_1()
+                                            .border_color(cx.theme().border)
+                                            .child(
+                                                div()
+                                                    .text_xs()
+                                                    .text_color(cx.theme().muted_foreground)
+                                                    .child(format!("#{}", id)),
+                                            )
+                                            .child(div().text_sm().child(text.clone()))
+                                    })
+                                    .collect()
+                            },
+                        ))
+                        .child(
+                            div()
+                                .absolute()
+                                .top_0()
+                                .left_0()
+                                .right_0()
+                                .bottom_0()
+                                .child(Scrollbar::vertical(
+                                    &self.scroll_state,
+                                    &self.list_state.scroll_handle(),
+                                )),
+                        ),
+                ),
+            )
+    }
+}
@@ -0,0 +1,250 @@
+use std::{collections::HashMap, time::Duration};
+
+use gpui::{
+    div, prelude::FluentBuilder as _, App, AppContext, Context, Entity, Focusable, IntoElement,
+    ParentElement, Render, SharedString, Styled, Subscription, Task, Timer, Window,
+};
+
+use gpui_component::{
+    h_flex,
+    tree::{DropPosition, Tree, TreeDelegate, TreeEntry, TreeEvent},
+    v_flex, ActiveTheme as _,
+};
+
+use crate::section;
+
+const ROOTS_COUNT: usize = 100;
+const CHILDREN_COUNT: usize = 1000;
+
+/// A file tree, the folders are loaded lazily when expanded.
+struct FileTreeDelegate {
+    labels: HashMap<SharedString, SharedString>,
+    children: HashMap<Option<SharedString>, Vec<SharedString>>,
+}
+
+impl FileTreeDelegate {
+    fn new() -> Self {
+        let mut this = Self {
+            labels: HashMap::new(),
+            children: HashMap::new(),
+        };
+        let roots = (0..ROOTS_COUNT)
+            .map(|ix| this.add_node(format!("{}", ix).into(), format!("Folder {}", ix)))
+            .collect();
+        this.children.insert(None, roots);
+        this
+    }
+
+    fn add_node(&mut self, id: SharedString, label: impl Into<SharedString>) -> SharedString {
+        self.labels.insert(id.clone(), label.into());
+        id
+    }
+
+    fn is_folder(id: &SharedString) -> bool {
+        // Only the first two levels are folders.
+        id.split('/').count() < 3
+    }
+
+    fn parent_of(&self, id: &SharedString) -> Option<SharedString> {
+        self.children
+            .iter()
+            .find(|(_, children)| children.contains(id))
+            .and_then(|(parent, _)| parent.clone())
+    }
+}
+
+impl TreeDelegate for FileTreeDelegate {
+    type Id = SharedString;
+    type Item = gpui::Div;
+
+    fn children(&self, parent: Option<&Self::Id>, _: &App) -> Option<Vec<Self::Id>> {
+        self.children.get(&parent.cloned()).cloned()
+    }
+
+    fn has_children(&self, id: &Self::Id, _: &App) -> bool {
+        Self::is_folder(id)
+    }
+
+    fn load_children(
+        &mut self,
+        id: &Self::Id,
+        _: &mut Window,
+        cx: &mut Context<Tree<Self>>,
+    ) -> Task<()> {
+        let id = id.clone();
+        cx.spawn(async move |this, cx| {
+            // Simulate a slow loading.
+            Timer::after(Duration::from_millis(300)).await;
+
+            _ = this.update(cx, |this, _| {
+                let delegate = this.delegate_mut();
+                let is_folder = id.split('/').count() < 2;
+                let count = if is_folder { 10 } else { CHILDREN_COUNT };
+                let children = (0..count)
+                    .map(|ix| {
+                        let label = if is_folder {
+                            format!("Folder {}", ix)
+                        } else {
+                            format!("file_{}.rs", ix)
+                        };
+                        delegate.add_node(format!("{}/{}", id, ix).into(), label)
+                    })
+                    .collect();
+                delegate.children.insert(Some(id), children);
+            });
+        })
+    }
+
+    fn label(&self, id: &Self::Id, _: &App) -> SharedString {
+        self.labels.get(id).cloned().unwrap_or_default()
+    }
+
+    fn render_item(
+        &self,
+        entry: &TreeEntry<Self::Id>,
+        _: bool,
+        _: &mut Window,
+        cx: &mut Context<Tree<Self>>,
+    ) -> Option<Self::Item> {
+        Some(
+            h_flex()
+                .gap_2()
+                .py_0p5()
+                .text_sm()
+                .child(self.label(&entry.id, cx))
+                .when(entry.has_children, |this| {
+                    this.child(
+                        div()
+                            .text_xs()
+                            .text_color(cx.theme().muted_foreground)
+                            .child(entry.id.clone()),
+                    )
+                }),
+        )
+    }
+
+    fn can_rename(&self, _: &Self::Id, _: &App) -> bool {
+        true
+    }
+
+    fn rename(
+        &mut self,
+        id: &Self::Id,
+        label: SharedString,
+        _: &mut Window,
+        _: &mut Context<Tree<Self>>,
+    ) {
+        self.labels.insert(id.clone(), label);
+    }
+
+    fn can_drag(&self, _: &Self::Id, _: &App) -> bool {
+        true
+    }
+
+    fn can_drop(&self, _: &[Self::Id], target: &Self::Id, position: DropPosition, _: &App) -> bool {
+        position != DropPosition::Inside || self.children.contains_key(&Some(target.clone()))
+    }
+
+    fn on_drop(
+        &mut self,
+        ids: Vec<Self::Id>,
+        target: &Self::Id,
+        position: DropPosition,
+        _: &mut Window,
+        _: &mut Context<Tree<Self>>,
+    ) {
+        for children in self.children.values_mut() {
+            children.retain(|id| !ids.contains(id));
+        }
+
+        let parent = match position {
+            DropPosition::Inside => Some(target.clone()),
+            _ => self.parent_of(target),
+        };
+        let Some(siblings) = self.children.get_mut(&parent) else {
+            return;
+        };
+        let ix = match position {
+            DropPosition::Inside => siblings.len(),
+            DropPosition::Before => siblings.iter().position(|id| id == target).unwrap_or(0),
+            DropPosition::After => siblings
+                .iter()
+                .position(|id| id == target)
+                .map_or(siblings.len(), |ix| ix + 1),
+        };
+        siblings.splice(ix..ix, ids);
+    }
+}
+
+pub struct TreeStory {
+    focus_handle: gpui::FocusHandle,
+    tree: Entity<Tree<FileTreeDelegate>>,
+    selected_count: usize,
+    _subscriptions: Vec<Subscription>,
+}
+
+impl super::Story for TreeStory {
+    fn title() -> &'static str {
+        "Tree"
+    }
+
+    fn description() -> &'static str {
+        "A virtualized tree view with lazy loading, multi-select, drag and drop and inline rename."
+    }
+
+    fn new_view(window: &mut Window, cx: &mut App) -> Entity<impl Render + Focusable> {
+        Self::view(window, cx)
+    }
+}
+
+impl TreeStory {
+    pub(crate) fn new(window: &mut Window, cx: &mut Context<Self>) -> Self {
+        let tree =
+            cx.new(|cx| Tree::new(FileTreeDelegate::new(), window, cx).multi_selectable(true));
+
+        let _subscriptions = vec![cx.subscribe(
+            &tree,
+            |this, _, ev: &TreeEvent<SharedString>, cx| match ev {
+                TreeEvent::Select(ids) => {
+                    this.selected_count = ids.len();
+                    cx.notify();
+                }
+                TreeEvent::Confirm(id) => println!("Tree Confirmed: {}", id),
+                _ => {}
+            },
+        )];
+
+        Self {
+            focus_handle: cx.focus_handle(),
+            tree,
+            selected_count: 0,
+            _subscriptions,
+        }
+    }
+
+    pub fn view(window: &mut Window, cx: &mut App) -> Entity<Self> {
+        cx.new(|cx| Self::new(window, cx))
+    }
+}
+
+impl Focusable for TreeStory {
+    fn focus_handle(&self, _: &gpui::App) -> gpui::FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl Render for TreeStory {
+    fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        v_flex().size_full().gap_6().child(
+            section(format!("File Tree ({} selected)", self.selected_count)).child(
+                v_flex()
+                    .w_full()
+                    .h_96()
+                    .border_1()
+                    .border_color(cx.theme().border)
+                    .rounded(cx.theme().radius)
+                    .child(self.tree.clone()),
+            ),
+        )
+    }
+}
@@ -8,4 +8,14 @@ pub struct Confirm {
     pub secondary: bool,
 }
 
-actions!(list, [Cancel, SelectPrev, SelectNext]);
+actions!(
+    list,
+    [
+        Cancel,
+        SelectPrev,
+        SelectNext,
+        SelectToPrev,
+        SelectToNext,
+        SelectAll
+    ]
+);
@@ -1,37 +1,58 @@
-use std::rc::Rc;
+use std::{ops::RangeInclusive, rc::Rc};
 
-use gpui::{px, App, Background, Bounds, Hsla, Pixels, SharedString, TextAlign, Window};
+use gpui::{px, AnyElement, App, Bounds, Hsla, IntoElement, Pixels, Window};
 use gpui_component_macros::IntoPlot;
 use num_traits::{Num, ToPrimitive};
 
 use crate::{
     plot::{
-        scale::{Scale, ScaleLinear, ScalePoint, Sealed},
-        shape::Area,
-        Axis, AxisText, Grid, Plot, StrokeStyle, AXIS_GAP,
+        scale::{Scale, Sealed, XAxisValue},
+        shape::{Area, Stack},
+        Axis, Canvas, Grid, NumberFormat, Paint, Plot, StrokeStyle, AXIS_GAP,
     },
     ActiveTheme,
 };
 
+use super::{
+    brush_fn,
+    hover::{self, ClickFn, TooltipFn},
+    paint_legend, paint_overview, paint_y2_axis,
+    series::series_color,
+    series_domain, split_overview, visible_range, visible_series, x_axis_texts, y_axis_ticks,
+    y_scale,
+    zoom::{self, BrushHandler},
+    ChartState, Series, YAxis,
+};
+
 #[derive(IntoPlot)]
 pub struct AreaChart<T, X, Y>
 where
     T: 'static,
-    X: Clone + PartialEq + Into<SharedString> + 'static,
+    X: XAxisValue,
     Y: Clone + Copy + PartialOrd + Num + ToPrimitive + Sealed + 'static,
 {
-    data: Vec<T>,
+    data: Rc<[T]>,
     x: Option<Rc<dyn Fn(&T) -> X>>,
-    y: Vec<Rc<dyn Fn(&T) -> Y>>,
+    series: Vec<Series<T, Y>>,
     stroke: Vec<Hsla>,
     stroke_style: StrokeStyle,
-    fill: Vec<Background>,
+    fill: Vec<Paint>,
     tick_margin: usize,
+    stacked: bool,
+    y_axis: YAxis,
+    y2_axis: YAxis,
+    legend: bool,
+    state: Option<ChartState>,
+    tooltip: Option<TooltipFn<T>>,
+    on_click: Option<ClickFn<T>>,
+    zoomable: bool,
+    overview: bool,
+    on_brush: Option<BrushHandler<X>>,
 }
 
 impl<T, X, Y> AreaChart<T, X, Y>
 where
-    X: Clone + PartialEq + Into<SharedString> + 'static,
+    X: XAxisValue,
     Y: Clone + Copy + PartialOrd + Num + ToPrimitive + Sealed + 'static,
 {
     pub fn new<I>(data: I) -> Self
@@ -44,8 +65,18 @@ where
             stroke: vec![],
             fill: vec![],
             tick_margin: 1,
+            stacked: false,
+            y_axis: YAxis::default(),
+            y2_axis: YAxis::default(),
+            legend: false,
+            state: None,
+            tooltip: None,
+            on_click: None,
+            zoomable: false,
+            overview: false,
+            on_brush: None,
             x: None,
-            y: vec![],
+            series: vec![],
         }
     }
 
@@ -54,17 +85,26 @@ where
         self
     }
 
+    /// Add a series without name.
     pub fn y(mut self, y: impl Fn(&T) -> Y + 'static) -> Self {
-        self.y.push(Rc::new(y));
+        self.series.push(Series::new("", y));
+        self
+    }
+
+    /// Add a series, each series is drawn as an area.
+    pub fn series(mut self, series: Series<T, Y>) -> Self {
+        self.series.push(series);
         self
     }
 
+    /// Add the stroke color of the next series, default is the color of the series.
     pub fn stroke(mut self, stroke: impl Into<Hsla>) -> Self {
         self.stroke.push(stroke.into());
         self
     }
 
-    pub fn fill(mut self, fill: impl Into<Background>) -> Self {
+    /// Add the fill of the next series, default is the color of the series with opacity.
+    pub fn fill(mut self, fill: impl Into<Paint>) -> Self {
         self.fill.push(fill.into());
         self
     }
@@ -78,90 +118,372 @@ where
         self.tick_margin = tick_margin;
         self
     }
+
+    /// Stack the areas of the series on the primary y-axis, in the order of the series.
+    pub fn stacked(mut self) -> Self {
+        self.stacked = true;
+        self
+    }
+
+    /// Use the log scale for the y-axis, the non-positive values are not drawn.
+    pub fn log_scale(mut self) -> Self {
+        self.y_axis.log_scale = true;
+        self
+    }
+
+    /// Set the min value of the y-axis, default is the nice value below 0 or the min of the data.
+    pub fn y_min(mut self, min: Y) -> Self {
+        self.y_axis.min = min.to_f64();
+        self
+    }
+
+    /// Set the max value of the y-axis, default is the nice value above the max of the data.
+    pub fn y_max(mut self, max: Y) -> Self {
+        self.y_axis.max = max.to_f64();
+        self
+    }
+
+    /// Set the format of the y-axis labels, e.g.: `NumberFormat::Percent`.
+    pub fn y_format(mut self, format: NumberFormat) -> Self {
+        self.y_axis.format = format;
+        self
+    }
+
+    /// Set the format of the secondary y-axis labels, see [`Series::secondary`].
+    pub fn y2_format(mut self, format: NumberFormat) -> Self {
+        self.y2_axis.format = format;
+        self
+    }
+
+    /// Show the legend of the series at the top.
+    pub fn legend(mut self) -> Self {
+        self.legend = true;
+        self
+    }
+
+    /// Set the state of the chart, to toggle the series by clicking the legend and to hover the data.
+    pub fn state(mut self, state: &ChartState) -> Self {
+        self.state = Some(state.clone());
+        self
+    }
+
+    /// Set the tooltip of the hovered data item, the hover is tracked by the [`ChartState`].
+    pub fn tooltip<E>(mut self, tooltip: impl Fn(&T, &mut Window, &mut App) -> E + 'static) -> Self
+    where
+        E: IntoElement,
+    {
+        self.tooltip = Some(Rc::new(move |d, window, cx| {
+            tooltip(d, window, cx).into_any_element()
+        }));
+        self
+    }
+
+    /// Set the handler of clicking a data item, e.g.: To drill down into the item.
+    pub fn on_click(mut self, handler: impl Fn(&T, &mut Window, &mut App) + 'static) -> Self {
+        self.on_click = Some(Rc::new(handler));
+        self
+    }
+
+    /// Zoom the x-axis by the mouse wheel with `ctrl` (`cmd` on macOS) and pan by dragging,
+    /// double click to reset the zoom.
+    ///
+    /// Drag with `shift` to zoom in the brushed range, the zoom is kept in the [`ChartState`].
+    pub fn zoomable(mut self) -> Self {
+        self.zoomable = true;
+        self
+    }
+
+    /// Show the overview of the full range at the bottom, drag the window in it to pan.
+    pub fn overview(mut self) -> Self {
+        self.overview = true;
+        self
+    }
+
+    /// Set the handler of the brush with the range of the x values, the data is sorted by x.
+    ///
+    /// Drag to brush, or drag with `shift` if the chart is [`zoomable`](Self::zoomable).
+    pub fn on_brush(
+        mut self,
+        handler: impl Fn(RangeInclusive<X>, &mut Window, &mut App) + 'static,
+    ) -> Self {
+        self.on_brush = Some(Rc::new(handler));
+        self
+    }
 }
 
 impl<T, X, Y> Plot for AreaChart<T, X, Y>
 where
-    X: Clone + PartialEq + Into<SharedString> + 'static,
+    X: XAxisValue,
     Y: Clone + Copy + PartialOrd + Num + ToPrimitive + Sealed + 'static,
 {
+    fn prepaint(
+        &mut self,
+        bounds: Bounds<Pixels>,
+        window: &mut Window,
+        cx: &mut App,
+    ) -> Option<AnyElement> {
+        hover::prepaint_tooltip(
+            &self.data,
+            self.tooltip.as_ref(),
+            self.state.as_ref(),
+            bounds,
+            window,
+            cx,
+        )
+    }
+
     fn paint(&mut self, bounds: Bounds<Pixels>, window: &mut Window, cx: &mut App) {
+        self.paint_canvas(bounds, &mut Canvas::Window(window, None), cx);
+    }
+
+    fn paint_canvas(&mut self, bounds: Bounds<Pixels>, canvas: &mut Canvas, cx: &mut App) {
         let Some(x_fn) = self.x.as_ref() else {
             return;
         };
 
-        if self.y.len() == 0 {
+        if self.series.is_empty() {
             return;
         }
 
+        let colors = self
+            .series
+            .iter()
+            .enumerate()
+            .map(|(ix, series)| {
+                self.stroke
+                    .get(ix)
+                    .copied()
+                    .or(series.color)
+                    .unwrap_or_else(|| series_color(ix, cx))
+            })
+            .collect::<Vec<_>>();
+        let state = self.state.as_ref();
+        let bounds = paint_legend(
+            &self.series,
+            &colors,
+            self.legend,
+            state,
+            bounds,
+            canvas,
+            cx,
+        );
+        let (bounds, overview) = if self.overview {
+            let (bounds, overview) = split_overview(bounds);
+            (bounds, Some(overview))
+        } else {
+            (bounds, None)
+        };
+        let visible = visible_series(&self.series, state);
+
         let width = bounds.size.width.to_f64();
         let height = bounds.size.height.to_f64() - AXIS_GAP;
 
-        // X scale
-        let x = ScalePoint::new(self.data.iter().map(|v| x_fn(v)).collect(), vec![0., width]);
+        // X scale, the full range is scaled to show the zoomed window in the width.
+        let x_window = state.map_or((0., 1.), |state| state.x_window());
+        let x_range = zoom::x_range(x_window, width);
+        let full_width = x_range[1] - x_range[0];
+        let x = X::scale(self.data.iter().map(|v| x_fn(v)).collect(), x_range);
+        let x_ticks = Rc::new(
+            self.data
+                .iter()
+                .map(|d| x.tick(&x_fn(d)))
+                .collect::<Vec<_>>(),
+        );
+        let range = visible_range(&x_ticks, width);
+        let data = &self.data[range.clone()];
+
+        // Stack the visible series on the primary y-axis.
+        let stacked = if self.stacked {
+            visible
+                .iter()
+                .filter(|ix| !self.series[**ix].secondary)
+                .fold(Stack::new(), |stack, ix| {
+                    stack.key(self.series[*ix].value_fn())
+                })
+                .series(&self.data)
+        } else {
+            vec![]
+        };
 
-        // Y scale
-        let domain = self
-            .data
+        // Y scale of the data in the window, the linear scale starts from 0 and ends at a nice value.
+        let domain = if self.stacked {
+            stacked
+                .iter()
+                .flat_map(|s| s.points[range.clone()].iter().flat_map(|p| [p.y0, p.y1]))
+                .collect()
+        } else {
+            series_domain(data, &self.series, &visible, false)
+        };
+        let y = y_scale(domain, height, &self.y_axis);
+        let y2 = visible
             .iter()
-            .flat_map(|v| self.y.iter().map(|y_fn| y_fn(v)))
-            .chain(Some(Y::zero()))
-            .collect::<Vec<_>>();
-        let y = ScaleLinear::new(domain, vec![10., height]);
+            .any(|ix| self.series[*ix].secondary)
+            .then(|| {
+                y_scale(
+                    series_domain(data, &self.series, &visible, true),
+                    height,
+                    &self.y2_axis,
+                )
+            });
 
         // Draw X axis
-        let data_len = self.data.len();
-        let x_label = self.data.iter().enumerate().filter_map(|(i, d)| {
-            if (i + 1) % self.tick_margin == 0 {
-                x.tick(&x_fn(d)).map(|x_tick| {
-                    let align = match i {
-                        0 => TextAlign::Left,
-                        i if i == data_len - 1 => TextAlign::Right,
-                        _ => TextAlign::Center,
-                    };
-                    AxisText::new(x_fn(d).into(), x_tick, cx.theme().muted_foreground).align(align)
-                })
-            } else {
-                None
-            }
-        });
+        let color = cx.theme().muted_foreground;
+        let x_label = x_axis_texts(
+            &self.data,
+            x_fn,
+            &x,
+            self.tick_margin,
+            width,
+            full_width,
+            color,
+        );
+        let (grid_y, y_label) = y_axis_ticks(&y, height, color);
 
         Axis::new()
             .x(height)
             .x_label(x_label)
+            .y(px(0.))
+            .y_label(y_label)
             .stroke(cx.theme().border)
-            .paint(&bounds, window, cx);
+            .paint(&bounds, canvas, cx);
+
+        if let Some(y2) = y2.as_ref() {
+            paint_y2_axis(y2, &bounds, height, canvas, cx);
+        }
 
         // Draw grid
         Grid::new()
-            .y((0..=3).map(|i| height * i as f64 / 4.0).collect())
+            .y(grid_y)
             .stroke(cx.theme().border)
             .dash_array(&[px(4.), px(2.)])
-            .paint(&bounds, window);
-
-        // Draw area
-        for (i, y_fn) in self.y.iter().enumerate() {
-            let x = x.clone();
-            let y = y.clone();
-            let x_fn = x_fn.clone();
-            let y_fn = y_fn.clone();
+            .paint(&bounds, canvas);
 
-            let fill = *self
+        let stroke_fill = |ix: usize| {
+            let stroke = colors[ix];
+            let fill = self
                 .fill
-                .get(i)
-                .unwrap_or(&cx.theme().chart_2.opacity(0.4).into());
-
-            let stroke = *self.stroke.get(i).unwrap_or(&cx.theme().chart_2);
-
-            Area::new()
-                .data(&self.data)
-                .x(move |d| x.tick(&x_fn(d)))
-                .y0(height)
-                .y1(move |d| y.tick(&y_fn(d)))
-                .stroke(stroke)
-                .stroke_style(self.stroke_style)
-                .fill(fill)
-                .paint(&bounds, window);
+                .get(ix)
+                .copied()
+                .unwrap_or_else(|| stroke.opacity(0.4).into());
+            (stroke, fill)
+        };
+        let stacked_keys = visible
+            .iter()
+            .copied()
+            .filter(|ix| self.stacked && !self.series[*ix].secondary)
+            .collect::<Vec<_>>();
+
+        // The areas are clipped in the bounds when zoomed in.
+        canvas.with_content_mask(bounds, |canvas| {
+            // Draw the crosshair of the hovered data.
+            let hovered = state
+                .and_then(|state| state.hovered_index())
+                .and_then(|ix| Some((ix, x_ticks.get(ix).copied().flatten()?)));
+            if let Some((_, x)) = hovered {
+                hover::paint_crosshair(x, height, &bounds, canvas, cx);
+            }
+
+            // Draw area
+            for (stack, ix) in stacked.iter().zip(stacked_keys.iter().copied()) {
+                let (stroke, fill) = stroke_fill(ix);
+                let x_ticks = x_ticks.clone();
+                let y0 = y.clone();
+                let y1 = y.clone();
+
+                Area::new()
+                    .data(
+                        stack.points[range.clone()]
+                            .iter()
+                            .map(|p| (p.index, p.y0, p.y1)),
+                    )
+                    .x(move |(i, _, _)| x_ticks[*i])
+                    .y0_fn(move |(_, v, _)| y0.tick(v))
+                    .y1(move |(_, _, v)| y1.tick(v))
+                    .stroke(stroke)
+                    .stroke_style(self.stroke_style)
+                    .fill(fill)
+                    .paint(&bounds, canvas);
+            }
+
+            for ix in visible.iter().copied() {
+                let series = &self.series[ix];
+                let y = match (series.secondary, y2.as_ref()) {
+                    (true, Some(y2)) => y2.clone(),
+                    (false, _) if self.stacked => continue,
+                    _ => y.clone(),
+                };
+                let (stroke, fill) = stroke_fill(ix);
+                let x_ticks = x_ticks.clone();
+                let value = series.value_fn();
+
+                Area::new()
+                    .data(
+                        self.data
+                            .iter()
+                            .enumerate()
+                            .skip(range.start)
+                            .take(range.len()),
+                    )
+                    .x(move |(i, _)| x_ticks[*i])
+                    .y0(height)
+                    .y1(move |(_, d)| y.tick(&value(d)?))
+                    .stroke(stroke)
+                    .stroke_style(self.stroke_style)
+                    .fill(fill)
+                    .paint(&bounds, canvas);
+            }
+
+            // Highlight the hovered points of the areas, at the top of the stacked areas.
+            if let Some((hovered, hovered_x)) = hovered {
+                for ix in visible.iter().copied() {
+                    let series = &self.series[ix];
+                    let value = match stacked_keys.iter().position(|key| *key == ix) {
+                        Some(k) => stacked[k].points.get(hovered).map(|p| p.y1),
+                        None => self.data.get(hovered).and_then(series.value_fn()),
+                    };
+                    let y = match (series.secondary, y2.as_ref()) {
+                        (true, Some(y2)) => y2,
+                        _ => &y,
+                    };
+                    if let Some(y) = value.and_then(|v| y.tick(&v)) {
+                        hover::paint_dot(hovered_x, y, colors[ix], &bounds, canvas, cx);
+                    }
+                }
+            }
+
+            if let Some(state) = state {
+                zoom::paint_brush(state, height, &bounds, canvas, cx);
+            }
+        });
+
+        // Draw the overview of the series on the primary y-axis.
+        if let Some(overview) = overview.as_ref() {
+            let lines = visible
+                .iter()
+                .filter(|ix| !self.series[**ix].secondary)
+                .map(|ix| {
+                    let value: Rc<dyn Fn(&T) -> Option<f64>> = Rc::new(self.series[*ix].value_fn());
+                    (value, colors[*ix])
+                })
+                .collect();
+            paint_overview(&self.data, x_fn, lines, x_window, overview, canvas, cx);
         }
+
+        if let Some(state) = state {
+            if self.zoomable || self.overview || self.on_brush.is_some() {
+                let brush = brush_fn(&self.data, x_fn, &x, self.on_brush.as_ref());
+                zoom::on_zoom_event(state, self.zoomable, brush, bounds, overview, canvas);
+            }
+        }
+
+        // Hover and click the nearest data of the mouse.
+        hover::on_mouse_event(
+            &self.data,
+            state,
+            self.on_click.as_ref(),
+            bounds,
+            move |point| Some(x.least_index(point.x.to_f64())),
+            canvas,
+        );
     }
 }
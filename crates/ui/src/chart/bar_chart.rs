@@ -1,19 +1,30 @@
 use std::rc::Rc;
 
-use gpui::{px, App, Bounds, Hsla, Pixels, SharedString, TextAlign, Window};
+use gpui::{
+    px, size, AnyElement, App, Bounds, Hsla, IntoElement, Pixels, SharedString, TextAlign, Window,
+};
 use gpui_component_macros::IntoPlot;
 use num_traits::{Num, ToPrimitive};
 
 use crate::{
     plot::{
         label::Text,
-        scale::{Scale, ScaleBand, ScaleLinear, Sealed},
-        shape::Bar,
-        Axis, AxisText, Grid, Plot, AXIS_GAP,
+        origin_point,
+        scale::{Scale, ScaleBand, Sealed},
+        shape::{Bar, Stack, StackOffset},
+        Axis, AxisText, Canvas, Grid, NumberFormat, Plot, Quad, AXIS_GAP,
     },
     ActiveTheme,
 };
 
+use super::{
+    hover::{self, ClickFn, TooltipFn},
+    paint_legend,
+    series::series_color,
+    transition::{self, Keyframe},
+    visible_series, y_axis_ticks, y_scale, ChartState, Series, YAxis,
+};
+
 #[derive(IntoPlot)]
 pub struct BarChart<T, X, Y>
 where
@@ -21,12 +32,19 @@ where
     X: PartialEq + Into<SharedString> + 'static,
     Y: Copy + PartialOrd + Num + ToPrimitive + Sealed + 'static,
 {
-    data: Vec<T>,
+    data: Rc<[T]>,
     x: Option<Rc<dyn Fn(&T) -> X>>,
-    y: Option<Rc<dyn Fn(&T) -> Y>>,
+    series: Vec<Series<T, Y>>,
     fill: Option<Rc<dyn Fn(&T) -> Hsla>>,
     tick_margin: usize,
     label: Option<Rc<dyn Fn(&T) -> SharedString>>,
+    stacked: bool,
+    y_axis: YAxis,
+    legend: bool,
+    state: Option<ChartState>,
+    tooltip: Option<TooltipFn<T>>,
+    on_click: Option<ClickFn<T>>,
+    animate: bool,
 }
 
 impl<T, X, Y> BarChart<T, X, Y>
@@ -41,10 +59,17 @@ where
         Self {
             data: data.into_iter().collect(),
             x: None,
-            y: None,
+            series: vec![],
             fill: None,
             tick_margin: 1,
             label: None,
+            stacked: false,
+            y_axis: YAxis::default(),
+            legend: false,
+            state: None,
+            tooltip: None,
+            on_click: None,
+            animate: false,
         }
     }
 
@@ -53,11 +78,21 @@ where
         self
     }
 
+    /// Add a series without name.
     pub fn y(mut self, y: impl Fn(&T) -> Y + 'static) -> Self {
-        self.y = Some(Rc::new(y));
+        self.series.push(Series::new("", y));
+        self
+    }
+
+    /// Add a series, the bars of the series are grouped side by side, or stacked by `stacked`.
+    ///
+    /// All the series of the bar chart are on the primary y-axis.
+    pub fn series(mut self, series: Series<T, Y>) -> Self {
+        self.series.push(series);
         self
     }
 
+    /// Set the fill color of each bar, default is the color of the series.
     pub fn fill<H>(mut self, fill: impl Fn(&T) -> H + 'static) -> Self
     where
         H: Into<Hsla> + 'static,
@@ -71,6 +106,7 @@ where
         self
     }
 
+    /// Set the label of each bar, the label is drawn on the bars of the first visible series.
     pub fn label<S>(mut self, label: impl Fn(&T) -> S + 'static) -> Self
     where
         S: Into<SharedString> + 'static,
@@ -78,6 +114,68 @@ where
         self.label = Some(Rc::new(move |t| label(t).into()));
         self
     }
+
+    /// Stack the bars of the series, the negative values are stacked below 0.
+    pub fn stacked(mut self) -> Self {
+        self.stacked = true;
+        self
+    }
+
+    /// Set the min value of the y-axis, default is the nice value below 0 or the min of the data.
+    pub fn y_min(mut self, min: Y) -> Self {
+        self.y_axis.min = min.to_f64();
+        self
+    }
+
+    /// Set the max value of the y-axis, default is the nice value above the max of the data.
+    pub fn y_max(mut self, max: Y) -> Self {
+        self.y_axis.max = max.to_f64();
+        self
+    }
+
+    /// Set the format of the y-axis labels, e.g.: `NumberFormat::Percent`.
+    pub fn y_format(mut self, format: NumberFormat) -> Self {
+        self.y_axis.format = format;
+        self
+    }
+
+    /// Show the legend of the series at the top.
+    pub fn legend(mut self) -> Self {
+        self.legend = true;
+        self
+    }
+
+    /// Set the state of the chart, to toggle the series by clicking the legend and to hover the data.
+    pub fn state(mut self, state: &ChartState) -> Self {
+        self.state = Some(state.clone());
+        self
+    }
+
+    /// Set the tooltip of the hovered data item, the hover is tracked by the [`ChartState`].
+    pub fn tooltip<E>(mut self, tooltip: impl Fn(&T, &mut Window, &mut App) -> E + 'static) -> Self
+    where
+        E: IntoElement,
+    {
+        self.tooltip = Some(Rc::new(move |d, window, cx| {
+            tooltip(d, window, cx).into_any_element()
+        }));
+        self
+    }
+
+    /// Set the handler of clicking a data item, e.g.: To drill down into the item.
+    pub fn on_click(mut self, handler: impl Fn(&T, &mut Window, &mut App) + 'static) -> Self {
+        self.on_click = Some(Rc::new(handler));
+        self
+    }
+
+    /// Animate the bars when the data is changed, the bars are keyed by the x value and the series.
+    ///
+    /// The inserted bars grow from the bottom and the removed bars shrink,
+    /// it requires the [`ChartState`] to keep the bars between the paints.
+    pub fn animate(mut self) -> Self {
+        self.animate = true;
+        self
+    }
 }
 
 impl<T, X, Y> Plot for BarChart<T, X, Y>
@@ -85,11 +183,53 @@ where
     X: PartialEq + Into<SharedString> + 'static,
     Y: Copy + PartialOrd + Num + ToPrimitive + Sealed + 'static,
 {
+    fn prepaint(
+        &mut self,
+        bounds: Bounds<Pixels>,
+        window: &mut Window,
+        cx: &mut App,
+    ) -> Option<AnyElement> {
+        hover::prepaint_tooltip(
+            &self.data,
+            self.tooltip.as_ref(),
+            self.state.as_ref(),
+            bounds,
+            window,
+            cx,
+        )
+    }
+
     fn paint(&mut self, bounds: Bounds<Pixels>, window: &mut Window, cx: &mut App) {
-        let (Some(x_fn), Some(y_fn)) = (self.x.as_ref(), self.y.as_ref()) else {
+        self.paint_canvas(bounds, &mut Canvas::Window(window, None), cx);
+    }
+
+    fn paint_canvas(&mut self, bounds: Bounds<Pixels>, canvas: &mut Canvas, cx: &mut App) {
+        let Some(x_fn) = self.x.as_ref() else {
             return;
         };
 
+        if self.series.is_empty() {
+            return;
+        }
+
+        let colors = self
+            .series
+            .iter()
+            .enumerate()
+            .map(|(ix, series)| series.color.unwrap_or_else(|| series_color(ix, cx)))
+            .collect::<Vec<_>>();
+        let state = self.state.as_ref();
+        let bounds = paint_legend(
+            &self.series,
+            &colors,
+            self.legend,
+            state,
+            bounds,
+            canvas,
+            cx,
+        );
+        let visible = visible_series(&self.series, state);
+
         let width = bounds.size.width.to_f64();
         let height = bounds.size.height.to_f64() - AXIS_GAP;
 
@@ -98,21 +238,44 @@ where
             .padding_inner(0.4)
             .padding_outer(0.2);
         let band_width = x.band_width();
-
-        // Y scale, ensure start from 0.
-        let y = ScaleLinear::new(
+        let x_ticks = Rc::new(
             self.data
                 .iter()
-                .map(|v| y_fn(v))
-                .chain(Some(Y::zero()))
-                .collect(),
-            vec![10., height],
+                .map(|d| x.tick(&x_fn(d)))
+                .collect::<Vec<_>>(),
         );
 
+        // Stack the visible series, the negative values are stacked below 0.
+        let stacked = if self.stacked {
+            visible
+                .iter()
+                .fold(Stack::new(), |stack, ix| {
+                    stack.key(self.series[*ix].value_fn())
+                })
+                .offset(StackOffset::Diverging)
+                .series(&self.data)
+        } else {
+            vec![]
+        };
+
+        // Y scale, ensure start from 0 and end at a nice value.
+        let domain = if self.stacked {
+            stacked
+                .iter()
+                .flat_map(|s| s.points.iter().flat_map(|p| [p.y0, p.y1]))
+                .collect()
+        } else {
+            visible
+                .iter()
+                .flat_map(|ix| self.data.iter().filter_map(self.series[*ix].value_fn()))
+                .collect()
+        };
+        let y = y_scale(domain, height, &self.y_axis);
+
         // Draw X axis
         let x_label = self.data.iter().enumerate().filter_map(|(i, d)| {
             if (i + 1) % self.tick_margin == 0 {
-                x.tick(&x_fn(d)).map(|x_tick| {
+                x_ticks[i].map(|x_tick| {
                     AxisText::new(
                         x_fn(d).into(),
                         x_tick + band_width / 2.,
@@ -125,39 +288,158 @@ where
             }
         });
 
+        let (grid_y, y_label) = y_axis_ticks(&y, height, cx.theme().muted_foreground);
+
         Axis::new()
             .x(height)
             .x_label(x_label)
+            .y(px(0.))
+            .y_label(y_label)
             .stroke(cx.theme().border)
-            .paint(&bounds, window, cx);
+            .paint(&bounds, canvas, cx);
 
         // Draw grid
         Grid::new()
-            .y((0..=3).map(|i| height * i as f64 / 4.0).collect())
+            .y(grid_y)
             .stroke(cx.theme().border)
             .dash_array(&[px(4.), px(2.)])
-            .paint(&bounds, window);
+            .paint(&bounds, canvas);
+
+        // Highlight the band of the hovered data.
+        let hovered_x = state
+            .and_then(|state| state.hovered_index())
+            .and_then(|ix| x_ticks.get(ix).copied().flatten());
+        if let Some(x) = hovered_x {
+            let padding = band_width * 0.2;
+            canvas.paint_quad(Quad::fill(
+                Bounds::new(
+                    origin_point(px((x - padding) as f32), px(0.), bounds.origin),
+                    size(px((band_width + padding * 2.) as f32), px(height as f32)),
+                ),
+                cx.theme().muted.opacity(0.5),
+            ));
+        }
 
-        // Draw bars
-        let x_fn = x_fn.clone();
-        let y_fn = y_fn.clone();
-        let default_fill = cx.theme().chart_2;
-        let fill = self.fill.clone();
+        // Draw bars, the bars start from 0 or the bottom if 0 is out of the domain.
         let label_color = cx.theme().foreground;
-        let mut bar = Bar::new()
-            .data(&self.data)
-            .band_width(band_width)
-            .x(move |d| x.tick(&x_fn(d)))
-            .y0(height)
-            .y1(move |d| y.tick(&y_fn(d)))
-            .fill(move |d| fill.as_ref().map(|f| f(d)).unwrap_or(default_fill));
-
-        if let Some(label) = self.label.as_ref() {
-            let label = label.clone();
-            bar =
-                bar.label(move |d, p| Text::new(label(d), p, label_color).align(TextAlign::Center));
+
+        // The bars of the series are side by side in a group, the stacked bars take the full band.
+        let group = ScaleBand::new(visible.clone(), vec![0., band_width])
+            .padding_inner(if visible.len() > 1 { 0.1 } else { 0. });
+        let group_width = if self.stacked {
+            band_width
+        } else {
+            group.band_width()
+        };
+
+        let mut layers = vec![];
+        for (k, ix) in visible.iter().copied().enumerate() {
+            let offset = if self.stacked {
+                0.
+            } else {
+                let Some(offset) = group.tick(&ix) else {
+                    continue;
+                };
+                offset
+            };
+
+            // The bars of the series, each bar is `(index, data, y0, y1)`.
+            let bars = if self.stacked {
+                stacked[k]
+                    .points
+                    .iter()
+                    .map(|p| (p.index, p.data, p.y0, Some(p.y1)))
+                    .collect::<Vec<_>>()
+            } else {
+                let value = self.series[ix].value_fn();
+                self.data
+                    .iter()
+                    .enumerate()
+                    .map(|(i, d)| (i, d, 0., value(d)))
+                    .collect()
+            };
+            layers.push((k, ix, offset, bars));
+        }
+
+        // Animate the bars `[x, width, y0, y1]`, the labels are drawn after the transition.
+        let frames = if self.animate {
+            let fill_color =
+                |ix: usize, d: &T| self.fill.as_ref().map(|f| f(d)).unwrap_or(colors[ix]);
+            let (x_ticks, y, fill_color) = (&x_ticks, &y, &fill_color);
+            let targets = layers
+                .iter()
+                .flat_map(|(_, ix, offset, bars)| {
+                    bars.iter().filter_map(move |(i, d, v0, v1)| {
+                        let x = x_ticks[*i]? + offset;
+                        let y0 = y.tick(v0)?.min(height);
+                        let y1 = y.tick(&(*v1)?)?;
+                        let key = format!("{}/{}", ix, x_fn(d).into());
+                        Some(Keyframe::new(
+                            key,
+                            *ix,
+                            vec![x, group_width, y0, y1],
+                            fill_color(*ix, d),
+                        ))
+                    })
+                })
+                .collect();
+            let collapse = |item: &Keyframe| {
+                let mut item = item.clone();
+                item.values[3] = item.values[2];
+                item
+            };
+            transition::animate(state, targets, collapse, collapse, bounds, canvas)
+        } else {
+            None
+        };
+
+        if let Some(frames) = frames {
+            for item in frames {
+                let [x, width, y0, y1] = item.values[..] else {
+                    continue;
+                };
+                canvas.paint_quad(Quad::fill(
+                    Bounds::from_corners(
+                        origin_point(px(x as f32), px(y0.min(y1) as f32), bounds.origin),
+                        origin_point(px((x + width) as f32), px(y0.max(y1) as f32), bounds.origin),
+                    ),
+                    item.color,
+                ));
+            }
+        } else {
+            for (k, ix, offset, bars) in layers {
+                let x_ticks = x_ticks.clone();
+                let y0 = y.clone();
+                let y1 = y.clone();
+                let default_fill = colors[ix];
+                let fill = self.fill.clone();
+                let mut bar = Bar::new()
+                    .data(bars)
+                    .band_width(group_width)
+                    .x(move |(i, _, _, _)| x_ticks[*i].map(|t| t + offset))
+                    .y0_fn(move |(_, _, v, _)| y0.tick(v).map(|t| t.min(height)))
+                    .y1(move |(_, _, _, v)| y1.tick(&(*v)?))
+                    .fill(move |(_, d, _, _)| fill.as_ref().map(|f| f(d)).unwrap_or(default_fill));
+
+                if let Some(label) = self.label.as_ref().filter(|_| k == 0) {
+                    let label = label.clone();
+                    bar = bar.label(move |(_, d, _, _), p| {
+                        Text::new(label(d), p, label_color).align(TextAlign::Center)
+                    });
+                }
+
+                bar.paint(&bounds, canvas, cx);
+            }
         }
 
-        bar.paint(&bounds, window, cx);
+        // Hover and click the band of the mouse.
+        hover::on_mouse_event(
+            &self.data,
+            state,
+            self.on_click.as_ref(),
+            bounds,
+            move |point| Some(x.least_index(point.x.to_f64())),
+            canvas,
+        );
     }
 }
@@ -0,0 +1,224 @@
+use gpui::{px, size, App, Bounds, Hsla, IntoElement, Pixels, RenderOnce, Window};
+
+use crate::{
+    plot::{
+        origin_point,
+        scale::{Scale, ScaleLinear},
+        Canvas, Plot, Quad,
+    },
+    ActiveTheme,
+};
+
+use super::sparkline::{render_inline, INLINE_WIDTH};
+
+/// The width of the target line.
+const TARGET_WIDTH: f64 = 2.;
+
+/// A tiny inline bullet chart, the value bar over the qualitative ranges with a target line.
+///
+/// The height is the line height of the text, e.g.: In the cells of a table or a list item.
+#[derive(IntoElement)]
+pub struct BulletChart {
+    value: f64,
+    target: Option<f64>,
+    ranges: Vec<f64>,
+    max: Option<f64>,
+    width: Pixels,
+    color: Option<Hsla>,
+}
+
+impl BulletChart {
+    pub fn new(value: f64) -> Self {
+        Self {
+            value,
+            target: None,
+            ranges: vec![],
+            max: None,
+            width: px(INLINE_WIDTH),
+            color: None,
+        }
+    }
+
+    /// Set the target value, drawn as a vertical line.
+    pub fn target(mut self, target: f64) -> Self {
+        self.target = Some(target);
+        self
+    }
+
+    /// Set the upper bounds of the qualitative ranges from 0, e.g.: `[50., 75., 100.]` for poor, fair and good.
+    pub fn ranges(mut self, ranges: impl IntoIterator<Item = f64>) -> Self {
+        self.ranges = ranges.into_iter().collect();
+        self
+    }
+
+    /// Set the max value of the scale, default is the max of the value, the target and the ranges.
+    pub fn max(mut self, max: f64) -> Self {
+        self.max = Some(max);
+        self
+    }
+
+    /// Set the width of the chart, default is 80px.
+    pub fn width(mut self, width: impl Into<Pixels>) -> Self {
+        self.width = width.into();
+        self
+    }
+
+    /// Set the color of the value bar, default is the first chart color of the theme.
+    pub fn color(mut self, color: impl Into<Hsla>) -> Self {
+        self.color = Some(color.into());
+        self
+    }
+
+    /// The max of the scale, `None` if there is no positive and finite max to draw.
+    fn domain_max(&self) -> Option<f64> {
+        let max = self.max.unwrap_or_else(|| {
+            self.ranges
+                .iter()
+                .copied()
+                .chain(self.target)
+                .fold(self.value, f64::max)
+        });
+        (max.is_finite() && max > 0.).then_some(max)
+    }
+
+    /// The x of the target line in the `width`, the line is kept inside the chart.
+    fn target_x(&self, x: impl Fn(f64) -> f64, width: f64) -> Option<f64> {
+        self.target
+            .map(|target| x(target).min(width - TARGET_WIDTH).max(0.))
+    }
+}
+
+/// Map the value in `0..=max` to `0..=width`, the values out of the domain are clamped and NaN is 0.
+fn x_scale(max: f64, width: f64) -> impl Fn(f64) -> f64 {
+    let x = ScaleLinear::new(vec![0., max], vec![0., width]).ascending();
+    move |v: f64| {
+        if v.is_nan() {
+            return 0.;
+        }
+        x.tick(&v.clamp(0., max)).unwrap_or_default()
+    }
+}
+
+impl Plot for BulletChart {
+    fn paint(&mut self, bounds: Bounds<Pixels>, window: &mut Window, cx: &mut App) {
+        self.paint_canvas(bounds, &mut Canvas::Window(window, None), cx);
+    }
+
+    fn paint_canvas(&mut self, bounds: Bounds<Pixels>, canvas: &mut Canvas, cx: &mut App) {
+        let width = bounds.size.width.to_f64();
+        let height = bounds.size.height.to_f64();
+
+        let Some(max) = self.domain_max() else {
+            return;
+        };
+
+        let x = x_scale(max, width);
+        let rect = |x0: f64, x1: f64, top: f64, bottom: f64, color: Hsla| {
+            Quad::fill(
+                Bounds::new(
+                    origin_point(px(x0 as f32), px(top as f32), bounds.origin),
+                    size(px((x1 - x0) as f32), px((bottom - top) as f32)),
+                ),
+                color,
+            )
+        };
+
+        // The ranges from the widest, the narrower ranges are darker.
+        let mut ranges = self.ranges.clone();
+        ranges.sort_by(|a, b| b.total_cmp(a));
+        let count = ranges.len();
+        for (ix, range) in ranges.into_iter().enumerate() {
+            let opacity = 0.15 + 0.3 * ix as f32 / count.max(2).saturating_sub(1) as f32;
+            canvas.paint_quad(rect(
+                0.,
+                x(range),
+                0.,
+                height,
+                cx.theme().muted_foreground.opacity(opacity),
+            ));
+        }
+
+        // The value bar in the middle third.
+        let color = self.color.unwrap_or(cx.theme().chart_1);
+        canvas.paint_quad(rect(
+            0.,
+            x(self.value),
+            height / 3.,
+            height * 2. / 3.,
+            color,
+        ));
+
+        if let Some(target) = self.target_x(&x, width) {
+            canvas.paint_quad(rect(
+                target,
+                target + TARGET_WIDTH,
+                height / 6.,
+                height * 5. / 6.,
+                cx.theme().foreground,
+            ));
+        }
+    }
+}
+
+impl RenderOnce for BulletChart {
+    fn render(self, window: &mut Window, _: &mut App) -> impl IntoElement {
+        let width = self.width;
+        render_inline(self, width, window)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{x_scale, BulletChart};
+
+    #[test]
+    fn test_domain_max() {
+        let chart = BulletChart::new(60.).target(80.).ranges([50., 100.]);
+        assert_eq!(chart.domain_max(), Some(100.));
+        assert_eq!(BulletChart::new(60.).target(80.).domain_max(), Some(80.));
+        assert_eq!(BulletChart::new(60.).max(200.).domain_max(), Some(200.));
+
+        // Nothing to draw without a positive and finite max.
+        assert_eq!(BulletChart::new(0.).domain_max(), None);
+        assert_eq!(BulletChart::new(-10.).domain_max(), None);
+        assert_eq!(BulletChart::new(f64::NAN).domain_max(), None);
+        assert_eq!(BulletChart::new(f64::INFINITY).domain_max(), None);
+        assert_eq!(BulletChart::new(10.).max(0.).domain_max(), None);
+        // NaN is skipped by the max of the other values.
+        assert_eq!(BulletChart::new(f64::NAN).target(5.).domain_max(), Some(5.));
+    }
+
+    #[test]
+    fn test_x_scale() {
+        let x = x_scale(100., 80.);
+        assert_eq!(x(0.), 0.);
+        assert_eq!(x(50.), 40.);
+        assert_eq!(x(100.), 80.);
+        // Clamped in the domain.
+        assert_eq!(x(-20.), 0.);
+        assert_eq!(x(150.), 80.);
+        assert_eq!(x(f64::INFINITY), 80.);
+        assert_eq!(x(f64::NAN), 0.);
+    }
+
+    #[test]
+    fn test_target_x() {
+        let x = x_scale(100., 80.);
+        assert_eq!(BulletChart::new(50.).target_x(&x, 80.), None);
+        assert_eq!(
+            BulletChart::new(50.).target(50.).target_x(&x, 80.),
+            Some(40.)
+        );
+        // The line at the max is kept inside the chart.
+        assert_eq!(
+            BulletChart::new(50.).target(100.).target_x(&x, 80.),
+            Some(78.)
+        );
+        assert_eq!(
+            BulletChart::new(50.).target(-5.).target_x(&x, 80.),
+            Some(0.)
+        );
+        // The chart is narrower than the line.
+        assert_eq!(BulletChart::new(50.).target(50.).target_x(&x, 1.), Some(0.));
+    }
+}
@@ -0,0 +1,307 @@
+use std::rc::Rc;
+
+use gpui::{px, size, AnyElement, App, Bounds, Hsla, IntoElement, Pixels, Window};
+use gpui_component_macros::IntoPlot;
+use num_traits::{Num, ToPrimitive};
+
+use crate::{
+    plot::{
+        origin_point,
+        scale::{Scale, Sealed, XAxisValue},
+        shape::Bar,
+        Axis, Canvas, Grid, NumberFormat, Plot, Quad, AXIS_GAP,
+    },
+    ActiveTheme,
+};
+
+use super::{
+    hover::{self, ClickFn, TooltipFn},
+    x_axis_texts, y_axis_ticks, y_scale, ChartState, YAxis,
+};
+
+/// The ratio of the candle body in the band of each data.
+const BODY_RATIO: f64 = 0.6;
+
+/// An OHLC candlestick chart, the candle is filled by the up color if close >= open, otherwise by the down color.
+#[derive(IntoPlot)]
+pub struct CandlestickChart<T, X, Y>
+where
+    T: 'static,
+    X: XAxisValue,
+    Y: Copy + PartialOrd + Num + ToPrimitive + Sealed + 'static,
+{
+    data: Rc<[T]>,
+    x: Option<Rc<dyn Fn(&T) -> X>>,
+    open: Option<Rc<dyn Fn(&T) -> Y>>,
+    high: Option<Rc<dyn Fn(&T) -> Y>>,
+    low: Option<Rc<dyn Fn(&T) -> Y>>,
+    close: Option<Rc<dyn Fn(&T) -> Y>>,
+    up_color: Option<Hsla>,
+    down_color: Option<Hsla>,
+    tick_margin: usize,
+    y_axis: YAxis,
+    state: Option<ChartState>,
+    tooltip: Option<TooltipFn<T>>,
+    on_click: Option<ClickFn<T>>,
+}
+
+impl<T, X, Y> CandlestickChart<T, X, Y>
+where
+    X: XAxisValue,
+    Y: Copy + PartialOrd + Num + ToPrimitive + Sealed + 'static,
+{
+    pub fn new<I>(data: I) -> Self
+    where
+        I: IntoIterator<Item = T>,
+    {
+        Self {
+            data: data.into_iter().collect(),
+            x: None,
+            open: None,
+            high: None,
+            low: None,
+            close: None,
+            up_color: None,
+            down_color: None,
+            tick_margin: 1,
+            y_axis: YAxis {
+                exclude_zero: true,
+                ..Default::default()
+            },
+            state: None,
+            tooltip: None,
+            on_click: None,
+        }
+    }
+
+    pub fn x(mut self, x: impl Fn(&T) -> X + 'static) -> Self {
+        self.x = Some(Rc::new(x));
+        self
+    }
+
+    pub fn open(mut self, open: impl Fn(&T) -> Y + 'static) -> Self {
+        self.open = Some(Rc::new(open));
+        self
+    }
+
+    pub fn high(mut self, high: impl Fn(&T) -> Y + 'static) -> Self {
+        self.high = Some(Rc::new(high));
+        self
+    }
+
+    pub fn low(mut self, low: impl Fn(&T) -> Y + 'static) -> Self {
+        self.low = Some(Rc::new(low));
+        self
+    }
+
+    pub fn close(mut self, close: impl Fn(&T) -> Y + 'static) -> Self {
+        self.close = Some(Rc::new(close));
+        self
+    }
+
+    /// Set the color of the rising candles, default is `success` of the theme.
+    pub fn up_color(mut self, color: impl Into<Hsla>) -> Self {
+        self.up_color = Some(color.into());
+        self
+    }
+
+    /// Set the color of the falling candles, default is `danger` of the theme.
+    pub fn down_color(mut self, color: impl Into<Hsla>) -> Self {
+        self.down_color = Some(color.into());
+        self
+    }
+
+    pub fn tick_margin(mut self, tick_margin: usize) -> Self {
+        self.tick_margin = tick_margin;
+        self
+    }
+
+    /// Set the format of the y-axis labels, e.g.: `NumberFormat::currency("$")`.
+    pub fn y_format(mut self, format: NumberFormat) -> Self {
+        self.y_axis.format = format;
+        self
+    }
+
+    /// Set the state of the chart, to hover the candles.
+    pub fn state(mut self, state: &ChartState) -> Self {
+        self.state = Some(state.clone());
+        self
+    }
+
+    /// Set the tooltip of the hovered candle, the hover is tracked by the [`ChartState`].
+    pub fn tooltip<E>(mut self, tooltip: impl Fn(&T, &mut Window, &mut App) -> E + 'static) -> Self
+    where
+        E: IntoElement,
+    {
+        self.tooltip = Some(Rc::new(move |d, window, cx| {
+            tooltip(d, window, cx).into_any_element()
+        }));
+        self
+    }
+
+    /// Set the handler of clicking a candle, e.g.: To drill down into the item.
+    pub fn on_click(mut self, handler: impl Fn(&T, &mut Window, &mut App) + 'static) -> Self {
+        self.on_click = Some(Rc::new(handler));
+        self
+    }
+}
+
+impl<T, X, Y> Plot for CandlestickChart<T, X, Y>
+where
+    X: XAxisValue,
+    Y: Copy + PartialOrd + Num + ToPrimitive + Sealed + 'static,
+{
+    fn prepaint(
+        &mut self,
+        bounds: Bounds<Pixels>,
+        window: &mut Window,
+        cx: &mut App,
+    ) -> Option<AnyElement> {
+        hover::prepaint_tooltip(
+            &self.data,
+            self.tooltip.as_ref(),
+            self.state.as_ref(),
+            bounds,
+            window,
+            cx,
+        )
+    }
+
+    fn paint(&mut self, bounds: Bounds<Pixels>, window: &mut Window, cx: &mut App) {
+        self.paint_canvas(bounds, &mut Canvas::Window(window, None), cx);
+    }
+
+    fn paint_canvas(&mut self, bounds: Bounds<Pixels>, canvas: &mut Canvas, cx: &mut App) {
+        let (Some(x_fn), Some(open), Some(high), Some(low), Some(close)) = (
+            self.x.as_ref(),
+            self.open.clone(),
+            self.high.clone(),
+            self.low.clone(),
+            self.close.clone(),
+        ) else {
+            return;
+        };
+
+        if self.data.is_empty() {
+            return;
+        }
+
+        let width = bounds.size.width.to_f64();
+        let height = bounds.size.height.to_f64() - AXIS_GAP;
+        let state = self.state.as_ref();
+
+        // X scale, each candle is at the center of its band.
+        let band = width / self.data.len() as f64;
+        let x = X::scale(
+            self.data.iter().map(|d| x_fn(d)).collect(),
+            vec![band / 2., width - band / 2.],
+        );
+        let x_ticks = Rc::new(
+            self.data
+                .iter()
+                .map(|d| x.tick(&x_fn(d)))
+                .collect::<Vec<_>>(),
+        );
+
+        // Y scale from the lows to the highs, not extended to 0.
+        let domain = self
+            .data
+            .iter()
+            .flat_map(|d| [low(d).to_f64(), high(d).to_f64()])
+            .flatten()
+            .filter(|v| v.is_finite())
+            .collect();
+        let y = y_scale(domain, height, &self.y_axis);
+
+        // Draw X axis
+        let color = cx.theme().muted_foreground;
+        let x_label = x_axis_texts(&self.data, x_fn, &x, self.tick_margin, width, width, color);
+        let (grid_y, y_label) = y_axis_ticks(&y, height, color);
+
+        Axis::new()
+            .x(height)
+            .x_label(x_label)
+            .y(px(0.))
+            .y_label(y_label)
+            .stroke(cx.theme().border)
+            .paint(&bounds, canvas, cx);
+
+        // Draw grid
+        Grid::new()
+            .y(grid_y)
+            .stroke(cx.theme().border)
+            .dash_array(&[px(4.), px(2.)])
+            .paint(&bounds, canvas);
+
+        // Highlight the band of the hovered candle.
+        let hovered_x = state
+            .and_then(|state| state.hovered_index())
+            .and_then(|ix| x_ticks.get(ix).copied().flatten());
+        if let Some(x) = hovered_x {
+            canvas.paint_quad(Quad::fill(
+                Bounds::new(
+                    origin_point(px((x - band / 2.) as f32), px(0.), bounds.origin),
+                    size(px(band as f32), px(height as f32)),
+                ),
+                cx.theme().muted.opacity(0.5),
+            ));
+        }
+
+        let up_color = self.up_color.unwrap_or(cx.theme().success);
+        let down_color = self.down_color.unwrap_or(cx.theme().danger);
+        let color = {
+            let (open, close) = (open.clone(), close.clone());
+            Rc::new(move |d: &T| {
+                if close(d) >= open(d) {
+                    up_color
+                } else {
+                    down_color
+                }
+            })
+        };
+
+        // Draw the wicks from the high to the low.
+        let wick_ticks = x_ticks.clone();
+        let (y0, y1) = (y.clone(), y.clone());
+        let wick_color = color.clone();
+        Bar::new()
+            .data(self.data.iter().enumerate())
+            .band_width(1.)
+            .x(move |(i, _)| wick_ticks[*i].map(|t| t - 0.5))
+            .y0_fn(move |(_, d)| y0.tick(&high(d).to_f64()?))
+            .y1(move |(_, d)| y1.tick(&low(d).to_f64()?))
+            .fill(move |(_, d)| wick_color(d))
+            .paint(&bounds, canvas, cx);
+
+        // Draw the bodies from the open to the close, at least 1px for the flat candles.
+        let body_width = (band * BODY_RATIO).max(1.);
+        let (y0, y1) = (y.clone(), y.clone());
+        let body_open = open.clone();
+        Bar::new()
+            .data(self.data.iter().enumerate())
+            .band_width(body_width)
+            .x(move |(i, _)| x_ticks[*i].map(|t| t - body_width / 2.))
+            .y0_fn(move |(_, d)| y0.tick(&body_open(d).to_f64()?))
+            .y1(move |(_, d)| {
+                let open = y1.tick(&open(d).to_f64()?)?;
+                let close = y1.tick(&close(d).to_f64()?)?;
+                Some(if (close - open).abs() < 1. {
+                    open + 1.
+                } else {
+                    close
+                })
+            })
+            .fill(move |(_, d)| color(d))
+            .paint(&bounds, canvas, cx);
+
+        // Hover and click the band of the mouse.
+        hover::on_mouse_event(
+            &self.data,
+            state,
+            self.on_click.as_ref(),
+            bounds,
+            move |point| Some(x.least_index(point.x.to_f64())),
+            canvas,
+        );
+    }
+}
@@ -0,0 +1,288 @@
+use std::rc::Rc;
+
+use gpui::{
+    point, px, AnyElement, App, Bounds, Hsla, IntoElement, Pixels, SharedString, TextAlign, Window,
+};
+use gpui_component_macros::IntoPlot;
+
+use crate::{
+    plot::{
+        label::{Label, Text, TEXT_HEIGHT},
+        Canvas, NumberFormat, Plot, PlotPath,
+    },
+    ActiveTheme,
+};
+
+use super::{
+    hover::{self, ClickFn, TooltipFn},
+    series::series_color,
+    ChartState,
+};
+
+/// The gap between the steps.
+const STEP_GAP: f64 = 2.;
+/// The min width of a step, to keep the small steps visible.
+const MIN_STEP_WIDTH: f64 = 4.;
+/// The opacity of the steps that are not hovered, when a step is hovered.
+const DIMMED_OPACITY: f32 = 0.6;
+
+/// A funnel chart of the conversion steps, each data item is a step from top to bottom.
+///
+/// The width of each step is in proportion to its value, and the label shows the value
+/// with the percentage of the first step.
+#[derive(IntoPlot)]
+pub struct FunnelChart<T: 'static> {
+    data: Rc<[T]>,
+    label: Option<Rc<dyn Fn(&T) -> SharedString>>,
+    value: Option<Rc<dyn Fn(&T) -> f64>>,
+    color: Option<Rc<dyn Fn(&T) -> Hsla>>,
+    format: NumberFormat,
+    state: Option<ChartState>,
+    tooltip: Option<TooltipFn<T>>,
+    on_click: Option<ClickFn<T>>,
+}
+
+impl<T> FunnelChart<T> {
+    pub fn new<I>(data: I) -> Self
+    where
+        I: IntoIterator<Item = T>,
+    {
+        Self {
+            data: data.into_iter().collect(),
+            label: None,
+            value: None,
+            color: None,
+            format: NumberFormat::default(),
+            state: None,
+            tooltip: None,
+            on_click: None,
+        }
+    }
+
+    /// Set the label of each step.
+    pub fn label<S>(mut self, label: impl Fn(&T) -> S + 'static) -> Self
+    where
+        S: Into<SharedString> + 'static,
+    {
+        self.label = Some(Rc::new(move |t| label(t).into()));
+        self
+    }
+
+    pub fn value(mut self, value: impl Fn(&T) -> f64 + 'static) -> Self {
+        self.value = Some(Rc::new(value));
+        self
+    }
+
+    /// Set the color of each step, default is the chart colors of the theme in order.
+    pub fn color<H>(mut self, color: impl Fn(&T) -> H + 'static) -> Self
+    where
+        H: Into<Hsla> + 'static,
+    {
+        self.color = Some(Rc::new(move |t| color(t).into()));
+        self
+    }
+
+    /// Set the format of the values in the labels.
+    pub fn format(mut self, format: NumberFormat) -> Self {
+        self.format = format;
+        self
+    }
+
+    /// Set the state of the chart, to hover the steps.
+    pub fn state(mut self, state: &ChartState) -> Self {
+        self.state = Some(state.clone());
+        self
+    }
+
+    /// Set the tooltip of the hovered step, the hover is tracked by the [`ChartState`].
+    pub fn tooltip<E>(mut self, tooltip: impl Fn(&T, &mut Window, &mut App) -> E + 'static) -> Self
+    where
+        E: IntoElement,
+    {
+        self.tooltip = Some(Rc::new(move |d, window, cx| {
+            tooltip(d, window, cx).into_any_element()
+        }));
+        self
+    }
+
+    /// Set the handler of clicking a step, e.g.: To drill down into the step.
+    pub fn on_click(mut self, handler: impl Fn(&T, &mut Window, &mut App) + 'static) -> Self {
+        self.on_click = Some(Rc::new(handler));
+        self
+    }
+}
+
+/// The width of the step of the `value` in proportion to the `max`, at least [`MIN_STEP_WIDTH`].
+fn step_width(value: f64, max: f64, width: f64) -> f64 {
+    if max > 0. && max.is_finite() {
+        (value / max * width).max(MIN_STEP_WIDTH)
+    } else {
+        MIN_STEP_WIDTH
+    }
+}
+
+/// The ratio of the `value` to the `first` step, `None` if the first step is 0.
+fn ratio_of(value: f64, first: f64) -> Option<f64> {
+    (first > 0. && first.is_finite()).then(|| value / first)
+}
+
+/// The index of the step at the `y` in the `n` steps.
+fn step_index(y: f64, step_height: f64, n: usize) -> Option<usize> {
+    let ix = (y / step_height).floor();
+    (ix >= 0. && (ix as usize) < n).then_some(ix as usize)
+}
+
+impl<T> Plot for FunnelChart<T> {
+    fn prepaint(
+        &mut self,
+        bounds: Bounds<Pixels>,
+        window: &mut Window,
+        cx: &mut App,
+    ) -> Option<AnyElement> {
+        hover::prepaint_tooltip(
+            &self.data,
+            self.tooltip.as_ref(),
+            self.state.as_ref(),
+            bounds,
+            window,
+            cx,
+        )
+    }
+
+    fn paint(&mut self, bounds: Bounds<Pixels>, window: &mut Window, cx: &mut App) {
+        self.paint_canvas(bounds, &mut Canvas::Window(window, None), cx);
+    }
+
+    fn paint_canvas(&mut self, bounds: Bounds<Pixels>, canvas: &mut Canvas, cx: &mut App) {
+        let Some(value_fn) = self.value.as_ref() else {
+            return;
+        };
+
+        let n = self.data.len();
+        if n == 0 {
+            return;
+        }
+
+        let width = bounds.size.width.to_f64();
+        let height = bounds.size.height.to_f64();
+        let step_height = height / n as f64;
+        let center = width / 2.;
+
+        let values = self
+            .data
+            .iter()
+            .map(|d| value_fn(d).max(0.))
+            .collect::<Vec<_>>();
+        let max = values.iter().copied().fold(0., f64::max);
+        let first = values[0];
+        let step_width = |v: f64| step_width(v, max, width);
+        let step = max / 100.;
+
+        // Each step is a trapezoid from its width to the width of the next step.
+        let state = self.state.as_ref();
+        let hovered = state.and_then(|state| state.hovered_index());
+        let origin = bounds.origin;
+        let mut labels = vec![];
+        for (ix, d) in self.data.iter().enumerate() {
+            let top_width = step_width(values[ix]);
+            let bottom_width = step_width(values.get(ix + 1).copied().unwrap_or(values[ix]));
+            let top = ix as f64 * step_height;
+            let bottom = top + step_height - STEP_GAP;
+
+            let p = |x: f64, y: f64| {
+                point(
+                    px((x + origin.x.to_f64()) as f32),
+                    px((y + origin.y.to_f64()) as f32),
+                )
+            };
+            let mut path = PlotPath::fill();
+            path.add_polygon(
+                &[
+                    p(center - top_width / 2., top),
+                    p(center + top_width / 2., top),
+                    p(center + bottom_width / 2., bottom),
+                    p(center - bottom_width / 2., bottom),
+                ],
+                true,
+            );
+
+            let mut color = match self.color.as_ref() {
+                Some(color_fn) => color_fn(d),
+                None => series_color(ix, cx),
+            };
+            if hovered.is_some_and(|hovered| hovered != ix) {
+                color = color.opacity(DIMMED_OPACITY);
+            }
+            canvas.paint_path(path, color);
+
+            // The label and the value with the percentage of the first step.
+            let percent = match ratio_of(values[ix], first) {
+                Some(ratio) => NumberFormat::Percent.format(ratio, 0.01),
+                None => "-".into(),
+            };
+            let value = format!("{} ({})", self.format.format(values[ix], step), percent);
+            let text = match self.label.as_ref() {
+                Some(label) => format!("{}: {}", label(d), value),
+                None => value,
+            };
+            labels.push(
+                Text::new(
+                    text,
+                    point(center, top + (step_height - STEP_GAP - TEXT_HEIGHT) / 2.),
+                    cx.theme().foreground,
+                )
+                .align(TextAlign::Center),
+            );
+        }
+        Label::new(labels).paint(&bounds, canvas, cx);
+
+        // Hover and click the step of the mouse.
+        hover::on_mouse_event(
+            &self.data,
+            state,
+            self.on_click.as_ref(),
+            bounds,
+            move |p| step_index(p.y.to_f64(), step_height, n),
+            canvas,
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ratio_of, step_index, step_width, MIN_STEP_WIDTH};
+
+    #[test]
+    fn test_step_width() {
+        assert_eq!(step_width(100., 100., 200.), 200.);
+        assert_eq!(step_width(25., 100., 200.), 50.);
+        // The small steps are kept visible.
+        assert_eq!(step_width(0., 100., 200.), MIN_STEP_WIDTH);
+        assert_eq!(step_width(1., 1e9, 200.), MIN_STEP_WIDTH);
+        // All the values are 0.
+        assert_eq!(step_width(0., 0., 200.), MIN_STEP_WIDTH);
+        assert_eq!(step_width(1., f64::INFINITY, 200.), MIN_STEP_WIDTH);
+    }
+
+    #[test]
+    fn test_ratio_of() {
+        assert_eq!(ratio_of(50., 200.), Some(0.25));
+        assert_eq!(ratio_of(200., 200.), Some(1.));
+        assert_eq!(ratio_of(0., 0.), None);
+        assert_eq!(ratio_of(50., 0.), None);
+        assert_eq!(ratio_of(50., f64::INFINITY), None);
+    }
+
+    #[test]
+    fn test_step_index() {
+        assert_eq!(step_index(0., 20., 3), Some(0));
+        assert_eq!(step_index(19.9, 20., 3), Some(0));
+        assert_eq!(step_index(20., 20., 3), Some(1));
+        assert_eq!(step_index(59., 20., 3), Some(2));
+        assert_eq!(step_index(60., 20., 3), None);
+        assert_eq!(step_index(-1., 20., 3), None);
+        // The chart has no height.
+        assert_eq!(step_index(0., 0., 3), None);
+        assert_eq!(step_index(10., 0., 3), None);
+    }
+}
@@ -0,0 +1,258 @@
+use std::f64::consts::PI;
+
+use gpui::{point, App, Bounds, FontWeight, Hsla, Pixels, SharedString, TextAlign, Window};
+use gpui_component_macros::IntoPlot;
+
+use crate::{
+    plot::{
+        label::{Label, Text, TEXT_HEIGHT},
+        shape::{Arc, ArcData},
+        Canvas, NumberFormat, Plot,
+    },
+    ActiveTheme,
+};
+
+/// The angle of the min value, the gauge is open at the bottom.
+const START_ANGLE: f64 = -PI * 0.75;
+/// The angle of the max value.
+const END_ANGLE: f64 = PI * 0.75;
+/// The width of the threshold bands outside the track.
+const BAND_WIDTH: f64 = 4.;
+/// The gap between the threshold bands and the track.
+const BAND_GAP: f64 = 2.;
+
+/// A radial gauge of a value in the range, with the threshold bands around it.
+#[derive(IntoPlot)]
+pub struct GaugeChart {
+    value: f64,
+    min: f64,
+    max: f64,
+    bands: Vec<(f64, Hsla)>,
+    color: Option<Hsla>,
+    label: Option<SharedString>,
+    format: NumberFormat,
+}
+
+impl GaugeChart {
+    pub fn new(value: f64) -> Self {
+        Self {
+            value,
+            min: 0.,
+            max: 100.,
+            bands: vec![],
+            color: None,
+            label: None,
+            format: NumberFormat::default(),
+        }
+    }
+
+    /// Set the min value of the gauge, default is 0.
+    pub fn min(mut self, min: f64) -> Self {
+        self.min = min;
+        self
+    }
+
+    /// Set the max value of the gauge, default is 100.
+    pub fn max(mut self, max: f64) -> Self {
+        self.max = max;
+        self
+    }
+
+    /// Add a threshold band from the end of the previous band (or the min) to the value `to`.
+    ///
+    /// The value arc is drawn in the color of the band that the value falls in.
+    pub fn band(mut self, to: f64, color: impl Into<Hsla>) -> Self {
+        self.bands.push((to, color.into()));
+        self
+    }
+
+    /// Set the color of the value arc, default is the color of the band or the first chart color of the theme.
+    pub fn color(mut self, color: impl Into<Hsla>) -> Self {
+        self.color = Some(color.into());
+        self
+    }
+
+    /// Set the label below the value, e.g.: The name of the metric.
+    pub fn label(mut self, label: impl Into<SharedString>) -> Self {
+        self.label = Some(label.into());
+        self
+    }
+
+    /// Set the format of the value and the range labels.
+    pub fn format(mut self, format: NumberFormat) -> Self {
+        self.format = format;
+        self
+    }
+
+    /// Return the angle of the value, clamped in the range, NaN is at the min.
+    fn angle(&self, value: f64) -> f64 {
+        let span = self.max - self.min;
+        let ratio = if span > 0. && !value.is_nan() {
+            ((value - self.min) / span).clamp(0., 1.)
+        } else {
+            0.
+        };
+        START_ANGLE + ratio * (END_ANGLE - START_ANGLE)
+    }
+
+    /// Return the color of the band that the value falls in.
+    fn band_color(&self, value: f64) -> Option<Hsla> {
+        self.bands
+            .iter()
+            .find(|(to, _)| value <= *to)
+            .or(self.bands.last())
+            .map(|(_, color)| *color)
+    }
+}
+
+fn arc_data(start_angle: f64, end_angle: f64) -> ArcData<'static, ()> {
+    ArcData {
+        data: &(),
+        index: 0,
+        value: 0.,
+        start_angle,
+        end_angle,
+        pad_angle: 0.,
+    }
+}
+
+impl Plot for GaugeChart {
+    fn paint(&mut self, bounds: Bounds<Pixels>, window: &mut Window, cx: &mut App) {
+        self.paint_canvas(bounds, &mut Canvas::Window(window, None), cx);
+    }
+
+    fn paint_canvas(&mut self, bounds: Bounds<Pixels>, canvas: &mut Canvas, cx: &mut App) {
+        let width = bounds.size.width.to_f64();
+        let height = bounds.size.height.to_f64();
+        let radius = (width / 2.).min(height / 2.) - TEXT_HEIGHT;
+        if radius <= 0. {
+            return;
+        }
+
+        // Draw the threshold bands outside the track.
+        let outer_radius = if self.bands.is_empty() {
+            radius
+        } else {
+            let band = Arc::new()
+                .inner_radius(radius - BAND_WIDTH)
+                .outer_radius(radius);
+            let mut from = self.min;
+            for (to, color) in &self.bands {
+                band.paint(
+                    &arc_data(self.angle(from), self.angle(*to)),
+                    *color,
+                    &bounds,
+                    canvas,
+                );
+                from = *to;
+            }
+            radius - BAND_WIDTH - BAND_GAP
+        };
+
+        // Draw the track and the value arc.
+        let track = Arc::new()
+            .inner_radius(outer_radius * 0.8)
+            .outer_radius(outer_radius);
+        track.paint(
+            &arc_data(START_ANGLE, END_ANGLE),
+            cx.theme().muted,
+            &bounds,
+            canvas,
+        );
+        let color = self
+            .color
+            .or_else(|| self.band_color(self.value))
+            .unwrap_or(cx.theme().chart_1);
+        track.paint(
+            &arc_data(START_ANGLE, self.angle(self.value)),
+            color,
+            &bounds,
+            canvas,
+        );
+
+        // Draw the value and the label in the center, the range labels at the ends of the arc.
+        let center = point(width / 2., height / 2.);
+        let step = (self.max - self.min) / 100.;
+        let font_size = (outer_radius * 0.3).max(12.);
+        let mut texts = vec![Text::new(
+            self.format.format(self.value, step),
+            point(center.x, center.y - font_size / 2.),
+            cx.theme().foreground,
+        )
+        .font_size(font_size as f32)
+        .font_weight(FontWeight::SEMIBOLD)
+        .align(TextAlign::Center)];
+
+        if let Some(label) = self.label.clone() {
+            texts.push(
+                Text::new(
+                    label,
+                    point(center.x, center.y + font_size / 2.),
+                    cx.theme().muted_foreground,
+                )
+                .align(TextAlign::Center),
+            );
+        }
+
+        let muted_foreground = cx.theme().muted_foreground;
+        for (value, angle) in [(self.min, START_ANGLE), (self.max, END_ANGLE)] {
+            // The angles start from 12 o'clock and go clockwise.
+            let p = point(
+                center.x + radius * angle.sin(),
+                center.y - radius * angle.cos(),
+            );
+            texts.push(
+                Text::new(self.format.format(value, step), p, muted_foreground)
+                    .align(TextAlign::Center),
+            );
+        }
+
+        Label::new(texts).paint(&bounds, canvas, cx);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use gpui::hsla;
+
+    use super::{GaugeChart, END_ANGLE, START_ANGLE};
+
+    #[test]
+    fn test_angle() {
+        let gauge = GaugeChart::new(0.).min(-50.).max(50.);
+        assert_eq!(gauge.angle(-50.), START_ANGLE);
+        assert_eq!(gauge.angle(0.), 0.);
+        assert_eq!(gauge.angle(50.), END_ANGLE);
+        // Clamped in the range.
+        assert_eq!(gauge.angle(-100.), START_ANGLE);
+        assert_eq!(gauge.angle(f64::INFINITY), END_ANGLE);
+        assert_eq!(gauge.angle(f64::NAN), START_ANGLE);
+
+        // The empty or reversed range is at the min.
+        let gauge = GaugeChart::new(0.).min(10.).max(10.);
+        assert_eq!(gauge.angle(10.), START_ANGLE);
+        let gauge = GaugeChart::new(0.).min(10.).max(0.);
+        assert_eq!(gauge.angle(5.), START_ANGLE);
+    }
+
+    #[test]
+    fn test_band_color() {
+        let (low, mid, high) = (
+            hsla(0., 1., 0.5, 1.),
+            hsla(0.1, 1., 0.5, 1.),
+            hsla(0.3, 1., 0.5, 1.),
+        );
+        assert_eq!(GaugeChart::new(0.).band_color(50.), None);
+
+        let gauge = GaugeChart::new(0.)
+            .band(30., low)
+            .band(70., mid)
+            .band(100., high);
+        assert_eq!(gauge.band_color(-10.), Some(low));
+        assert_eq!(gauge.band_color(30.), Some(low));
+        assert_eq!(gauge.band_color(50.), Some(mid));
+        assert_eq!(gauge.band_color(100.), Some(high));
+        // The values over the bands are in the last band.
+        assert_eq!(gauge.band_color(120.), Some(high));
+    }
+}
@@ -0,0 +1,295 @@
+use std::rc::Rc;
+
+use gpui::{
+    point, px, size, AnyElement, App, Bounds, Hsla, IntoElement, Pixels, SharedString, TextAlign,
+    Window,
+};
+use gpui_component_macros::IntoPlot;
+use num_traits::{Num, ToPrimitive};
+
+use crate::{
+    plot::{
+        label::{TEXT_GAP, TEXT_HEIGHT},
+        origin_point,
+        scale::{ScaleSequential, Sealed},
+        shape::Bar,
+        Axis, AxisText, Canvas, Plot, Quad, AXIS_GAP,
+    },
+    ActiveTheme,
+};
+
+use super::{
+    hover::{self, ClickFn, TooltipFn},
+    ChartState,
+};
+
+/// The width of the y-axis labels at the left side.
+const Y_LABEL_WIDTH: f64 = 40.;
+/// The gap between the cells.
+const CELL_GAP: f64 = 2.;
+
+/// A matrix heatmap, the cells are colored by the value.
+///
+/// For a calendar heatmap, use the weeks as `x` and the weekdays as `y`.
+#[derive(IntoPlot)]
+pub struct HeatmapChart<T, X, Y, V>
+where
+    T: 'static,
+    X: PartialEq + Into<SharedString> + 'static,
+    Y: PartialEq + Into<SharedString> + 'static,
+    V: Copy + PartialOrd + Num + ToPrimitive + Sealed + 'static,
+{
+    data: Rc<[T]>,
+    x: Option<Rc<dyn Fn(&T) -> X>>,
+    y: Option<Rc<dyn Fn(&T) -> Y>>,
+    value: Option<Rc<dyn Fn(&T) -> V>>,
+    colors: Vec<Hsla>,
+    tick_margin: usize,
+    state: Option<ChartState>,
+    tooltip: Option<TooltipFn<T>>,
+    on_click: Option<ClickFn<T>>,
+}
+
+impl<T, X, Y, V> HeatmapChart<T, X, Y, V>
+where
+    X: PartialEq + Into<SharedString> + 'static,
+    Y: PartialEq + Into<SharedString> + 'static,
+    V: Copy + PartialOrd + Num + ToPrimitive + Sealed + 'static,
+{
+    pub fn new<I>(data: I) -> Self
+    where
+        I: IntoIterator<Item = T>,
+    {
+        Self {
+            data: data.into_iter().collect(),
+            x: None,
+            y: None,
+            value: None,
+            colors: vec![],
+            tick_margin: 1,
+            state: None,
+            tooltip: None,
+            on_click: None,
+        }
+    }
+
+    /// Set the column of each data, the columns are in the order of the data.
+    pub fn x(mut self, x: impl Fn(&T) -> X + 'static) -> Self {
+        self.x = Some(Rc::new(x));
+        self
+    }
+
+    /// Set the row of each data, the rows are in the order of the data from top to bottom.
+    pub fn y(mut self, y: impl Fn(&T) -> Y + 'static) -> Self {
+        self.y = Some(Rc::new(y));
+        self
+    }
+
+    /// Set the value of each data, the color of the cell is mapped from the value.
+    pub fn value(mut self, value: impl Fn(&T) -> V + 'static) -> Self {
+        self.value = Some(Rc::new(value));
+        self
+    }
+
+    /// Set the colors from the min to the max value, default is from the faint `chart_1` to `chart_1`.
+    pub fn colors<H>(mut self, colors: impl IntoIterator<Item = H>) -> Self
+    where
+        H: Into<Hsla>,
+    {
+        self.colors = colors.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Label every `tick_margin` columns on the x-axis.
+    pub fn tick_margin(mut self, tick_margin: usize) -> Self {
+        self.tick_margin = tick_margin;
+        self
+    }
+
+    /// Set the state of the chart, to hover the cells.
+    pub fn state(mut self, state: &ChartState) -> Self {
+        self.state = Some(state.clone());
+        self
+    }
+
+    /// Set the tooltip of the hovered cell, the hover is tracked by the [`ChartState`].
+    pub fn tooltip<E>(mut self, tooltip: impl Fn(&T, &mut Window, &mut App) -> E + 'static) -> Self
+    where
+        E: IntoElement,
+    {
+        self.tooltip = Some(Rc::new(move |d, window, cx| {
+            tooltip(d, window, cx).into_any_element()
+        }));
+        self
+    }
+
+    /// Set the handler of clicking a cell, e.g.: To drill down into the item.
+    pub fn on_click(mut self, handler: impl Fn(&T, &mut Window, &mut App) + 'static) -> Self {
+        self.on_click = Some(Rc::new(handler));
+        self
+    }
+}
+
+/// Return the distinct values in the order of the first appearance, and the index of each value.
+fn distinct<T, V>(data: &[T], f: impl Fn(&T) -> V) -> (Vec<V>, Vec<usize>)
+where
+    V: PartialEq,
+{
+    let mut values: Vec<V> = vec![];
+    let indices = data
+        .iter()
+        .map(|d| {
+            let value = f(d);
+            values.iter().position(|v| *v == value).unwrap_or_else(|| {
+                values.push(value);
+                values.len() - 1
+            })
+        })
+        .collect();
+    (values, indices)
+}
+
+impl<T, X, Y, V> Plot for HeatmapChart<T, X, Y, V>
+where
+    X: PartialEq + Into<SharedString> + 'static,
+    Y: PartialEq + Into<SharedString> + 'static,
+    V: Copy + PartialOrd + Num + ToPrimitive + Sealed + 'static,
+{
+    fn prepaint(
+        &mut self,
+        bounds: Bounds<Pixels>,
+        window: &mut Window,
+        cx: &mut App,
+    ) -> Option<AnyElement> {
+        hover::prepaint_tooltip(
+            &self.data,
+            self.tooltip.as_ref(),
+            self.state.as_ref(),
+            bounds,
+            window,
+            cx,
+        )
+    }
+
+    fn paint(&mut self, bounds: Bounds<Pixels>, window: &mut Window, cx: &mut App) {
+        self.paint_canvas(bounds, &mut Canvas::Window(window, None), cx);
+    }
+
+    fn paint_canvas(&mut self, bounds: Bounds<Pixels>, canvas: &mut Canvas, cx: &mut App) {
+        let (Some(x_fn), Some(y_fn), Some(value_fn)) =
+            (self.x.as_ref(), self.y.as_ref(), self.value.as_ref())
+        else {
+            return;
+        };
+
+        let (columns, column_indices) = distinct(&self.data, |d| x_fn(d));
+        let (rows, row_indices) = distinct(&self.data, |d| y_fn(d));
+        if columns.is_empty() || rows.is_empty() {
+            return;
+        }
+
+        let width = bounds.size.width.to_f64() - Y_LABEL_WIDTH;
+        let height = bounds.size.height.to_f64() - AXIS_GAP;
+        let cell_width = width / columns.len() as f64;
+        let cell_height = height / rows.len() as f64;
+        let state = self.state.as_ref();
+
+        // Draw the labels of the rows at the left side, and the columns at the bottom.
+        let color = cx.theme().muted_foreground;
+        let y_label = rows.into_iter().enumerate().map(|(i, row)| {
+            let tick = (i as f64 + 0.5) * cell_height - TEXT_HEIGHT / 2.;
+            AxisText::new(row.into(), tick, color).align(TextAlign::Right)
+        });
+        let x_label = columns
+            .into_iter()
+            .enumerate()
+            .filter(|(i, _)| (i + 1) % self.tick_margin == 0)
+            .map(|(i, column)| {
+                let tick = Y_LABEL_WIDTH + (i as f64 + 0.5) * cell_width;
+                AxisText::new(column.into(), tick, color).align(TextAlign::Center)
+            });
+
+        Axis::new()
+            .x(height)
+            .hide_x_axis()
+            .x_label(x_label)
+            .y(px((Y_LABEL_WIDTH - TEXT_GAP * 3.) as f32))
+            .y_label(y_label)
+            .paint(&bounds, canvas, cx);
+
+        // Draw cells, the color scale is from the min to the max value.
+        let cells_bounds = Bounds::new(
+            point(bounds.origin.x + px(Y_LABEL_WIDTH as f32), bounds.origin.y),
+            size(px(width as f32), px(height as f32)),
+        );
+        let colors = if self.colors.is_empty() {
+            vec![cx.theme().chart_1.opacity(0.1), cx.theme().chart_1]
+        } else {
+            self.colors.clone()
+        };
+        let scale = ScaleSequential::new(self.data.iter().map(|d| value_fn(d)).collect(), colors);
+        let value_fn = value_fn.clone();
+        let column_ticks = column_indices.clone();
+        let row_ticks = row_indices.clone();
+
+        Bar::new()
+            .data(self.data.iter().enumerate())
+            .band_width((cell_width - CELL_GAP).max(1.))
+            .x(move |(i, _)| Some(column_ticks[*i] as f64 * cell_width + CELL_GAP / 2.))
+            .y0_fn(move |(i, _)| Some(row_ticks[*i] as f64 * cell_height + CELL_GAP / 2.))
+            .y1(move |(i, _)| Some((row_ticks[*i] + 1) as f64 * cell_height - CELL_GAP / 2.))
+            .fill(move |(_, d)| scale.color(&value_fn(d)).unwrap_or_default())
+            .paint(&cells_bounds, canvas, cx);
+
+        // Outline the hovered cell.
+        let hovered = state
+            .and_then(|state| state.hovered_index())
+            .filter(|ix| *ix < self.data.len());
+        if let Some(ix) = hovered {
+            canvas.paint_quad(
+                Quad::fill(
+                    Bounds::new(
+                        origin_point(
+                            px((column_indices[ix] as f64 * cell_width) as f32),
+                            px((row_indices[ix] as f64 * cell_height) as f32),
+                            cells_bounds.origin,
+                        ),
+                        size(px(cell_width as f32), px(cell_height as f32)),
+                    ),
+                    gpui::transparent_black(),
+                )
+                .border_widths(px(2.))
+                .border_color(cx.theme().foreground),
+            );
+        }
+
+        // The data of each cell, the later data overrides the earlier data in the same cell.
+        let column_count = column_indices.iter().max().map_or(0, |max| max + 1);
+        let mut cells = vec![None; column_count * (row_indices.iter().max().unwrap_or(&0) + 1)];
+        for (ix, (column, row)) in column_indices.iter().zip(row_indices.iter()).enumerate() {
+            cells[row * column_count + column] = Some(ix);
+        }
+
+        // Hover and click the cell of the mouse.
+        hover::on_mouse_event(
+            &self.data,
+            state,
+            self.on_click.as_ref(),
+            bounds,
+            move |point| {
+                let x = point.x.to_f64() - Y_LABEL_WIDTH;
+                let y = point.y.to_f64();
+                if x < 0. || y < 0. {
+                    return None;
+                }
+
+                let (column, row) = ((x / cell_width) as usize, (y / cell_height) as usize);
+                if column >= column_count {
+                    return None;
+                }
+                cells.get(row * column_count + column).copied().flatten()
+            },
+            canvas,
+        );
+    }
+}
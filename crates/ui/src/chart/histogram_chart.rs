@@ -0,0 +1,240 @@
+use std::rc::Rc;
+
+use gpui::{px, size, AnyElement, App, Bounds, Hsla, IntoElement, Pixels, Window};
+use gpui_component_macros::IntoPlot;
+
+use crate::{
+    plot::{
+        origin_point,
+        scale::{Scale, ScaleLinear},
+        shape::{Bar, Bin, BinData},
+        Axis, Canvas, Grid, NumberFormat, Plot, Quad, AXIS_GAP,
+    },
+    ActiveTheme,
+};
+
+use super::{
+    hover::{self, ClickFn, TooltipFn},
+    x_axis_ticks, y_axis_ticks, y_scale, ChartState, YAxis,
+};
+
+/// The gap between the bars.
+const BAR_GAP: f64 = 1.;
+
+/// A histogram of the values, the values are binned automatically by Sturges' formula.
+#[derive(IntoPlot)]
+pub struct HistogramChart<T: 'static> {
+    data: Rc<[T]>,
+    value: Option<Rc<dyn Fn(&T) -> f64>>,
+    thresholds: Option<usize>,
+    fill: Option<Hsla>,
+    x_format: NumberFormat,
+    state: Option<ChartState>,
+    tooltip: Option<TooltipFn<BinData>>,
+    on_click: Option<ClickFn<BinData>>,
+}
+
+impl<T> HistogramChart<T> {
+    pub fn new<I>(data: I) -> Self
+    where
+        I: IntoIterator<Item = T>,
+    {
+        Self {
+            data: data.into_iter().collect(),
+            value: None,
+            thresholds: None,
+            fill: None,
+            x_format: NumberFormat::default(),
+            state: None,
+            tooltip: None,
+            on_click: None,
+        }
+    }
+
+    /// Set the value of each data to bin, the non-finite values are ignored.
+    pub fn value(mut self, value: impl Fn(&T) -> f64 + 'static) -> Self {
+        self.value = Some(Rc::new(value));
+        self
+    }
+
+    /// Set about the count of the bins, the bins are split at the nice round values.
+    pub fn bins(mut self, count: usize) -> Self {
+        self.thresholds = Some(count);
+        self
+    }
+
+    /// Set the fill color of the bars, default is `chart_2` of the theme.
+    pub fn fill(mut self, fill: impl Into<Hsla>) -> Self {
+        self.fill = Some(fill.into());
+        self
+    }
+
+    /// Set the format of the x-axis labels, e.g.: `NumberFormat::Percent`.
+    pub fn x_format(mut self, format: NumberFormat) -> Self {
+        self.x_format = format;
+        self
+    }
+
+    /// Set the state of the chart, to hover the bins.
+    pub fn state(mut self, state: &ChartState) -> Self {
+        self.state = Some(state.clone());
+        self
+    }
+
+    /// Set the tooltip of the hovered bin, the hover is tracked by the [`ChartState`].
+    pub fn tooltip<E>(
+        mut self,
+        tooltip: impl Fn(&BinData, &mut Window, &mut App) -> E + 'static,
+    ) -> Self
+    where
+        E: IntoElement,
+    {
+        self.tooltip = Some(Rc::new(move |d, window, cx| {
+            tooltip(d, window, cx).into_any_element()
+        }));
+        self
+    }
+
+    /// Set the handler of clicking a bin, the `indices` of the bin are the indices of the data.
+    pub fn on_click(mut self, handler: impl Fn(&BinData, &mut Window, &mut App) + 'static) -> Self {
+        self.on_click = Some(Rc::new(handler));
+        self
+    }
+
+    fn bins(&self) -> Rc<[BinData]> {
+        let Some(value) = self.value.clone() else {
+            return Rc::new([]);
+        };
+
+        let mut bin = Bin::new().value(move |d: &T| Some(value(d)));
+        if let Some(count) = self.thresholds {
+            bin = bin.thresholds(count);
+        }
+        bin.bins(&self.data).into()
+    }
+}
+
+impl<T> Plot for HistogramChart<T> {
+    fn prepaint(
+        &mut self,
+        bounds: Bounds<Pixels>,
+        window: &mut Window,
+        cx: &mut App,
+    ) -> Option<AnyElement> {
+        self.tooltip.as_ref()?;
+        hover::prepaint_tooltip(
+            &self.bins(),
+            self.tooltip.as_ref(),
+            self.state.as_ref(),
+            bounds,
+            window,
+            cx,
+        )
+    }
+
+    fn paint(&mut self, bounds: Bounds<Pixels>, window: &mut Window, cx: &mut App) {
+        self.paint_canvas(bounds, &mut Canvas::Window(window, None), cx);
+    }
+
+    fn paint_canvas(&mut self, bounds: Bounds<Pixels>, canvas: &mut Canvas, cx: &mut App) {
+        let bins = self.bins();
+        let (Some(first), Some(last)) = (bins.first(), bins.last()) else {
+            return;
+        };
+
+        let width = bounds.size.width.to_f64();
+        let height = bounds.size.height.to_f64() - AXIS_GAP;
+        let state = self.state.as_ref();
+
+        // X scale from the first to the last threshold, a single value is at the center.
+        let (x0, x1) = if first.x0 == last.x1 {
+            (first.x0 - 0.5, last.x1 + 0.5)
+        } else {
+            (first.x0, last.x1)
+        };
+        let x = ScaleLinear::new(vec![x0, x1], vec![0., width])
+            .ascending()
+            .format(self.x_format.clone());
+        let y = y_scale(
+            bins.iter().map(|bin| bin.len() as f64).collect(),
+            height,
+            &YAxis::default(),
+        );
+
+        // Draw axis and grid
+        let color = cx.theme().muted_foreground;
+        let (_, x_label) = x_axis_ticks(&x, width, color);
+        let (grid_y, y_label) = y_axis_ticks(&y, height, color);
+
+        Axis::new()
+            .x(height)
+            .x_label(x_label)
+            .y(px(0.))
+            .y_label(y_label)
+            .stroke(cx.theme().border)
+            .paint(&bounds, canvas, cx);
+
+        Grid::new()
+            .y(grid_y)
+            .stroke(cx.theme().border)
+            .dash_array(&[px(4.), px(2.)])
+            .paint(&bounds, canvas);
+
+        // The left and the right of each bar, a single value takes the full width.
+        let bars = bins
+            .iter()
+            .map(|bin| {
+                if bin.x0 == bin.x1 {
+                    Some((0., width))
+                } else {
+                    Some((x.tick(&bin.x0)?, x.tick(&bin.x1)?))
+                }
+            })
+            .collect::<Vec<_>>();
+
+        // Highlight the hovered bin.
+        let hovered = state
+            .and_then(|state| state.hovered_index())
+            .and_then(|ix| bars.get(ix).copied().flatten());
+        if let Some((left, right)) = hovered {
+            canvas.paint_quad(Quad::fill(
+                Bounds::new(
+                    origin_point(px(left as f32), px(0.), bounds.origin),
+                    size(px((right - left) as f32), px(height as f32)),
+                ),
+                cx.theme().muted.opacity(0.5),
+            ));
+        }
+
+        // Draw bars
+        let band_width = bars
+            .iter()
+            .flatten()
+            .next()
+            .map_or(0., |(left, right)| (right - left - BAR_GAP).max(1.));
+        let fill = self.fill.unwrap_or(cx.theme().chart_2);
+        let (y0, y1) = (y.clone(), y.clone());
+        Bar::new()
+            .data(bins.iter().zip(bars.iter()))
+            .band_width(band_width)
+            .x(|(_, bar)| bar.map(|(left, _)| left + BAR_GAP / 2.))
+            .y0_fn(move |_| y0.tick(&0.))
+            .y1(move |(bin, _)| y1.tick(&(bin.len() as f64)))
+            .fill(move |_| fill)
+            .paint(&bounds, canvas, cx);
+
+        // Hover and click the bin of the mouse.
+        hover::on_mouse_event(
+            &bins,
+            state,
+            self.on_click.as_ref(),
+            bounds,
+            move |point| {
+                let x = point.x.to_f64();
+                bars.iter()
+                    .position(|bar| bar.is_some_and(|(left, right)| (left..=right).contains(&x)))
+            },
+            canvas,
+        );
+    }
+}
@@ -0,0 +1,197 @@
+use std::rc::Rc;
+
+use gpui::{
+    px, size, AnyElement, App, AvailableSpace, Bounds, Hitbox, Hsla, IntoElement, MouseButton,
+    MouseMoveEvent, MouseUpEvent, ParentElement, Pixels, Point, Window,
+};
+
+use crate::{
+    plot::{
+        origin_point,
+        tooltip::{Tooltip, TooltipPosition},
+        Canvas, Quad,
+    },
+    ActiveTheme,
+};
+
+use super::ChartState;
+
+/// Render the tooltip of the hovered data item.
+pub(super) type TooltipFn<T> = Rc<dyn Fn(&T, &mut Window, &mut App) -> AnyElement>;
+
+/// Handle the click on a data item.
+pub(super) type ClickFn<T> = Rc<dyn Fn(&T, &mut Window, &mut App)>;
+
+/// The gap between the mouse and the tooltip.
+const TOOLTIP_GAP: f64 = 16.;
+const DOT_SIZE: f64 = 8.;
+
+/// Return true if the mouse is in the bounds, and the plot is not covered by other elements
+/// at the mouse, e.g.: A popover or a modal over the chart.
+pub(super) fn is_hovered(
+    hitbox: Option<&Hitbox>,
+    bounds: &Bounds<Pixels>,
+    position: Point<Pixels>,
+    window: &Window,
+) -> bool {
+    bounds.contains(&position) && hitbox.is_none_or(|hitbox| hitbox.is_hovered(window))
+}
+
+/// Register the mouse events to hover and click the data items in the bounds.
+///
+/// The `hit_test` returns the index of the data item at the position relative to the bounds.
+/// The events are not registered when exporting.
+pub(super) fn on_mouse_event<T: 'static>(
+    data: &Rc<[T]>,
+    state: Option<&ChartState>,
+    on_click: Option<&ClickFn<T>>,
+    bounds: Bounds<Pixels>,
+    hit_test: impl Fn(Point<Pixels>) -> Option<usize> + 'static,
+    canvas: &mut Canvas,
+) {
+    let hitbox = canvas.hitbox().cloned();
+    let Some(window) = canvas.window() else {
+        return;
+    };
+    let hit_test = Rc::new(hit_test);
+
+    if let Some(state) = state.cloned() {
+        let view_id = window.current_view();
+        let hit_test = hit_test.clone();
+        let hitbox = hitbox.clone();
+        window.on_mouse_event(move |event: &MouseMoveEvent, phase, window, cx| {
+            if !phase.bubble() {
+                return;
+            }
+
+            let ix = if is_hovered(hitbox.as_ref(), &bounds, event.position, window) {
+                hit_test(event.position - bounds.origin)
+            } else {
+                None
+            };
+            if state.set_hovered_index(ix) {
+                cx.notify(view_id);
+            }
+        });
+    }
+
+    if let Some(on_click) = on_click.cloned() {
+        let data = data.clone();
+        let state = state.cloned();
+        window.on_mouse_event(move |event: &MouseUpEvent, phase, window, cx| {
+            if !phase.bubble()
+                || event.button != MouseButton::Left
+                || !is_hovered(hitbox.as_ref(), &bounds, event.position, window)
+            {
+                return;
+            }
+
+            // Not a click if the mouse is dragged, e.g.: To pan the chart.
+            if state.as_ref().is_some_and(|state| state.drag_moved()) {
+                return;
+            }
+
+            if let Some(item) = hit_test(event.position - bounds.origin).and_then(|ix| data.get(ix))
+            {
+                on_click(item, window, cx);
+            }
+        });
+    }
+}
+
+/// Prepaint the tooltip of the hovered data item, at the left or right side of the mouse.
+pub(super) fn prepaint_tooltip<T>(
+    data: &[T],
+    tooltip: Option<&TooltipFn<T>>,
+    state: Option<&ChartState>,
+    bounds: Bounds<Pixels>,
+    window: &mut Window,
+    cx: &mut App,
+) -> Option<AnyElement> {
+    let tooltip = tooltip?;
+    let item = data.get(state?.hovered_index()?)?;
+    let mouse = window.mouse_position();
+    if !bounds.contains(&mouse) {
+        return None;
+    }
+
+    // Show the tooltip at the right of the mouse in the left half, and vice versa.
+    let x = (mouse.x - bounds.origin.x).to_f64();
+    let width = bounds.size.width.to_f64();
+    let (position, gap) = if x < width / 2. {
+        (TooltipPosition::Left, x + TOOLTIP_GAP)
+    } else {
+        (TooltipPosition::Right, width - x + TOOLTIP_GAP)
+    };
+
+    let mut element = Tooltip::new()
+        .position(position)
+        .gap(px(gap as f32))
+        .child(tooltip(item, window, cx))
+        .into_any_element();
+    element.layout_as_root(
+        size(
+            AvailableSpace::Definite(bounds.size.width),
+            AvailableSpace::Definite(bounds.size.height),
+        ),
+        window,
+        cx,
+    );
+    element.prepaint_at(bounds.origin, window, cx);
+    Some(element)
+}
+
+/// Paint the crosshair, the vertical line at `x` and the horizontal line at the mouse.
+pub(super) fn paint_crosshair(
+    x: f64,
+    height: f64,
+    bounds: &Bounds<Pixels>,
+    canvas: &mut Canvas,
+    cx: &App,
+) {
+    let color = cx.theme().muted_foreground.opacity(0.5);
+    canvas.paint_quad(Quad::fill(
+        Bounds::new(
+            origin_point(px(x as f32), px(0.), bounds.origin),
+            size(px(1.), px(height as f32)),
+        ),
+        color,
+    ));
+
+    let Some(mouse) = canvas.mouse_position() else {
+        return;
+    };
+    let y = (mouse.y - bounds.origin.y).to_f64();
+    if (0. ..=height).contains(&y) {
+        canvas.paint_quad(Quad::fill(
+            Bounds::new(
+                origin_point(px(0.), px(y as f32), bounds.origin),
+                size(bounds.size.width, px(1.)),
+            ),
+            color,
+        ));
+    }
+}
+
+/// Paint the dot to highlight the hovered point.
+pub(super) fn paint_dot(
+    x: f64,
+    y: f64,
+    color: Hsla,
+    bounds: &Bounds<Pixels>,
+    canvas: &mut Canvas,
+    cx: &App,
+) {
+    let dot_size = px(DOT_SIZE as f32);
+    let origin = origin_point(
+        px((x - DOT_SIZE / 2.) as f32),
+        px((y - DOT_SIZE / 2.) as f32),
+        bounds.origin,
+    );
+    canvas.paint_quad(
+        Quad::fill(Bounds::new(origin, size(dot_size, dot_size)), color)
+            .corner_radii(dot_size / 2.)
+            .border_widths(px(2.))
+            .border_color(cx.theme().background),
+    );
+}
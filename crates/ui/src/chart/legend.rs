@@ -0,0 +1,82 @@
+use gpui::{point, px, size, App, Bounds, Hsla, MouseDownEvent, Pixels, SharedString};
+
+use crate::{
+    plot::{
+        label::{Label, Text, TEXT_SIZE},
+        origin_point, Canvas, Quad,
+    },
+    ActiveTheme,
+};
+
+use super::{hover, ChartState};
+
+/// The height of the legend at the top of the chart.
+pub(super) const LEGEND_HEIGHT: f64 = 24.;
+const SWATCH_SIZE: f64 = 8.;
+const SWATCH_GAP: f64 = 4.;
+const ITEM_GAP: f64 = 16.;
+
+/// The legend of the series, click an item to toggle the series if the chart has a [`ChartState`].
+pub(super) struct Legend {
+    items: Vec<(SharedString, Hsla)>,
+    state: Option<ChartState>,
+}
+
+impl Legend {
+    pub(super) fn new(items: Vec<(SharedString, Hsla)>, state: Option<ChartState>) -> Self {
+        Self { items, state }
+    }
+
+    /// Paint the Legend at the top of the bounds.
+    pub(super) fn paint(&self, bounds: &Bounds<Pixels>, canvas: &mut Canvas, cx: &mut App) {
+        let swatch_top = (LEGEND_HEIGHT - SWATCH_SIZE) / 2.;
+        let text_top = (LEGEND_HEIGHT - TEXT_SIZE) / 2. - 1.;
+
+        let mut x = 0.;
+        let mut texts = vec![];
+        for (ix, (name, color)) in self.items.iter().enumerate() {
+            let hidden = self.state.as_ref().is_some_and(|state| state.is_hidden(ix));
+            let (swatch_color, text_color) = if hidden {
+                (color.opacity(0.3), cx.theme().muted_foreground.opacity(0.5))
+            } else {
+                (*color, cx.theme().muted_foreground)
+            };
+
+            let swatch = Bounds::new(
+                origin_point(px(x as f32), px(swatch_top as f32), bounds.origin),
+                size(px(SWATCH_SIZE as f32), px(SWATCH_SIZE as f32)),
+            );
+            canvas.paint_quad(Quad::fill(swatch, swatch_color).corner_radii(px(2.)));
+
+            let text_x = x + SWATCH_SIZE + SWATCH_GAP;
+            let text_width = canvas.text_width(name, px(TEXT_SIZE as f32)).to_f64();
+            texts.push(Text::new(
+                name.clone(),
+                point(px(text_x as f32), px(text_top as f32)),
+                text_color,
+            ));
+
+            let item_width = SWATCH_SIZE + SWATCH_GAP + text_width;
+            let hitbox = canvas.hitbox().cloned();
+            if let (Some(state), Some(window)) = (self.state.clone(), canvas.window()) {
+                let view_id = window.current_view();
+                let item_bounds = Bounds::new(
+                    origin_point(px(x as f32), px(0.), bounds.origin),
+                    size(px(item_width as f32), px(LEGEND_HEIGHT as f32)),
+                );
+                window.on_mouse_event(move |event: &MouseDownEvent, phase, window, cx| {
+                    if phase.bubble()
+                        && hover::is_hovered(hitbox.as_ref(), &item_bounds, event.position, window)
+                    {
+                        state.toggle(ix);
+                        cx.notify(view_id);
+                    }
+                });
+            }
+
+            x += item_width + ITEM_GAP;
+        }
+
+        Label::new(texts).paint(bounds, canvas, cx);
+    }
+}
@@ -1,37 +1,59 @@
-use std::rc::Rc;
+use std::{ops::RangeInclusive, rc::Rc};
 
-use gpui::{px, App, Bounds, Hsla, Pixels, SharedString, TextAlign, Window};
+use gpui::{px, AnyElement, App, Bounds, IntoElement, Pixels, SharedString, Window};
 use gpui_component_macros::IntoPlot;
 use num_traits::{Num, ToPrimitive};
 
 use crate::{
     plot::{
-        scale::{Scale, ScaleLinear, ScalePoint, Sealed},
-        shape::Line,
-        Axis, AxisText, Grid, Plot, StrokeStyle, AXIS_GAP,
+        scale::{Scale, Sealed, XAxisValue},
+        shape::{Downsample, Line},
+        Axis, Canvas, Grid, NumberFormat, Plot, StrokeStyle, AXIS_GAP,
     },
     ActiveTheme,
 };
 
+use super::{
+    brush_fn,
+    hover::{self, ClickFn, TooltipFn},
+    paint_legend, paint_overview, paint_y2_axis,
+    series::series_color,
+    series_domain, split_overview,
+    transition::{self, Keyframe},
+    visible_range, visible_series, x_axis_texts, y_axis_ticks, y_scale,
+    zoom::{self, BrushHandler},
+    ChartState, Series, YAxis,
+};
+
 #[derive(IntoPlot)]
 pub struct LineChart<T, X, Y>
 where
     T: 'static,
-    X: PartialEq + Into<SharedString> + 'static,
+    X: XAxisValue,
     Y: Copy + PartialOrd + Num + ToPrimitive + Sealed + 'static,
 {
-    data: Vec<T>,
+    data: Rc<[T]>,
     x: Option<Rc<dyn Fn(&T) -> X>>,
-    y: Option<Rc<dyn Fn(&T) -> Y>>,
-    stroke: Option<Hsla>,
+    series: Vec<Series<T, Y>>,
     stroke_style: StrokeStyle,
     dot: bool,
     tick_margin: usize,
+    y_axis: YAxis,
+    y2_axis: YAxis,
+    legend: bool,
+    state: Option<ChartState>,
+    tooltip: Option<TooltipFn<T>>,
+    on_click: Option<ClickFn<T>>,
+    zoomable: bool,
+    overview: bool,
+    on_brush: Option<BrushHandler<X>>,
+    animate: bool,
+    key: Option<Rc<dyn Fn(&T) -> SharedString>>,
 }
 
 impl<T, X, Y> LineChart<T, X, Y>
 where
-    X: PartialEq + Into<SharedString> + 'static,
+    X: XAxisValue,
     Y: Copy + PartialOrd + Num + ToPrimitive + Sealed + 'static,
 {
     pub fn new<I>(data: I) -> Self
@@ -40,12 +62,22 @@ where
     {
         Self {
             data: data.into_iter().collect(),
-            stroke: None,
             stroke_style: Default::default(),
             dot: false,
             x: None,
-            y: None,
+            series: vec![],
             tick_margin: 1,
+            y_axis: YAxis::default(),
+            y2_axis: YAxis::default(),
+            legend: false,
+            state: None,
+            tooltip: None,
+            on_click: None,
+            zoomable: false,
+            overview: false,
+            on_brush: None,
+            animate: false,
+            key: None,
         }
     }
 
@@ -54,8 +86,15 @@ where
         self
     }
 
+    /// Add a series without name.
     pub fn y(mut self, y: impl Fn(&T) -> Y + 'static) -> Self {
-        self.y = Some(Rc::new(y));
+        self.series.push(Series::new("", y));
+        self
+    }
+
+    /// Add a series, each series is drawn as a line.
+    pub fn series(mut self, series: Series<T, Y>) -> Self {
+        self.series.push(series);
         self
     }
 
@@ -73,80 +112,378 @@ where
         self.tick_margin = tick_margin;
         self
     }
+
+    /// Use the log scale for the y-axis, the non-positive values are not drawn.
+    pub fn log_scale(mut self) -> Self {
+        self.y_axis.log_scale = true;
+        self
+    }
+
+    /// Set the min value of the y-axis, default is the nice value below 0 or the min of the data.
+    pub fn y_min(mut self, min: Y) -> Self {
+        self.y_axis.min = min.to_f64();
+        self
+    }
+
+    /// Set the max value of the y-axis, default is the nice value above the max of the data.
+    pub fn y_max(mut self, max: Y) -> Self {
+        self.y_axis.max = max.to_f64();
+        self
+    }
+
+    /// Set the format of the y-axis labels, e.g.: `NumberFormat::Percent`.
+    pub fn y_format(mut self, format: NumberFormat) -> Self {
+        self.y_axis.format = format;
+        self
+    }
+
+    /// Set the format of the secondary y-axis labels, see [`Series::secondary`].
+    pub fn y2_format(mut self, format: NumberFormat) -> Self {
+        self.y2_axis.format = format;
+        self
+    }
+
+    /// Show the legend of the series at the top.
+    pub fn legend(mut self) -> Self {
+        self.legend = true;
+        self
+    }
+
+    /// Set the state of the chart, to toggle the series by clicking the legend and to hover the data.
+    pub fn state(mut self, state: &ChartState) -> Self {
+        self.state = Some(state.clone());
+        self
+    }
+
+    /// Set the tooltip of the hovered data item, the hover is tracked by the [`ChartState`].
+    pub fn tooltip<E>(mut self, tooltip: impl Fn(&T, &mut Window, &mut App) -> E + 'static) -> Self
+    where
+        E: IntoElement,
+    {
+        self.tooltip = Some(Rc::new(move |d, window, cx| {
+            tooltip(d, window, cx).into_any_element()
+        }));
+        self
+    }
+
+    /// Set the handler of clicking a data item, e.g.: To drill down into the item.
+    pub fn on_click(mut self, handler: impl Fn(&T, &mut Window, &mut App) + 'static) -> Self {
+        self.on_click = Some(Rc::new(handler));
+        self
+    }
+
+    /// Zoom the x-axis by the mouse wheel with `ctrl` (`cmd` on macOS) and pan by dragging,
+    /// double click to reset the zoom.
+    ///
+    /// Drag with `shift` to zoom in the brushed range, the zoom is kept in the [`ChartState`].
+    pub fn zoomable(mut self) -> Self {
+        self.zoomable = true;
+        self
+    }
+
+    /// Show the overview of the full range at the bottom, drag the window in it to pan.
+    pub fn overview(mut self) -> Self {
+        self.overview = true;
+        self
+    }
+
+    /// Set the handler of the brush with the range of the x values, the data is sorted by x.
+    ///
+    /// Drag to brush, or drag with `shift` if the chart is [`zoomable`](Self::zoomable).
+    pub fn on_brush(
+        mut self,
+        handler: impl Fn(RangeInclusive<X>, &mut Window, &mut App) + 'static,
+    ) -> Self {
+        self.on_brush = Some(Rc::new(handler));
+        self
+    }
+
+    /// Animate the lines when the data is changed, the lines morph between the keyed points.
+    ///
+    /// The inserted points rise from the bottom and the removed points fall,
+    /// it requires the [`ChartState`] to keep the points between the paints.
+    pub fn animate(mut self) -> Self {
+        self.animate = true;
+        self
+    }
+
+    /// Set the key of each data to animate, default is the label of the x value or the index of the data.
+    pub fn key<S>(mut self, key: impl Fn(&T) -> S + 'static) -> Self
+    where
+        S: Into<SharedString> + 'static,
+    {
+        self.key = Some(Rc::new(move |t| key(t).into()));
+        self
+    }
 }
 
 impl<T, X, Y> Plot for LineChart<T, X, Y>
 where
-    X: PartialEq + Into<SharedString> + 'static,
+    X: XAxisValue,
     Y: Copy + PartialOrd + Num + ToPrimitive + Sealed + 'static,
 {
+    fn prepaint(
+        &mut self,
+        bounds: Bounds<Pixels>,
+        window: &mut Window,
+        cx: &mut App,
+    ) -> Option<AnyElement> {
+        hover::prepaint_tooltip(
+            &self.data,
+            self.tooltip.as_ref(),
+            self.state.as_ref(),
+            bounds,
+            window,
+            cx,
+        )
+    }
+
     fn paint(&mut self, bounds: Bounds<Pixels>, window: &mut Window, cx: &mut App) {
-        let (Some(x_fn), Some(y_fn)) = (self.x.as_ref(), self.y.as_ref()) else {
+        self.paint_canvas(bounds, &mut Canvas::Window(window, None), cx);
+    }
+
+    fn paint_canvas(&mut self, bounds: Bounds<Pixels>, canvas: &mut Canvas, cx: &mut App) {
+        let Some(x_fn) = self.x.as_ref() else {
             return;
         };
 
+        if self.series.is_empty() {
+            return;
+        }
+
+        let colors = self
+            .series
+            .iter()
+            .enumerate()
+            .map(|(ix, series)| series.color.unwrap_or_else(|| series_color(ix, cx)))
+            .collect::<Vec<_>>();
+        let state = self.state.as_ref();
+        let bounds = paint_legend(
+            &self.series,
+            &colors,
+            self.legend,
+            state,
+            bounds,
+            canvas,
+            cx,
+        );
+        let (bounds, overview) = if self.overview {
+            let (bounds, overview) = split_overview(bounds);
+            (bounds, Some(overview))
+        } else {
+            (bounds, None)
+        };
+        let visible = visible_series(&self.series, state);
+
         let width = bounds.size.width.to_f64();
         let height = bounds.size.height.to_f64() - AXIS_GAP;
 
-        // X scale
-        let x = ScalePoint::new(self.data.iter().map(|v| x_fn(v)).collect(), vec![0., width]);
+        // X scale, the full range is scaled to show the zoomed window in the width.
+        let x_window = state.map_or((0., 1.), |state| state.x_window());
+        let x_range = zoom::x_range(x_window, width);
+        let full_width = x_range[1] - x_range[0];
+        let x = X::scale(self.data.iter().map(|v| x_fn(v)).collect(), x_range);
+        let x_ticks = self
+            .data
+            .iter()
+            .map(|d| x.tick(&x_fn(d)))
+            .collect::<Vec<_>>();
+        let range = visible_range(&x_ticks, width);
+        let data = &self.data[range.clone()];
 
-        // Y scale, ensure start from 0.
-        let y = ScaleLinear::new(
-            self.data
-                .iter()
-                .map(|v| y_fn(v))
-                .chain(Some(Y::zero()))
-                .collect(),
-            vec![10., height],
+        // Y scale of the data in the window, the linear scale starts from 0 and ends at a nice value.
+        let y = y_scale(
+            series_domain(data, &self.series, &visible, false),
+            height,
+            &self.y_axis,
         );
+        let y2 = visible
+            .iter()
+            .any(|ix| self.series[*ix].secondary)
+            .then(|| {
+                y_scale(
+                    series_domain(data, &self.series, &visible, true),
+                    height,
+                    &self.y2_axis,
+                )
+            });
 
         // Draw X axis
-        let data_len = self.data.len();
-        let x_label = self.data.iter().enumerate().filter_map(|(i, d)| {
-            if (i + 1) % self.tick_margin == 0 {
-                x.tick(&x_fn(d)).map(|x_tick| {
-                    let align = match i {
-                        0 => TextAlign::Left,
-                        i if i == data_len - 1 => TextAlign::Right,
-                        _ => TextAlign::Center,
-                    };
-                    AxisText::new(x_fn(d).into(), x_tick, cx.theme().muted_foreground).align(align)
-                })
-            } else {
-                None
-            }
-        });
+        let color = cx.theme().muted_foreground;
+        let x_label = x_axis_texts(
+            &self.data,
+            x_fn,
+            &x,
+            self.tick_margin,
+            width,
+            full_width,
+            color,
+        );
+        let (grid_y, y_label) = y_axis_ticks(&y, height, color);
 
         Axis::new()
             .x(height)
             .x_label(x_label)
+            .y(px(0.))
+            .y_label(y_label)
             .stroke(cx.theme().border)
-            .paint(&bounds, window, cx);
+            .paint(&bounds, canvas, cx);
+
+        if let Some(y2) = y2.as_ref() {
+            paint_y2_axis(y2, &bounds, height, canvas, cx);
+        }
 
         // Draw grid
         Grid::new()
-            .y((0..=3).map(|i| height * i as f64 / 4.0).collect())
+            .y(grid_y)
             .stroke(cx.theme().border)
             .dash_array(&[px(4.), px(2.)])
-            .paint(&bounds, window);
-
-        // Draw line
-        let stroke = self.stroke.unwrap_or(cx.theme().chart_2);
-        let x_fn = x_fn.clone();
-        let y_fn = y_fn.clone();
-        let mut line = Line::new()
-            .data(&self.data)
-            .x(move |d| x.tick(&x_fn(d)))
-            .y(move |d| y.tick(&y_fn(d)))
-            .stroke(stroke)
-            .stroke_style(self.stroke_style)
-            .stroke_width(2.);
-
-        if self.dot {
-            line = line.dot().dot_size(8.).dot_fill_color(stroke);
+            .paint(&bounds, canvas);
+
+        // The lines are clipped in the bounds when zoomed in.
+        canvas.with_content_mask(bounds, |canvas| {
+            // Draw the crosshair of the hovered data.
+            let hovered = state
+                .and_then(|state| state.hovered_index())
+                .and_then(|ix| Some((self.data.get(ix)?, x_ticks[ix]?)));
+            if let Some((_, x)) = hovered {
+                hover::paint_crosshair(x, height, &bounds, canvas, cx);
+            }
+
+            // The y scale of the series, the secondary series are on the secondary y-axis.
+            let series_y = |ix: usize| match (self.series[ix].secondary, y2.as_ref()) {
+                (true, Some(y2)) => y2.clone(),
+                _ => y.clone(),
+            };
+
+            // Animate the points `[x, y]` of the lines.
+            let frames = if self.animate {
+                let (key_fn, x_ticks, colors) = (self.key.as_ref(), &x_ticks, &colors);
+                let targets = visible
+                    .iter()
+                    .flat_map(|ix| {
+                        let y = series_y(*ix);
+                        let value = self.series[*ix].value_fn();
+                        data.iter().zip(range.clone()).filter_map(move |(d, i)| {
+                            let y = y.tick(&value(d)?)?;
+                            let key = match key_fn {
+                                Some(key_fn) => key_fn(d),
+                                None => x_fn(d).label().unwrap_or_else(|| i.to_string().into()),
+                            };
+                            Some(Keyframe::new(
+                                format!("{}/{}", ix, key),
+                                *ix,
+                                vec![x_ticks[i]?, y],
+                                colors[*ix],
+                            ))
+                        })
+                    })
+                    .collect();
+                let bottom = |item: &Keyframe| {
+                    let mut item = item.clone();
+                    item.values[1] = height;
+                    item
+                };
+                transition::animate(state, targets, bottom, bottom, bounds, canvas)
+            } else {
+                None
+            };
+
+            // Draw lines
+            if let Some(mut frames) = frames {
+                frames.sort_by(|a, b| {
+                    a.group
+                        .cmp(&b.group)
+                        .then(a.values[0].total_cmp(&b.values[0]))
+                });
+                for points in frames.chunk_by(|a, b| a.group == b.group) {
+                    let stroke = points[0].color;
+                    let mut line = Line::new()
+                        .data(points.iter())
+                        .x(|item| item.values.first().copied())
+                        .y(|item| item.values.get(1).copied())
+                        .stroke(stroke)
+                        .stroke_style(self.stroke_style)
+                        .stroke_width(2.);
+
+                    if self.dot {
+                        line = line.dot().dot_size(8.).dot_fill_color(stroke);
+                    }
+
+                    line.paint(&bounds, canvas);
+                }
+            } else {
+                for ix in visible.iter().copied() {
+                    let y = series_y(ix);
+                    let x = x.clone();
+                    let x_fn = x_fn.clone();
+                    let value = self.series[ix].value_fn();
+                    let stroke = colors[ix];
+
+                    let mut line = Line::new()
+                        .data(data.iter())
+                        .x(move |d| x.tick(&x_fn(d)))
+                        .y(move |d| y.tick(&value(d)?))
+                        .stroke(stroke)
+                        .stroke_style(self.stroke_style)
+                        .stroke_width(2.)
+                        .downsample(Downsample::MinMax);
+
+                    if self.dot {
+                        line = line.dot().dot_size(8.).dot_fill_color(stroke);
+                    }
+
+                    line.paint(&bounds, canvas);
+                }
+            }
+
+            // Highlight the hovered points of the lines.
+            if let Some((d, hovered_x)) = hovered {
+                for ix in visible.iter().copied() {
+                    let series = &self.series[ix];
+                    let y = match (series.secondary, y2.as_ref()) {
+                        (true, Some(y2)) => y2,
+                        _ => &y,
+                    };
+                    if let Some(y) = series.value_fn()(d).and_then(|v| y.tick(&v)) {
+                        hover::paint_dot(hovered_x, y, colors[ix], &bounds, canvas, cx);
+                    }
+                }
+            }
+
+            if let Some(state) = state {
+                zoom::paint_brush(state, height, &bounds, canvas, cx);
+            }
+        });
+
+        // Draw the overview of the series on the primary y-axis.
+        if let Some(overview) = overview.as_ref() {
+            let lines = visible
+                .iter()
+                .filter(|ix| !self.series[**ix].secondary)
+                .map(|ix| {
+                    let value: Rc<dyn Fn(&T) -> Option<f64>> = Rc::new(self.series[*ix].value_fn());
+                    (value, colors[*ix])
+                })
+                .collect();
+            paint_overview(&self.data, x_fn, lines, x_window, overview, canvas, cx);
+        }
+
+        if let Some(state) = state {
+            if self.zoomable || self.overview || self.on_brush.is_some() {
+                let brush = brush_fn(&self.data, x_fn, &x, self.on_brush.as_ref());
+                zoom::on_zoom_event(state, self.zoomable, brush, bounds, overview, canvas);
+            }
         }
 
-        line.paint(&bounds, window);
+        // Hover and click the nearest data of the mouse.
+        hover::on_mouse_event(
+            &self.data,
+            state,
+            self.on_click.as_ref(),
+            bounds,
+            move |point| Some(x.least_index(point.x.to_f64())),
+            canvas,
+        );
     }
 }
@@ -1,9 +1,361 @@
 mod area_chart;
 mod bar_chart;
+mod bullet_chart;
+mod candlestick_chart;
+mod funnel_chart;
+mod gauge_chart;
+mod heatmap_chart;
+mod histogram_chart;
+mod hover;
+mod legend;
 mod line_chart;
 mod pie_chart;
+mod radar_chart;
+mod scatter_chart;
+mod series;
+mod sparkline;
+mod state;
+mod transition;
+mod zoom;
 
 pub use area_chart::AreaChart;
 pub use bar_chart::BarChart;
+pub use bullet_chart::BulletChart;
+pub use candlestick_chart::CandlestickChart;
+pub use funnel_chart::FunnelChart;
+pub use gauge_chart::GaugeChart;
+pub use heatmap_chart::HeatmapChart;
+pub use histogram_chart::HistogramChart;
 pub use line_chart::LineChart;
 pub use pie_chart::PieChart;
+pub use radar_chart::RadarChart;
+pub use scatter_chart::ScatterChart;
+pub use series::Series;
+pub use sparkline::Sparkline;
+pub use state::ChartState;
+
+use std::{ops::Range, rc::Rc};
+
+use gpui::{point, px, size, App, Bounds, Hsla, Pixels, TextAlign};
+use num_traits::ToPrimitive;
+
+use crate::{
+    plot::{
+        label::{TEXT_GAP, TEXT_HEIGHT},
+        scale::{Scale, ScaleLinear, ScaleLog, XAxisValue},
+        shape::{Downsample, Line},
+        Axis, AxisText, Canvas, NumberFormat, StrokeStyle,
+    },
+    ActiveTheme,
+};
+
+use legend::{Legend, LEGEND_HEIGHT};
+use zoom::{BrushFn, BrushHandler, OVERVIEW_HEIGHT};
+
+/// The width for each tick of the x-axis labelled by the scale.
+const X_TICK_WIDTH: f64 = 80.;
+/// The count of the ticks for the y-axis labelled by the scale.
+const Y_TICKS_COUNT: usize = 5;
+
+/// Return the labels of the x-axis in the `width`.
+///
+/// If the x values have labels, every `tick_margin` data is labelled,
+/// otherwise the axis is labelled at the ticks of the scale, e.g.: The calendar intervals of `ScaleTime`.
+///
+/// The `full_width` is the width of the full range, it's larger than `width` if the x-axis is zoomed in.
+fn x_axis_texts<T, X>(
+    data: &[T],
+    x_fn: &Rc<dyn Fn(&T) -> X>,
+    x: &X::Scale,
+    tick_margin: usize,
+    width: f64,
+    full_width: f64,
+    color: Hsla,
+) -> Vec<AxisText>
+where
+    X: XAxisValue,
+{
+    // Keep the labels at the edges inside the chart.
+    let align = |tick: f64| {
+        if tick < X_TICK_WIDTH / 4. {
+            TextAlign::Left
+        } else if tick > width - X_TICK_WIDTH / 4. {
+            TextAlign::Right
+        } else {
+            TextAlign::Center
+        }
+    };
+    let in_width = |tick: &f64| (0. ..=width).contains(tick);
+
+    let texts = data
+        .iter()
+        .enumerate()
+        .filter_map(|(i, d)| {
+            if (i + 1) % tick_margin != 0 {
+                return None;
+            }
+
+            let value = x_fn(d);
+            let text = value.label()?;
+            x.tick(&value)
+                .filter(in_width)
+                .map(|x_tick| AxisText::new(text, x_tick, color).align(align(x_tick)))
+        })
+        .collect::<Vec<_>>();
+    if !texts.is_empty() {
+        return texts;
+    }
+
+    let count = (full_width / X_TICK_WIDTH).round().max(2.) as usize;
+    x.axis_ticks(count)
+        .into_iter()
+        .filter(|(tick, _)| in_width(tick))
+        .map(|(tick, text)| AxisText::new(text, tick, color).align(align(tick)))
+        .collect()
+}
+
+/// Return the gridlines and the labels of the linear x-axis in the `width`.
+fn x_axis_ticks(x: &ScaleLinear<f64>, width: f64, color: Hsla) -> (Vec<f64>, Vec<AxisText>) {
+    let count = (width / X_TICK_WIDTH).round().max(2.) as usize;
+    let ticks = x.axis_ticks(count);
+
+    let grid = ticks.iter().map(|(tick, _)| *tick).collect();
+    let texts = ticks
+        .into_iter()
+        .map(|(tick, text)| {
+            // Keep the labels at the edges inside the chart.
+            let align = if tick < X_TICK_WIDTH / 4. {
+                TextAlign::Left
+            } else if tick > width - X_TICK_WIDTH / 4. {
+                TextAlign::Right
+            } else {
+                TextAlign::Center
+            };
+            AxisText::new(text, tick, color).align(align)
+        })
+        .collect();
+    (grid, texts)
+}
+
+/// Return the range of the data in the `width`, with 2 more data at each side to keep the lines to the edges.
+///
+/// The data is sorted by x.
+fn visible_range(x_ticks: &[Option<f64>], width: f64) -> Range<usize> {
+    let visible = |tick: &Option<f64>| tick.is_some_and(|tick| (0. ..=width).contains(&tick));
+    let Some(start) = x_ticks.iter().position(visible) else {
+        // All the data is out of the width, keep the data around the width.
+        let start = x_ticks
+            .iter()
+            .rposition(|tick| tick.is_some_and(|tick| tick < 0.))
+            .unwrap_or(0);
+        return start..(start + 2).min(x_ticks.len());
+    };
+    let end = x_ticks.iter().rposition(visible).unwrap_or(start);
+    start.saturating_sub(2)..(end + 3).min(x_ticks.len())
+}
+
+/// Split the bounds to the chart and the overview at the bottom.
+fn split_overview(bounds: Bounds<Pixels>) -> (Bounds<Pixels>, Bounds<Pixels>) {
+    let overview_height = px(OVERVIEW_HEIGHT as f32);
+    let chart_height = bounds.size.height - overview_height - px(TEXT_GAP as f32 * 4.);
+    (
+        Bounds::new(bounds.origin, size(bounds.size.width, chart_height)),
+        Bounds::new(
+            point(
+                bounds.origin.x,
+                bounds.origin.y + bounds.size.height - overview_height,
+            ),
+            size(bounds.size.width, overview_height),
+        ),
+    )
+}
+
+/// Paint the overview of the lines in the full range, and the zoomed window.
+fn paint_overview<T, X>(
+    data: &[T],
+    x_fn: &Rc<dyn Fn(&T) -> X>,
+    lines: Vec<(Rc<dyn Fn(&T) -> Option<f64>>, Hsla)>,
+    x_window: (f64, f64),
+    bounds: &Bounds<Pixels>,
+    canvas: &mut Canvas,
+    cx: &mut App,
+) where
+    X: XAxisValue,
+{
+    let width = bounds.size.width.to_f64();
+    let height = bounds.size.height.to_f64();
+    let x = X::scale(data.iter().map(|d| x_fn(d)).collect(), vec![0., width]);
+    let domain = lines
+        .iter()
+        .flat_map(|(value, _)| data.iter().filter_map(|d| value(d)))
+        .collect();
+    let y = ScaleLinear::new(domain, vec![2., height - 2.]);
+
+    for (value, color) in lines {
+        let x = x.clone();
+        let x_fn = x_fn.clone();
+        let y = y.clone();
+        Line::new()
+            .data(data.iter())
+            .x(move |d| x.tick(&x_fn(d)))
+            .y(move |d| y.tick(&value(d)?))
+            .stroke(color.opacity(0.6))
+            .stroke_style(StrokeStyle::Linear)
+            .downsample(Downsample::MinMax)
+            .paint(bounds, canvas);
+    }
+
+    zoom::paint_overview_window(x_window, bounds, canvas, cx);
+}
+
+/// Return the brush to emit the range of the x values, if the chart has a brush handler.
+fn brush_fn<T, X>(
+    data: &Rc<[T]>,
+    x_fn: &Rc<dyn Fn(&T) -> X>,
+    x: &X::Scale,
+    on_brush: Option<&BrushHandler<X>>,
+) -> Option<BrushFn>
+where
+    T: 'static,
+    X: XAxisValue,
+{
+    let on_brush = on_brush?.clone();
+    let data = data.clone();
+    let x_fn = x_fn.clone();
+    let x = x.clone();
+    Some(Rc::new(move |start, end, window, cx| {
+        let start = data.get(x.least_index(start)).map(|d| x_fn(d));
+        let end = data.get(x.least_index(end)).map(|d| x_fn(d));
+        if let (Some(start), Some(end)) = (start, end) {
+            on_brush(start..=end, window, cx);
+        }
+    }))
+}
+
+/// The options of the y-axis, shared by the charts.
+#[derive(Default)]
+struct YAxis {
+    log_scale: bool,
+    /// Don't extend the linear scale to 0, e.g.: For the prices.
+    exclude_zero: bool,
+    min: Option<f64>,
+    max: Option<f64>,
+    format: NumberFormat,
+}
+
+/// Return the scale of the y-axis.
+///
+/// The linear scale starts from 0 (unless `exclude_zero`) and is extended to the nice values,
+/// the `min` and `max` of the options override the domain.
+/// The log scale is extended to include the `min` and `max`.
+fn y_scale(domain: Vec<f64>, height: f64, options: &YAxis) -> Rc<dyn Scale<f64>> {
+    if options.log_scale {
+        let domain = domain
+            .into_iter()
+            .chain(options.min)
+            .chain(options.max)
+            .collect();
+        return Rc::new(ScaleLog::new(domain, vec![10., height]).format(options.format.clone()));
+    }
+
+    let zero = (!options.exclude_zero).then_some(0.);
+    let domain = domain.into_iter().chain(zero).collect();
+    let mut scale = ScaleLinear::new(domain, vec![10., height])
+        .nice(Y_TICKS_COUNT)
+        .format(options.format.clone());
+    if let Some(min) = options.min {
+        scale = scale.domain_min(min);
+    }
+    if let Some(max) = options.max {
+        scale = scale.domain_max(max);
+    }
+    Rc::new(scale)
+}
+
+/// Return the gridlines and the labels of the y-axis.
+///
+/// The scale without ticks (e.g.: The domain has a single value) has 4 gridlines without labels.
+fn y_axis_ticks(y: &Rc<dyn Scale<f64>>, height: f64, color: Hsla) -> (Vec<f64>, Vec<AxisText>) {
+    let ticks = y.axis_ticks(Y_TICKS_COUNT);
+    if ticks.is_empty() {
+        return ((0..=3).map(|i| height * i as f64 / 4.0).collect(), vec![]);
+    }
+
+    let grid = ticks.iter().map(|(tick, _)| *tick).collect();
+    let texts = ticks
+        .into_iter()
+        .map(|(tick, text)| AxisText::new(text, tick - TEXT_HEIGHT, color))
+        .collect();
+    (grid, texts)
+}
+
+/// Paint the labels of the secondary y-axis at the right side, without gridlines.
+fn paint_y2_axis(
+    y2: &Rc<dyn Scale<f64>>,
+    bounds: &Bounds<Pixels>,
+    height: f64,
+    canvas: &mut Canvas,
+    cx: &mut App,
+) {
+    let (_, y2_label) = y_axis_ticks(y2, height, cx.theme().muted_foreground);
+    Axis::new()
+        .y(bounds.size.width - px((TEXT_GAP * 2.) as f32))
+        .y_label(y2_label.into_iter().map(|t| t.align(TextAlign::Right)))
+        .paint(bounds, canvas, cx);
+}
+
+/// Return the values of the visible series on the primary or the secondary y-axis.
+fn series_domain<T, Y>(
+    data: &[T],
+    series: &[Series<T, Y>],
+    visible: &[usize],
+    secondary: bool,
+) -> Vec<f64>
+where
+    T: 'static,
+    Y: ToPrimitive + 'static,
+{
+    visible
+        .iter()
+        .map(|ix| &series[*ix])
+        .filter(|series| series.secondary == secondary)
+        .flat_map(|series| data.iter().filter_map(series.value_fn()))
+        .collect()
+}
+
+/// Return the indices of the visible series, the series hidden by the legend are excluded.
+fn visible_series<T, Y>(series: &[Series<T, Y>], state: Option<&ChartState>) -> Vec<usize> {
+    (0..series.len())
+        .filter(|ix| !state.is_some_and(|state| state.is_hidden(*ix)))
+        .collect()
+}
+
+/// Paint the legend of the series if `legend` is true.
+///
+/// Returns the bounds to plot the chart, below the legend.
+fn paint_legend<T, Y>(
+    series: &[Series<T, Y>],
+    colors: &[Hsla],
+    legend: bool,
+    state: Option<&ChartState>,
+    bounds: Bounds<Pixels>,
+    canvas: &mut Canvas,
+    cx: &mut App,
+) -> Bounds<Pixels> {
+    if !legend {
+        return bounds;
+    }
+
+    let items = series
+        .iter()
+        .zip(colors)
+        .map(|(series, color)| (series.name.clone(), *color))
+        .collect();
+    Legend::new(items, state.cloned()).paint(&bounds, canvas, cx);
+
+    let legend_height = px(LEGEND_HEIGHT as f32);
+    Bounds::new(
+        point(bounds.origin.x, bounds.origin.y + legend_height),
+        size(bounds.size.width, bounds.size.height - legend_height),
+    )
+}
@@ -1,25 +1,39 @@
 use std::rc::Rc;
 
-use gpui::{App, Bounds, Hsla, Pixels, Window};
+use gpui::{point, AnyElement, App, Bounds, Hsla, IntoElement, Pixels, SharedString, Window};
 use gpui_component_macros::IntoPlot;
 use num_traits::Zero;
 
 use crate::{
     plot::{
-        shape::{Arc, Pie},
-        Plot,
+        shape::{Arc, ArcData, Pie},
+        Canvas, Plot,
     },
     ActiveTheme,
 };
 
+use super::{
+    hover::{self, ClickFn, TooltipFn},
+    transition::{self, Keyframe},
+    ChartState,
+};
+
+/// The outer radius grows by this size for the hovered slice.
+const HOVER_RADIUS: f64 = 4.;
+
 #[derive(IntoPlot)]
 pub struct PieChart<T: 'static> {
-    data: Vec<T>,
+    data: Rc<[T]>,
     inner_radius: f64,
     outer_radius: f64,
     pad_angle: f64,
     value: Option<Rc<dyn Fn(&T) -> f64>>,
     color: Option<Rc<dyn Fn(&T) -> Hsla>>,
+    state: Option<ChartState>,
+    tooltip: Option<TooltipFn<T>>,
+    on_click: Option<ClickFn<T>>,
+    animate: bool,
+    key: Option<Rc<dyn Fn(&T) -> SharedString>>,
 }
 
 impl<T> PieChart<T> {
@@ -34,6 +48,11 @@ impl<T> PieChart<T> {
             pad_angle: 0.,
             value: None,
             color: None,
+            state: None,
+            tooltip: None,
+            on_click: None,
+            animate: false,
+            key: None,
         }
     }
 
@@ -64,10 +83,71 @@ impl<T> PieChart<T> {
         self.color = Some(Rc::new(move |t| color(t).into()));
         self
     }
+
+    /// Set the state of the chart, to hover the slices.
+    pub fn state(mut self, state: &ChartState) -> Self {
+        self.state = Some(state.clone());
+        self
+    }
+
+    /// Set the tooltip of the hovered slice, the hover is tracked by the [`ChartState`].
+    pub fn tooltip<E>(mut self, tooltip: impl Fn(&T, &mut Window, &mut App) -> E + 'static) -> Self
+    where
+        E: IntoElement,
+    {
+        self.tooltip = Some(Rc::new(move |d, window, cx| {
+            tooltip(d, window, cx).into_any_element()
+        }));
+        self
+    }
+
+    /// Set the handler of clicking a slice, e.g.: To drill down into the item.
+    pub fn on_click(mut self, handler: impl Fn(&T, &mut Window, &mut App) + 'static) -> Self {
+        self.on_click = Some(Rc::new(handler));
+        self
+    }
+
+    /// Animate the slices when the data is changed, the angles are interpolated between the keyed slices.
+    ///
+    /// The inserted slices open from the start angle and the removed slices close,
+    /// it requires the [`ChartState`] to keep the slices between the paints.
+    pub fn animate(mut self) -> Self {
+        self.animate = true;
+        self
+    }
+
+    /// Set the key of each slice to animate, default is the index of the data.
+    pub fn key<S>(mut self, key: impl Fn(&T) -> S + 'static) -> Self
+    where
+        S: Into<SharedString> + 'static,
+    {
+        self.key = Some(Rc::new(move |t| key(t).into()));
+        self
+    }
 }
 
 impl<T> Plot for PieChart<T> {
+    fn prepaint(
+        &mut self,
+        bounds: Bounds<Pixels>,
+        window: &mut Window,
+        cx: &mut App,
+    ) -> Option<AnyElement> {
+        hover::prepaint_tooltip(
+            &self.data,
+            self.tooltip.as_ref(),
+            self.state.as_ref(),
+            bounds,
+            window,
+            cx,
+        )
+    }
+
     fn paint(&mut self, bounds: Bounds<Pixels>, window: &mut Window, cx: &mut App) {
+        self.paint_canvas(bounds, &mut Canvas::Window(window, None), cx);
+    }
+
+    fn paint_canvas(&mut self, bounds: Bounds<Pixels>, canvas: &mut Canvas, cx: &mut App) {
         let Some(value_fn) = self.value.as_ref() else {
             return;
         };
@@ -81,22 +161,95 @@ impl<T> Plot for PieChart<T> {
         let arc = Arc::new()
             .inner_radius(self.inner_radius)
             .outer_radius(outer_radius);
+        let hovered_arc = Arc::new()
+            .inner_radius(self.inner_radius)
+            .outer_radius(outer_radius + HOVER_RADIUS);
         let value_fn = value_fn.clone();
         let mut pie = Pie::<T>::new().value(move |d| Some(value_fn(d)));
         pie = pie.pad_angle(self.pad_angle);
         let arcs = pie.arcs(&self.data);
 
-        for a in &arcs {
-            arc.paint(
-                a,
-                if let Some(color_fn) = self.color.as_ref() {
-                    color_fn(a.data)
+        let state = self.state.as_ref();
+        let color = |d: &T| {
+            if let Some(color_fn) = self.color.as_ref() {
+                color_fn(d)
+            } else {
+                cx.theme().chart_2
+            }
+        };
+
+        // Animate the slices `[start_angle, end_angle]`.
+        let frames = if self.animate {
+            let targets = arcs
+                .iter()
+                .map(|a| {
+                    let key = match self.key.as_ref() {
+                        Some(key_fn) => key_fn(a.data),
+                        None => a.index.to_string().into(),
+                    };
+                    Keyframe::new(
+                        key,
+                        a.index,
+                        vec![a.start_angle, a.end_angle],
+                        color(a.data),
+                    )
+                })
+                .collect();
+            let close = |item: &Keyframe| {
+                let mut item = item.clone();
+                item.values[1] = item.values[0];
+                item
+            };
+            transition::animate(state, targets, close, close, bounds, canvas)
+        } else {
+            None
+        };
+
+        if let Some(frames) = frames {
+            for item in frames {
+                let [start_angle, end_angle] = item.values[..] else {
+                    continue;
+                };
+                let a = ArcData {
+                    data: &(),
+                    index: item.group,
+                    value: 0.,
+                    start_angle,
+                    end_angle,
+                    pad_angle: self.pad_angle,
+                };
+                arc.paint(&a, item.color, &bounds, canvas);
+            }
+        } else {
+            let hovered = state.and_then(|state| state.hovered_index());
+            for a in &arcs {
+                if hovered == Some(a.index) {
+                    hovered_arc.paint(a, color(a.data), &bounds, canvas);
                 } else {
-                    cx.theme().chart_2
-                },
-                &bounds,
-                window,
-            );
+                    arc.paint(a, color(a.data), &bounds, canvas);
+                }
+            }
         }
+
+        // Hover and click the slice of the mouse.
+        let data = self.data.clone();
+        let center = point(
+            bounds.size.width.to_f64() / 2.,
+            bounds.size.height.to_f64() / 2.,
+        );
+        hover::on_mouse_event(
+            &self.data,
+            state,
+            self.on_click.as_ref(),
+            bounds,
+            move |p| {
+                let p = point(p.x.to_f64() - center.x, p.y.to_f64() - center.y);
+                pie.arcs(&data)
+                    .iter()
+                    .find(|a| arc.contains(a, p))
+                    .map(|a| a.index)
+            },
+            canvas,
+        );
     }
 }
@@ -0,0 +1,288 @@
+use std::{f64::consts::TAU, rc::Rc};
+
+use gpui::{point, px, App, Bounds, Pixels, Point, SharedString, TextAlign, Window};
+use gpui_component_macros::IntoPlot;
+use num_traits::{Num, ToPrimitive};
+
+use crate::{
+    plot::{
+        label::{Label, Text, TEXT_GAP, TEXT_HEIGHT},
+        polygon_path,
+        scale::{Scale, ScaleLinear, Sealed},
+        Canvas, Plot, PlotPath,
+    },
+    ActiveTheme,
+};
+
+use super::{paint_legend, series::series_color, visible_series, ChartState, Series};
+
+/// The count of the rings of the grid.
+const RINGS_COUNT: usize = 4;
+/// The space of the axis labels around the chart.
+const LABEL_SPACE: f64 = 40.;
+
+/// A radar (spider) chart, each data item is an axis from the center and each series is a polygon.
+///
+/// It's used to compare the series in multiple metrics.
+#[derive(IntoPlot)]
+pub struct RadarChart<T, Y>
+where
+    T: 'static,
+    Y: Copy + PartialOrd + Num + ToPrimitive + Sealed + 'static,
+{
+    data: Rc<[T]>,
+    axis: Option<Rc<dyn Fn(&T) -> SharedString>>,
+    series: Vec<Series<T, Y>>,
+    max: Option<f64>,
+    legend: bool,
+    state: Option<ChartState>,
+}
+
+impl<T, Y> RadarChart<T, Y>
+where
+    Y: Copy + PartialOrd + Num + ToPrimitive + Sealed + 'static,
+{
+    pub fn new<I>(data: I) -> Self
+    where
+        I: IntoIterator<Item = T>,
+    {
+        Self {
+            data: data.into_iter().collect(),
+            axis: None,
+            series: vec![],
+            max: None,
+            legend: false,
+            state: None,
+        }
+    }
+
+    /// Set the label of the axis of each data item.
+    pub fn axis<S>(mut self, axis: impl Fn(&T) -> S + 'static) -> Self
+    where
+        S: Into<SharedString> + 'static,
+    {
+        self.axis = Some(Rc::new(move |t| axis(t).into()));
+        self
+    }
+
+    /// Add a series without name.
+    pub fn y(mut self, y: impl Fn(&T) -> Y + 'static) -> Self {
+        self.series.push(Series::new("", y));
+        self
+    }
+
+    /// Add a series, each series is drawn as a polygon.
+    pub fn series(mut self, series: Series<T, Y>) -> Self {
+        self.series.push(series);
+        self
+    }
+
+    /// Set the max value of the axes, default is the nice value above the max of the data.
+    pub fn max(mut self, max: Y) -> Self {
+        self.max = max.to_f64();
+        self
+    }
+
+    /// Show the legend of the series at the top.
+    pub fn legend(mut self) -> Self {
+        self.legend = true;
+        self
+    }
+
+    /// Set the state of the chart, to toggle the series by clicking the legend.
+    pub fn state(mut self, state: &ChartState) -> Self {
+        self.state = Some(state.clone());
+        self
+    }
+}
+
+/// The scale of the radius from 0 to a nice value above the values, or to the `max`.
+fn radius_scale(
+    values: impl IntoIterator<Item = f64>,
+    radius: f64,
+    max: Option<f64>,
+) -> ScaleLinear<f64> {
+    let mut domain = values
+        .into_iter()
+        .filter(|v| v.is_finite())
+        .collect::<Vec<_>>();
+    domain.push(0.);
+    let r = ScaleLinear::new(domain, vec![0., radius])
+        .ascending()
+        .nice(RINGS_COUNT);
+    match max {
+        Some(max) => r.domain_max(max),
+        None => r,
+    }
+}
+
+/// The point at `r` from the `center` on the axis `ix` of the `n` axes.
+///
+/// The axes start from 12 o'clock and go clockwise.
+fn vertex(center: Point<f64>, ix: usize, n: usize, r: f64) -> Point<f64> {
+    let angle = ix as f64 / n.max(1) as f64 * TAU;
+    point(center.x + r * angle.sin(), center.y - r * angle.cos())
+}
+
+impl<T, Y> Plot for RadarChart<T, Y>
+where
+    Y: Copy + PartialOrd + Num + ToPrimitive + Sealed + 'static,
+{
+    fn paint(&mut self, bounds: Bounds<Pixels>, window: &mut Window, cx: &mut App) {
+        self.paint_canvas(bounds, &mut Canvas::Window(window, None), cx);
+    }
+
+    fn paint_canvas(&mut self, bounds: Bounds<Pixels>, canvas: &mut Canvas, cx: &mut App) {
+        // A polygon needs 3 axes at least.
+        if self.series.is_empty() || self.data.len() < 3 {
+            return;
+        }
+
+        let colors = self
+            .series
+            .iter()
+            .enumerate()
+            .map(|(ix, series)| series.color.unwrap_or_else(|| series_color(ix, cx)))
+            .collect::<Vec<_>>();
+        let state = self.state.as_ref();
+        let bounds = paint_legend(
+            &self.series,
+            &colors,
+            self.legend,
+            state,
+            bounds,
+            canvas,
+            cx,
+        );
+        let visible = visible_series(&self.series, state);
+
+        let width = bounds.size.width.to_f64();
+        let height = bounds.size.height.to_f64();
+        let radius = (width.min(height) / 2. - LABEL_SPACE).max(0.);
+        let center = point(width / 2., height / 2.);
+
+        let r = radius_scale(
+            visible
+                .iter()
+                .flat_map(|ix| self.data.iter().filter_map(self.series[*ix].value_fn())),
+            radius,
+            self.max,
+        );
+
+        let n = self.data.len();
+        let vertex = |ix: usize, r: f64| vertex(center, ix, n, r);
+
+        // Draw the grid, the rings and the spokes.
+        let border = cx.theme().border;
+        for tick in r.ticks(RINGS_COUNT) {
+            let Some(ring) = r.tick(&tick).filter(|ring| *ring > 0.) else {
+                continue;
+            };
+            let points = (0..=n).map(|ix| vertex(ix % n, ring)).collect::<Vec<_>>();
+            canvas.paint_path(polygon_path(&points, &bounds), border);
+        }
+        for ix in 0..n {
+            canvas.paint_path(polygon_path(&[center, vertex(ix, radius)], &bounds), border);
+        }
+
+        // Draw the series, the missing values are at the center.
+        for ix in visible.iter().copied() {
+            let value = self.series[ix].value_fn();
+            let points = self
+                .data
+                .iter()
+                .enumerate()
+                .map(|(i, d)| {
+                    let v = value(d).and_then(|v| r.tick(&v)).unwrap_or(0.);
+                    let p = vertex(i, v.clamp(0., radius));
+                    point(
+                        px((p.x + bounds.origin.x.to_f64()) as f32),
+                        px((p.y + bounds.origin.y.to_f64()) as f32),
+                    )
+                })
+                .collect::<Vec<_>>();
+
+            let mut fill = PlotPath::fill();
+            fill.add_polygon(&points, true);
+            canvas.paint_path(fill, colors[ix].opacity(0.2));
+
+            let mut stroke = PlotPath::stroke(px(2.));
+            stroke.add_polygon(&points, true);
+            canvas.paint_path(stroke, colors[ix]);
+        }
+
+        // Draw the labels of the axes outside the grid.
+        if let Some(axis) = self.axis.as_ref() {
+            let color = cx.theme().muted_foreground;
+            let labels = self.data.iter().enumerate().map(|(ix, d)| {
+                let p = vertex(ix, radius + TEXT_GAP * 2.);
+                let align = if (p.x - center.x).abs() < 1. {
+                    TextAlign::Center
+                } else if p.x > center.x {
+                    TextAlign::Left
+                } else {
+                    TextAlign::Right
+                };
+                // Keep the labels above the top and below the bottom away from the grid.
+                let y = if p.y < center.y - 1. {
+                    p.y - TEXT_HEIGHT
+                } else if p.y > center.y + 1. {
+                    p.y
+                } else {
+                    p.y - TEXT_HEIGHT / 2.
+                };
+                Text::new(axis(d), point(p.x, y), color).align(align)
+            });
+            Label::from(labels).paint(&bounds, canvas, cx);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use gpui::{point, Point};
+
+    use super::{radius_scale, vertex};
+    use crate::plot::scale::Scale as _;
+
+    fn assert_point(p: Point<f64>, x: f64, y: f64) {
+        assert!(
+            (p.x - x).abs() < 1e-9 && (p.y - y).abs() < 1e-9,
+            "{:?} != ({}, {})",
+            p,
+            x,
+            y
+        );
+    }
+
+    #[test]
+    fn test_vertex() {
+        let center = point(50., 50.);
+        assert_point(vertex(center, 0, 4, 10.), 50., 40.);
+        assert_point(vertex(center, 1, 4, 10.), 60., 50.);
+        assert_point(vertex(center, 2, 4, 10.), 50., 60.);
+        assert_point(vertex(center, 3, 4, 10.), 40., 50.);
+        assert_point(vertex(center, 4, 4, 10.), 50., 40.);
+        assert_point(vertex(center, 1, 4, 0.), 50., 50.);
+        assert_point(vertex(center, 0, 0, 10.), 50., 40.);
+    }
+
+    #[test]
+    fn test_radius_scale() {
+        // The max 72 is extended to 80.
+        let r = radius_scale([12., 72., f64::NAN], 100., None);
+        assert_eq!(r.tick(&0.), Some(0.));
+        assert_eq!(r.tick(&40.), Some(50.));
+        assert_eq!(r.tick(&80.), Some(100.));
+
+        let r = radius_scale([12., 72.], 100., Some(200.));
+        assert_eq!(r.tick(&100.), Some(50.));
+
+        // The values are all 0 or missing, there is nothing to scale.
+        for values in [vec![], vec![0., 0.], vec![f64::NAN]] {
+            let r = radius_scale(values, 100., None);
+            assert_eq!(r.tick(&0.), None);
+            assert!(r.ticks(4).is_empty());
+        }
+    }
+}
@@ -0,0 +1,296 @@
+use std::rc::Rc;
+
+use gpui::{px, size, AnyElement, App, Bounds, Hsla, IntoElement, Pixels, Window};
+use gpui_component_macros::IntoPlot;
+use num_traits::{Num, ToPrimitive};
+
+use crate::{
+    plot::{
+        origin_point,
+        scale::{Scale, ScaleLinear, Sealed},
+        Axis, Canvas, Grid, NumberFormat, Plot, Quad, AXIS_GAP,
+    },
+    ActiveTheme,
+};
+
+use super::{
+    hover::{self, ClickFn, TooltipFn},
+    x_axis_ticks, y_axis_ticks, y_scale, ChartState, YAxis, X_TICK_WIDTH,
+};
+
+/// The radius of the dots, and the min radius of the bubbles.
+const DOT_RADIUS: f64 = 4.;
+/// The max radius of the bubbles.
+const MAX_BUBBLE_RADIUS: f64 = 24.;
+/// The distance out of a dot to hover it.
+const HOVER_DISTANCE: f64 = 4.;
+
+#[derive(IntoPlot)]
+pub struct ScatterChart<T, X, Y>
+where
+    T: 'static,
+    X: Copy + PartialOrd + Num + ToPrimitive + Sealed + 'static,
+    Y: Copy + PartialOrd + Num + ToPrimitive + Sealed + 'static,
+{
+    data: Rc<[T]>,
+    x: Option<Rc<dyn Fn(&T) -> X>>,
+    y: Option<Rc<dyn Fn(&T) -> Y>>,
+    size: Option<Rc<dyn Fn(&T) -> f64>>,
+    fill: Option<Rc<dyn Fn(&T) -> Hsla>>,
+    x_format: NumberFormat,
+    y_axis: YAxis,
+    state: Option<ChartState>,
+    tooltip: Option<TooltipFn<T>>,
+    on_click: Option<ClickFn<T>>,
+}
+
+impl<T, X, Y> ScatterChart<T, X, Y>
+where
+    X: Copy + PartialOrd + Num + ToPrimitive + Sealed + 'static,
+    Y: Copy + PartialOrd + Num + ToPrimitive + Sealed + 'static,
+{
+    pub fn new<I>(data: I) -> Self
+    where
+        I: IntoIterator<Item = T>,
+    {
+        Self {
+            data: data.into_iter().collect(),
+            x: None,
+            y: None,
+            size: None,
+            fill: None,
+            x_format: NumberFormat::default(),
+            y_axis: YAxis {
+                exclude_zero: true,
+                ..Default::default()
+            },
+            state: None,
+            tooltip: None,
+            on_click: None,
+        }
+    }
+
+    pub fn x(mut self, x: impl Fn(&T) -> X + 'static) -> Self {
+        self.x = Some(Rc::new(x));
+        self
+    }
+
+    pub fn y(mut self, y: impl Fn(&T) -> Y + 'static) -> Self {
+        self.y = Some(Rc::new(y));
+        self
+    }
+
+    /// Set the size of each dot to draw a bubble chart, the area of the bubble grows with the value.
+    pub fn size(mut self, size: impl Fn(&T) -> f64 + 'static) -> Self {
+        self.size = Some(Rc::new(size));
+        self
+    }
+
+    /// Set the fill color of each dot, default is `chart_1` of the theme.
+    pub fn fill<H>(mut self, fill: impl Fn(&T) -> H + 'static) -> Self
+    where
+        H: Into<Hsla> + 'static,
+    {
+        self.fill = Some(Rc::new(move |t| fill(t).into()));
+        self
+    }
+
+    /// Set the format of the x-axis labels, e.g.: `NumberFormat::Percent`.
+    pub fn x_format(mut self, format: NumberFormat) -> Self {
+        self.x_format = format;
+        self
+    }
+
+    /// Set the format of the y-axis labels, e.g.: `NumberFormat::Percent`.
+    pub fn y_format(mut self, format: NumberFormat) -> Self {
+        self.y_axis.format = format;
+        self
+    }
+
+    /// Set the state of the chart, to hover the dots.
+    pub fn state(mut self, state: &ChartState) -> Self {
+        self.state = Some(state.clone());
+        self
+    }
+
+    /// Set the tooltip of the hovered dot, the hover is tracked by the [`ChartState`].
+    pub fn tooltip<E>(mut self, tooltip: impl Fn(&T, &mut Window, &mut App) -> E + 'static) -> Self
+    where
+        E: IntoElement,
+    {
+        self.tooltip = Some(Rc::new(move |d, window, cx| {
+            tooltip(d, window, cx).into_any_element()
+        }));
+        self
+    }
+
+    /// Set the handler of clicking a dot, e.g.: To drill down into the item.
+    pub fn on_click(mut self, handler: impl Fn(&T, &mut Window, &mut App) + 'static) -> Self {
+        self.on_click = Some(Rc::new(handler));
+        self
+    }
+
+    /// Return the radius of each dot, the radius of the bubbles grows with the square root of the size.
+    fn radius(&self) -> Vec<f64> {
+        let Some(size_fn) = self.size.as_ref() else {
+            return vec![DOT_RADIUS; self.data.len()];
+        };
+
+        let sizes = self
+            .data
+            .iter()
+            .map(|d| {
+                Some(size_fn(d))
+                    .filter(|v| v.is_finite())
+                    .map(|v| v.max(0.).sqrt())
+            })
+            .collect::<Vec<_>>();
+        let r = ScaleLinear::new(
+            sizes.iter().flatten().copied().collect(),
+            vec![DOT_RADIUS, MAX_BUBBLE_RADIUS],
+        )
+        .ascending();
+        sizes
+            .iter()
+            .map(|size| size.and_then(|v| r.tick(&v)).unwrap_or(DOT_RADIUS))
+            .collect()
+    }
+}
+
+impl<T, X, Y> Plot for ScatterChart<T, X, Y>
+where
+    X: Copy + PartialOrd + Num + ToPrimitive + Sealed + 'static,
+    Y: Copy + PartialOrd + Num + ToPrimitive + Sealed + 'static,
+{
+    fn prepaint(
+        &mut self,
+        bounds: Bounds<Pixels>,
+        window: &mut Window,
+        cx: &mut App,
+    ) -> Option<AnyElement> {
+        hover::prepaint_tooltip(
+            &self.data,
+            self.tooltip.as_ref(),
+            self.state.as_ref(),
+            bounds,
+            window,
+            cx,
+        )
+    }
+
+    fn paint(&mut self, bounds: Bounds<Pixels>, window: &mut Window, cx: &mut App) {
+        self.paint_canvas(bounds, &mut Canvas::Window(window, None), cx);
+    }
+
+    fn paint_canvas(&mut self, bounds: Bounds<Pixels>, canvas: &mut Canvas, cx: &mut App) {
+        let (Some(x_fn), Some(y_fn)) = (self.x.as_ref(), self.y.as_ref()) else {
+            return;
+        };
+
+        let width = bounds.size.width.to_f64();
+        let height = bounds.size.height.to_f64() - AXIS_GAP;
+        let state = self.state.as_ref();
+
+        // X scale from left to right, the both scales are extended to the nice values.
+        let x_value = |d: &T| x_fn(d).to_f64().filter(|v| v.is_finite());
+        let y_value = |d: &T| y_fn(d).to_f64().filter(|v| v.is_finite());
+        let x = ScaleLinear::new(
+            self.data.iter().filter_map(x_value).collect(),
+            vec![0., width],
+        )
+        .nice((width / X_TICK_WIDTH).round().max(2.) as usize)
+        .ascending()
+        .format(self.x_format.clone());
+        let y = y_scale(
+            self.data.iter().filter_map(y_value).collect(),
+            height,
+            &self.y_axis,
+        );
+
+        // Draw axis and grid
+        let color = cx.theme().muted_foreground;
+        let (grid_x, x_label) = x_axis_ticks(&x, width, color);
+        let (grid_y, y_label) = y_axis_ticks(&y, height, color);
+
+        Axis::new()
+            .x(height)
+            .x_label(x_label)
+            .y(px(0.))
+            .y_label(y_label)
+            .stroke(cx.theme().border)
+            .paint(&bounds, canvas, cx);
+
+        Grid::new()
+            .x(grid_x)
+            .y(grid_y)
+            .stroke(cx.theme().border)
+            .dash_array(&[px(4.), px(2.)])
+            .paint(&bounds, canvas);
+
+        // The center and the radius of each dot.
+        let points = self
+            .data
+            .iter()
+            .zip(self.radius())
+            .map(|(d, r)| Some((x.tick(&x_value(d)?)?, y.tick(&y_value(d)?)?, r)))
+            .collect::<Vec<_>>();
+
+        // Draw dots, the hovered dot is drawn at the top.
+        let hovered = state.and_then(|state| state.hovered_index());
+        let default_fill = cx.theme().chart_1;
+        let dots = points
+            .iter()
+            .enumerate()
+            .filter(|(ix, _)| Some(*ix) != hovered)
+            .chain(hovered.and_then(|ix| Some((ix, points.get(ix)?))));
+        for (ix, point) in dots {
+            let Some((x, y, r)) = *point else {
+                continue;
+            };
+            let color = self
+                .fill
+                .as_ref()
+                .map_or(default_fill, |fill| fill(&self.data[ix]));
+            let (background, border_width, border_color) = if Some(ix) == hovered {
+                (color, px(2.), cx.theme().background)
+            } else {
+                (color.opacity(0.6), px(1.), color)
+            };
+
+            canvas.paint_quad(
+                Quad::fill(
+                    Bounds::new(
+                        origin_point(px((x - r) as f32), px((y - r) as f32), bounds.origin),
+                        size(px((r * 2.) as f32), px((r * 2.) as f32)),
+                    ),
+                    background,
+                )
+                .corner_radii(px(r as f32))
+                .border_widths(border_width)
+                .border_color(border_color),
+            );
+        }
+
+        // Hover and click the nearest dot of the mouse.
+        hover::on_mouse_event(
+            &self.data,
+            state,
+            self.on_click.as_ref(),
+            bounds,
+            move |point| {
+                let (mouse_x, mouse_y) = (point.x.to_f64(), point.y.to_f64());
+                points
+                    .iter()
+                    .enumerate()
+                    .filter_map(|(ix, point)| {
+                        let (x, y, r) = (*point)?;
+                        let distance = (x - mouse_x).hypot(y - mouse_y);
+                        (distance <= r + HOVER_DISTANCE).then_some((ix, distance))
+                    })
+                    .min_by(|a, b| a.1.total_cmp(&b.1))
+                    .map(|(ix, _)| ix)
+            },
+            canvas,
+        );
+    }
+}
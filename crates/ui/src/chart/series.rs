@@ -0,0 +1,63 @@
+use std::rc::Rc;
+
+use gpui::{App, Hsla, SharedString};
+use num_traits::ToPrimitive;
+
+use crate::ActiveTheme;
+
+/// A series of `LineChart`, `AreaChart`, `BarChart` or `RadarChart`.
+pub struct Series<T, Y> {
+    pub(super) name: SharedString,
+    pub(super) y: Rc<dyn Fn(&T) -> Y>,
+    pub(super) color: Option<Hsla>,
+    pub(super) secondary: bool,
+}
+
+impl<T, Y> Series<T, Y>
+where
+    T: 'static,
+    Y: ToPrimitive + 'static,
+{
+    /// Create a series with the name shown in the legend.
+    pub fn new(name: impl Into<SharedString>, y: impl Fn(&T) -> Y + 'static) -> Self {
+        Self {
+            name: name.into(),
+            y: Rc::new(y),
+            color: None,
+            secondary: false,
+        }
+    }
+
+    /// Set the color of the series, default is the chart colors of the theme in order.
+    pub fn color(mut self, color: impl Into<Hsla>) -> Self {
+        self.color = Some(color.into());
+        self
+    }
+
+    /// Plot the series on the secondary y-axis at the right side.
+    ///
+    /// The secondary series are not stacked.
+    pub fn secondary(mut self) -> Self {
+        self.secondary = true;
+        self
+    }
+
+    /// Get the function of the value as f64, the non-finite values are None.
+    pub(super) fn value_fn(&self) -> impl Fn(&T) -> Option<f64> + 'static {
+        let y = self.y.clone();
+        move |d| y(d).to_f64().filter(|v| v.is_finite())
+    }
+}
+
+/// Return the default color of the series at the index.
+pub(super) fn series_color(ix: usize, cx: &App) -> Hsla {
+    // Start from `chart_2` to keep the color of the single series.
+    let colors = [
+        cx.theme().chart_2,
+        cx.theme().chart_3,
+        cx.theme().chart_4,
+        cx.theme().chart_5,
+        cx.theme().chart_1,
+    ];
+    colors[ix % colors.len()]
+}
@@ -0,0 +1,429 @@
+use std::rc::Rc;
+
+use gpui::{
+    canvas, div, px, size, App, Bounds, Hsla, IntoElement, ParentElement, Pixels, RenderOnce,
+    Styled, Window,
+};
+use num_traits::ToPrimitive;
+
+use crate::{
+    plot::{
+        origin_point,
+        scale::{Scale, ScaleBand, ScaleLinear},
+        shape::{Bar, Line},
+        Canvas, Plot, Quad, StrokeStyle,
+    },
+    ActiveTheme,
+};
+
+/// The default width of the inline charts.
+pub(super) const INLINE_WIDTH: f32 = 80.;
+/// The size of the min, max and last markers.
+const MARKER_SIZE: f64 = 4.;
+
+/// Render the inline chart in the `width` and the line height of the text.
+pub(super) fn render_inline<P>(mut plot: P, width: Pixels, window: &Window) -> impl IntoElement
+where
+    P: Plot + 'static,
+{
+    div()
+        .flex_shrink_0()
+        .w(width)
+        .h(window.line_height())
+        .child(
+            canvas(
+                |_, _, _| {},
+                move |bounds, _, window, cx| plot.paint(bounds, window, cx),
+            )
+            .size_full(),
+        )
+}
+
+#[derive(Clone, Copy, Default, PartialEq)]
+enum SparklineKind {
+    #[default]
+    Line,
+    Bar,
+    WinLoss,
+}
+
+/// A tiny inline chart of the values without axes, e.g.: In the cells of a table or a list item.
+///
+/// The height is the line height of the text. It's drawn as a line by default,
+/// or the bars by [`bar`](Self::bar), or the win/loss bars by [`win_loss`](Self::win_loss).
+#[derive(IntoElement)]
+pub struct Sparkline {
+    values: Rc<[f64]>,
+    kind: SparklineKind,
+    width: Pixels,
+    color: Option<Hsla>,
+    negative_color: Option<Hsla>,
+    stroke_style: StrokeStyle,
+    min_marker: bool,
+    max_marker: bool,
+    last_marker: bool,
+}
+
+impl Sparkline {
+    /// Create a sparkline of the values, the values that are not numbers are skipped.
+    pub fn new<I>(values: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: ToPrimitive,
+    {
+        Self {
+            values: values
+                .into_iter()
+                .map(|v| v.to_f64().unwrap_or(f64::NAN))
+                .collect(),
+            kind: SparklineKind::Line,
+            width: px(INLINE_WIDTH),
+            color: None,
+            negative_color: None,
+            stroke_style: StrokeStyle::default(),
+            min_marker: false,
+            max_marker: false,
+            last_marker: false,
+        }
+    }
+
+    /// Draw the values as the bars from 0.
+    pub fn bar(mut self) -> Self {
+        self.kind = SparklineKind::Bar;
+        self
+    }
+
+    /// Draw the win/loss bars, the positive values are up and the negative values are down.
+    pub fn win_loss(mut self) -> Self {
+        self.kind = SparklineKind::WinLoss;
+        self
+    }
+
+    /// Set the width of the sparkline, default is 80px.
+    pub fn width(mut self, width: impl Into<Pixels>) -> Self {
+        self.width = width.into();
+        self
+    }
+
+    /// Set the color of the line or the bars, default is the first chart color of the theme.
+    pub fn color(mut self, color: impl Into<Hsla>) -> Self {
+        self.color = Some(color.into());
+        self
+    }
+
+    /// Set the color of the negative bars, default is the danger color of the theme.
+    pub fn negative_color(mut self, color: impl Into<Hsla>) -> Self {
+        self.negative_color = Some(color.into());
+        self
+    }
+
+    pub fn linear(mut self) -> Self {
+        self.stroke_style = StrokeStyle::Linear;
+        self
+    }
+
+    /// Mark the min value in the danger color.
+    pub fn min_marker(mut self) -> Self {
+        self.min_marker = true;
+        self
+    }
+
+    /// Mark the max value in the success color.
+    pub fn max_marker(mut self) -> Self {
+        self.max_marker = true;
+        self
+    }
+
+    /// Mark the last value in the color of the sparkline.
+    pub fn last_marker(mut self) -> Self {
+        self.last_marker = true;
+        self
+    }
+
+    /// Return the indices of the marked values with the colors, the later markers are painted above.
+    fn markers(&self, color: Hsla, min_color: Hsla, max_color: Hsla) -> Vec<(usize, Hsla)> {
+        let values = self
+            .values
+            .iter()
+            .copied()
+            .enumerate()
+            .filter(|(_, v)| v.is_finite());
+
+        let mut markers = vec![];
+        if self.last_marker {
+            if let Some((ix, _)) = values.clone().last() {
+                markers.push((ix, color));
+            }
+        }
+        if self.min_marker {
+            if let Some((ix, _)) = values.clone().min_by(|a, b| a.1.total_cmp(&b.1)) {
+                markers.push((ix, min_color));
+            }
+        }
+        if self.max_marker {
+            if let Some((ix, _)) = values.max_by(|a, b| a.1.total_cmp(&b.1)) {
+                markers.push((ix, max_color));
+            }
+        }
+        markers
+    }
+
+    /// The y scale of the values in the `range`, a flat line is in the middle.
+    fn y_scale(&self, with_zero: bool, range: Vec<f64>) -> ScaleLinear<f64> {
+        let mut domain = self
+            .values
+            .iter()
+            .copied()
+            .filter(|v| v.is_finite())
+            .collect::<Vec<_>>();
+        if with_zero {
+            domain.push(0.);
+        }
+
+        let scale = ScaleLinear::new(domain.clone(), range);
+        let min = domain.iter().copied().fold(f64::INFINITY, f64::min);
+        let max = domain.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+        if min == max {
+            scale.domain_min(min - 1.).domain_max(max + 1.)
+        } else {
+            scale
+        }
+    }
+
+    fn paint_line(
+        &self,
+        color: Hsla,
+        markers: &[(usize, Hsla)],
+        bounds: &Bounds<Pixels>,
+        canvas: &mut Canvas,
+    ) {
+        let width = bounds.size.width.to_f64();
+        let height = bounds.size.height.to_f64();
+
+        // Keep the space of the markers around the line.
+        let pad = MARKER_SIZE / 2.;
+        let step = (width - pad * 2.) / self.values.len().saturating_sub(1).max(1) as f64;
+        let x = move |ix: usize| pad + ix as f64 * step;
+        let y = self.y_scale(false, vec![pad, height - pad]);
+        let tick = {
+            let values = self.values.clone();
+            move |ix: usize| {
+                values
+                    .get(ix)
+                    .filter(|v| v.is_finite())
+                    .and_then(|v| y.tick(v))
+            }
+        };
+
+        Line::new()
+            .data(0..self.values.len())
+            .x(move |ix| Some(x(*ix)))
+            .y({
+                let tick = tick.clone();
+                move |ix| tick(*ix)
+            })
+            .stroke(color)
+            .stroke_width(1.5)
+            .stroke_style(self.stroke_style)
+            .paint(bounds, canvas);
+
+        for (ix, color) in markers {
+            let Some(y) = tick(*ix) else {
+                continue;
+            };
+            let marker = px(MARKER_SIZE as f32);
+            canvas.paint_quad(
+                Quad::fill(
+                    Bounds::new(
+                        origin_point(
+                            px((x(*ix) - pad) as f32),
+                            px((y - pad) as f32),
+                            bounds.origin,
+                        ),
+                        size(marker, marker),
+                    ),
+                    *color,
+                )
+                .corner_radii(marker / 2.),
+            );
+        }
+    }
+
+    fn paint_bars(
+        &self,
+        color: Hsla,
+        negative_color: Hsla,
+        markers: Vec<(usize, Hsla)>,
+        bounds: &Bounds<Pixels>,
+        canvas: &mut Canvas,
+        cx: &mut App,
+    ) {
+        let width = bounds.size.width.to_f64();
+        let height = bounds.size.height.to_f64();
+
+        let x =
+            ScaleBand::new((0..self.values.len()).collect(), vec![0., width]).padding_inner(0.2);
+        let band_width = x.band_width();
+
+        // The bars start from 0, or the win/loss bars start from the middle.
+        let (y0, y1): (f64, Rc<dyn Fn(f64) -> Option<f64>>) = match self.kind {
+            SparklineKind::WinLoss => {
+                let middle = height / 2.;
+                (
+                    middle,
+                    Rc::new(move |v| {
+                        if v > 0. {
+                            Some(0.)
+                        } else if v < 0. {
+                            Some(height)
+                        } else {
+                            None
+                        }
+                    }),
+                )
+            }
+            _ => {
+                let y = self.y_scale(true, vec![0., height]);
+                (y.tick(&0.).unwrap_or(height), Rc::new(move |v| y.tick(&v)))
+            }
+        };
+
+        Bar::new()
+            .data(
+                self.values
+                    .iter()
+                    .copied()
+                    .enumerate()
+                    .filter(|(_, v)| v.is_finite()),
+            )
+            .band_width(band_width)
+            .x(move |(ix, _)| x.tick(ix))
+            .y0(y0)
+            .y1(move |(_, v)| y1(*v))
+            .fill(move |(ix, v)| {
+                markers
+                    .iter()
+                    .rev()
+                    .find(|(marker, _)| marker == ix)
+                    .map(|(_, color)| *color)
+                    .unwrap_or(if *v < 0. { negative_color } else { color })
+            })
+            .paint(bounds, canvas, cx);
+    }
+}
+
+impl Plot for Sparkline {
+    fn paint(&mut self, bounds: Bounds<Pixels>, window: &mut Window, cx: &mut App) {
+        self.paint_canvas(bounds, &mut Canvas::Window(window, None), cx);
+    }
+
+    fn paint_canvas(&mut self, bounds: Bounds<Pixels>, canvas: &mut Canvas, cx: &mut App) {
+        if self.values.is_empty() {
+            return;
+        }
+
+        let color = self.color.unwrap_or(cx.theme().chart_1);
+        let negative_color = self.negative_color.unwrap_or(cx.theme().danger);
+        let markers = self.markers(color, cx.theme().danger, cx.theme().success);
+
+        match self.kind {
+            SparklineKind::Line => self.paint_line(color, &markers, &bounds, canvas),
+            SparklineKind::Bar | SparklineKind::WinLoss => {
+                self.paint_bars(color, negative_color, markers, &bounds, canvas, cx)
+            }
+        }
+    }
+}
+
+impl RenderOnce for Sparkline {
+    fn render(self, window: &mut Window, _: &mut App) -> impl IntoElement {
+        let width = self.width;
+        render_inline(self, width, window)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use gpui::{hsla, px, size, TestAppContext};
+
+    use super::Sparkline;
+    use crate::plot::{scale::Scale as _, Plot as _};
+
+    #[test]
+    fn test_markers() {
+        let (color, min, max) = (
+            hsla(0., 0., 0., 1.),
+            hsla(0., 1., 0.5, 1.),
+            hsla(0.3, 1., 0.5, 1.),
+        );
+        let values = [3., f64::NAN, 1., 5., 1., f64::NAN];
+
+        let sparkline = Sparkline::new(values);
+        assert!(sparkline.markers(color, min, max).is_empty());
+
+        // The NaN values are skipped, the first min and the last max are marked.
+        let sparkline = Sparkline::new(values)
+            .last_marker()
+            .min_marker()
+            .max_marker();
+        assert_eq!(
+            sparkline.markers(color, min, max),
+            vec![(4, color), (2, min), (3, max)]
+        );
+
+        let sparkline = Sparkline::new([2., 2.]).min_marker().max_marker();
+        assert_eq!(sparkline.markers(color, min, max), vec![(0, min), (1, max)]);
+
+        let sparkline = Sparkline::new([f64::NAN, f64::INFINITY])
+            .last_marker()
+            .min_marker()
+            .max_marker();
+        assert!(sparkline.markers(color, min, max).is_empty());
+    }
+
+    #[test]
+    fn test_y_scale() {
+        let y = Sparkline::new([2., 4., f64::NAN]).y_scale(false, vec![0., 20.]);
+        assert_eq!(y.tick(&2.), Some(20.));
+        assert_eq!(y.tick(&4.), Some(0.));
+
+        // The bars start from 0.
+        let y = Sparkline::new([2., 4.]).y_scale(true, vec![0., 20.]);
+        assert_eq!(y.tick(&0.), Some(20.));
+        assert_eq!(y.tick(&2.), Some(10.));
+
+        // A flat line is in the middle.
+        let y = Sparkline::new([5., 5., f64::NAN]).y_scale(false, vec![0., 20.]);
+        assert_eq!(y.tick(&5.), Some(10.));
+        let y = Sparkline::new([0., 0.]).y_scale(true, vec![0., 20.]);
+        assert_eq!(y.tick(&0.), Some(10.));
+
+        // No values to scale.
+        let y = Sparkline::new([f64::NAN]).y_scale(false, vec![0., 20.]);
+        assert_eq!(y.tick(&0.), None);
+    }
+
+    #[gpui::test]
+    fn test_to_svg(cx: &mut TestAppContext) {
+        cx.update(|cx| {
+            crate::theme::init(cx);
+
+            let svg = Sparkline::new([1., -2., 0., 3.])
+                .win_loss()
+                .color(hsla(0., 0., 0., 1.))
+                .negative_color(hsla(0., 1., 0.5, 1.))
+                .to_svg(size(px(44.), px(20.)), cx);
+
+            assert_eq!(
+                svg,
+                concat!(
+                    "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"44\" height=\"20\" viewBox=\"0 0 44 20\">\n",
+                    "<rect x=\"0\" y=\"0\" width=\"8.8\" height=\"10\" fill=\"#000000\"/>\n",
+                    "<rect x=\"11.73\" y=\"10\" width=\"8.8\" height=\"10\" fill=\"#ff0000\"/>\n",
+                    "<rect x=\"35.2\" y=\"0\" width=\"8.8\" height=\"10\" fill=\"#000000\"/>\n",
+                    "</svg>\n",
+                )
+            );
+        });
+    }
+}
@@ -0,0 +1,114 @@
+use std::{cell::RefCell, collections::HashSet, rc::Rc};
+
+use super::{
+    transition::Transition,
+    zoom::{clamp_window, Drag},
+};
+
+/// The state of a chart, keep it in the view and pass it to the chart by `state`.
+///
+/// It keeps the series hidden by clicking the legend, the data item hovered by the mouse,
+/// the zoomed window of the x-axis, and the transition of the animated chart.
+#[derive(Clone, Default)]
+pub struct ChartState(Rc<RefCell<ChartStateInner>>);
+
+#[derive(Default)]
+struct ChartStateInner {
+    hidden_series: HashSet<usize>,
+    hovered_index: Option<usize>,
+    x_window: Option<(f64, f64)>,
+    drag: Option<Drag>,
+    drag_moved: bool,
+    transition: Transition,
+}
+
+impl ChartState {
+    /// Return true if the series at the index is hidden.
+    pub fn is_hidden(&self, ix: usize) -> bool {
+        self.0.borrow().hidden_series.contains(&ix)
+    }
+
+    /// Hide or show the series at the index.
+    pub fn set_hidden(&self, ix: usize, hidden: bool) {
+        let mut state = self.0.borrow_mut();
+        if hidden {
+            state.hidden_series.insert(ix);
+        } else {
+            state.hidden_series.remove(&ix);
+        }
+    }
+
+    /// Toggle the visibility of the series at the index.
+    pub fn toggle(&self, ix: usize) {
+        let hidden = self.is_hidden(ix);
+        self.set_hidden(ix, !hidden);
+    }
+
+    /// Show all the series.
+    pub fn show_all(&self) {
+        self.0.borrow_mut().hidden_series.clear();
+    }
+
+    /// Return the index of the hovered data item.
+    pub fn hovered_index(&self) -> Option<usize> {
+        self.0.borrow().hovered_index
+    }
+
+    /// Set the index of the hovered data item, returns true if it's changed.
+    pub fn set_hovered_index(&self, ix: Option<usize>) -> bool {
+        let mut state = self.0.borrow_mut();
+        let changed = state.hovered_index != ix;
+        state.hovered_index = ix;
+        changed
+    }
+
+    /// Return the visible window of the x-axis, in the ratio of the full range.
+    ///
+    /// Default is `(0., 1.)` to show the full range.
+    pub fn x_window(&self) -> (f64, f64) {
+        self.0.borrow().x_window.unwrap_or((0., 1.))
+    }
+
+    /// Set the visible window of the x-axis, in the ratio of the full range.
+    pub fn set_x_window(&self, start: f64, end: f64) {
+        self.0.borrow_mut().x_window = Some(clamp_window(start, end));
+    }
+
+    /// Reset the zoom to show the full range.
+    pub fn reset_zoom(&self) {
+        self.0.borrow_mut().x_window = None;
+    }
+
+    /// Return true if the x-axis is zoomed in.
+    pub fn is_zoomed(&self) -> bool {
+        self.x_window() != (0., 1.)
+    }
+
+    pub(super) fn drag(&self) -> Option<Drag> {
+        self.0.borrow().drag
+    }
+
+    /// Start a new drag by the mouse down, or None to not drag.
+    pub(super) fn start_drag(&self, drag: Option<Drag>) {
+        let mut state = self.0.borrow_mut();
+        state.drag = drag;
+        state.drag_moved = false;
+    }
+
+    pub(super) fn set_drag(&self, drag: Option<Drag>) {
+        self.0.borrow_mut().drag = drag;
+    }
+
+    /// Return true if the mouse is moved since the last mouse down, the click is ignored.
+    pub(super) fn drag_moved(&self) -> bool {
+        self.0.borrow().drag_moved
+    }
+
+    pub(super) fn set_drag_moved(&self) {
+        self.0.borrow_mut().drag_moved = true;
+    }
+
+    pub(super) fn with_transition<R>(&self, f: impl FnOnce(&mut Transition) -> R) -> R {
+        f(&mut self.0.borrow_mut().transition)
+    }
+}
@@ -0,0 +1,322 @@
+use std::time::{Duration, Instant};
+
+use gpui::{Bounds, Hsla, Pixels, SharedString};
+
+use crate::{animation::cubic_bezier, plot::Canvas};
+
+use super::ChartState;
+
+/// The duration of the transitions when the data is changed.
+const DURATION: Duration = Duration::from_millis(400);
+
+/// A keyed item of the chart to animate.
+///
+/// The `values` are in pixels, e.g.: `[x, width, y0, y1]` of a bar, or `[start_angle, end_angle]` of a slice.
+#[derive(Clone, Debug, PartialEq)]
+pub(super) struct Keyframe {
+    pub key: SharedString,
+    /// The group of the item, e.g.: The index of the series.
+    pub group: usize,
+    pub values: Vec<f64>,
+    pub color: Hsla,
+}
+
+impl Keyframe {
+    pub fn new(key: impl Into<SharedString>, group: usize, values: Vec<f64>, color: Hsla) -> Self {
+        Self {
+            key: key.into(),
+            group,
+            values,
+            color,
+        }
+    }
+
+    /// Interpolate to the `other` by `t` in `[0, 1]`, the values are taken from `other` if the lengths are different.
+    fn interpolate(&self, other: &Self, t: f64) -> Self {
+        let lerp = |a: f64, b: f64| a + (b - a) * t;
+        let values = if self.values.len() == other.values.len() {
+            self.values
+                .iter()
+                .zip(&other.values)
+                .map(|(a, b)| lerp(*a, *b))
+                .collect()
+        } else {
+            other.values.clone()
+        };
+        let (a, b) = (self.color, other.color);
+
+        Self {
+            key: other.key.clone(),
+            group: other.group,
+            values,
+            color: Hsla {
+                h: lerp(a.h as f64, b.h as f64) as f32,
+                s: lerp(a.s as f64, b.s as f64) as f32,
+                l: lerp(a.l as f64, b.l as f64) as f32,
+                a: lerp(a.a as f64, b.a as f64) as f32,
+            },
+        }
+    }
+}
+
+/// An item in the transition, it moves from the `from` to the `to`.
+#[derive(Clone, Debug)]
+struct Tween {
+    from: Keyframe,
+    to: Keyframe,
+    /// The item is removed from the data, it's dropped at the end of the transition.
+    removed: bool,
+}
+
+/// The transition of the keyed items between the changes of the data.
+///
+/// When the targets are changed, the items move from where they are painted to the new targets,
+/// the inserted items enter from the `enter` values, and the removed items exit to the `exit` values.
+#[derive(Default)]
+pub(super) struct Transition {
+    tweens: Vec<Tween>,
+    started_at: Option<Instant>,
+    bounds: Option<Bounds<Pixels>>,
+}
+
+impl Transition {
+    /// Return the progress of the transition in `[0, 1]` with the easing, `1.` if it's ended.
+    fn progress(&self, now: Instant) -> f64 {
+        let Some(started_at) = self.started_at else {
+            return 1.;
+        };
+
+        let t = now.saturating_duration_since(started_at).as_secs_f32() / DURATION.as_secs_f32();
+        if t >= 1. {
+            1.
+        } else {
+            cubic_bezier(0.4, 0., 0.2, 1.)(t) as f64
+        }
+    }
+
+    /// Update the targets, a new transition is started if the targets are changed.
+    ///
+    /// If `jump` is true, e.g.: The chart is resized, the items jump to the targets without the transition.
+    pub fn update(
+        &mut self,
+        targets: Vec<Keyframe>,
+        now: Instant,
+        jump: bool,
+        enter: impl Fn(&Keyframe) -> Keyframe,
+        exit: impl Fn(&Keyframe) -> Keyframe,
+    ) {
+        let changed = self.tweens.iter().filter(|tween| !tween.removed).count() != targets.len()
+            || self
+                .tweens
+                .iter()
+                .filter(|tween| !tween.removed)
+                .zip(&targets)
+                .any(|(tween, target)| tween.to != *target);
+
+        if jump {
+            self.tweens = targets
+                .into_iter()
+                .map(|target| Tween {
+                    from: target.clone(),
+                    to: target,
+                    removed: false,
+                })
+                .collect();
+            self.started_at = None;
+            return;
+        }
+
+        if !changed {
+            return;
+        }
+
+        // Start from the painted items, the removed items in the last transition are still exiting.
+        let current = self.frames(now);
+        let mut tweens = targets
+            .into_iter()
+            .map(|target| {
+                let from = current
+                    .iter()
+                    .find(|item| item.key == target.key)
+                    .cloned()
+                    .unwrap_or_else(|| enter(&target));
+                Tween {
+                    from,
+                    to: target,
+                    removed: false,
+                }
+            })
+            .collect::<Vec<_>>();
+
+        for item in current {
+            if !tweens.iter().any(|tween| tween.to.key == item.key) {
+                tweens.push(Tween {
+                    to: exit(&item),
+                    from: item,
+                    removed: true,
+                });
+            }
+        }
+
+        self.tweens = tweens;
+        self.started_at = Some(now);
+    }
+
+    /// Return the items to paint at `now`, the removed items are included until the transition is ended.
+    pub fn frames(&self, now: Instant) -> Vec<Keyframe> {
+        let t = self.progress(now);
+        self.tweens
+            .iter()
+            .filter(|tween| t < 1. || !tween.removed)
+            .map(|tween| tween.from.interpolate(&tween.to, t))
+            .collect()
+    }
+
+    /// Return true if the transition is running at `now`.
+    pub fn is_running(&self, now: Instant) -> bool {
+        self.progress(now) < 1.
+    }
+}
+
+/// Animate the items to the `targets` by the transition in the state.
+///
+/// Return the items to paint if the transition is running, otherwise None to paint the data as it is,
+/// e.g.: The chart is not animated, or it's exporting.
+pub(super) fn animate(
+    state: Option<&ChartState>,
+    targets: Vec<Keyframe>,
+    enter: impl Fn(&Keyframe) -> Keyframe,
+    exit: impl Fn(&Keyframe) -> Keyframe,
+    bounds: Bounds<Pixels>,
+    canvas: &mut Canvas,
+) -> Option<Vec<Keyframe>> {
+    let state = state?;
+    let window = canvas.window()?;
+
+    let now = Instant::now();
+    let frames = state.with_transition(|transition| {
+        let jump = transition.bounds.is_some_and(|b| b.size != bounds.size);
+        transition.bounds = Some(bounds);
+        transition.update(targets, now, jump, enter, exit);
+        transition.is_running(now).then(|| transition.frames(now))
+    })?;
+
+    window.request_animation_frame();
+    Some(frames)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn keyframe(key: &'static str, values: Vec<f64>) -> Keyframe {
+        Keyframe::new(key, 0, values, Hsla::default())
+    }
+
+    fn grow(item: &Keyframe) -> Keyframe {
+        Keyframe {
+            values: vec![0.],
+            ..item.clone()
+        }
+    }
+
+    #[test]
+    fn test_interpolate() {
+        let a = keyframe("a", vec![0., 10.]);
+        let b = keyframe("a", vec![10., 30.]);
+        assert_eq!(a.interpolate(&b, 0.).values, vec![0., 10.]);
+        assert_eq!(a.interpolate(&b, 0.5).values, vec![5., 20.]);
+        assert_eq!(a.interpolate(&b, 1.).values, vec![10., 30.]);
+
+        let c = keyframe("a", vec![1.]);
+        assert_eq!(a.interpolate(&c, 0.5).values, vec![1.]);
+    }
+
+    #[test]
+    fn test_transition() {
+        let now = Instant::now();
+        let mut transition = Transition::default();
+        assert!(!transition.is_running(now));
+        assert!(transition.frames(now).is_empty());
+
+        // The first items enter.
+        transition.update(
+            vec![keyframe("a", vec![10.]), keyframe("b", vec![20.])],
+            now,
+            false,
+            grow,
+            grow,
+        );
+        assert!(transition.is_running(now));
+        assert_eq!(
+            transition.frames(now),
+            vec![keyframe("a", vec![0.]), keyframe("b", vec![0.])]
+        );
+
+        let end = now + DURATION;
+        assert!(!transition.is_running(end));
+        assert_eq!(
+            transition.frames(end),
+            vec![keyframe("a", vec![10.]), keyframe("b", vec![20.])]
+        );
+
+        // The same targets don't restart the transition.
+        transition.update(
+            vec![keyframe("a", vec![10.]), keyframe("b", vec![20.])],
+            end,
+            false,
+            grow,
+            grow,
+        );
+        assert!(!transition.is_running(end));
+
+        // Update "a", remove "b" and insert "c".
+        transition.update(
+            vec![keyframe("a", vec![30.]), keyframe("c", vec![40.])],
+            end,
+            false,
+            grow,
+            grow,
+        );
+        assert!(transition.is_running(end));
+        assert_eq!(
+            transition.frames(end),
+            vec![
+                keyframe("a", vec![10.]),
+                keyframe("c", vec![0.]),
+                keyframe("b", vec![20.])
+            ]
+        );
+
+        let middle = transition.frames(end + DURATION / 2);
+        assert_eq!(middle.len(), 3);
+        assert!(middle[0].values[0] > 10. && middle[0].values[0] < 30.);
+        assert!(middle[2].values[0] > 0. && middle[2].values[0] < 20.);
+
+        // The removed "b" is dropped at the end.
+        assert_eq!(
+            transition.frames(end + DURATION),
+            vec![keyframe("a", vec![30.]), keyframe("c", vec![40.])]
+        );
+    }
+
+    #[test]
+    fn test_transition_interrupted() {
+        let now = Instant::now();
+        let mut transition = Transition::default();
+        transition.update(vec![keyframe("a", vec![10.])], now, true, grow, grow);
+        assert!(!transition.is_running(now));
+        assert_eq!(transition.frames(now), vec![keyframe("a", vec![10.])]);
+
+        // Restart from the painted values in the middle of the transition.
+        transition.update(vec![keyframe("a", vec![20.])], now, false, grow, grow);
+        let middle = now + DURATION / 2;
+        let value = transition.frames(middle)[0].values[0];
+        transition.update(vec![keyframe("a", vec![0.])], middle, false, grow, grow);
+        assert_eq!(transition.frames(middle)[0].values[0], value);
+        assert_eq!(
+            transition.frames(middle + DURATION),
+            vec![keyframe("a", vec![0.])]
+        );
+    }
+}
@@ -0,0 +1,388 @@
+use std::{ops::RangeInclusive, rc::Rc};
+
+use gpui::{
+    px, size, App, Bounds, MouseButton, MouseDownEvent, MouseMoveEvent, MouseUpEvent, Pixels,
+    ScrollWheelEvent, Window,
+};
+
+use crate::{
+    plot::{origin_point, Canvas, Quad},
+    ActiveTheme,
+};
+
+use super::{hover, ChartState};
+
+/// The min span of the window, zoom in up to 1000x.
+const MIN_SPAN: f64 = 0.001;
+/// The zoom factor for each pixel of the mouse wheel.
+const ZOOM_SPEED: f64 = 0.002;
+/// The min distance in pixels to start dragging.
+const DRAG_THRESHOLD: f64 = 2.;
+/// The height of the overview at the bottom of the chart.
+pub(super) const OVERVIEW_HEIGHT: f64 = 48.;
+
+/// Handle the brush with the x-range relative to the plot, from left to right.
+pub(super) type BrushFn = Rc<dyn Fn(f64, f64, &mut Window, &mut App)>;
+
+/// Handle the brush with the range of the x values.
+pub(super) type BrushHandler<X> = Rc<dyn Fn(RangeInclusive<X>, &mut Window, &mut App)>;
+
+/// The dragging on the chart.
+#[derive(Clone, Copy)]
+pub(super) enum Drag {
+    /// Pan the window, from the x of the mouse down and the window at that time.
+    Pan { x: f64, window: (f64, f64) },
+    /// Select the x-range from `start` to `end`, relative to the plot.
+    Brush { start: f64, end: f64 },
+    /// Move the window in the overview, from the x of the mouse down and the window at that time.
+    Overview { x: f64, window: (f64, f64) },
+}
+
+/// Clamp the window in the full range, the span is kept if possible.
+pub(super) fn clamp_window(start: f64, end: f64) -> (f64, f64) {
+    let span = (end - start).clamp(MIN_SPAN, 1.);
+    let start = start.clamp(0., 1. - span);
+    (start, start + span)
+}
+
+/// Zoom the window by the factor, keep the `anchor` (the ratio in the window) at the same place.
+fn zoom_window(window: (f64, f64), anchor: f64, factor: f64) -> (f64, f64) {
+    let (start, end) = window;
+    let span = end - start;
+    let new_span = (span * factor).clamp(MIN_SPAN, 1.);
+    let new_start = start + (span - new_span) * anchor;
+    clamp_window(new_start, new_start + new_span)
+}
+
+/// Move the window by the delta of the full range.
+fn pan_window(window: (f64, f64), delta: f64) -> (f64, f64) {
+    clamp_window(window.0 + delta, window.1 + delta)
+}
+
+/// Return the x-range of the full data, to show the window in the `width`.
+pub(super) fn x_range(window: (f64, f64), width: f64) -> Vec<f64> {
+    let (start, end) = window;
+    let full_width = width / (end - start);
+    let start_x = -start * full_width;
+    vec![start_x, start_x + full_width]
+}
+
+/// Register the mouse events to zoom, pan and brush the chart.
+///
+/// - Scroll the mouse wheel with `ctrl` (`cmd` on macOS) to zoom, and the horizontal wheel
+///   to pan if `zoomable`. Otherwise the wheel scrolls the parent, e.g.: The page of the chart.
+/// - Drag to pan if `zoomable`, double click to reset the zoom.
+/// - Drag with `shift` to brush if `zoomable`, the brushed range is zoomed in.
+///   Drag to brush if the chart is not `zoomable` but has the `brush`.
+/// - Drag the window in the overview.
+///
+/// The events are only handled if the plot is not covered by other elements at the mouse,
+/// and not registered when exporting.
+pub(super) fn on_zoom_event(
+    state: &ChartState,
+    zoomable: bool,
+    brush: Option<BrushFn>,
+    bounds: Bounds<Pixels>,
+    overview: Option<Bounds<Pixels>>,
+    canvas: &mut Canvas,
+) {
+    let hitbox = canvas.hitbox().cloned();
+    let Some(window) = canvas.window() else {
+        return;
+    };
+    let view_id = window.current_view();
+    let width = bounds.size.width.to_f64();
+    if width <= 0. {
+        return;
+    }
+
+    if zoomable {
+        let state = state.clone();
+        let hitbox = hitbox.clone();
+        window.on_mouse_event(move |event: &ScrollWheelEvent, phase, window, cx| {
+            if !phase.bubble()
+                || !hover::is_hovered(hitbox.as_ref(), &bounds, event.position, window)
+            {
+                return;
+            }
+
+            let delta = event.delta.pixel_delta(window.line_height());
+            let (dx, dy) = (delta.x.to_f64(), delta.y.to_f64());
+            let x_window = state.x_window();
+            let new_window = if dy.abs() >= dx.abs() {
+                if !event.modifiers.secondary() {
+                    return;
+                }
+                let anchor = (event.position.x - bounds.origin.x).to_f64() / width;
+                zoom_window(x_window, anchor, (-dy * ZOOM_SPEED).exp())
+            } else {
+                pan_window(x_window, -dx / width * (x_window.1 - x_window.0))
+            };
+            // Let the parent scroll if the window can't be moved, e.g.: Not zoomed in.
+            if new_window == x_window {
+                return;
+            }
+
+            state.set_x_window(new_window.0, new_window.1);
+            cx.stop_propagation();
+            cx.notify(view_id);
+        });
+    }
+
+    window.on_mouse_event({
+        let state = state.clone();
+        let hitbox = hitbox.clone();
+        let brushable = zoomable || brush.is_some();
+        move |event: &MouseDownEvent, phase, window, cx| {
+            if !phase.bubble()
+                || event.button != MouseButton::Left
+                || hitbox
+                    .as_ref()
+                    .is_some_and(|hitbox| !hitbox.is_hovered(window))
+            {
+                return;
+            }
+
+            let x_window = state.x_window();
+            if let Some(overview) = overview.filter(|b| b.contains(&event.position)) {
+                // Drag the window, or center the window at the mouse out of the window.
+                let overview_width = overview.size.width.to_f64();
+                let x = (event.position.x - overview.origin.x).to_f64();
+                let ratio = x / overview_width;
+                let mut new_window = x_window;
+                if ratio < x_window.0 || ratio > x_window.1 {
+                    let half_span = (x_window.1 - x_window.0) / 2.;
+                    new_window = clamp_window(ratio - half_span, ratio + half_span);
+                    state.set_x_window(new_window.0, new_window.1);
+                }
+                state.start_drag(Some(Drag::Overview {
+                    x,
+                    window: new_window,
+                }));
+                cx.notify(view_id);
+                return;
+            }
+
+            if !bounds.contains(&event.position) {
+                return;
+            }
+
+            let x = (event.position.x - bounds.origin.x).to_f64();
+            if zoomable && event.click_count == 2 {
+                state.reset_zoom();
+                state.start_drag(None);
+                cx.notify(view_id);
+            } else if brushable && (event.modifiers.shift || !zoomable) {
+                state.start_drag(Some(Drag::Brush { start: x, end: x }));
+            } else if zoomable {
+                state.start_drag(Some(Drag::Pan {
+                    x,
+                    window: x_window,
+                }));
+            } else {
+                state.start_drag(None);
+            }
+        }
+    });
+
+    window.on_mouse_event({
+        let state = state.clone();
+        let hitbox = hitbox.clone();
+        move |event: &MouseMoveEvent, phase, window, cx| {
+            if !phase.bubble() {
+                return;
+            }
+            let Some(drag) = state.drag() else {
+                return;
+            };
+            if event.pressed_button != Some(MouseButton::Left) {
+                // The mouse is released out of the window.
+                state.set_drag(None);
+                cx.notify(view_id);
+                return;
+            }
+            // Pause the dragging when the mouse is over other elements, e.g.: A popover.
+            if hitbox
+                .as_ref()
+                .is_some_and(|hitbox| !hitbox.is_hovered(window))
+            {
+                return;
+            }
+
+            let x = (event.position.x - bounds.origin.x).to_f64();
+            let drag_x = match drag {
+                Drag::Pan { x, .. } | Drag::Overview { x, .. } => x,
+                Drag::Brush { start, .. } => start,
+            };
+            let dx = match drag {
+                Drag::Overview { .. } => {
+                    overview.map_or(0., |b| (event.position.x - b.origin.x).to_f64() - drag_x)
+                }
+                _ => x - drag_x,
+            };
+            if dx.abs() > DRAG_THRESHOLD {
+                state.set_drag_moved();
+            }
+
+            match drag {
+                Drag::Pan { window, .. } => {
+                    let new_window = pan_window(window, -dx / width * (window.1 - window.0));
+                    state.set_x_window(new_window.0, new_window.1);
+                }
+                Drag::Overview { window, .. } => {
+                    let overview_width = overview.map_or(width, |b| b.size.width.to_f64());
+                    let new_window = pan_window(window, dx / overview_width);
+                    state.set_x_window(new_window.0, new_window.1);
+                }
+                Drag::Brush { start, .. } => {
+                    state.set_drag(Some(Drag::Brush {
+                        start,
+                        end: x.clamp(0., width),
+                    }));
+                }
+            }
+            cx.notify(view_id);
+        }
+    });
+
+    window.on_mouse_event({
+        let state = state.clone();
+        move |event: &MouseUpEvent, phase, window, cx| {
+            if !phase.bubble() || event.button != MouseButton::Left {
+                return;
+            }
+            let Some(drag) = state.drag() else {
+                return;
+            };
+            state.set_drag(None);
+            // Cancel the brush if released over other elements.
+            let hovered = hitbox
+                .as_ref()
+                .is_none_or(|hitbox| hitbox.is_hovered(window));
+
+            if let Drag::Brush { start, end } = drag {
+                if hovered && state.drag_moved() {
+                    let (start, end) = (start.min(end), start.max(end));
+                    if zoomable {
+                        let (window_start, window_end) = state.x_window();
+                        let span = window_end - window_start;
+                        state.set_x_window(
+                            window_start + start / width * span,
+                            window_start + end / width * span,
+                        );
+                    }
+                    if let Some(brush) = brush.as_ref() {
+                        brush(start, end, window, cx);
+                    }
+                }
+            }
+            cx.notify(view_id);
+        }
+    });
+}
+
+/// Paint the range of the brush when dragging.
+pub(super) fn paint_brush(
+    state: &ChartState,
+    height: f64,
+    bounds: &Bounds<Pixels>,
+    canvas: &mut Canvas,
+    cx: &App,
+) {
+    let Some(Drag::Brush { start, end }) = state.drag() else {
+        return;
+    };
+
+    canvas.paint_quad(Quad::fill(
+        Bounds::new(
+            origin_point(px(start.min(end) as f32), px(0.), bounds.origin),
+            size(px((end - start).abs() as f32), px(height as f32)),
+        ),
+        cx.theme().primary.opacity(0.15),
+    ));
+}
+
+/// Paint the window in the overview, the ranges out of the window are dimmed.
+pub(super) fn paint_overview_window(
+    x_window: (f64, f64),
+    bounds: &Bounds<Pixels>,
+    canvas: &mut Canvas,
+    cx: &App,
+) {
+    let width = bounds.size.width.to_f64();
+    let (start, end) = (x_window.0 * width, x_window.1 * width);
+    let dim = cx.theme().background.opacity(0.6);
+
+    canvas.paint_quad(Quad::fill(
+        Bounds::new(bounds.origin, size(px(start as f32), bounds.size.height)),
+        dim,
+    ));
+    canvas.paint_quad(Quad::fill(
+        Bounds::new(
+            origin_point(px(end as f32), px(0.), bounds.origin),
+            size(px((width - end) as f32), bounds.size.height),
+        ),
+        dim,
+    ));
+    canvas.paint_quad(
+        Quad::fill(
+            Bounds::new(
+                origin_point(px(start as f32), px(0.), bounds.origin),
+                size(px((end - start) as f32), bounds.size.height),
+            ),
+            gpui::transparent_black(),
+        )
+        .border_widths(px(1.))
+        .border_color(cx.theme().border)
+        .corner_radii(px(2.)),
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_window(window: (f64, f64), expected: (f64, f64)) {
+        assert!(
+            (window.0 - expected.0).abs() < 1e-9 && (window.1 - expected.1).abs() < 1e-9,
+            "{:?} != {:?}",
+            window,
+            expected
+        );
+    }
+
+    #[test]
+    fn test_clamp_window() {
+        assert_window(clamp_window(0., 1.), (0., 1.));
+        assert_window(clamp_window(-0.2, 0.3), (0., 0.5));
+        assert_window(clamp_window(0.8, 1.2), (0.6, 1.));
+        assert_window(clamp_window(-1., 2.), (0., 1.));
+        assert_window(clamp_window(0.5, 0.5), (0.5, 0.5 + MIN_SPAN));
+    }
+
+    #[test]
+    fn test_zoom_window() {
+        // Zoom in at the center.
+        assert_window(zoom_window((0., 1.), 0.5, 0.5), (0.25, 0.75));
+        // Zoom in at the left, the left is kept.
+        assert_window(zoom_window((0.2, 0.6), 0., 0.5), (0.2, 0.4));
+        // Zoom out beyond the full range.
+        assert_window(zoom_window((0.2, 0.6), 0.5, 10.), (0., 1.));
+        // Zoom out at the right edge.
+        assert_window(zoom_window((0.5, 1.), 1., 1.5), (0.25, 1.));
+    }
+
+    #[test]
+    fn test_pan_window() {
+        assert_window(pan_window((0.2, 0.4), 0.1), (0.3, 0.5));
+        assert_window(pan_window((0.2, 0.4), -0.5), (0., 0.2));
+        assert_window(pan_window((0.2, 0.4), 0.8), (0.8, 1.));
+    }
+
+    #[test]
+    fn test_x_range() {
+        assert_eq!(x_range((0., 1.), 100.), vec![0., 100.]);
+        assert_eq!(x_range((0.5, 1.), 100.), vec![-100., 100.]);
+        assert_eq!(x_range((0.25, 0.5), 100.), vec![-100., 300.]);
+    }
+}
@@ -9,8 +9,10 @@ use rust_i18n::t;
 
 use crate::{
     actions::{Cancel, Confirm, SelectNext, SelectPrev},
+    fuzzy::fuzzy_search,
     h_flex,
     input::clear_button,
+    label::Label,
     list::{List, ListDelegate, ListItem},
     v_flex, ActiveTheme, Disableable as _, Icon, IconName, Sizable, Size, StyleSized, StyledExt,
 };
@@ -103,6 +105,14 @@ pub trait DropdownDelegate: Sized {
     fn perform_search(&mut self, _query: &str, _window: &mut Window, _: &mut App) -> Task<()> {
         Task::ready(())
     }
+
+    /// Return the byte positions of the matched characters in the item's title,
+    /// the matched characters will be highlighted.
+    ///
+    /// Default is None, that means no highlight.
+    fn match_positions(&self, _ix: usize) -> Option<&[usize]> {
+        None
+    }
 }
 
 impl<T: DropdownItem> DropdownDelegate for Vec<T> {
@@ -156,12 +166,20 @@ where
             .map_or(Size::Medium, |dropdown| dropdown.read(cx).size);
 
         if let Some(item) = self.delegate.get(ix) {
+            let title = item.title();
             let list_item = ListItem::new(("list-item", ix))
                 .check_icon(IconName::Check)
                 .selected(selected)
                 .input_text_size(size)
                 .list_size(size)
-                .child(div().whitespace_nowrap().child(item.title().to_string()));
+                .child(div().whitespace_nowrap().map(
+                    |this| match self.delegate.match_positions(ix) {
+                        Some(positions) if !positions.is_empty() => {
+                            this.child(Label::new(title).match_positions(positions))
+                        }
+                        _ => this.child(title.to_string()),
+                    },
+                ));
             Some(list_item)
         } else {
             None
@@ -266,9 +284,12 @@ pub struct Dropdown<D: DropdownDelegate + 'static> {
     disabled: bool,
 }
 
+/// A searchable items for the Dropdown, the items are filtered by [`crate::fuzzy`] matching
+/// of the title, and sorted by the match score.
 pub struct SearchableVec<T> {
     items: Vec<T>,
     matched_items: Vec<T>,
+    matched_positions: Vec<Vec<usize>>,
 }
 
 impl<T: DropdownItem + Clone> SearchableVec<T> {
@@ -277,16 +298,14 @@ impl<T: DropdownItem + Clone> SearchableVec<T> {
         Self {
             items: items.clone(),
             matched_items: items,
+            matched_positions: vec![],
         }
     }
 }
 
 impl<T: DropdownItem + Clone> From<Vec<T>> for SearchableVec<T> {
     fn from(items: Vec<T>) -> Self {
-        Self {
-            items: items.clone(),
-            matched_items: items,
-        }
+        Self::new(items)
     }
 }
 
@@ -320,15 +339,27 @@ impl<T: DropdownItem + Clone> DropdownDelegate for SearchableVec<T> {
     }
 
     fn perform_search(&mut self, query: &str, _window: &mut Window, _: &mut App) -> Task<()> {
-        self.matched_items = self
+        let titles = self
             .items
             .iter()
-            .filter(|item| item.title().to_lowercase().contains(&query.to_lowercase()))
-            .cloned()
+            .map(|item| item.title())
+            .collect::<Vec<_>>();
+        let matches = fuzzy_search(query, titles.iter().map(|title| title.as_ref()));
+
+        self.matched_items = matches
+            .iter()
+            .map(|(ix, _)| self.items[*ix].clone())
             .collect();
+        self.matched_positions = matches.into_iter().map(|(_, m)| m.positions).collect();
 
         Task::ready(())
     }
+
+    fn match_positions(&self, ix: usize) -> Option<&[usize]> {
+        self.matched_positions
+            .get(ix)
+            .map(|positions| positions.as_slice())
+    }
 }
 
 impl<D> DropdownState<D>
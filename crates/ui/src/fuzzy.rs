@@ -0,0 +1,248 @@
+//! A fuzzy matcher for searching in List, Dropdown or command palettes.
+//!
+//! The query is matched as a subsequence of the text, the score is higher
+//! when the matched characters are consecutive or at the start of words
+//! (after a separator or at a camelCase boundary).
+//!
+//! The matching is case-insensitive, unless the query contains uppercase characters (smart case).
+use std::ops::Range;
+
+const SCORE_MATCH: i32 = 16;
+const BONUS_FIRST_CHAR: i32 = 10;
+const BONUS_BOUNDARY: i32 = 8;
+const BONUS_CAMEL_CASE: i32 = 7;
+const BONUS_CONSECUTIVE: i32 = 8;
+const PENALTY_GAP: i32 = 3;
+const PENALTY_LEADING_MAX: i32 = 9;
+
+/// The result of a fuzzy match.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FuzzyMatch {
+    /// The score of the match, higher is better.
+    pub score: i32,
+    /// The byte offsets of the matched characters in the text, in ascending order.
+    pub positions: Vec<usize>,
+}
+
+impl FuzzyMatch {
+    /// Return the byte ranges of the matched characters in the text,
+    /// the consecutive characters will be merged into one range.
+    ///
+    /// This can be used to highlight the matched characters with `StyledText`.
+    pub fn ranges(&self, text: &str) -> Vec<Range<usize>> {
+        match_ranges(text, &self.positions)
+    }
+}
+
+/// Fuzzy match the `query` in the `text`.
+///
+/// Returns None if the query is not a subsequence of the text,
+/// an empty query will match any text with score 0.
+pub fn fuzzy_match(query: &str, text: &str) -> Option<FuzzyMatch> {
+    let case_sensitive = query.chars().any(|c| c.is_uppercase());
+    let query = query
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .map(|c| if case_sensitive { c } else { to_lower(c) })
+        .collect::<Vec<_>>();
+    if query.is_empty() {
+        return Some(FuzzyMatch::default());
+    }
+
+    let chars = text.char_indices().collect::<Vec<_>>();
+    if chars.len() < query.len() || !is_subsequence(&query, &chars, case_sensitive) {
+        return None;
+    }
+
+    let bonus = (0..chars.len())
+        .map(|ix| char_bonus(&chars, ix))
+        .collect::<Vec<_>>();
+    let eq = |q: char, c: char| {
+        if case_sensitive {
+            q == c
+        } else {
+            q == to_lower(c)
+        }
+    };
+
+    // scores[i][j] is the best score when the `query[i]` is matched at `chars[j]`,
+    // `prevs[i][j]` is the position of `query[i - 1]` for that score.
+    let n = query.len();
+    let m = chars.len();
+    let mut scores = vec![vec![None::<i32>; m]; n];
+    let mut prevs = vec![vec![0usize; m]; n];
+
+    for (j, (_, c)) in chars.iter().enumerate() {
+        if eq(query[0], *c) {
+            let leading = (j as i32 * PENALTY_GAP).min(PENALTY_LEADING_MAX);
+            let first = if j == 0 { BONUS_FIRST_CHAR } else { 0 };
+            scores[0][j] = Some(SCORE_MATCH + bonus[j] + first - leading);
+        }
+    }
+
+    for i in 1..n {
+        // The best score (and position) of `query[i - 1]` before `j - 1`, with the gap penalty.
+        let mut gapped: Option<(i32, usize)> = None;
+        for j in i..m {
+            if j >= 2 {
+                gapped = gapped.map(|(score, k)| (score - PENALTY_GAP, k));
+                if let Some(score) = scores[i - 1][j - 2] {
+                    let score = score - PENALTY_GAP;
+                    if gapped.is_none_or(|(best, _)| score > best) {
+                        gapped = Some((score, j - 2));
+                    }
+                }
+            }
+
+            if !eq(query[i], chars[j].1) {
+                continue;
+            }
+
+            let consecutive = scores[i - 1][j - 1].map(|score| (score + BONUS_CONSECUTIVE, j - 1));
+            let best = match (consecutive, gapped) {
+                (Some(a), Some(b)) => Some(if a.0 >= b.0 { a } else { b }),
+                (a, b) => a.or(b),
+            };
+
+            if let Some((score, k)) = best {
+                scores[i][j] = Some(score + SCORE_MATCH + bonus[j]);
+                prevs[i][j] = k;
+            }
+        }
+    }
+
+    let (mut j, score) = scores[n - 1]
+        .iter()
+        .enumerate()
+        .filter_map(|(j, score)| score.map(|score| (j, score)))
+        .max_by(|a, b| a.1.cmp(&b.1).then(b.0.cmp(&a.0)))?;
+
+    let mut positions = vec![0; n];
+    for i in (0..n).rev() {
+        positions[i] = chars[j].0;
+        j = prevs[i][j];
+    }
+
+    Some(FuzzyMatch { score, positions })
+}
+
+/// Fuzzy match the `query` in all the `texts`, returns the index and the match of the matched texts.
+///
+/// The results are sorted by the score in descending order,
+/// the texts with the same score keep the original order.
+pub fn fuzzy_search<'a>(
+    query: &str,
+    texts: impl IntoIterator<Item = &'a str>,
+) -> Vec<(usize, FuzzyMatch)> {
+    let mut matches = texts
+        .into_iter()
+        .enumerate()
+        .filter_map(|(ix, text)| fuzzy_match(query, text).map(|m| (ix, m)))
+        .collect::<Vec<_>>();
+    matches.sort_by(|a, b| b.1.score.cmp(&a.1.score));
+    matches
+}
+
+/// Convert the matched byte positions to byte ranges of the text,
+/// the consecutive characters will be merged into one range.
+pub fn match_ranges(text: &str, positions: &[usize]) -> Vec<Range<usize>> {
+    let mut ranges: Vec<Range<usize>> = vec![];
+    for &pos in positions {
+        let Some(c) = text.get(pos..).and_then(|s| s.chars().next()) else {
+            continue;
+        };
+        let end = pos + c.len_utf8();
+        match ranges.last_mut() {
+            Some(last) if last.end == pos => last.end = end,
+            _ => ranges.push(pos..end),
+        }
+    }
+    ranges
+}
+
+#[inline]
+fn to_lower(c: char) -> char {
+    c.to_lowercase().next().unwrap_or(c)
+}
+
+fn is_subsequence(query: &[char], chars: &[(usize, char)], case_sensitive: bool) -> bool {
+    let mut query = query.iter().peekable();
+    for (_, c) in chars {
+        let c = if case_sensitive { *c } else { to_lower(*c) };
+        if query.peek() == Some(&&c) {
+            query.next();
+        }
+    }
+    query.peek().is_none()
+}
+
+fn char_bonus(chars: &[(usize, char)], ix: usize) -> i32 {
+    let c = chars[ix].1;
+    let Some(prev) = ix.checked_sub(1).map(|ix| chars[ix].1) else {
+        return BONUS_BOUNDARY;
+    };
+
+    if !prev.is_alphanumeric() && c.is_alphanumeric() {
+        BONUS_BOUNDARY
+    } else if prev.is_lowercase() && c.is_uppercase() {
+        BONUS_CAMEL_CASE
+    } else if prev.is_alphabetic() && c.is_numeric() {
+        BONUS_CAMEL_CASE
+    } else {
+        0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{fuzzy_match, fuzzy_search, match_ranges};
+
+    #[test]
+    fn test_fuzzy_match() {
+        assert_eq!(fuzzy_match("", "hello").unwrap().score, 0);
+        assert!(fuzzy_match("abc", "ab").is_none());
+        assert!(fuzzy_match("hlo", "hello").is_some());
+        assert!(fuzzy_match("olh", "hello").is_none());
+
+        assert_eq!(
+            fuzzy_match("hlo", "hello").unwrap().positions,
+            vec![0, 3, 4]
+        );
+        // Prefer the consecutive match.
+        assert_eq!(fuzzy_match("lo", "hello").unwrap().positions, vec![3, 4]);
+        // Prefer the word boundary.
+        assert_eq!(
+            fuzzy_match("fb", "foo_bar fab").unwrap().positions,
+            vec![0, 4]
+        );
+        // Prefer the camelCase boundary.
+        assert_eq!(fuzzy_match("fb", "fooBarfb").unwrap().positions, vec![0, 3]);
+        // Positions are byte offsets.
+        assert_eq!(fuzzy_match("b", "中文b").unwrap().positions, vec![6]);
+    }
+
+    #[test]
+    fn test_smart_case() {
+        assert!(fuzzy_match("HW", "hello world").is_none());
+        assert!(fuzzy_match("hw", "Hello World").is_some());
+        assert!(fuzzy_match("HW", "Hello World").is_some());
+    }
+
+    #[test]
+    fn test_fuzzy_search() {
+        let texts = ["abc", "xaybzc", "a_b_c", "xyz"];
+        let results = fuzzy_search("abc", texts);
+        assert_eq!(
+            results.iter().map(|(ix, _)| *ix).collect::<Vec<_>>(),
+            vec![0, 2, 1]
+        );
+        assert!(results[0].1.score > results[1].1.score);
+    }
+
+    #[test]
+    fn test_match_ranges() {
+        assert_eq!(match_ranges("hello", &[0, 1, 3]), vec![0..2, 3..4]);
+        assert_eq!(match_ranges("中文b", &[0, 3, 6]), vec![0..7]);
+        assert_eq!(match_ranges("abc", &[5]), vec![]);
+    }
+}
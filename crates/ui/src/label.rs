@@ -1,9 +1,11 @@
+use std::ops::Range;
+
 use gpui::{
-    div, rems, App, IntoElement, ParentElement, RenderOnce, SharedString, StyleRefinement, Styled,
-    Window,
+    div, prelude::FluentBuilder as _, rems, App, FontWeight, HighlightStyle, IntoElement,
+    ParentElement, RenderOnce, SharedString, StyleRefinement, Styled, StyledText, Window,
 };
 
-use crate::{ActiveTheme, StyledExt};
+use crate::{fuzzy::match_ranges, ActiveTheme, StyledExt};
 
 const MASKED: &'static str = "•";
 
@@ -13,6 +15,7 @@ pub struct Label {
     label: SharedString,
     chars_count: usize,
     masked: bool,
+    highlights: Vec<Range<usize>>,
 }
 
 impl Label {
@@ -24,6 +27,7 @@ impl Label {
             label,
             chars_count,
             masked: false,
+            highlights: vec![],
         }
     }
 
@@ -31,6 +35,18 @@ impl Label {
         self.masked = masked;
         self
     }
+
+    /// Set the byte ranges of the label to highlight, e.g.: The matched text of a search.
+    pub fn highlights(mut self, ranges: impl IntoIterator<Item = Range<usize>>) -> Self {
+        self.highlights = ranges.into_iter().collect();
+        self
+    }
+
+    /// Highlight the matched characters by the byte positions of a [`crate::fuzzy::FuzzyMatch`].
+    pub fn match_positions(mut self, positions: &[usize]) -> Self {
+        self.highlights = match_ranges(&self.label, positions);
+        self
+    }
 }
 
 impl Styled for Label {
@@ -46,11 +62,28 @@ impl RenderOnce for Label {
         } else {
             self.label
         };
+        let highlight_style = HighlightStyle {
+            color: Some(cx.theme().link),
+            font_weight: Some(FontWeight::SEMIBOLD),
+            ..Default::default()
+        };
 
         div()
             .line_height(rems(1.25))
             .text_color(cx.theme().foreground)
             .refine_style(&self.style)
-            .child(text)
+            .map(|this| {
+                if self.masked || self.highlights.is_empty() {
+                    this.child(text)
+                } else {
+                    this.child(
+                        StyledText::new(text).with_highlights(
+                            self.highlights
+                                .into_iter()
+                                .map(|range| (range, highlight_style)),
+                        ),
+                    )
+                }
+            })
     }
 }
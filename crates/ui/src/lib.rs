@@ -1,3 +1,6 @@
+// The derive macros refer to the items by `gpui_component::…`, also in this crate.
+extern crate self as gpui_component;
+
 mod colors;
 mod event;
 mod focusable;
@@ -5,6 +8,7 @@ mod icon;
 #[cfg(any(feature = "inspector", debug_assertions))]
 mod inspector;
 mod kbd;
+mod measured_list;
 mod menu;
 mod root;
 mod styled;
@@ -32,6 +36,7 @@ pub mod dock;
 pub mod drawer;
 pub mod dropdown;
 pub mod form;
+pub mod fuzzy;
 pub mod highlighter;
 pub mod history;
 pub mod indicator;
@@ -57,6 +62,7 @@ pub mod tag;
 pub mod text;
 pub mod theme;
 pub mod tooltip;
+pub mod tree;
 
 #[cfg(feature = "webview")]
 pub mod webview;
@@ -71,6 +77,7 @@ pub use event::InteractiveElementExt;
 pub use focusable::FocusableCycle;
 #[cfg(any(feature = "inspector", debug_assertions))]
 pub use inspector::*;
+pub use measured_list::{v_measured_list, MeasuredList, MeasuredListState};
 pub use menu::{context_menu, popup_menu};
 pub use root::{ContextModal, Root};
 pub use styled::*;
@@ -108,6 +115,7 @@ pub fn init(cx: &mut App) {
     popover::init(cx);
     menu::init(cx);
     table::init(cx);
+    tree::init(cx);
 }
 
 #[inline]
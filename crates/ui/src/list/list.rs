@@ -1,7 +1,9 @@
 use std::ops::Range;
 use std::time::Duration;
 
-use crate::actions::{Cancel, Confirm, SelectNext, SelectPrev};
+use crate::actions::{
+    Cancel, Confirm, SelectAll, SelectNext, SelectPrev, SelectToNext, SelectToPrev,
+};
 use crate::input::InputState;
 use crate::{h_flex, Icon, Sizable as _};
 use crate::{
@@ -12,13 +14,16 @@ use crate::{
 use gpui::{
     div, prelude::FluentBuilder, uniform_list, AnyElement, AppContext, Entity, FocusHandle,
     Focusable, InteractiveElement, IntoElement, KeyBinding, Length, ListSizingBehavior,
-    MouseButton, ParentElement, Render, Styled, Task, UniformListScrollHandle, Window,
+    MouseButton, ParentElement, Render, SharedString, Styled, Task, UniformListScrollHandle,
+    Window,
 };
 use gpui::{px, App, Context, EventEmitter, MouseDownEvent, ScrollStrategy, Subscription};
 use rust_i18n::t;
 use smol::Timer;
 
 use super::loading::Loading;
+use super::section::{ListRow, ListRows};
+use super::selection::ListSelection;
 
 pub fn init(cx: &mut App) {
     let context: Option<&str> = Some("List");
@@ -28,6 +33,9 @@ pub fn init(cx: &mut App) {
         KeyBinding::new("secondary-enter", Confirm { secondary: true }, context),
         KeyBinding::new("up", SelectPrev, context),
         KeyBinding::new("down", SelectNext, context),
+        KeyBinding::new("shift-up", SelectToPrev, context),
+        KeyBinding::new("shift-down", SelectToNext, context),
+        KeyBinding::new("secondary-a", SelectAll, context),
     ]);
 }
 
@@ -39,6 +47,8 @@ pub enum ListEvent {
     Confirm(usize),
     /// Pressed ESC to deselect the item.
     Cancel,
+    /// The selected items changed in multi-selection mode, with the selected indices in ascending order.
+    SelectionChange(Vec<usize>),
 }
 
 /// A delegate for the List.
@@ -58,8 +68,44 @@ pub trait ListDelegate: Sized + 'static {
     }
 
     /// Return the number of items in the list.
+    ///
+    /// If the list has sections, this is the total number of items in all sections.
     fn items_count(&self, cx: &App) -> usize;
 
+    /// Return the number of sections, default is 0 means the list has no sections.
+    ///
+    /// The items are still addressed by a flat index (for `render_item`, `set_selected_index`...),
+    /// the items of section 0 come first, then the items of section 1, and so on.
+    fn sections_count(&self, cx: &App) -> usize {
+        0
+    }
+
+    /// Return the number of items in the given section.
+    ///
+    /// The sum of all sections must be equal to `items_count`.
+    fn section_items_count(&self, section: usize, cx: &App) -> usize {
+        0
+    }
+
+    /// Render the header of the given section.
+    ///
+    /// The header will stick to the top of the list when scrolling through the section.
+    fn render_section_header(
+        &self,
+        section: usize,
+        window: &mut Window,
+        cx: &mut Context<List<Self>>,
+    ) -> Option<AnyElement> {
+        None
+    }
+
+    /// Return the label of the section to show in the section index rail, e.g.: "A".
+    ///
+    /// If all sections return None, the section index rail will not be shown.
+    fn section_index_label(&self, section: usize, cx: &App) -> Option<SharedString> {
+        None
+    }
+
     /// Render the item at the given index.
     ///
     /// Return None will skip the item.
@@ -117,6 +163,28 @@ pub trait ListDelegate: Sized + 'static {
         cx: &mut Context<List<Self>>,
     );
 
+    /// Set the selected indices in multi-selection mode, called every time the selection changes.
+    ///
+    /// The indices are in ascending order.
+    fn set_selected_indices(
+        &mut self,
+        indices: &[usize],
+        window: &mut Window,
+        cx: &mut Context<List<Self>>,
+    ) {
+    }
+
+    /// Return the selected indices after the items changed by `perform_search`.
+    ///
+    /// The indices are changed after filtering, so the delegate should keep the selected items
+    /// (e.g.: by id) in `set_selected_indices` and return their new indices here
+    /// to keep the selection.
+    ///
+    /// Default is None, that means clear the selection after search.
+    fn selected_indices(&self, cx: &App) -> Option<Vec<usize>> {
+        None
+    }
+
     /// Set the confirm and give the selected index, this is means user have clicked the item or pressed Enter.
     ///
     /// This will always to `set_selected_index` before confirm.
@@ -157,6 +225,7 @@ pub struct List<D: ListDelegate> {
     query_input: Option<Entity<InputState>>,
     last_query: Option<String>,
     selectable: bool,
+    multi_selectable: bool,
     querying: bool,
     scrollbar_visible: bool,
     vertical_scroll_handle: UniformListScrollHandle,
@@ -165,6 +234,11 @@ pub struct List<D: ListDelegate> {
     selected_index: Option<usize>,
     right_clicked_index: Option<usize>,
     reset_on_cancel: bool,
+    selection: ListSelection,
+    /// The selected indices of the last `SelectionChange` event.
+    emitted_selection: Vec<usize>,
+    rows: ListRows,
+    first_visible_row: usize,
     _search_task: Task<()>,
     _load_more_task: Task<()>,
     _query_input_subscription: Subscription,
@@ -196,9 +270,14 @@ where
             max_height: None,
             scrollbar_visible: true,
             selectable: true,
+            multi_selectable: false,
             querying: false,
             size: Size::default(),
             reset_on_cancel: true,
+            selection: ListSelection::default(),
+            emitted_selection: Vec::new(),
+            rows: ListRows::default(),
+            first_visible_row: 0,
             _search_task: Task::ready(()),
             _load_more_task: Task::ready(()),
             _query_input_subscription,
@@ -232,6 +311,16 @@ where
         self
     }
 
+    /// Sets whether the list can select multiple items, default is false.
+    ///
+    /// - `ctrl` (`cmd` on macOS) + click to toggle an item.
+    /// - `shift` + click or `shift` + `up`/`down` to select a range.
+    /// - `ctrl-a` (`cmd-a` on macOS) to select all items.
+    pub fn multi_selectable(mut self, multi_selectable: bool) -> Self {
+        self.multi_selectable = multi_selectable;
+        self
+    }
+
     pub fn set_query_input(
         &mut self,
         query_input: Entity<InputState>,
@@ -287,6 +376,53 @@ where
         self.selected_index
     }
 
+    /// Returns the selected indices in multi-selection mode, in ascending order.
+    pub fn selected_indices(&self) -> Vec<usize> {
+        self.selection.indices()
+    }
+
+    /// Set the selected indices in multi-selection mode, this method will not scroll to the items.
+    pub fn set_selected_indices(
+        &mut self,
+        indices: impl IntoIterator<Item = usize>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.selection.set(indices);
+        self.selected_index = self.selection.head();
+        self.update_selection(window, cx);
+    }
+
+    /// Toggle the selection of the item in multi-selection mode, e.g.: Click the checkbox of the item.
+    pub fn toggle_selected_index(
+        &mut self,
+        ix: usize,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if !self.multi_selectable {
+            return;
+        }
+
+        self.selection.toggle(ix);
+        self.selected_index = Some(ix);
+        self.update_selection(window, cx);
+    }
+
+    /// Sync the multi-selection to the delegate and emit the change event if the selected
+    /// indices changed.
+    fn update_selection(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        self.delegate
+            .set_selected_index(self.selected_index, window, cx);
+        let indices = self.selection.indices();
+        if indices != self.emitted_selection {
+            self.delegate.set_selected_indices(&indices, window, cx);
+            self.emitted_selection = indices.clone();
+            cx.emit(ListEvent::SelectionChange(indices));
+        }
+        cx.notify();
+    }
+
     fn render_scrollbar(&self, _: &mut Window, _: &mut Context<Self>) -> Option<impl IntoElement> {
         if !self.scrollbar_visible {
             return None;
@@ -301,10 +437,19 @@ where
     /// Scroll to the item at the given index.
     pub fn scroll_to_item(&mut self, ix: usize, _: &mut Window, cx: &mut Context<Self>) {
         self.vertical_scroll_handle
-            .scroll_to_item(ix, ScrollStrategy::Top);
+            .scroll_to_item(self.rows.row_of_item(ix), ScrollStrategy::Top);
         cx.notify();
     }
 
+    /// Scroll to the header of the given section.
+    pub fn scroll_to_section(&mut self, section: usize, _: &mut Window, cx: &mut Context<Self>) {
+        if let Some(row_ix) = self.rows.row_of_section(section) {
+            self.vertical_scroll_handle
+                .scroll_to_item(row_ix, ScrollStrategy::Top);
+            cx.notify();
+        }
+    }
+
     /// Get scroll handle
     pub fn scroll_handle(&self) -> &UniformListScrollHandle {
         &self.vertical_scroll_handle
@@ -313,7 +458,27 @@ where
     pub fn scroll_to_selected_item(&mut self, _window: &mut Window, _cx: &mut Context<Self>) {
         if let Some(ix) = self.selected_index {
             self.vertical_scroll_handle
-                .scroll_to_item(ix, ScrollStrategy::Top);
+                .scroll_to_item(self.rows.row_of_item(ix), ScrollStrategy::Top);
+        }
+    }
+
+    /// Rebuild the rows from the delegate's sections.
+    ///
+    /// This is called on every render, the rows are only rebuilt if the number of items
+    /// (or the items in the sections) changed, then the selected indices out of range are
+    /// removed, and the change is synced to the delegate after the render.
+    fn update_rows(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let items_count = self.delegate.items_count(cx);
+        let section_counts = (0..self.delegate.sections_count(cx))
+            .map(|section| self.delegate.section_items_count(section, cx))
+            .collect::<Vec<_>>();
+        if self.rows.is_built_from(items_count, &section_counts) {
+            return;
+        }
+
+        self.rows = ListRows::new(items_count, &section_counts);
+        if self.multi_selectable && self.selection.truncate(items_count) {
+            cx.defer_in(window, |this, window, cx| this.update_selection(window, cx));
         }
     }
 
@@ -333,6 +498,7 @@ where
 
                 self.set_querying(true, window, cx);
                 let search = self.delegate.perform_search(&text, window, cx);
+                self.update_rows(window, cx);
 
                 if self.delegate.items_count(cx) > 0 {
                     self._set_selected_index(Some(0), window, cx);
@@ -343,10 +509,17 @@ where
                 self._search_task = cx.spawn_in(window, async move |this, window| {
                     search.await;
 
-                    _ = this.update_in(window, |this, _, _| {
+                    _ = this.update_in(window, |this, window, cx| {
                         this.vertical_scroll_handle
                             .scroll_to_item(0, ScrollStrategy::Top);
                         this.last_query = Some(text);
+                        if this.multi_selectable {
+                            match this.delegate.selected_indices(cx) {
+                                Some(indices) => this.selection.set(indices),
+                                None => this.selection.clear(),
+                            }
+                            this.update_selection(window, cx);
+                        }
                     });
 
                     // Always wait 100ms to avoid flicker
@@ -410,6 +583,10 @@ where
 
         if self.reset_on_cancel {
             self._set_selected_index(None, window, cx);
+            if self.multi_selectable && !self.selection.is_empty() {
+                self.selection.clear();
+                self.update_selection(window, cx);
+            }
         }
 
         self.delegate.cancel(window, cx);
@@ -443,9 +620,22 @@ where
         self.delegate.set_selected_index(Some(ix), window, cx);
         self.scroll_to_selected_item(window, cx);
         cx.emit(ListEvent::Select(ix));
+        if self.multi_selectable {
+            self.selection.select(ix);
+            self.update_selection(window, cx);
+        }
         cx.notify();
     }
 
+    /// Extend the selection range to the item in multi-selection mode.
+    fn select_item_to(&mut self, ix: usize, window: &mut Window, cx: &mut Context<Self>) {
+        self.selection.select_to(ix);
+        self.selected_index = Some(ix);
+        self.scroll_to_selected_item(window, cx);
+        cx.emit(ListEvent::Select(ix));
+        self.update_selection(window, cx);
+    }
+
     fn on_action_select_prev(
         &mut self,
         _: &SelectPrev,
@@ -493,13 +683,159 @@ where
         self.select_item(selected_index, window, cx);
     }
 
+    fn on_action_select_to_prev(
+        &mut self,
+        _: &SelectToPrev,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if !self.multi_selectable {
+            self.on_action_select_prev(&SelectPrev, window, cx);
+            return;
+        }
+
+        let Some(ix) = self.selection.head().or(self.selected_index) else {
+            self.on_action_select_prev(&SelectPrev, window, cx);
+            return;
+        };
+        self.select_item_to(ix.saturating_sub(1), window, cx);
+    }
+
+    fn on_action_select_to_next(
+        &mut self,
+        _: &SelectToNext,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if !self.multi_selectable {
+            self.on_action_select_next(&SelectNext, window, cx);
+            return;
+        }
+
+        let items_count = self.delegate.items_count(cx);
+        let Some(ix) = self.selection.head().or(self.selected_index) else {
+            self.on_action_select_next(&SelectNext, window, cx);
+            return;
+        };
+        self.select_item_to((ix + 1).min(items_count.saturating_sub(1)), window, cx);
+    }
+
+    fn on_action_select_all(&mut self, _: &SelectAll, window: &mut Window, cx: &mut Context<Self>) {
+        if !self.multi_selectable {
+            cx.propagate();
+            return;
+        }
+
+        self.selection.select_all(self.delegate.items_count(cx));
+        self.update_selection(window, cx);
+    }
+
+    fn render_list_row(
+        &mut self,
+        row_ix: usize,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> AnyElement {
+        match self.rows.row(row_ix) {
+            Some(ListRow::Header(section)) => self
+                .render_section_header(section, window, cx)
+                .into_any_element(),
+            Some(ListRow::Item(ix)) => self.render_list_item(ix, window, cx).into_any_element(),
+            None => div().into_any_element(),
+        }
+    }
+
+    fn render_section_header(
+        &mut self,
+        section: usize,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> impl IntoElement {
+        div()
+            .id(("list-section", section))
+            .w_full()
+            .bg(cx.theme().list_head)
+            .children(self.delegate.render_section_header(section, window, cx))
+    }
+
+    /// Render the header of the section at the top of the visible rows,
+    /// it will cover the header (or item) of the first row.
+    fn render_sticky_header(
+        &mut self,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Option<impl IntoElement> {
+        let section = self.rows.section_of_row(self.first_visible_row)?;
+        Some(
+            div()
+                .absolute()
+                .top_0()
+                .left_0()
+                .right_0()
+                .child(self.render_section_header(section, window, cx)),
+        )
+    }
+
+    fn render_section_index(&self, cx: &mut Context<Self>) -> Option<impl IntoElement> {
+        let labels = (0..self.rows.sections_count())
+            .filter_map(|section| {
+                self.delegate
+                    .section_index_label(section, cx)
+                    .map(|label| (section, label))
+            })
+            .collect::<Vec<_>>();
+        if labels.is_empty() {
+            return None;
+        }
+
+        let current_section = self.rows.section_of_row(self.first_visible_row);
+
+        Some(
+            v_flex()
+                .id("list-section-index")
+                .absolute()
+                .top_0()
+                .bottom_0()
+                .right_3()
+                .justify_center()
+                .items_center()
+                .text_xs()
+                .text_color(cx.theme().muted_foreground)
+                .children(labels.into_iter().map(|(section, label)| {
+                    div()
+                        .id(("section-index", section))
+                        .px_1()
+                        .cursor_pointer()
+                        .when(current_section == Some(section), |this| {
+                            this.text_color(cx.theme().foreground)
+                        })
+                        .hover(|this| this.text_color(cx.theme().foreground))
+                        .child(label)
+                        .on_mouse_down(
+                            MouseButton::Left,
+                            cx.listener(move |this, _, window, cx| {
+                                cx.stop_propagation();
+                                this.scroll_to_section(section, window, cx);
+                                if this.selectable {
+                                    let ix = this.rows.first_item_of_section(section);
+                                    if ix.is_some() {
+                                        this.set_selected_index(ix, window, cx);
+                                    }
+                                }
+                            }),
+                        )
+                })),
+        )
+    }
+
     fn render_list_item(
         &mut self,
         ix: usize,
         window: &mut Window,
         cx: &mut Context<Self>,
     ) -> impl IntoElement {
-        let selected = self.selected_index == Some(ix);
+        let selected = self.selected_index == Some(ix)
+            || (self.multi_selectable && self.selection.contains(ix));
         let right_clicked = self.right_clicked_index == Some(ix);
 
         div()
@@ -525,6 +861,18 @@ where
                     MouseButton::Left,
                     cx.listener(move |this, ev: &MouseDownEvent, window, cx| {
                         this.right_clicked_index = None;
+                        if this.multi_selectable {
+                            if ev.modifiers.shift {
+                                this.select_item_to(ix, window, cx);
+                                return;
+                            } else if ev.modifiers.secondary() {
+                                this.toggle_selected_index(ix, window, cx);
+                                return;
+                            }
+
+                            this.selection.select(ix);
+                            this.update_selection(window, cx);
+                        }
                         this.selected_index = Some(ix);
                         this.on_action_confirm(
                             &Confirm {
@@ -564,8 +912,10 @@ where
     D: ListDelegate,
 {
     fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        self.update_rows(window, cx);
         let vertical_scroll_handle = self.vertical_scroll_handle.clone();
-        let items_count = self.delegate.items_count(cx);
+        let items_count = self.rows.items_count();
+        let rows_count = self.rows.len();
         let loading = self.delegate.loading(cx);
         let sizing_behavior = if self.max_height.is_some() {
             ListSizingBehavior::Infer
@@ -620,6 +970,9 @@ where
                     .on_action(cx.listener(Self::on_action_confirm))
                     .on_action(cx.listener(Self::on_action_select_next))
                     .on_action(cx.listener(Self::on_action_select_prev))
+                    .on_action(cx.listener(Self::on_action_select_to_next))
+                    .on_action(cx.listener(Self::on_action_select_to_prev))
+                    .on_action(cx.listener(Self::on_action_select_all))
                     .map(|this| {
                         if let Some(view) = initial_view {
                             this.child(view)
@@ -637,20 +990,31 @@ where
                                         this.child(
                                             uniform_list(
                                                 "uniform-list",
-                                                items_count,
+                                                rows_count,
                                                 cx.processor(
                                                     move |list, visible_range: Range<usize>, window, cx| {
+                                                        if list.first_visible_row != visible_range.start {
+                                                            list.first_visible_row = visible_range.start;
+                                                            if list.rows.is_sectioned() {
+                                                                cx.notify();
+                                                            }
+                                                        }
+
+                                                        let visible_end = list
+                                                            .rows
+                                                            .item_at_or_after_row(visible_range.end)
+                                                            .unwrap_or(items_count);
                                                         list.load_more_if_need(
                                                             items_count,
-                                                            visible_range.end,
+                                                            visible_end,
                                                             window,
                                                             cx,
                                                         );
 
                                                         visible_range
-                                                            .map(|ix| {
-                                                                list.render_list_item(
-                                                                    ix, window, cx,
+                                                            .map(|row_ix| {
+                                                                list.render_list_row(
+                                                                    row_ix, window, cx,
                                                                 )
                                                             })
                                                             .collect::<Vec<_>>()
@@ -663,6 +1027,10 @@ where
                                             .into_any_element(),
                                         )
                                     })
+                                    .when(items_count > 0, |this| {
+                                        this.children(self.render_sticky_header(window, cx))
+                                            .children(self.render_section_index(cx))
+                                    })
                                     .children(self.render_scrollbar(window, cx)),
                             )
                         }
@@ -1,4 +1,4 @@
-use crate::{h_flex, ActiveTheme, Disableable, Icon, Selectable, Sizable as _};
+use crate::{checkbox::Checkbox, h_flex, ActiveTheme, Disableable, Icon, Selectable, Sizable as _};
 use gpui::{
     div, prelude::FluentBuilder as _, AnyElement, App, ClickEvent, Div, ElementId,
     InteractiveElement, IntoElement, MouseButton, MouseMoveEvent, ParentElement, RenderOnce,
@@ -14,6 +14,8 @@ pub struct ListItem {
     selected: bool,
     confirmed: bool,
     check_icon: Option<Icon>,
+    checkbox: Option<bool>,
+    on_checkbox_click: Option<Box<dyn Fn(&bool, &mut Window, &mut App) + 'static>>,
     on_click: Option<Box<dyn Fn(&ClickEvent, &mut Window, &mut App) + 'static>>,
     on_mouse_enter: Option<Box<dyn Fn(&MouseMoveEvent, &mut Window, &mut App) + 'static>>,
     suffix: Option<Box<dyn Fn(&mut Window, &mut App) -> AnyElement + 'static>>,
@@ -32,6 +34,8 @@ impl ListItem {
             on_click: None,
             on_mouse_enter: None,
             check_icon: None,
+            checkbox: None,
+            on_checkbox_click: None,
             suffix: None,
             children: SmallVec::new(),
         }
@@ -43,6 +47,26 @@ impl ListItem {
         self
     }
 
+    /// Set to show a checkbox at the start of the item, default is None.
+    ///
+    /// This is useful for the multi-selectable List, the `checked` should be
+    /// whether the item is in the selected indices.
+    pub fn checkbox(mut self, checked: bool) -> Self {
+        self.checkbox = Some(checked);
+        self
+    }
+
+    /// Set the click handler of the checkbox, the argument is the new checked state.
+    ///
+    /// For example, call `List::toggle_selected_index` to toggle the item in a multi-selectable List.
+    pub fn on_checkbox_click(
+        mut self,
+        handler: impl Fn(&bool, &mut Window, &mut App) + 'static,
+    ) -> Self {
+        self.on_checkbox_click = Some(Box::new(handler));
+        self
+    }
+
     /// Set ListItem as the selected item style.
     pub fn selected(mut self, selected: bool) -> Self {
         self.selected = selected;
@@ -155,6 +179,25 @@ impl RenderOnce for ListItem {
                     .items_center()
                     .justify_between()
                     .gap_x_1()
+                    .when_some(self.checkbox, |this, checked| {
+                        let on_checkbox_click = self.on_checkbox_click;
+                        this.child(
+                            div()
+                                .when(on_checkbox_click.is_some(), |this| {
+                                    this.on_mouse_down(MouseButton::Left, move |_, _, cx| {
+                                        cx.stop_propagation();
+                                    })
+                                })
+                                .child(
+                                    Checkbox::new("checkbox")
+                                        .checked(checked)
+                                        .disabled(self.disabled)
+                                        .when_some(on_checkbox_click, |this, on_click| {
+                                            this.on_click(on_click)
+                                        }),
+                                ),
+                        )
+                    })
                     .child(div().w_full().children(self.children))
                     .when_some(self.check_icon, |this, icon| {
                         this.child(
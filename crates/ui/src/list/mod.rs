@@ -1,6 +1,8 @@
 mod list;
 mod list_item;
 mod loading;
+mod section;
+mod selection;
 
 pub use list::*;
 pub use list_item::*;
@@ -0,0 +1,223 @@
+/// A row in a sectioned list, either a section header or an item.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ListRow {
+    /// The header of the section at the given index.
+    Header(usize),
+    /// The item at the given (flat) index.
+    Item(usize),
+}
+
+/// The mapping between the rows rendered by the List and the delegate's items.
+///
+/// When the delegate has no sections, every row is an item and no lookup is needed.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) struct ListRows {
+    rows: Vec<ListRow>,
+    /// The row index of each item.
+    item_rows: Vec<usize>,
+    /// The row index of each section header.
+    section_rows: Vec<usize>,
+    /// The first item index of each section.
+    section_starts: Vec<usize>,
+    items_count: usize,
+}
+
+impl ListRows {
+    /// Build the rows from the number of items in each section.
+    ///
+    /// If `section_counts` is empty, the list is flat with `items_count` items.
+    pub(crate) fn new(items_count: usize, section_counts: &[usize]) -> Self {
+        if section_counts.is_empty() {
+            return Self {
+                items_count,
+                ..Default::default()
+            };
+        }
+
+        let mut this = Self::default();
+        let mut item_ix = 0;
+        for (section, count) in section_counts.iter().enumerate() {
+            this.section_rows.push(this.rows.len());
+            this.section_starts.push(item_ix);
+            this.rows.push(ListRow::Header(section));
+            for _ in 0..*count {
+                this.item_rows.push(this.rows.len());
+                this.rows.push(ListRow::Item(item_ix));
+                item_ix += 1;
+            }
+        }
+        this.items_count = item_ix;
+        this
+    }
+
+    /// Return true if the rows were built from the same counts, so they don't need to be rebuilt.
+    pub(crate) fn is_built_from(&self, items_count: usize, section_counts: &[usize]) -> bool {
+        if section_counts.is_empty() {
+            return !self.is_sectioned() && self.items_count == items_count;
+        }
+
+        section_counts.len() == self.sections_count()
+            && section_counts.iter().enumerate().all(|(section, count)| {
+                let start = self.section_starts[section];
+                let end = self
+                    .section_starts
+                    .get(section + 1)
+                    .copied()
+                    .unwrap_or(self.items_count);
+                end - start == *count
+            })
+    }
+
+    #[inline]
+    pub(crate) fn is_sectioned(&self) -> bool {
+        !self.section_rows.is_empty()
+    }
+
+    /// Return the number of rows, including the section headers.
+    #[inline]
+    pub(crate) fn len(&self) -> usize {
+        if self.is_sectioned() {
+            self.rows.len()
+        } else {
+            self.items_count
+        }
+    }
+
+    /// Return the number of items, excluding the section headers.
+    #[inline]
+    pub(crate) fn items_count(&self) -> usize {
+        self.items_count
+    }
+
+    #[inline]
+    pub(crate) fn sections_count(&self) -> usize {
+        self.section_rows.len()
+    }
+
+    pub(crate) fn row(&self, row_ix: usize) -> Option<ListRow> {
+        if self.is_sectioned() {
+            self.rows.get(row_ix).copied()
+        } else if row_ix < self.items_count {
+            Some(ListRow::Item(row_ix))
+        } else {
+            None
+        }
+    }
+
+    /// Return the row index of the item.
+    pub(crate) fn row_of_item(&self, ix: usize) -> usize {
+        if self.is_sectioned() {
+            self.item_rows.get(ix).copied().unwrap_or(ix)
+        } else {
+            ix
+        }
+    }
+
+    /// Return the row index of the section header.
+    pub(crate) fn row_of_section(&self, section: usize) -> Option<usize> {
+        self.section_rows.get(section).copied()
+    }
+
+    /// Return the first item index of the section, None if the section is empty.
+    pub(crate) fn first_item_of_section(&self, section: usize) -> Option<usize> {
+        let start = *self.section_starts.get(section)?;
+        let end = self
+            .section_starts
+            .get(section + 1)
+            .copied()
+            .unwrap_or(self.items_count);
+        (start < end).then_some(start)
+    }
+
+    /// Return the section that contains the given row, None if the list is not sectioned.
+    pub(crate) fn section_of_row(&self, row_ix: usize) -> Option<usize> {
+        if !self.is_sectioned() {
+            return None;
+        }
+
+        match self.section_rows.binary_search(&row_ix) {
+            Ok(section) => Some(section),
+            Err(0) => None,
+            Err(section) => Some(section - 1),
+        }
+    }
+
+    /// Return the item index at or after the given row, used to skip the headers.
+    pub(crate) fn item_at_or_after_row(&self, row_ix: usize) -> Option<usize> {
+        (row_ix..self.len()).find_map(|row_ix| match self.row(row_ix) {
+            Some(ListRow::Item(ix)) => Some(ix),
+            _ => None,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ListRow, ListRows};
+
+    #[test]
+    fn test_flat_rows() {
+        let rows = ListRows::new(3, &[]);
+        assert!(!rows.is_sectioned());
+        assert_eq!(rows.len(), 3);
+        assert_eq!(rows.row(2), Some(ListRow::Item(2)));
+        assert_eq!(rows.row(3), None);
+        assert_eq!(rows.row_of_item(1), 1);
+        assert_eq!(rows.section_of_row(1), None);
+    }
+
+    #[test]
+    fn test_sectioned_rows() {
+        let rows = ListRows::new(0, &[2, 0, 3]);
+        assert!(rows.is_sectioned());
+        assert_eq!(rows.len(), 8);
+        assert_eq!(rows.items_count(), 5);
+        assert_eq!(rows.sections_count(), 3);
+        assert_eq!(
+            (0..rows.len())
+                .filter_map(|ix| rows.row(ix))
+                .collect::<Vec<_>>(),
+            vec![
+                ListRow::Header(0),
+                ListRow::Item(0),
+                ListRow::Item(1),
+                ListRow::Header(1),
+                ListRow::Header(2),
+                ListRow::Item(2),
+                ListRow::Item(3),
+                ListRow::Item(4),
+            ]
+        );
+
+        assert_eq!(rows.row_of_item(0), 1);
+        assert_eq!(rows.row_of_item(2), 5);
+        assert_eq!(rows.row_of_section(2), Some(4));
+        assert_eq!(rows.row_of_section(3), None);
+
+        assert_eq!(rows.first_item_of_section(0), Some(0));
+        assert_eq!(rows.first_item_of_section(1), None);
+        assert_eq!(rows.first_item_of_section(2), Some(2));
+
+        assert_eq!(rows.section_of_row(0), Some(0));
+        assert_eq!(rows.section_of_row(2), Some(0));
+        assert_eq!(rows.section_of_row(3), Some(1));
+        assert_eq!(rows.section_of_row(7), Some(2));
+
+        assert_eq!(rows.item_at_or_after_row(3), Some(2));
+        assert_eq!(rows.item_at_or_after_row(8), None);
+    }
+
+    #[test]
+    fn test_is_built_from() {
+        let rows = ListRows::new(3, &[]);
+        assert!(rows.is_built_from(3, &[]));
+        assert!(!rows.is_built_from(4, &[]));
+        assert!(!rows.is_built_from(3, &[3]));
+
+        let rows = ListRows::new(0, &[2, 0, 3]);
+        assert!(rows.is_built_from(5, &[2, 0, 3]));
+        assert!(!rows.is_built_from(5, &[2, 1, 2]));
+        assert!(!rows.is_built_from(5, &[2, 0, 3, 0]));
+        assert!(!rows.is_built_from(5, &[]));
+    }
+}
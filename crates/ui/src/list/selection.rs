@@ -0,0 +1,166 @@
+use std::collections::BTreeSet;
+
+/// The selected items of a multi-selectable List.
+///
+/// The `anchor` is the item where the range selection starts,
+/// and the `head` is the item that was last moved to (by click or keyboard).
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) struct ListSelection {
+    selected: BTreeSet<usize>,
+    anchor: Option<usize>,
+    head: Option<usize>,
+}
+
+impl ListSelection {
+    /// Return the selected indices in ascending order.
+    pub(crate) fn indices(&self) -> Vec<usize> {
+        self.selected.iter().copied().collect()
+    }
+
+    #[inline]
+    pub(crate) fn contains(&self, ix: usize) -> bool {
+        self.selected.contains(&ix)
+    }
+
+    #[inline]
+    pub(crate) fn is_empty(&self) -> bool {
+        self.selected.is_empty()
+    }
+
+    #[inline]
+    pub(crate) fn head(&self) -> Option<usize> {
+        self.head
+    }
+
+    /// Replace the selection with the given indices.
+    pub(crate) fn set(&mut self, indices: impl IntoIterator<Item = usize>) {
+        self.selected = indices.into_iter().collect();
+        self.anchor = self.selected.first().copied();
+        self.head = self.selected.last().copied();
+    }
+
+    pub(crate) fn clear(&mut self) {
+        self.selected.clear();
+        self.anchor = None;
+        self.head = None;
+    }
+
+    /// Select only the given item, e.g.: Click without modifiers.
+    pub(crate) fn select(&mut self, ix: usize) {
+        self.selected.clear();
+        self.selected.insert(ix);
+        self.anchor = Some(ix);
+        self.head = Some(ix);
+    }
+
+    /// Toggle the given item, e.g.: Click with `ctrl` or `cmd`.
+    pub(crate) fn toggle(&mut self, ix: usize) {
+        if !self.selected.remove(&ix) {
+            self.selected.insert(ix);
+        }
+        self.anchor = Some(ix);
+        self.head = Some(ix);
+    }
+
+    /// Select the range from the anchor to the given item, e.g.: Click with `shift`.
+    ///
+    /// The items outside the range will be deselected.
+    pub(crate) fn select_to(&mut self, ix: usize) {
+        let anchor = self.anchor.unwrap_or(ix);
+        let (start, end) = if anchor <= ix {
+            (anchor, ix)
+        } else {
+            (ix, anchor)
+        };
+        self.selected = (start..=end).collect();
+        self.anchor = Some(anchor);
+        self.head = Some(ix);
+    }
+
+    /// Select all items.
+    pub(crate) fn select_all(&mut self, items_count: usize) {
+        self.selected = (0..items_count).collect();
+        if self.anchor.is_none() && items_count > 0 {
+            self.anchor = Some(0);
+            self.head = Some(items_count - 1);
+        }
+    }
+
+    /// Remove the indices that are out of the items count, e.g.: After items removed.
+    ///
+    /// Returns true if any selected index was removed.
+    pub(crate) fn truncate(&mut self, items_count: usize) -> bool {
+        let len = self.selected.len();
+        self.selected.retain(|ix| *ix < items_count);
+        if self.anchor.is_some_and(|ix| ix >= items_count) {
+            self.anchor = self.selected.first().copied();
+        }
+        if self.head.is_some_and(|ix| ix >= items_count) {
+            self.head = self.selected.last().copied();
+        }
+        self.selected.len() != len
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ListSelection;
+
+    #[test]
+    fn test_select_and_toggle() {
+        let mut selection = ListSelection::default();
+        assert!(selection.is_empty());
+
+        selection.select(3);
+        assert_eq!(selection.indices(), vec![3]);
+
+        selection.toggle(1);
+        selection.toggle(5);
+        assert_eq!(selection.indices(), vec![1, 3, 5]);
+        assert_eq!(selection.head(), Some(5));
+
+        selection.toggle(3);
+        assert_eq!(selection.indices(), vec![1, 5]);
+        assert!(!selection.contains(3));
+
+        selection.select(2);
+        assert_eq!(selection.indices(), vec![2]);
+    }
+
+    #[test]
+    fn test_select_range() {
+        let mut selection = ListSelection::default();
+        selection.select(4);
+        selection.select_to(7);
+        assert_eq!(selection.indices(), vec![4, 5, 6, 7]);
+
+        // The anchor is kept, so the range is flipped.
+        selection.select_to(2);
+        assert_eq!(selection.indices(), vec![2, 3, 4]);
+        assert_eq!(selection.head(), Some(2));
+
+        // Without anchor, only the item is selected.
+        let mut selection = ListSelection::default();
+        selection.select_to(3);
+        assert_eq!(selection.indices(), vec![3]);
+    }
+
+    #[test]
+    fn test_select_all_and_truncate() {
+        let mut selection = ListSelection::default();
+        selection.select_all(4);
+        assert_eq!(selection.indices(), vec![0, 1, 2, 3]);
+        assert_eq!(selection.head(), Some(3));
+
+        assert!(selection.truncate(2));
+        assert_eq!(selection.indices(), vec![0, 1]);
+        assert_eq!(selection.head(), Some(1));
+        assert!(!selection.truncate(2));
+
+        selection.set([5, 1]);
+        assert_eq!(selection.indices(), vec![1, 5]);
+        selection.clear();
+        assert!(selection.is_empty());
+        assert_eq!(selection.head(), None);
+    }
+}
@@ -0,0 +1,469 @@
+//! The layout of the measured list, without any dependency on the element,
+//! all the sizes are in pixels.
+use std::ops::Range;
+
+/// A Fenwick tree (binary indexed tree) for the prefix sums, to get the item origin in `O(log n)`.
+#[derive(Debug, Clone, Default)]
+struct PrefixSums {
+    tree: Vec<f64>,
+}
+
+impl PrefixSums {
+    fn from_values(values: impl Iterator<Item = f64>) -> Self {
+        let mut tree = vec![0.];
+        tree.extend(values);
+        for i in 1..tree.len() {
+            let parent = i + (i & i.wrapping_neg());
+            if parent < tree.len() {
+                tree[parent] += tree[i];
+            }
+        }
+        Self { tree }
+    }
+
+    /// Append a value at the end in `O(log n)`.
+    fn push(&mut self, value: f64) {
+        if self.tree.is_empty() {
+            self.tree.push(0.);
+        }
+
+        // The node `i` holds the sum of the values in `(i - lowbit(i), i]`.
+        let i = self.tree.len();
+        let node = value + self.prefix(i - 1) - self.prefix(i - (i & i.wrapping_neg()));
+        self.tree.push(node);
+    }
+
+    /// Remove the values from `len` to the end.
+    fn truncate(&mut self, len: usize) {
+        self.tree.truncate(len + 1);
+    }
+
+    fn add(&mut self, ix: usize, delta: f64) {
+        let mut i = ix + 1;
+        while i < self.tree.len() {
+            self.tree[i] += delta;
+            i += i & i.wrapping_neg();
+        }
+    }
+
+    /// The sum of the values in `0..ix`.
+    fn prefix(&self, ix: usize) -> f64 {
+        let mut i = ix.min(self.tree.len().saturating_sub(1));
+        let mut sum = 0.;
+        while i > 0 {
+            sum += self.tree[i];
+            i -= i & i.wrapping_neg();
+        }
+        sum
+    }
+}
+
+/// The heights of the items, the unmeasured items use the average height of the measured items.
+#[derive(Debug, Clone)]
+pub(crate) struct ItemHeights {
+    heights: Vec<Option<f32>>,
+    /// The prefix sums of the measured heights.
+    sums: PrefixSums,
+    /// The prefix sums of the measured count.
+    counts: PrefixSums,
+    measured_sum: f64,
+    measured_count: usize,
+    default_height: f32,
+}
+
+impl ItemHeights {
+    pub(crate) fn new(items_count: usize, default_height: f32) -> Self {
+        let mut this = Self {
+            heights: vec![],
+            sums: PrefixSums::default(),
+            counts: PrefixSums::default(),
+            measured_sum: 0.,
+            measured_count: 0,
+            default_height,
+        };
+        this.rebuild(vec![None; items_count]);
+        this
+    }
+
+    fn rebuild(&mut self, heights: Vec<Option<f32>>) {
+        self.sums = PrefixSums::from_values(heights.iter().map(|h| h.unwrap_or(0.) as f64));
+        self.counts =
+            PrefixSums::from_values(heights.iter().map(|h| if h.is_some() { 1. } else { 0. }));
+        self.measured_sum = heights.iter().flatten().map(|h| *h as f64).sum();
+        self.measured_count = heights.iter().flatten().count();
+        self.heights = heights;
+    }
+
+    #[inline]
+    pub(crate) fn len(&self) -> usize {
+        self.heights.len()
+    }
+
+    /// The height used for the unmeasured items.
+    pub(crate) fn estimated_height(&self) -> f32 {
+        if self.measured_count > 0 {
+            (self.measured_sum / self.measured_count as f64) as f32
+        } else {
+            self.default_height
+        }
+    }
+
+    /// The offset of the item from the top of the list.
+    pub(crate) fn origin(&self, ix: usize) -> f64 {
+        let ix = ix.min(self.len());
+        let unmeasured = ix as f64 - self.counts.prefix(ix);
+        self.sums.prefix(ix) + unmeasured * self.estimated_height() as f64
+    }
+
+    pub(crate) fn total_height(&self) -> f64 {
+        self.origin(self.len())
+    }
+
+    /// Set the measured height of the item, returns true if the height changed.
+    pub(crate) fn set_height(&mut self, ix: usize, height: f32) -> bool {
+        let Some(old) = self.heights.get(ix).copied() else {
+            return false;
+        };
+
+        match old {
+            Some(old) if (old - height).abs() < 0.01 => return false,
+            Some(old) => {
+                self.sums.add(ix, (height - old) as f64);
+                self.measured_sum += (height - old) as f64;
+            }
+            None => {
+                self.sums.add(ix, height as f64);
+                self.counts.add(ix, 1.);
+                self.measured_sum += height as f64;
+                self.measured_count += 1;
+            }
+        }
+        self.heights[ix] = Some(height);
+        true
+    }
+
+    /// Mark the item as unmeasured, it will be measured again when visible.
+    pub(crate) fn invalidate(&mut self, ix: usize) {
+        let Some(Some(old)) = self.heights.get(ix).copied() else {
+            return;
+        };
+
+        self.sums.add(ix, -(old as f64));
+        self.counts.add(ix, -1.);
+        self.measured_sum -= old as f64;
+        self.measured_count -= 1;
+        self.heights[ix] = None;
+    }
+
+    /// Mark all items as unmeasured, e.g.: After the width of the list changed.
+    pub(crate) fn invalidate_all(&mut self) {
+        let count = self.len();
+        self.rebuild(vec![None; count]);
+    }
+
+    /// Replace the items in the `range` with `count` unmeasured items.
+    ///
+    /// The changes at the tail (e.g.: append) are updated in `O(count log n)`,
+    /// otherwise the prefix sums are rebuilt.
+    pub(crate) fn splice(&mut self, range: Range<usize>, count: usize) {
+        let len = self.len();
+        let range = range.start.min(len)..range.end.min(len);
+        if range.end < len {
+            let mut heights = std::mem::take(&mut self.heights);
+            heights.splice(range, std::iter::repeat_n(None, count));
+            self.rebuild(heights);
+            return;
+        }
+
+        for height in self.heights.drain(range.start..).flatten() {
+            self.measured_sum -= height as f64;
+            self.measured_count -= 1;
+        }
+        self.sums.truncate(range.start);
+        self.counts.truncate(range.start);
+        for _ in 0..count {
+            self.heights.push(None);
+            self.sums.push(0.);
+            self.counts.push(0.);
+        }
+    }
+
+    /// Return the index of the item at the offset, clamped to the last item.
+    pub(crate) fn index_at(&self, offset: f64) -> usize {
+        if self.len() == 0 || offset <= 0. {
+            return 0;
+        }
+
+        // The last item whose origin <= offset.
+        let (mut low, mut high) = (0, self.len());
+        while high - low > 1 {
+            let mid = (low + high) / 2;
+            if self.origin(mid) <= offset {
+                low = mid;
+            } else {
+                high = mid;
+            }
+        }
+        low
+    }
+}
+
+/// The first visible item and the offset scrolled into it,
+/// the scroll position is kept by this anchor when the items before it are resized.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub(crate) struct ScrollAnchor {
+    pub(crate) ix: usize,
+    pub(crate) offset: f64,
+}
+
+/// The scroll state of the measured list.
+#[derive(Debug, Clone)]
+pub(crate) struct MeasuredLayout {
+    pub(crate) heights: ItemHeights,
+    pub(crate) anchor: ScrollAnchor,
+    /// Keep scrolled to the bottom when the content grows, if the list is at the bottom.
+    pub(crate) follow_tail: bool,
+    pub(crate) at_bottom: bool,
+}
+
+impl MeasuredLayout {
+    pub(crate) fn new(items_count: usize, default_height: f32) -> Self {
+        Self {
+            heights: ItemHeights::new(items_count, default_height),
+            anchor: ScrollAnchor::default(),
+            follow_tail: false,
+            at_bottom: false,
+        }
+    }
+
+    fn max_scroll_top(&self, viewport_height: f64) -> f64 {
+        (self.heights.total_height() - viewport_height).max(0.)
+    }
+
+    /// Return the scroll top by the anchor, or the bottom if following the tail.
+    pub(crate) fn scroll_top(&self, viewport_height: f64) -> f64 {
+        let max = self.max_scroll_top(viewport_height);
+        if self.follow_tail && self.at_bottom {
+            return max;
+        }
+
+        (self.heights.origin(self.anchor.ix) + self.anchor.offset).clamp(0., max)
+    }
+
+    /// Update the anchor by the scroll top, e.g.: When the user scrolled.
+    pub(crate) fn set_scroll_top(&mut self, scroll_top: f64, viewport_height: f64) {
+        let max = self.max_scroll_top(viewport_height);
+        let scroll_top = scroll_top.clamp(0., max);
+        let ix = self.heights.index_at(scroll_top);
+        self.anchor = ScrollAnchor {
+            ix,
+            offset: scroll_top - self.heights.origin(ix),
+        };
+        self.at_bottom = scroll_top >= max - 1.;
+    }
+
+    /// Scroll to the item, to make it at the top of the viewport.
+    pub(crate) fn scroll_to_item(&mut self, ix: usize) {
+        self.anchor = ScrollAnchor {
+            ix: ix.min(self.heights.len().saturating_sub(1)),
+            offset: 0.,
+        };
+        self.at_bottom = false;
+    }
+
+    pub(crate) fn scroll_to_bottom(&mut self) {
+        self.anchor = ScrollAnchor {
+            ix: self.heights.len().saturating_sub(1),
+            offset: 0.,
+        };
+        self.at_bottom = true;
+    }
+
+    /// Replace the items in the `range` with `count` new items, the anchor is moved along with
+    /// the items, so the visible items are not moved after prepend.
+    pub(crate) fn splice(&mut self, range: Range<usize>, count: usize) {
+        let removed = range.end.saturating_sub(range.start);
+        if self.heights.len() == 0 {
+            // No item to anchor, keep at the top.
+        } else if self.anchor.ix >= range.end {
+            self.anchor.ix = self.anchor.ix + count - removed;
+        } else if self.anchor.ix >= range.start {
+            self.anchor = ScrollAnchor {
+                ix: range.start,
+                offset: 0.,
+            };
+        }
+        self.heights.splice(range, count);
+        self.anchor.ix = self.anchor.ix.min(self.heights.len().saturating_sub(1));
+    }
+
+    /// Return the range of the items that are visible in the viewport.
+    pub(crate) fn visible_range(&self, scroll_top: f64, viewport_height: f64) -> Range<usize> {
+        let len = self.heights.len();
+        if len == 0 {
+            return 0..0;
+        }
+
+        let start = self.heights.index_at(scroll_top);
+        let bottom = scroll_top + viewport_height;
+        let mut end = start;
+        while end < len && self.heights.origin(end) < bottom {
+            end += 1;
+        }
+        start..end.max(start + 1).min(len)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ItemHeights, MeasuredLayout, PrefixSums};
+
+    #[test]
+    fn test_prefix_sums() {
+        let mut sums = PrefixSums::from_values([1., 2., 3., 4., 5.].into_iter());
+        assert_eq!(sums.prefix(0), 0.);
+        assert_eq!(sums.prefix(3), 6.);
+        assert_eq!(sums.prefix(5), 15.);
+        assert_eq!(sums.prefix(10), 15.);
+        sums.add(1, 10.);
+        assert_eq!(sums.prefix(1), 1.);
+        assert_eq!(sums.prefix(2), 13.);
+        assert_eq!(sums.prefix(5), 25.);
+
+        // Push is the same as building from all the values.
+        let values = (1..=20).map(|v| v as f64).collect::<Vec<_>>();
+        let mut sums = PrefixSums::default();
+        for value in &values {
+            sums.push(*value);
+        }
+        assert_eq!(
+            sums.tree,
+            PrefixSums::from_values(values.iter().copied()).tree
+        );
+        sums.truncate(7);
+        sums.push(100.);
+        assert_eq!(sums.prefix(7), 28.);
+        assert_eq!(sums.prefix(8), 128.);
+    }
+
+    #[test]
+    fn test_item_heights() {
+        let mut heights = ItemHeights::new(5, 20.);
+        assert_eq!(heights.estimated_height(), 20.);
+        assert_eq!(heights.origin(3), 60.);
+        assert_eq!(heights.total_height(), 100.);
+
+        assert!(heights.set_height(1, 40.));
+        assert!(!heights.set_height(1, 40.));
+        // Unmeasured items use the average of the measured heights.
+        assert_eq!(heights.estimated_height(), 40.);
+        assert_eq!(heights.origin(2), 80.);
+        assert_eq!(heights.total_height(), 200.);
+
+        assert!(heights.set_height(0, 10.));
+        assert_eq!(heights.estimated_height(), 25.);
+        assert_eq!(heights.origin(1), 10.);
+        assert_eq!(heights.origin(2), 50.);
+        assert_eq!(heights.total_height(), 125.);
+
+        assert_eq!(heights.index_at(-5.), 0);
+        assert_eq!(heights.index_at(9.), 0);
+        assert_eq!(heights.index_at(10.), 1);
+        assert_eq!(heights.index_at(60.), 2);
+        assert_eq!(heights.index_at(1000.), 4);
+
+        heights.invalidate(1);
+        assert_eq!(heights.total_height(), 50.);
+
+        // The measured heights are moved along with the items.
+        heights.splice(0..0, 2);
+        assert_eq!(heights.len(), 7);
+        assert_eq!(heights.origin(3) - heights.origin(2), 10.);
+
+        heights.invalidate_all();
+        assert_eq!(heights.total_height(), 140.);
+    }
+
+    #[test]
+    fn test_splice_tail() {
+        let mut heights = ItemHeights::new(3, 20.);
+        heights.set_height(1, 40.);
+        heights.set_height(2, 10.);
+
+        // Append at the tail.
+        heights.splice(3..3, 2);
+        assert_eq!(heights.len(), 5);
+        assert_eq!(heights.estimated_height(), 25.);
+        assert_eq!(heights.origin(3), 75.);
+        assert!(heights.set_height(4, 25.));
+        assert_eq!(heights.total_height(), 125.);
+
+        // Replace the tail, the removed heights are not measured anymore.
+        heights.splice(2..10, 1);
+        assert_eq!(heights.len(), 3);
+        assert_eq!(heights.estimated_height(), 40.);
+        assert_eq!(heights.total_height(), 120.);
+
+        // Same as rebuilding from the heights.
+        let mut rebuilt = ItemHeights::new(0, 20.);
+        rebuilt.rebuild(heights.heights.clone());
+        assert_eq!(rebuilt.sums.tree, heights.sums.tree);
+        assert_eq!(rebuilt.counts.tree, heights.counts.tree);
+    }
+
+    #[test]
+    fn test_scroll_anchor() {
+        let mut layout = MeasuredLayout::new(10, 20.);
+        layout.set_scroll_top(50., 40.);
+        assert_eq!(layout.anchor.ix, 2);
+        assert_eq!(layout.anchor.offset, 10.);
+        assert_eq!(layout.scroll_top(40.), 50.);
+        assert_eq!(layout.visible_range(50., 40.), 2..5);
+
+        // The item before the anchor grows, the scroll top follows.
+        layout.heights.set_height(0, 60.);
+        layout.heights.set_height(1, 60.);
+        assert_eq!(layout.scroll_top(40.), 130.);
+
+        // Prepend items, keep the anchor at the same item.
+        layout.splice(0..0, 3);
+        assert_eq!(layout.anchor.ix, 5);
+        assert_eq!(layout.heights.origin(5) + 10., layout.scroll_top(40.));
+
+        // Remove the anchor item.
+        layout.splice(4..6, 0);
+        assert_eq!(layout.anchor.ix, 4);
+        assert_eq!(layout.anchor.offset, 0.);
+    }
+
+    #[test]
+    fn test_splice_empty() {
+        let mut layout = MeasuredLayout::new(0, 20.);
+        layout.splice(0..0, 10);
+        assert_eq!(layout.anchor.ix, 0);
+        assert_eq!(layout.scroll_top(50.), 0.);
+    }
+
+    #[test]
+    fn test_follow_tail() {
+        let mut layout = MeasuredLayout::new(10, 20.);
+        layout.follow_tail = true;
+        layout.scroll_to_bottom();
+        assert_eq!(layout.scroll_top(50.), 150.);
+
+        // Append items, keep at the bottom.
+        layout.splice(10..10, 5);
+        assert_eq!(layout.scroll_top(50.), 250.);
+
+        // Scroll up to stop following.
+        layout.set_scroll_top(100., 50.);
+        assert!(!layout.at_bottom);
+        layout.splice(15..15, 5);
+        assert_eq!(layout.scroll_top(50.), 100.);
+
+        // Scroll to the bottom again to follow.
+        layout.set_scroll_top(350., 50.);
+        assert!(layout.at_bottom);
+        assert_eq!(layout.scroll_top(50.), 350.);
+    }
+}
@@ -0,0 +1,392 @@
+//! Measured List for render a large number of variable height rows.
+//!
+//! Unlike the `v_virtual_list`, the item heights are not required to know ahead,
+//! each item is measured when it is first rendered, and the unmeasured items use
+//! the average height of the measured items as the estimate.
+//!
+//! The scroll position is anchored to the first visible item, so the visible items
+//! will not jump when the items before them are resized or prepended.
+//!
+//! With `follow_tail`, the list keeps scrolled to the bottom when new items are appended,
+//! until the user scrolls up, this is useful for chat or log views.
+mod layout;
+
+use std::{cell::RefCell, ops::Range, rc::Rc};
+
+use gpui::{
+    div, point, px, size, AnyElement, App, AvailableSpace, Bounds, ContentMask, Context, Div,
+    Element, ElementId, Entity, GlobalElementId, Hitbox, InteractiveElement, IntoElement, Pixels,
+    Render, ScrollHandle, Size, Stateful, StatefulInteractiveElement, StyleRefinement, Styled,
+    Window,
+};
+use smallvec::SmallVec;
+
+use layout::MeasuredLayout;
+
+/// The default estimated height of the unmeasured items, before any item is measured.
+const DEFAULT_ITEM_HEIGHT: f32 = 32.;
+
+/// The max times to render the visible items in a frame,
+/// the visible range may change after the items are measured.
+const MAX_MEASURE_PASSES: usize = 3;
+
+/// The state of the [`MeasuredList`], includes the measured heights and the scroll anchor.
+///
+/// The state is shared, keep it in the view and pass it to [`v_measured_list`] on each render,
+/// call [`MeasuredListState::splice`] when the items changed.
+#[derive(Clone)]
+pub struct MeasuredListState(Rc<RefCell<StateInner>>);
+
+struct StateInner {
+    layout: MeasuredLayout,
+    scroll_handle: ScrollHandle,
+    /// The scroll top set by last prepaint, to detect the scroll by user.
+    last_scroll_top: Option<f64>,
+    /// The width of last prepaint, the items need to be measured again if changed.
+    last_width: Option<Pixels>,
+}
+
+impl MeasuredListState {
+    /// Create a new state with the number of the items.
+    pub fn new(items_count: usize) -> Self {
+        Self(Rc::new(RefCell::new(StateInner {
+            layout: MeasuredLayout::new(items_count, DEFAULT_ITEM_HEIGHT),
+            scroll_handle: ScrollHandle::new(),
+            last_scroll_top: None,
+            last_width: None,
+        })))
+    }
+
+    /// Set the estimated height for the unmeasured items before any item is measured, default: 32px.
+    pub fn estimated_item_height(self, height: impl Into<Pixels>) -> Self {
+        {
+            let mut state = self.0.borrow_mut();
+            let count = state.layout.heights.len();
+            state.layout.heights = layout::ItemHeights::new(count, height.into().0);
+        }
+        self
+    }
+
+    /// Set true to keep the list scrolled to the bottom when the content grows, default: false.
+    ///
+    /// The list starts at the bottom, and stops following when the user scrolls up,
+    /// scroll back to the bottom to follow again.
+    pub fn follow_tail(self, follow_tail: bool) -> Self {
+        self.set_follow_tail(follow_tail);
+        if follow_tail {
+            self.scroll_to_bottom();
+        }
+        self
+    }
+
+    /// Set to keep the list scrolled to the bottom when the content grows.
+    pub fn set_follow_tail(&self, follow_tail: bool) {
+        self.0.borrow_mut().layout.follow_tail = follow_tail;
+    }
+
+    /// Return true if following the tail and the list is scrolled to the bottom.
+    pub fn is_following_tail(&self) -> bool {
+        let state = self.0.borrow();
+        state.layout.follow_tail && state.layout.at_bottom
+    }
+
+    /// Return true if the list is scrolled to the bottom.
+    pub fn is_at_bottom(&self) -> bool {
+        self.0.borrow().layout.at_bottom
+    }
+
+    /// The number of the items.
+    pub fn items_count(&self) -> usize {
+        self.0.borrow().layout.heights.len()
+    }
+
+    /// Return the scroll handle, it can be used for the scrollbar.
+    pub fn scroll_handle(&self) -> ScrollHandle {
+        self.0.borrow().scroll_handle.clone()
+    }
+
+    /// Reset the state with the new number of the items, all items will be measured again.
+    pub fn reset(&self, items_count: usize) {
+        let mut state = self.0.borrow_mut();
+        let len = state.layout.heights.len();
+        state.layout.splice(0..len, items_count);
+    }
+
+    /// Replace the items in the `range` with `count` new items.
+    ///
+    /// The scroll position is kept on the first visible item,
+    /// so prepend items (`splice(0..0, count)`) will not move the visible items.
+    pub fn splice(&self, range: Range<usize>, count: usize) {
+        self.0.borrow_mut().layout.splice(range, count);
+    }
+
+    /// Measure the item again when it is rendered next time, call this when the content of the item changed.
+    pub fn remeasure_item(&self, ix: usize) {
+        self.0.borrow_mut().layout.heights.invalidate(ix);
+    }
+
+    /// Measure all items again when they are rendered next time.
+    pub fn remeasure(&self) {
+        self.0.borrow_mut().layout.heights.invalidate_all();
+    }
+
+    /// Scroll to the item, to make it at the top of the list.
+    pub fn scroll_to_item(&self, ix: usize) {
+        self.0.borrow_mut().layout.scroll_to_item(ix);
+    }
+
+    /// Scroll to the bottom of the list.
+    pub fn scroll_to_bottom(&self) {
+        self.0.borrow_mut().layout.scroll_to_bottom();
+    }
+}
+
+/// Create a measured list in Vertical direction, the items can have different heights.
+///
+/// The `f` is called with the range of the visible items to render them,
+/// each item is laid out with the width of the list to measure the height.
+pub fn v_measured_list<R, V>(
+    view: Entity<V>,
+    id: impl Into<ElementId>,
+    state: &MeasuredListState,
+    f: impl 'static + Fn(&mut V, Range<usize>, &mut Window, &mut Context<V>) -> Vec<R>,
+) -> MeasuredList
+where
+    R: IntoElement,
+    V: Render,
+{
+    let id: ElementId = id.into();
+    let scroll_handle = state.scroll_handle();
+    let render_range = move |visible_range, window: &mut Window, cx: &mut App| {
+        view.update(cx, |this, cx| {
+            f(this, visible_range, window, cx)
+                .into_iter()
+                .map(|component| component.into_any_element())
+                .collect()
+        })
+    };
+
+    MeasuredList {
+        base: div()
+            .id(id.clone())
+            .size_full()
+            .overflow_y_scroll()
+            .track_scroll(&scroll_handle),
+        id,
+        state: state.clone(),
+        scroll_handle,
+        render_items: Box::new(render_range),
+    }
+}
+
+/// A virtual list with variable height items, see [`v_measured_list`].
+pub struct MeasuredList {
+    id: ElementId,
+    base: Stateful<Div>,
+    state: MeasuredListState,
+    scroll_handle: ScrollHandle,
+    render_items: Box<
+        dyn for<'a> Fn(Range<usize>, &'a mut Window, &'a mut App) -> SmallVec<[AnyElement; 64]>,
+    >,
+}
+
+impl Styled for MeasuredList {
+    fn style(&mut self) -> &mut StyleRefinement {
+        self.base.style()
+    }
+}
+
+/// Frame state used by the [`MeasuredList`].
+pub struct MeasuredListFrameState {
+    /// Visible items to be painted.
+    items: SmallVec<[AnyElement; 32]>,
+}
+
+impl IntoElement for MeasuredList {
+    type Element = Self;
+
+    fn into_element(self) -> Self::Element {
+        self
+    }
+}
+
+impl Element for MeasuredList {
+    type RequestLayoutState = MeasuredListFrameState;
+    type PrepaintState = Option<Hitbox>;
+
+    fn id(&self) -> Option<ElementId> {
+        Some(self.id.clone())
+    }
+
+    fn source_location(&self) -> Option<&'static std::panic::Location<'static>> {
+        None
+    }
+
+    fn request_layout(
+        &mut self,
+        global_id: Option<&GlobalElementId>,
+        inspector_id: Option<&gpui::InspectorElementId>,
+        window: &mut Window,
+        cx: &mut App,
+    ) -> (gpui::LayoutId, Self::RequestLayoutState) {
+        let (layout_id, _) = self
+            .base
+            .request_layout(global_id, inspector_id, window, cx);
+
+        (
+            layout_id,
+            MeasuredListFrameState {
+                items: SmallVec::new(),
+            },
+        )
+    }
+
+    fn prepaint(
+        &mut self,
+        global_id: Option<&GlobalElementId>,
+        inspector_id: Option<&gpui::InspectorElementId>,
+        bounds: Bounds<Pixels>,
+        layout: &mut Self::RequestLayoutState,
+        window: &mut Window,
+        cx: &mut App,
+    ) -> Self::PrepaintState {
+        let style = self
+            .base
+            .interactivity()
+            .compute_style(global_id, None, window, cx);
+        let border = style.border_widths.to_pixels(window.rem_size());
+        let padding = style
+            .padding
+            .to_pixels(bounds.size.into(), window.rem_size());
+
+        let padded_bounds = Bounds::from_corners(
+            bounds.origin + point(border.left + padding.left, border.top + padding.top),
+            bounds.bottom_right()
+                - point(border.right + padding.right, border.bottom + padding.bottom),
+        );
+        let padding_height = border.top + padding.top + border.bottom + padding.bottom;
+        let viewport_height = padded_bounds.size.height.0 as f64;
+        let width = padded_bounds.size.width;
+
+        {
+            let mut state = self.state.0.borrow_mut();
+            if state
+                .last_width
+                .is_some_and(|last_width| last_width != width)
+            {
+                state.layout.heights.invalidate_all();
+            }
+            state.last_width = Some(width);
+
+            // The scroll offset was changed by the user (e.g.: mouse wheel or scrollbar),
+            // move the anchor to the new position.
+            let scroll_top = -self.scroll_handle.offset().y.0 as f64;
+            if state
+                .last_scroll_top
+                .is_some_and(|last| (last - scroll_top).abs() > 0.5)
+            {
+                state.layout.set_scroll_top(scroll_top, viewport_height);
+            }
+        }
+
+        // Render and measure the visible items, the state must not be borrowed when rendering,
+        // because the view may access the state in the render function.
+        let available_space = size(AvailableSpace::Definite(width), AvailableSpace::MinContent);
+        let mut items = SmallVec::<[AnyElement; 64]>::new();
+        let mut visible_range = 0..0;
+        let mut scroll_top = 0.;
+        for _ in 0..MAX_MEASURE_PASSES {
+            {
+                let state = self.state.0.borrow();
+                scroll_top = state.layout.scroll_top(viewport_height);
+                visible_range = state.layout.visible_range(scroll_top, viewport_height);
+            }
+            if visible_range.is_empty() {
+                items.clear();
+                break;
+            }
+
+            items = (self.render_items)(visible_range.clone(), window, cx);
+            let sizes = items
+                .iter_mut()
+                .map(|item| item.layout_as_root(available_space, window, cx))
+                .collect::<Vec<_>>();
+
+            let mut state = self.state.0.borrow_mut();
+            let mut changed = false;
+            for (ix, item_size) in visible_range.clone().zip(sizes) {
+                changed |= state.layout.heights.set_height(ix, item_size.height.0);
+            }
+            if !changed {
+                break;
+            }
+        }
+
+        let (content_height, item_origins) = {
+            let mut state = self.state.0.borrow_mut();
+            // Keep the anchor on the first visible item with the final heights.
+            scroll_top = state.layout.scroll_top(viewport_height);
+            state.layout.set_scroll_top(scroll_top, viewport_height);
+            state.last_scroll_top = Some(scroll_top);
+
+            let item_origins = visible_range
+                .clone()
+                .map(|ix| px((state.layout.heights.origin(ix) - scroll_top) as f32))
+                .collect::<Vec<_>>();
+            (px(state.layout.heights.total_height() as f32), item_origins)
+        };
+
+        self.scroll_handle
+            .set_offset(point(px(0.), -px(scroll_top as f32)));
+
+        let content_size = Size {
+            width: bounds.size.width,
+            height: content_height + padding_height,
+        };
+
+        self.base.interactivity().prepaint(
+            global_id,
+            inspector_id,
+            bounds,
+            content_size,
+            window,
+            cx,
+            |_, _, hitbox, window, cx| {
+                let content_mask = ContentMask { bounds };
+                window.with_content_mask(Some(content_mask), |window| {
+                    for (mut item, origin) in items.into_iter().zip(item_origins) {
+                        item.prepaint_at(padded_bounds.origin + point(px(0.), origin), window, cx);
+                        layout.items.push(item);
+                    }
+                });
+
+                hitbox
+            },
+        )
+    }
+
+    fn paint(
+        &mut self,
+        global_id: Option<&GlobalElementId>,
+        inspector_id: Option<&gpui::InspectorElementId>,
+        bounds: Bounds<Pixels>,
+        layout: &mut Self::RequestLayoutState,
+        hitbox: &mut Self::PrepaintState,
+        window: &mut Window,
+        cx: &mut App,
+    ) {
+        self.base.interactivity().paint(
+            global_id,
+            inspector_id,
+            bounds,
+            hitbox.as_ref(),
+            window,
+            cx,
+            |_, window, cx| {
+                for item in &mut layout.items {
+                    item.paint(window, cx);
+                }
+            },
+        )
+    }
+}
@@ -1,9 +1,8 @@
-use gpui::{
-    point, px, App, Bounds, FontWeight, Hsla, PathBuilder, Pixels, Point, SharedString, TextAlign,
-    Window,
-};
+use gpui::{point, px, App, Bounds, FontWeight, Hsla, Pixels, Point, SharedString, TextAlign};
 
-use super::{label::Label, label::Text, label::TEXT_GAP, label::TEXT_SIZE, origin_point};
+use super::{
+    label::Label, label::Text, label::TEXT_GAP, label::TEXT_SIZE, origin_point, Canvas, PlotPath,
+};
 
 pub const AXIS_GAP: f64 = 18.;
 
@@ -122,17 +121,15 @@ impl Axis {
         self
     }
 
-    fn draw_axis(&self, start_point: Point<Pixels>, end_point: Point<Pixels>, window: &mut Window) {
-        let mut builder = PathBuilder::stroke(px(1.));
+    fn draw_axis(&self, start_point: Point<Pixels>, end_point: Point<Pixels>, canvas: &mut Canvas) {
+        let mut builder = PlotPath::stroke(px(1.));
         builder.move_to(start_point);
         builder.line_to(end_point);
-        if let Ok(path) = builder.build() {
-            window.paint_path(path, self.stroke);
-        }
+        canvas.paint_path(builder, self.stroke);
     }
 
     /// Paint the Axis.
-    pub fn paint(&self, bounds: &Bounds<Pixels>, window: &mut Window, cx: &mut App) {
+    pub fn paint(&self, bounds: &Bounds<Pixels>, canvas: &mut Canvas, cx: &mut App) {
         let origin = bounds.origin;
 
         // X axis
@@ -141,11 +138,11 @@ impl Axis {
                 self.draw_axis(
                     origin_point(px(0.), x, origin),
                     origin_point(bounds.size.width, x, origin),
-                    window,
+                    canvas,
                 );
             }
         }
-        self.x_label.paint(bounds, window, cx);
+        self.x_label.paint(bounds, canvas, cx);
 
         // Y axis
         if let Some(y) = self.y {
@@ -153,10 +150,10 @@ impl Axis {
                 self.draw_axis(
                     origin_point(y, px(0.), origin),
                     origin_point(y, bounds.size.height, origin),
-                    window,
+                    canvas,
                 );
             }
         }
-        self.y_label.paint(bounds, window, cx);
+        self.y_label.paint(bounds, canvas, cx);
     }
 }
@@ -0,0 +1,259 @@
+use gpui::{
+    quad, Background, BorderStyle, Bounds, ContentMask, Hitbox, Hsla, LinearColorStop, Pixels,
+    Point, SharedString, TextRun, Window,
+};
+use serde::Deserialize;
+
+use super::{svg::SvgDocument, PlotPath};
+
+/// The paint of the paths, a color or a linear gradient.
+#[derive(Clone, Copy, Debug)]
+pub enum Paint {
+    Color(Hsla),
+    LinearGradient {
+        /// The angle in degrees, `0.` is from the bottom to the top.
+        angle: f32,
+        from: LinearColorStop,
+        to: LinearColorStop,
+    },
+}
+
+impl Default for Paint {
+    fn default() -> Self {
+        Self::Color(Hsla::default())
+    }
+}
+
+impl From<Hsla> for Paint {
+    fn from(color: Hsla) -> Self {
+        Self::Color(color)
+    }
+}
+
+impl From<Paint> for Background {
+    fn from(paint: Paint) -> Self {
+        match paint {
+            Paint::Color(color) => color.into(),
+            Paint::LinearGradient { angle, from, to } => gpui::linear_gradient(angle, from, to),
+        }
+    }
+}
+
+impl From<Background> for Paint {
+    /// The fields of the [`Background`] are private, they are read by the serialization.
+    ///
+    /// The pattern backgrounds are painted in the solid color.
+    fn from(background: Background) -> Self {
+        #[derive(Deserialize)]
+        struct Fields {
+            tag: String,
+            solid: Hsla,
+            gradient_angle_or_pattern_height: f32,
+            colors: [LinearColorStop; 2],
+        }
+
+        let Some(fields) = serde_json::to_value(background)
+            .ok()
+            .and_then(|value| serde_json::from_value::<Fields>(value).ok())
+        else {
+            return Self::default();
+        };
+
+        match fields.tag.as_str() {
+            "LinearGradient" => Self::LinearGradient {
+                angle: fields.gradient_angle_or_pattern_height,
+                from: fields.colors[0],
+                to: fields.colors[1],
+            },
+            _ => Self::Color(fields.solid),
+        }
+    }
+}
+
+/// Create a linear gradient to paint the paths, the same as [`gpui::linear_gradient`].
+pub fn linear_gradient(
+    angle: f32,
+    from: impl Into<LinearColorStop>,
+    to: impl Into<LinearColorStop>,
+) -> Paint {
+    Paint::LinearGradient {
+        angle,
+        from: from.into(),
+        to: to.into(),
+    }
+}
+
+/// A rectangle to paint in the [`Canvas`], e.g.: The bars and the dots.
+#[derive(Clone, Copy, Debug)]
+pub struct Quad {
+    pub bounds: Bounds<Pixels>,
+    pub corner_radii: Pixels,
+    pub background: Hsla,
+    pub border_widths: Pixels,
+    pub border_color: Hsla,
+}
+
+impl Quad {
+    /// Create a filled Quad, the same as [`gpui::fill`].
+    pub fn fill(bounds: Bounds<Pixels>, background: impl Into<Hsla>) -> Self {
+        Self {
+            bounds,
+            corner_radii: Pixels::default(),
+            background: background.into(),
+            border_widths: Pixels::default(),
+            border_color: Hsla::default(),
+        }
+    }
+
+    /// Set the corner radii of the Quad.
+    pub fn corner_radii(mut self, corner_radii: impl Into<Pixels>) -> Self {
+        self.corner_radii = corner_radii.into();
+        self
+    }
+
+    /// Set the border widths of the Quad, the border is inside the bounds.
+    pub fn border_widths(mut self, border_widths: impl Into<Pixels>) -> Self {
+        self.border_widths = border_widths.into();
+        self
+    }
+
+    /// Set the border color of the Quad.
+    pub fn border_color(mut self, border_color: impl Into<Hsla>) -> Self {
+        self.border_color = border_color.into();
+        self
+    }
+}
+
+/// The target to paint the [`Plot`](super::Plot), the window or an SVG document to export.
+///
+/// The window is painted with the hitbox of the plot, if any, to handle the mouse events.
+pub enum Canvas<'a> {
+    Window(&'a mut Window, Option<&'a Hitbox>),
+    Svg(&'a mut SvgDocument),
+}
+
+impl Canvas<'_> {
+    /// Return the window to register the events, it's `None` when exporting.
+    pub fn window(&mut self) -> Option<&mut Window> {
+        match self {
+            Self::Window(window, _) => Some(&mut **window),
+            Self::Svg(_) => None,
+        }
+    }
+
+    /// Return the hitbox of the plot, to check the mouse is over the plot and not covered
+    /// by other elements (e.g.: A popover), it's `None` when exporting.
+    pub fn hitbox(&self) -> Option<&Hitbox> {
+        match self {
+            Self::Window(_, hitbox) => *hitbox,
+            Self::Svg(_) => None,
+        }
+    }
+
+    /// Return the position of the mouse, it's `None` when exporting.
+    pub fn mouse_position(&self) -> Option<Point<Pixels>> {
+        match self {
+            Self::Window(window, _) => Some(window.mouse_position()),
+            Self::Svg(_) => None,
+        }
+    }
+
+    /// Measure the width of the text in the font size.
+    pub fn text_width(&mut self, text: &SharedString, font_size: Pixels) -> Pixels {
+        let window = match self {
+            Self::Window(window, _) => window,
+            Self::Svg(svg) => return svg.text_width(text, font_size),
+        };
+
+        let text_run = TextRun {
+            len: text.len(),
+            font: window.text_style().font(),
+            color: Hsla::default(),
+            background_color: None,
+            underline: None,
+            strikethrough: None,
+        };
+        window
+            .text_system()
+            .shape_text(text.clone(), font_size, &[text_run], None, None)
+            .ok()
+            .and_then(|lines| lines.first().map(|line| line.size(font_size).width))
+            .unwrap_or_default()
+    }
+
+    /// Paint the Quad.
+    pub fn paint_quad(&mut self, q: Quad) {
+        match self {
+            Self::Window(window, _) => window.paint_quad(quad(
+                q.bounds,
+                q.corner_radii,
+                q.background,
+                q.border_widths,
+                q.border_color,
+                BorderStyle::default(),
+            )),
+            Self::Svg(svg) => svg.quad(&q),
+        }
+    }
+
+    /// Paint the path, it's skipped if the path is not valid.
+    pub fn paint_path(&mut self, path: PlotPath, paint: impl Into<Paint>) {
+        let paint = paint.into();
+        match self {
+            Self::Window(window, _) => {
+                if let Some(path) = path.build() {
+                    window.paint_path(path, paint);
+                }
+            }
+            Self::Svg(svg) => svg.path(&path, paint),
+        }
+    }
+
+    /// Paint in the `bounds`, the content out of the bounds is clipped.
+    pub fn with_content_mask<R>(
+        &mut self,
+        bounds: Bounds<Pixels>,
+        f: impl FnOnce(&mut Canvas) -> R,
+    ) -> R {
+        match self {
+            Self::Window(window, hitbox) => window
+                .with_content_mask(Some(ContentMask { bounds }), |window| {
+                    f(&mut Canvas::Window(window, *hitbox))
+                }),
+            Self::Svg(svg) => {
+                svg.push_clip(bounds);
+                let result = f(&mut Canvas::Svg(svg));
+                svg.pop_clip();
+                result
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use gpui::{hsla, linear_color_stop, Background};
+
+    use super::Paint;
+
+    #[test]
+    fn test_paint_from_background() {
+        let black = hsla(0., 0., 0., 1.);
+        let white = hsla(0., 0., 1., 1.);
+
+        let paint = Paint::from(Background::from(white));
+        assert!(matches!(paint, Paint::Color(color) if color == white));
+
+        let paint = Paint::from(gpui::linear_gradient(
+            90.,
+            linear_color_stop(black, 0.),
+            linear_color_stop(white, 1.),
+        ));
+        let Paint::LinearGradient { angle, from, to } = paint else {
+            panic!("expected a linear gradient, got {:?}", paint);
+        };
+        assert_eq!(angle, 90.);
+        assert_eq!((from.color, from.percentage), (black, 0.));
+        assert_eq!((to.color, to.percentage), (white, 1.));
+    }
+}
@@ -0,0 +1,171 @@
+use std::rc::Rc;
+
+use gpui::SharedString;
+
+/// The SI prefixes from 10^-9 to 10^12.
+const SI_PREFIXES: [(i32, &str); 8] = [
+    (-9, "n"),
+    (-6, "µ"),
+    (-3, "m"),
+    (0, ""),
+    (3, "k"),
+    (6, "M"),
+    (9, "G"),
+    (12, "T"),
+];
+
+/// The number format of the axis labels.
+///
+/// The `step` between the ticks decides the decimals, e.g.: the ticks with step 0.5 have 1 decimal.
+#[derive(Clone, Default)]
+pub enum NumberFormat {
+    /// Format with the decimals of the step and the thousands separator, e.g.: `1,000`, `0.5`.
+    #[default]
+    Auto,
+    /// Format with the fixed decimals and the thousands separator.
+    Fixed(usize),
+    /// Format with the SI prefix, e.g.: `1.5k`, `20M`, `300µ`.
+    Si,
+    /// Format as percentage, 1.0 is `100%`.
+    Percent,
+    /// Format as currency with the symbol, e.g.: `$1,200`, `-$0.50`.
+    Currency(SharedString),
+    /// Format with the custom function.
+    Custom(Rc<dyn Fn(f64) -> SharedString>),
+}
+
+impl NumberFormat {
+    /// Create a currency format with the symbol.
+    pub fn currency(symbol: impl Into<SharedString>) -> Self {
+        Self::Currency(symbol.into())
+    }
+
+    /// Create a custom format.
+    pub fn custom(f: impl Fn(f64) -> SharedString + 'static) -> Self {
+        Self::Custom(Rc::new(f))
+    }
+
+    /// Format the value, the `step` is the distance between the ticks.
+    pub fn format(&self, value: f64, step: f64) -> SharedString {
+        match self {
+            Self::Auto => group_thousands(value, precision(step)).into(),
+            Self::Fixed(decimals) => group_thousands(value, *decimals).into(),
+            Self::Si => format_si(value).into(),
+            Self::Percent => {
+                format!("{}%", group_thousands(value * 100., precision(step * 100.))).into()
+            }
+            Self::Currency(symbol) => {
+                let decimals = if precision(step) > 0 { 2 } else { 0 };
+                let text = group_thousands(value.abs(), decimals);
+                if value < 0. {
+                    format!("-{}{}", symbol, text).into()
+                } else {
+                    format!("{}{}", symbol, text).into()
+                }
+            }
+            Self::Custom(f) => f(value),
+        }
+    }
+}
+
+/// Return the decimals to show the step, e.g.: 0.5 has 1 decimal.
+fn precision(step: f64) -> usize {
+    let step = step.abs();
+    if step == 0. || !step.is_finite() {
+        return 0;
+    }
+
+    (-step.log10().floor()).max(0.) as usize
+}
+
+/// Format the value with the decimals and the thousands separator.
+fn group_thousands(value: f64, decimals: usize) -> String {
+    let text = format!("{:.*}", decimals, value.abs());
+    let (int, frac) = text
+        .split_once('.')
+        .map_or((text.as_str(), None), |(int, frac)| (int, Some(frac)));
+
+    let mut grouped = String::new();
+    for (i, c) in int.chars().enumerate() {
+        if i > 0 && (int.len() - i) % 3 == 0 {
+            grouped.push(',');
+        }
+        grouped.push(c);
+    }
+    if let Some(frac) = frac {
+        grouped.push('.');
+        grouped.push_str(frac);
+    }
+
+    // Avoid `-0` after rounding.
+    if value < 0. && grouped.chars().any(|c| c.is_ascii_digit() && c != '0') {
+        format!("-{}", grouped)
+    } else {
+        grouped
+    }
+}
+
+/// Format the value with the SI prefix, keep at most 3 significant digits.
+fn format_si(value: f64) -> String {
+    if value == 0. || !value.is_finite() {
+        return format!("{}", value);
+    }
+
+    let exp = (value.abs().log10().floor() as i32).div_euclid(3) * 3;
+    let (exp, prefix) = SI_PREFIXES
+        .iter()
+        .copied()
+        .min_by_key(|(e, _)| (e - exp).abs())
+        .unwrap_or((0, ""));
+    let scaled = value / 10f64.powi(exp);
+
+    let decimals = (2 - scaled.abs().log10().floor() as i32).max(0) as usize;
+    let text = format!("{:.*}", decimals, scaled);
+    let text = if text.contains('.') {
+        text.trim_end_matches('0').trim_end_matches('.')
+    } else {
+        &text
+    };
+    format!("{}{}", text, prefix)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::NumberFormat;
+
+    #[test]
+    fn test_format_auto() {
+        let format = NumberFormat::Auto;
+        assert_eq!(format.format(0., 20.), "0");
+        assert_eq!(format.format(1234567., 1000.), "1,234,567");
+        assert_eq!(format.format(-1200., 200.), "-1,200");
+        assert_eq!(format.format(1.5, 0.5), "1.5");
+        assert_eq!(format.format(1., 0.5), "1.0");
+        assert_eq!(format.format(0.05, 0.01), "0.05");
+        assert_eq!(format.format(-0.001, 0.1), "0.0");
+        assert_eq!(NumberFormat::Fixed(2).format(1000., 100.), "1,000.00");
+    }
+
+    #[test]
+    fn test_format_si() {
+        let format = NumberFormat::Si;
+        assert_eq!(format.format(0., 1.), "0");
+        assert_eq!(format.format(1500., 500.), "1.5k");
+        assert_eq!(format.format(2000., 500.), "2k");
+        assert_eq!(format.format(25_000_000., 1.), "25M");
+        assert_eq!(format.format(-123456., 1.), "-123k");
+        assert_eq!(format.format(0.0003, 1.), "300µ");
+        assert_eq!(format.format(42., 1.), "42");
+    }
+
+    #[test]
+    fn test_format_percent_and_currency() {
+        assert_eq!(NumberFormat::Percent.format(0.25, 0.05), "25%");
+        assert_eq!(NumberFormat::Percent.format(0.125, 0.005), "12.5%");
+        let format = NumberFormat::currency("$");
+        assert_eq!(format.format(1200., 200.), "$1,200");
+        assert_eq!(format.format(-0.5, 0.1), "-$0.50");
+        let format = NumberFormat::custom(|v| format!("{} ms", v).into());
+        assert_eq!(format.format(20., 10.), "20 ms");
+    }
+}
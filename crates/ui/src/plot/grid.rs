@@ -1,6 +1,6 @@
-use gpui::{px, Bounds, Hsla, PathBuilder, Pixels, Point, Window};
+use gpui::{px, Bounds, Hsla, Pixels, Point};
 
-use super::origin_point;
+use super::{origin_point, Canvas, PlotPath};
 
 pub struct Grid {
     x: Vec<Pixels>,
@@ -75,21 +75,19 @@ impl Grid {
     }
 
     /// Paint the Grid.
-    pub fn paint(&self, bounds: &Bounds<Pixels>, window: &mut Window) {
+    pub fn paint(&self, bounds: &Bounds<Pixels>, canvas: &mut Canvas) {
         let points = self.points(bounds);
 
         for (start, end) in points {
-            let mut builder = PathBuilder::stroke(px(1.));
+            let mut builder = PlotPath::stroke(px(1.));
 
             if let Some(dash_array) = &self.dash_array {
-                builder = builder.dash_array(&dash_array);
+                builder = builder.dash_array(dash_array);
             }
 
             builder.move_to(start);
             builder.line_to(end);
-            if let Ok(line) = builder.build() {
-                window.paint_path(line, self.stroke);
-            }
+            canvas.paint_path(builder, self.stroke);
         }
     }
 }
@@ -2,10 +2,9 @@ use std::fmt::Debug;
 
 use gpui::{
     point, px, App, Bounds, FontWeight, Hsla, Pixels, Point, SharedString, TextAlign, TextRun,
-    Window,
 };
 
-use super::origin_point;
+use super::{origin_point, Canvas};
 
 pub const TEXT_SIZE: f64 = 10.;
 pub const TEXT_GAP: f64 = 2.;
@@ -74,7 +73,7 @@ impl Label {
     }
 
     /// Paint the Label.
-    pub fn paint(&self, bounds: &Bounds<Pixels>, window: &mut Window, cx: &mut App) {
+    pub fn paint(&self, bounds: &Bounds<Pixels>, canvas: &mut Canvas, cx: &mut App) {
         for Text {
             text,
             origin,
@@ -85,6 +84,13 @@ impl Label {
         } in self.0.iter()
         {
             let origin = origin_point(origin.x, origin.y, bounds.origin);
+            let window = match canvas {
+                Canvas::Window(window, _) => window,
+                Canvas::Svg(svg) => {
+                    svg.text(text, origin, *font_size, *font_weight, *align, *color);
+                    continue;
+                }
+            };
 
             let text_run = TextRun {
                 len: text.len(),
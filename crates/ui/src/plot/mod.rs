@@ -1,22 +1,72 @@
 mod axis;
+mod canvas;
+mod format;
 mod grid;
 pub mod label;
+mod path;
 pub mod scale;
 pub mod shape;
+mod svg;
 pub mod tooltip;
 
 pub use gpui_component_macros::IntoPlot;
 
 use std::{fmt::Debug, ops::Add};
 
-use gpui::{point, px, App, Bounds, IntoElement, Path, PathBuilder, Pixels, Point, Window};
+use gpui::{point, px, AnyElement, App, Bounds, IntoElement, Pixels, Point, Size, Window};
+
+use crate::ActiveTheme;
 
 pub use axis::{Axis, AxisText, AXIS_GAP};
+pub use canvas::{linear_gradient, Canvas, Paint, Quad};
+pub use format::NumberFormat;
 pub use grid::Grid;
 pub use label::Label;
+pub use path::PlotPath;
+pub use svg::SvgDocument;
 
 pub trait Plot: IntoElement {
+    /// Prepaint the overlay of the plot (e.g.: the tooltip), it's painted above the plot.
+    fn prepaint(
+        &mut self,
+        _bounds: Bounds<Pixels>,
+        _window: &mut Window,
+        _cx: &mut App,
+    ) -> Option<AnyElement> {
+        None
+    }
+
     fn paint(&mut self, bounds: Bounds<Pixels>, window: &mut Window, cx: &mut App);
+
+    /// Paint the plot in the [`Canvas`], the window or an SVG document to export.
+    ///
+    /// The default paints in the window by [`Plot::paint`] and nothing is exported,
+    /// implement it to support the [`to_svg`](Self::to_svg) and [`to_png`](Self::to_png).
+    fn paint_canvas(&mut self, bounds: Bounds<Pixels>, canvas: &mut Canvas, cx: &mut App) {
+        if let Some(window) = canvas.window() {
+            self.paint(bounds, window, cx);
+        }
+    }
+
+    /// Export the plot to an SVG document in the `size`, without a window.
+    ///
+    /// The plot is painted by the theme, the fonts of the text are resolved when rendering.
+    fn to_svg(&mut self, size: Size<Pixels>, cx: &mut App) -> String {
+        let mut svg = SvgDocument::new(size, cx.theme().font_family.clone());
+        self.paint_canvas(
+            Bounds::new(Point::default(), size),
+            &mut Canvas::Svg(&mut svg),
+            cx,
+        );
+        svg.to_string()
+    }
+
+    /// Export the plot to a PNG image in the `size`, without a window.
+    ///
+    /// The `scale` is the scale factor of the pixels, e.g.: `2.` for the image of the retina display.
+    fn to_png(&mut self, size: Size<Pixels>, scale: f32, cx: &mut App) -> anyhow::Result<Vec<u8>> {
+        svg::render_png(&self.to_svg(size, cx), scale)
+    }
 }
 
 #[derive(Clone, Copy, Default)]
@@ -33,11 +83,19 @@ where
     point(x, y) + origin
 }
 
-pub fn polygon<T>(points: &[Point<T>], bounds: &Bounds<Pixels>) -> Option<Path<Pixels>>
+pub fn polygon<T>(points: &[Point<T>], bounds: &Bounds<Pixels>) -> Option<gpui::Path<Pixels>>
+where
+    T: Default + Clone + Copy + Debug + Into<f64> + PartialEq,
+{
+    polygon_path(points, bounds).build()
+}
+
+/// Return the polygon as a [`PlotPath`], so that it can be painted in any [`Canvas`].
+pub fn polygon_path<T>(points: &[Point<T>], bounds: &Bounds<Pixels>) -> PlotPath
 where
     T: Default + Clone + Copy + Debug + Into<f64> + PartialEq,
 {
-    let mut path = PathBuilder::stroke(px(1.));
+    let mut path = PlotPath::stroke(px(1.));
     let points = &points
         .iter()
         .map(|p| {
@@ -48,5 +106,5 @@ where
         })
         .collect::<Vec<_>>();
     path.add_polygon(points, false);
-    path.build().ok()
+    path
 }
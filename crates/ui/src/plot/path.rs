@@ -0,0 +1,248 @@
+use std::fmt::Write;
+
+use gpui::{Path, PathBuilder, Pixels, Point};
+
+use super::svg::number;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Command {
+    MoveTo(Point<Pixels>),
+    LineTo(Point<Pixels>),
+    CubicBezierTo {
+        to: Point<Pixels>,
+        control_a: Point<Pixels>,
+        control_b: Point<Pixels>,
+    },
+    ArcTo {
+        radii: Point<Pixels>,
+        x_rotation: Pixels,
+        large_arc: bool,
+        sweep: bool,
+        to: Point<Pixels>,
+    },
+    Close,
+}
+
+/// A path to paint in the [`Canvas`](super::Canvas), it's built into a path of the window or written to SVG.
+///
+/// The methods are the same as the [`PathBuilder`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct PlotPath {
+    /// The width of the stroke, it's `None` for the path to fill.
+    pub(super) stroke_width: Option<Pixels>,
+    pub(super) dash_array: Option<Vec<Pixels>>,
+    commands: Vec<Command>,
+}
+
+impl PlotPath {
+    /// Create a path to stroke with the `width`.
+    pub fn stroke(width: Pixels) -> Self {
+        Self {
+            stroke_width: Some(width),
+            dash_array: None,
+            commands: vec![],
+        }
+    }
+
+    /// Create a path to fill.
+    pub fn fill() -> Self {
+        Self {
+            stroke_width: None,
+            dash_array: None,
+            commands: vec![],
+        }
+    }
+
+    /// Set the dash array of the stroke.
+    pub fn dash_array(mut self, dash_array: &[Pixels]) -> Self {
+        self.dash_array = Some(dash_array.to_vec());
+        self
+    }
+
+    pub fn move_to(&mut self, to: Point<Pixels>) {
+        self.commands.push(Command::MoveTo(to));
+    }
+
+    pub fn line_to(&mut self, to: Point<Pixels>) {
+        self.commands.push(Command::LineTo(to));
+    }
+
+    pub fn cubic_bezier_to(
+        &mut self,
+        to: Point<Pixels>,
+        control_a: Point<Pixels>,
+        control_b: Point<Pixels>,
+    ) {
+        self.commands.push(Command::CubicBezierTo {
+            to,
+            control_a,
+            control_b,
+        });
+    }
+
+    pub fn arc_to(
+        &mut self,
+        radii: Point<Pixels>,
+        x_rotation: Pixels,
+        large_arc: bool,
+        sweep: bool,
+        to: Point<Pixels>,
+    ) {
+        self.commands.push(Command::ArcTo {
+            radii,
+            x_rotation,
+            large_arc,
+            sweep,
+            to,
+        });
+    }
+
+    pub fn close(&mut self) {
+        self.commands.push(Command::Close);
+    }
+
+    /// Add a polygon through the points.
+    pub fn add_polygon(&mut self, points: &[Point<Pixels>], closed: bool) {
+        let Some((first, rest)) = points.split_first() else {
+            return;
+        };
+
+        self.move_to(*first);
+        for p in rest {
+            self.line_to(*p);
+        }
+        if closed {
+            self.close();
+        }
+    }
+
+    /// Build the path to paint in the window.
+    pub fn build(&self) -> Option<Path<Pixels>> {
+        let mut builder = match self.stroke_width {
+            Some(width) => PathBuilder::stroke(width),
+            None => PathBuilder::fill(),
+        };
+        if let Some(dash_array) = &self.dash_array {
+            builder = builder.dash_array(dash_array);
+        }
+
+        for command in &self.commands {
+            match *command {
+                Command::MoveTo(to) => builder.move_to(to),
+                Command::LineTo(to) => builder.line_to(to),
+                Command::CubicBezierTo {
+                    to,
+                    control_a,
+                    control_b,
+                } => builder.cubic_bezier_to(to, control_a, control_b),
+                Command::ArcTo {
+                    radii,
+                    x_rotation,
+                    large_arc,
+                    sweep,
+                    to,
+                } => builder.arc_to(radii, x_rotation, large_arc, sweep, to),
+                Command::Close => builder.close(),
+            }
+        }
+
+        builder.build().ok()
+    }
+
+    /// Return the path data of SVG, the `d` attribute of the `<path>`.
+    pub fn svg_data(&self) -> String {
+        let point = |p: Point<Pixels>| format!("{} {}", number(p.x.to_f64()), number(p.y.to_f64()));
+
+        let mut data = String::new();
+        for command in &self.commands {
+            if !data.is_empty() {
+                data.push(' ');
+            }
+
+            let _ = match *command {
+                Command::MoveTo(to) => write!(data, "M{}", point(to)),
+                Command::LineTo(to) => write!(data, "L{}", point(to)),
+                Command::CubicBezierTo {
+                    to,
+                    control_a,
+                    control_b,
+                } => write!(
+                    data,
+                    "C{} {} {}",
+                    point(control_a),
+                    point(control_b),
+                    point(to)
+                ),
+                Command::ArcTo {
+                    radii,
+                    x_rotation,
+                    large_arc,
+                    sweep,
+                    to,
+                } => write!(
+                    data,
+                    "A{} {} {} {} {}",
+                    point(radii),
+                    number(x_rotation.to_f64()),
+                    large_arc as u8,
+                    sweep as u8,
+                    point(to)
+                ),
+                Command::Close => write!(data, "Z"),
+            };
+        }
+        data
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use gpui::{point, px};
+
+    #[test]
+    fn test_svg_data() {
+        let mut path = PlotPath::stroke(px(1.));
+        path.move_to(point(px(0.), px(10.)));
+        path.line_to(point(px(20.5), px(10.)));
+        path.cubic_bezier_to(
+            point(px(40.), px(0.)),
+            point(px(25.), px(10.)),
+            point(px(35.), px(0.125)),
+        );
+        path.arc_to(
+            point(px(5.), px(5.)),
+            px(0.),
+            false,
+            true,
+            point(px(50.), px(5.)),
+        );
+        path.close();
+
+        assert_eq!(
+            path.svg_data(),
+            "M0 10 L20.5 10 C25 10 35 0.13 40 0 A5 5 0 0 1 50 5 Z"
+        );
+        assert_eq!(path.stroke_width, Some(px(1.)));
+    }
+
+    #[test]
+    fn test_add_polygon() {
+        let mut path = PlotPath::fill();
+        path.add_polygon(
+            &[
+                point(px(0.), px(0.)),
+                point(px(10.), px(0.)),
+                point(px(10.), px(10.)),
+            ],
+            true,
+        );
+        assert_eq!(path.svg_data(), "M0 0 L10 0 L10 10 Z");
+        assert_eq!(path.stroke_width, None);
+
+        let mut path = PlotPath::fill();
+        path.add_polygon(&[], true);
+        assert_eq!(path.svg_data(), "");
+    }
+}
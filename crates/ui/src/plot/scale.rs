@@ -1,12 +1,23 @@
 mod band;
 mod linear;
+mod log;
 mod point;
 mod sealed;
+mod sequential;
+mod time;
+
+use std::{borrow::Cow, fmt::Display, rc::Rc, sync::Arc};
+
+use chrono::{DateTime, TimeZone};
+use gpui::SharedString;
 
 pub use band::ScaleBand;
 pub use linear::ScaleLinear;
+pub use log::ScaleLog;
 pub use point::ScalePoint;
 pub(crate) use sealed::Sealed;
+pub use sequential::ScaleSequential;
+pub use time::{ScaleTime, TimeInterval};
 
 pub trait Scale<T> {
     /// Get the tick of the scale.
@@ -14,4 +25,130 @@ pub trait Scale<T> {
 
     /// Get the least index of the scale.
     fn least_index(&self, tick: f64) -> usize;
+
+    /// Get about `count` ticks to label the axis, returns the tick and the label text.
+    ///
+    /// Default is empty for the scales that label each value, e.g.: `ScalePoint`.
+    fn axis_ticks(&self, _count: usize) -> Vec<(f64, SharedString)> {
+        vec![]
+    }
+}
+
+/// The value of the x-axis in `LineChart` and `AreaChart`, it decides the scale of the x-axis.
+///
+/// - The text values use [`ScalePoint`], each value is labelled on the axis.
+///   It's implemented for the string types that can be converted into [`SharedString`],
+///   other types can be wrapped in [`PointValue`], or implement it the same way with
+///   `ScalePoint<Self>`.
+/// - The [`DateTime`] values use [`ScaleTime`], the axis is labelled at the calendar intervals.
+///
+/// The x values were any `PartialEq + Into<SharedString>` types before, the types other than
+/// the string types need to be wrapped in [`PointValue`] now, e.g.: `.x(|d| PointValue(d.kind))`.
+pub trait XAxisValue: Clone + PartialEq + 'static {
+    type Scale: Scale<Self> + Clone + 'static;
+
+    /// Create the scale of the x-axis.
+    fn scale(domain: Vec<Self>, range: Vec<f64>) -> Self::Scale;
+
+    /// Get the label of the value, None to label the axis by the ticks of the scale.
+    fn label(&self) -> Option<SharedString>;
+}
+
+macro_rules! impl_text_x_axis_value {
+    ($($ty:ty => |$value:ident| $label:expr),* $(,)?) => {
+        $(
+            impl XAxisValue for $ty {
+                type Scale = ScalePoint<Self>;
+
+                fn scale(domain: Vec<Self>, range: Vec<f64>) -> Self::Scale {
+                    ScalePoint::new(domain, range)
+                }
+
+                fn label(&self) -> Option<SharedString> {
+                    let $value = self;
+                    Some($label)
+                }
+            }
+        )*
+    };
+}
+
+impl_text_x_axis_value!(
+    SharedString => |value| value.clone(),
+    String => |value| value.clone().into(),
+    &'static str => |value| (*value).into(),
+    Cow<'static, str> => |value| value.to_string().into(),
+    Arc<str> => |value| value.to_string().into(),
+    Rc<str> => |value| value.to_string().into(),
+);
+
+/// The x value of any type that can be converted into [`SharedString`], it uses [`ScalePoint`]
+/// and each value is labelled on the axis.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct PointValue<T>(pub T);
+
+impl<T> XAxisValue for PointValue<T>
+where
+    T: Clone + PartialEq + Into<SharedString> + 'static,
+{
+    type Scale = ScalePoint<Self>;
+
+    fn scale(domain: Vec<Self>, range: Vec<f64>) -> Self::Scale {
+        ScalePoint::new(domain, range)
+    }
+
+    fn label(&self) -> Option<SharedString> {
+        Some(self.0.clone().into())
+    }
+}
+
+impl<Tz> XAxisValue for DateTime<Tz>
+where
+    Tz: TimeZone + 'static,
+    Tz::Offset: Display + 'static,
+{
+    type Scale = ScaleTime<Tz>;
+
+    fn scale(domain: Vec<Self>, range: Vec<f64>) -> Self::Scale {
+        ScaleTime::new(domain, range)
+    }
+
+    fn label(&self) -> Option<SharedString> {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use gpui::SharedString;
+
+    use super::{PointValue, Scale, XAxisValue};
+
+    #[derive(Clone, Copy, PartialEq)]
+    enum Kind {
+        Fruit,
+        Vegetable,
+    }
+
+    impl From<Kind> for SharedString {
+        fn from(kind: Kind) -> Self {
+            match kind {
+                Kind::Fruit => "Fruit".into(),
+                Kind::Vegetable => "Vegetable".into(),
+            }
+        }
+    }
+
+    #[test]
+    fn test_point_value() {
+        let domain = vec![PointValue(Kind::Fruit), PointValue(Kind::Vegetable)];
+        let scale = PointValue::scale(domain, vec![0., 100.]);
+
+        assert_eq!(scale.tick(&PointValue(Kind::Fruit)), Some(0.));
+        assert_eq!(scale.tick(&PointValue(Kind::Vegetable)), Some(100.));
+        assert_eq!(
+            PointValue(Kind::Vegetable).label(),
+            Some(SharedString::from("Vegetable"))
+        );
+    }
 }
@@ -48,6 +48,15 @@ impl<T> ScaleBand<T> {
         self.padding_outer = padding_outer;
         self
     }
+
+    /// Get the start of the first band and the step between the bands.
+    fn start_step(&self) -> (f64, f64) {
+        let len = self.domain.len();
+        let ratio = 1. + self.padding_inner / (len.max(2) - 1) as f64;
+        let padding_outer_width = self.avg_width * self.padding_outer;
+        let avg_width = (self.range_diff - padding_outer_width * 2.) / len.max(1) as f64;
+        (padding_outer_width, avg_width * ratio)
+    }
 }
 
 impl<T> Scale<T> for ScaleBand<T>
@@ -63,15 +72,22 @@ where
             return Some((self.range_diff - self.band_width()) / 2.);
         }
 
-        let ratio = 1. + self.padding_inner / (self.domain.len() - 1) as f64;
-        let padding_outer_width = self.avg_width * self.padding_outer;
-        let avg_width = (self.range_diff - padding_outer_width * 2.) / self.domain.len() as f64;
-        Some(index as f64 * avg_width * ratio + padding_outer_width)
+        let (start, step) = self.start_step();
+        Some(index as f64 * step + start)
     }
 
     fn least_index(&self, tick: f64) -> usize {
-        let index = (tick / self.avg_width).round() as usize;
-        index.min(self.domain.len().saturating_sub(1))
+        let domain_len = self.domain.len();
+        let (start, step) = self.start_step();
+        if domain_len <= 1 || step <= 0. {
+            return 0;
+        }
+
+        // The band with the nearest center.
+        let index = ((tick - start - self.band_width() / 2.) / step)
+            .round()
+            .max(0.) as usize;
+        index.min(domain_len - 1)
     }
 }
 
@@ -102,4 +118,26 @@ mod tests {
         assert_eq!(scale.tick(&3), Some(0.));
         assert_eq!(scale.band_width(), 0.);
     }
+
+    #[test]
+    fn test_scale_band_least_index() {
+        let scale = ScaleBand::new(vec![1, 2, 3], vec![0., 90.]);
+        assert_eq!(scale.least_index(-10.), 0);
+        assert_eq!(scale.least_index(10.), 0);
+        assert_eq!(scale.least_index(50.), 1);
+        assert_eq!(scale.least_index(80.), 2);
+        assert_eq!(scale.least_index(200.), 2);
+
+        let scale = ScaleBand::new(vec![1, 2, 3, 4], vec![0., 200.])
+            .padding_inner(0.4)
+            .padding_outer(0.2);
+        for (ix, value) in [1, 2, 3, 4].iter().enumerate() {
+            let tick = scale.tick(value).unwrap();
+            assert_eq!(scale.least_index(tick), ix);
+            assert_eq!(scale.least_index(tick + scale.band_width()), ix);
+        }
+
+        let scale = ScaleBand::new(vec![1], vec![0., 90.]);
+        assert_eq!(scale.least_index(80.), 0);
+    }
 }
@@ -1,17 +1,27 @@
 // @reference: https://d3js.org/d3-scale/linear
 
+use std::marker::PhantomData;
+
+use gpui::SharedString;
 use itertools::Itertools;
 use num_traits::{Num, ToPrimitive};
 
 use super::{sealed::Sealed, Scale};
+use crate::plot::NumberFormat;
+
+/// The max iterations of `nice`, the domain is stable in 2 or 3 iterations normally.
+const MAX_NICE_ITERATIONS: usize = 10;
 
 #[derive(Clone)]
 pub struct ScaleLinear<T> {
     domain_len: usize,
-    domain_min: T,
-    domain_diff: T,
+    domain_min: f64,
+    domain_max: f64,
     range_min: f64,
     range_diff: f64,
+    ascending: bool,
+    format: NumberFormat,
+    _marker: PhantomData<T>,
 }
 
 impl<T> ScaleLinear<T>
@@ -33,12 +43,118 @@ where
 
         Self {
             domain_len: domain.len(),
-            domain_min,
-            domain_diff: domain_max - domain_min,
+            domain_min: domain_min.to_f64().unwrap_or_default(),
+            domain_max: domain_max.to_f64().unwrap_or_default(),
             range_min,
             range_diff: range_max - range_min,
+            ascending: false,
+            format: NumberFormat::default(),
+            _marker: PhantomData,
+        }
+    }
+
+    /// Override the min value of the domain, e.g.: Always start at 0.
+    pub fn domain_min(mut self, min: T) -> Self {
+        self.domain_min = min.to_f64().unwrap_or_default();
+        self
+    }
+
+    /// Override the max value of the domain, e.g.: Always end at 100 for the percentages.
+    pub fn domain_max(mut self, max: T) -> Self {
+        self.domain_max = max.to_f64().unwrap_or_default();
+        self
+    }
+
+    /// Map the min of the domain to the min of the range, e.g.: For the x-axis from left to right.
+    ///
+    /// Default maps the max of the domain to the min of the range, for the y-axis from top to bottom.
+    pub fn ascending(mut self) -> Self {
+        self.ascending = true;
+        self
+    }
+
+    /// Set the format of the axis labels, default is [`NumberFormat::Auto`].
+    pub fn format(mut self, format: NumberFormat) -> Self {
+        self.format = format;
+        self
+    }
+
+    /// Extend the domain to the nice round values of the step for about `count` ticks.
+    ///
+    /// e.g.: The domain `[0.2, 9.6]` becomes `[0, 10]`.
+    pub fn nice(mut self, count: usize) -> Self {
+        let mut prev_step = None;
+        for _ in 0..MAX_NICE_ITERATIONS {
+            let step = tick_step(self.domain_min, self.domain_max, count);
+            if step == 0. || !step.is_finite() || prev_step == Some(step) {
+                break;
+            }
+
+            self.domain_min = (self.domain_min / step).floor() * step;
+            self.domain_max = (self.domain_max / step).ceil() * step;
+            prev_step = Some(step);
         }
+        self
+    }
+
+    /// Get the step of about `count` ticks, the step is 1, 2 or 5 times a power of 10.
+    pub fn tick_step(&self, count: usize) -> f64 {
+        tick_step(self.domain_min, self.domain_max, count)
+    }
+
+    /// Get about `count` ticks in the domain, the ticks are the multiples of the `tick_step`.
+    pub fn ticks(&self, count: usize) -> Vec<f64> {
+        let step = self.tick_step(count);
+        if step == 0. || !step.is_finite() {
+            return vec![];
+        }
+
+        // Allow the float error at the domain edges.
+        let epsilon = 1e-9;
+        if step >= 1. {
+            let start = (self.domain_min / step - epsilon).ceil() as i64;
+            let end = (self.domain_max / step + epsilon).floor() as i64;
+            (start..=end).map(|i| i as f64 * step).collect()
+        } else {
+            // Divide by the inverse for the steps less than 1 to avoid the float error, e.g.: 3 * 0.1 != 0.3
+            let inverse = (1. / step).round();
+            let start = (self.domain_min * inverse - epsilon).ceil() as i64;
+            let end = (self.domain_max * inverse + epsilon).floor() as i64;
+            (start..=end).map(|i| i as f64 / inverse).collect()
+        }
+    }
+
+    fn position(&self, value: f64) -> Option<f64> {
+        let domain_diff = self.domain_max - self.domain_min;
+        if domain_diff == 0. {
+            return None;
+        }
+
+        let ratio = (value - self.domain_min) / domain_diff;
+        let ratio = if self.ascending { ratio } else { 1. - ratio };
+        Some(ratio * self.range_diff + self.range_min)
+    }
+}
+
+/// Return the step of about `count` ticks in `[start, stop]`, rounded to 1, 2 or 5 times a power of 10.
+fn tick_step(start: f64, stop: f64, count: usize) -> f64 {
+    let step = (stop - start).abs() / count.max(1) as f64;
+    if step == 0. || !step.is_finite() {
+        return 0.;
     }
+
+    let power = 10f64.powf(step.log10().floor());
+    let error = step / power;
+    let factor = if error >= 50f64.sqrt() {
+        10.
+    } else if error >= 10f64.sqrt() {
+        5.
+    } else if error >= 2f64.sqrt() {
+        2.
+    } else {
+        1.
+    };
+    factor * power
 }
 
 impl<T> Scale<T> for ScaleLinear<T>
@@ -46,19 +162,21 @@ where
     T: Copy + PartialOrd + Num + ToPrimitive + Sealed,
 {
     fn tick(&self, value: &T) -> Option<f64> {
-        if self.domain_diff.is_zero() {
-            return None;
-        }
-
-        let ratio = ((*value - self.domain_min) / self.domain_diff).to_f64()?;
-
-        Some((1. - ratio) * self.range_diff + self.range_min)
+        self.position(value.to_f64()?)
     }
 
     fn least_index(&self, tick: f64) -> usize {
         let index = (tick / self.range_diff).round() as usize;
         index.min(self.domain_len.saturating_sub(1))
     }
+
+    fn axis_ticks(&self, count: usize) -> Vec<(f64, SharedString)> {
+        let step = self.tick_step(count);
+        self.ticks(count)
+            .into_iter()
+            .filter_map(|v| Some((self.position(v)?, self.format.format(v, step))))
+            .collect()
+    }
 }
 
 #[cfg(test)]
@@ -85,4 +203,80 @@ mod tests {
         assert_eq!(scale.tick(&2.), Some(0.));
         assert_eq!(scale.tick(&3.), Some(0.));
     }
+
+    #[test]
+    fn test_scale_linear_ascending() {
+        let scale = ScaleLinear::new(vec![1., 2., 3.], vec![10., 110.]).ascending();
+        assert_eq!(scale.tick(&1.), Some(10.));
+        assert_eq!(scale.tick(&2.), Some(60.));
+        assert_eq!(scale.tick(&3.), Some(110.));
+        assert_eq!(
+            scale.axis_ticks(2).first().map(|(tick, _)| *tick),
+            Some(10.)
+        );
+    }
+
+    #[test]
+    fn test_scale_linear_ticks() {
+        let scale = ScaleLinear::new(vec![0., 10.], vec![0., 100.]);
+        assert_eq!(scale.tick_step(5), 2.);
+        assert_eq!(scale.ticks(5), vec![0., 2., 4., 6., 8., 10.]);
+        assert_eq!(scale.ticks(2), vec![0., 5., 10.]);
+
+        let scale = ScaleLinear::new(vec![0.1, 0.9], vec![0., 100.]);
+        assert_eq!(scale.ticks(5), vec![0.2, 0.4, 0.6, 0.8]);
+        assert_eq!(
+            scale.ticks(10),
+            vec![0.1, 0.2, 0.3, 0.4, 0.5, 0.6, 0.7, 0.8, 0.9]
+        );
+
+        let scale = ScaleLinear::new(vec![-120., 480.], vec![0., 100.]);
+        assert_eq!(scale.ticks(5), vec![-100., 0., 100., 200., 300., 400.]);
+
+        let scale = ScaleLinear::new(vec![5., 5.], vec![0., 100.]);
+        assert!(scale.ticks(5).is_empty());
+    }
+
+    #[test]
+    fn test_scale_linear_nice() {
+        let scale = ScaleLinear::new(vec![0.2, 9.6], vec![0., 100.]).nice(5);
+        assert_eq!(scale.tick(&0.), Some(100.));
+        assert_eq!(scale.tick(&10.), Some(0.));
+
+        let scale = ScaleLinear::new(vec![0., 1234.], vec![0., 100.]).nice(5);
+        assert_eq!(
+            scale.ticks(5),
+            vec![0., 200., 400., 600., 800., 1000., 1200., 1400.]
+        );
+        assert_eq!(scale.tick(&1400.), Some(0.));
+
+        let scale = ScaleLinear::new(vec![12., 87.], vec![0., 100.])
+            .nice(5)
+            .domain_min(0.);
+        assert_eq!(scale.tick(&0.), Some(100.));
+        assert_eq!(scale.tick(&100.), Some(0.));
+    }
+
+    #[test]
+    fn test_scale_linear_axis_ticks() {
+        let scale = ScaleLinear::new(vec![0., 1.], vec![0., 100.]);
+        assert_eq!(
+            scale.axis_ticks(2),
+            vec![
+                (100., "0.0".into()),
+                (50., "0.5".into()),
+                (0., "1.0".into())
+            ]
+        );
+
+        let scale = ScaleLinear::new(vec![0., 3000.], vec![0., 100.]).format(NumberFormat::Si);
+        assert_eq!(
+            scale
+                .axis_ticks(3)
+                .into_iter()
+                .map(|(_, label)| label)
+                .collect::<Vec<_>>(),
+            vec!["0", "1k", "2k", "3k"]
+        );
+    }
 }
@@ -0,0 +1,198 @@
+// @reference: https://d3js.org/d3-scale/log
+
+use gpui::SharedString;
+use itertools::Itertools;
+use num_traits::ToPrimitive;
+
+use super::Scale;
+use crate::plot::NumberFormat;
+
+#[derive(Clone)]
+pub struct ScaleLog {
+    base: f64,
+    domain_len: usize,
+    domain_min: f64,
+    domain_max: f64,
+    range_min: f64,
+    range_diff: f64,
+    format: NumberFormat,
+}
+
+impl ScaleLog {
+    /// Create a log scale with base 10, the non-positive values of the domain are ignored.
+    pub fn new<T>(domain: Vec<T>, range: Vec<f64>) -> Self
+    where
+        T: ToPrimitive,
+    {
+        let (domain_min, domain_max) = domain
+            .iter()
+            .filter_map(|v| v.to_f64())
+            .filter(|v| *v > 0.)
+            .minmax_by(|a, b| a.total_cmp(b))
+            .into_option()
+            .unwrap_or((0., 0.));
+
+        let (range_min, range_max) = range
+            .iter()
+            .minmax()
+            .into_option()
+            .map_or((0., 0.), |(min, max)| (*min, *max));
+
+        Self {
+            base: 10.,
+            domain_len: domain.len(),
+            domain_min,
+            domain_max,
+            range_min,
+            range_diff: range_max - range_min,
+            format: NumberFormat::default(),
+        }
+    }
+
+    /// Set the base of the logarithm, default is 10.
+    pub fn base(mut self, base: f64) -> Self {
+        self.base = base;
+        self
+    }
+
+    /// Set the format of the axis labels, default is [`NumberFormat::Auto`].
+    pub fn format(mut self, format: NumberFormat) -> Self {
+        self.format = format;
+        self
+    }
+
+    fn log(&self, value: f64) -> f64 {
+        if self.base == 10. {
+            value.log10()
+        } else if self.base == 2. {
+            value.log2()
+        } else {
+            value.ln() / self.base.ln()
+        }
+    }
+
+    /// Return `value * base ^ exp`.
+    fn times_pow(&self, value: f64, exp: i32) -> f64 {
+        // Divide for the negative exponents to avoid the float error, e.g.: 0.1 * 0.1 != 0.01
+        if exp >= 0 {
+            value * self.base.powi(exp)
+        } else {
+            value / self.base.powi(-exp)
+        }
+    }
+
+    /// Get the ticks at the powers of the base, about `count` ticks.
+    ///
+    /// If the domain covers too few decades for `count` ticks,
+    /// the ticks at 2 and 5 times of the powers are added for base 10.
+    pub fn ticks(&self, count: usize) -> Vec<f64> {
+        if self.domain_min <= 0. || self.base <= 1. {
+            return vec![];
+        }
+
+        let start = self.log(self.domain_min).floor() as i32;
+        let end = self.log(self.domain_max).ceil() as i32;
+        let decades = (end - start) as usize;
+        let count = count.max(1);
+
+        let (step, multiples): (i32, &[f64]) = if decades > count {
+            (decades.div_ceil(count) as i32, &[1.])
+        } else if decades * 3 <= count && self.base == 10. {
+            (1, &[1., 2., 5.])
+        } else {
+            (1, &[1.])
+        };
+
+        // Allow the float error at the domain edges.
+        let epsilon = 1e-9;
+        (start..=end)
+            .filter(|exp| exp.rem_euclid(step) == 0)
+            .flat_map(|exp| multiples.iter().map(move |m| self.times_pow(*m, exp)))
+            .filter(|v| {
+                *v >= self.domain_min * (1. - epsilon) && *v <= self.domain_max * (1. + epsilon)
+            })
+            .collect()
+    }
+}
+
+impl<T> Scale<T> for ScaleLog
+where
+    T: ToPrimitive,
+{
+    fn tick(&self, value: &T) -> Option<f64> {
+        let value = value.to_f64()?;
+        if value <= 0. || self.domain_min <= 0. {
+            return None;
+        }
+
+        let log_min = self.log(self.domain_min);
+        let log_diff = self.log(self.domain_max) - log_min;
+        if log_diff == 0. {
+            return None;
+        }
+
+        let ratio = (self.log(value) - log_min) / log_diff;
+        Some((1. - ratio) * self.range_diff + self.range_min)
+    }
+
+    fn least_index(&self, tick: f64) -> usize {
+        let index = (tick / self.range_diff).round() as usize;
+        index.min(self.domain_len.saturating_sub(1))
+    }
+
+    fn axis_ticks(&self, count: usize) -> Vec<(f64, SharedString)> {
+        // Each tick is formatted by itself as the step, for enough decimals of the values less than 1.
+        self.ticks(count)
+            .into_iter()
+            .filter_map(|v| Some((Scale::<f64>::tick(self, &v)?, self.format.format(v, v))))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scale_log_1() {
+        let scale = ScaleLog::new(vec![1., 10., 100.], vec![0., 100.]);
+        assert_eq!(scale.tick(&1.), Some(100.));
+        assert_eq!(scale.tick(&10.), Some(50.));
+        assert_eq!(scale.tick(&100.), Some(0.));
+        assert_eq!(scale.tick(&0.), None);
+        assert_eq!(scale.tick(&-1.), None);
+    }
+
+    #[test]
+    fn test_scale_log_2() {
+        let scale = ScaleLog::new(vec![0., -1.], vec![0., 100.]);
+        assert_eq!(scale.tick(&1.), None);
+        assert!(scale.ticks(5).is_empty());
+
+        let scale = ScaleLog::new(vec![2., 2.], vec![0., 100.]);
+        assert_eq!(scale.tick(&2.), None);
+    }
+
+    #[test]
+    fn test_scale_log_ticks() {
+        let scale = ScaleLog::new(vec![1., 10000.], vec![0., 100.]);
+        assert_eq!(scale.ticks(5), vec![1., 10., 100., 1000., 10000.]);
+        assert_eq!(scale.ticks(2), vec![1., 100., 10000.]);
+
+        let scale = ScaleLog::new(vec![0.01, 3.], vec![0., 100.]);
+        assert_eq!(
+            scale.ticks(10),
+            vec![0.01, 0.02, 0.05, 0.1, 0.2, 0.5, 1., 2.]
+        );
+        assert_eq!(
+            Scale::<f64>::axis_ticks(&scale, 10)
+                .into_iter()
+                .map(|(_, label)| label)
+                .collect::<Vec<_>>(),
+            vec!["0.01", "0.02", "0.05", "0.1", "0.2", "0.5", "1", "2"]
+        );
+
+        let scale = ScaleLog::new(vec![1., 8.], vec![0., 100.]).base(2.);
+        assert_eq!(scale.ticks(5), vec![1., 2., 4., 8.]);
+    }
+}
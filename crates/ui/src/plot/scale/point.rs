@@ -1,13 +1,13 @@
 // @reference: https://d3js.org/d3-scale/point
 
 use itertools::Itertools;
-use num_traits::Zero;
 
 use super::Scale;
 
 #[derive(Clone)]
 pub struct ScalePoint<T> {
     domain: Vec<T>,
+    range_min: f64,
     range_tick: f64,
 }
 
@@ -17,19 +17,23 @@ where
 {
     pub fn new(domain: Vec<T>, range: Vec<f64>) -> Self {
         let len = domain.len();
-        let range_tick = if len.is_zero() {
+        let (range_min, range_max) = range
+            .iter()
+            .copied()
+            .minmax()
+            .into_option()
+            .unwrap_or_default();
+        let range_tick = if len <= 1 {
             0.
         } else {
-            let range_diff = range
-                .iter()
-                .minmax()
-                .into_option()
-                .map_or(0., |(min, max)| max - min);
-
-            range_diff / (len - 1) as f64
+            (range_max - range_min) / (len - 1) as f64
         };
 
-        Self { domain, range_tick }
+        Self {
+            domain,
+            range_min,
+            range_tick,
+        }
     }
 }
 
@@ -39,11 +43,11 @@ where
 {
     fn tick(&self, value: &T) -> Option<f64> {
         let index = self.domain.iter().position(|v| v == value)?;
-        Some(index as f64 * self.range_tick)
+        Some(index as f64 * self.range_tick + self.range_min)
     }
 
     fn least_index(&self, tick: f64) -> usize {
-        let index = (tick / self.range_tick).round() as usize;
+        let index = ((tick - self.range_min) / self.range_tick).round().max(0.) as usize;
         index.min(self.domain.len().saturating_sub(1))
     }
 }
@@ -72,4 +76,16 @@ mod tests {
         assert_eq!(scale.tick(&2), Some(0.));
         assert_eq!(scale.tick(&3), Some(0.));
     }
+
+    #[test]
+    fn test_scale_point_range_min() {
+        let scale = ScalePoint::new(vec![1, 2, 3], vec![-100., 100.]);
+        assert_eq!(scale.tick(&1), Some(-100.));
+        assert_eq!(scale.tick(&2), Some(0.));
+        assert_eq!(scale.tick(&3), Some(100.));
+        assert_eq!(scale.least_index(-200.), 0);
+        assert_eq!(scale.least_index(-40.), 1);
+        assert_eq!(scale.least_index(60.), 2);
+        assert_eq!(scale.least_index(200.), 2);
+    }
 }
@@ -0,0 +1,135 @@
+// @reference: https://d3js.org/d3-scale/sequential
+
+use std::marker::PhantomData;
+
+use gpui::Hsla;
+use itertools::Itertools;
+use num_traits::ToPrimitive;
+
+/// The scale to map the continuous values to the colors, e.g.: For the cells of a heatmap.
+#[derive(Clone)]
+pub struct ScaleSequential<T> {
+    domain_min: f64,
+    domain_max: f64,
+    colors: Vec<Hsla>,
+    _marker: PhantomData<T>,
+}
+
+impl<T> ScaleSequential<T>
+where
+    T: ToPrimitive,
+{
+    /// Create a scale to interpolate the `colors` from the min to the max of the domain.
+    pub fn new(domain: Vec<T>, colors: Vec<Hsla>) -> Self {
+        let (domain_min, domain_max) = domain
+            .iter()
+            .filter_map(|v| v.to_f64())
+            .filter(|v| v.is_finite())
+            .minmax_by(|a, b| a.total_cmp(b))
+            .into_option()
+            .unwrap_or((0., 0.));
+
+        Self {
+            domain_min,
+            domain_max,
+            colors,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Get the color of the value, the values out of the domain are clamped.
+    pub fn color(&self, value: &T) -> Option<Hsla> {
+        let value = value.to_f64().filter(|v| v.is_finite())?;
+        let (first, last) = (self.colors.first()?, self.colors.last()?);
+        let domain_diff = self.domain_max - self.domain_min;
+        if domain_diff == 0. {
+            return Some(*last);
+        }
+
+        let ratio = ((value - self.domain_min) / domain_diff).clamp(0., 1.);
+        if self.colors.len() == 1 || ratio == 0. {
+            return Some(*first);
+        }
+        if ratio == 1. {
+            return Some(*last);
+        }
+
+        // Interpolate between the 2 colors around the ratio.
+        let position = ratio * (self.colors.len() - 1) as f64;
+        let index = (position.floor() as usize).min(self.colors.len() - 2);
+        let t = (position - index as f64) as f32;
+        Some(interpolate(self.colors[index], self.colors[index + 1], t))
+    }
+}
+
+/// Interpolate the colors in HSL, the hue goes the shorter way around the color wheel.
+fn interpolate(a: Hsla, b: Hsla, t: f32) -> Hsla {
+    // The hue of the gray is meaningless, keep the hue of the other color.
+    let (a_h, b_h) = match (a.s == 0., b.s == 0.) {
+        (true, false) => (b.h, b.h),
+        (false, true) => (a.h, a.h),
+        _ => (a.h, b.h),
+    };
+    let diff = (b_h - a_h + 0.5).rem_euclid(1.) - 0.5;
+
+    Hsla {
+        h: (a_h + diff * t).rem_euclid(1.),
+        s: a.s + (b.s - a.s) * t,
+        l: a.l + (b.l - a.l) * t,
+        a: a.a + (b.a - a.a) * t,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use gpui::hsla;
+
+    use super::*;
+
+    #[test]
+    fn test_scale_sequential() {
+        let scale = ScaleSequential::new(
+            vec![0., 5., 10.],
+            vec![hsla(0.2, 0.5, 0.9, 1.), hsla(0.2, 0.5, 0.1, 1.)],
+        );
+        assert_eq!(scale.color(&0.), Some(hsla(0.2, 0.5, 0.9, 1.)));
+        assert_eq!(scale.color(&10.), Some(hsla(0.2, 0.5, 0.1, 1.)));
+        assert_eq!(scale.color(&20.), Some(hsla(0.2, 0.5, 0.1, 1.)));
+        assert_eq!(scale.color(&-1.), Some(hsla(0.2, 0.5, 0.9, 1.)));
+        let mid = scale.color(&5.).unwrap();
+        assert!((mid.l - 0.5).abs() < 1e-6);
+        assert_eq!(scale.color(&f64::NAN), None);
+
+        let scale = ScaleSequential::new(vec![1., 1.], vec![hsla(0., 0., 0., 1.)]);
+        assert_eq!(scale.color(&1.), Some(hsla(0., 0., 0., 1.)));
+
+        let scale = ScaleSequential::<f64>::new(vec![0., 1.], vec![]);
+        assert_eq!(scale.color(&1.), None);
+    }
+
+    #[test]
+    fn test_scale_sequential_stops() {
+        let scale = ScaleSequential::new(
+            vec![0., 100.],
+            vec![
+                hsla(0., 1., 0.5, 1.),
+                hsla(0.1, 1., 0.5, 1.),
+                hsla(0.9, 1., 0.5, 1.),
+            ],
+        );
+        let color = scale.color(&25.).unwrap();
+        assert!((color.h - 0.05).abs() < 1e-6);
+
+        // The hue goes from 0.1 to 0.9 through 0.
+        let color = scale.color(&75.).unwrap();
+        assert!((color.h - 0.0).abs() < 1e-6 || (color.h - 1.).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_interpolate_gray() {
+        let color = interpolate(hsla(0., 0., 1., 1.), hsla(0.6, 0.8, 0.4, 1.), 0.5);
+        assert!((color.h - 0.6).abs() < 1e-6);
+        assert!((color.s - 0.4).abs() < 1e-6);
+        assert!((color.l - 0.7).abs() < 1e-6);
+    }
+}
@@ -0,0 +1,429 @@
+// @reference: https://d3js.org/d3-scale/time
+
+use std::fmt::Display;
+
+use chrono::{
+    DateTime, Datelike, Days, Locale, Months, NaiveDate, NaiveDateTime, TimeDelta, TimeZone,
+    Timelike,
+};
+use gpui::SharedString;
+use itertools::Itertools;
+use rust_i18n::t;
+
+use super::Scale;
+
+const SECOND: i64 = 1000;
+const MINUTE: i64 = SECOND * 60;
+const HOUR: i64 = MINUTE * 60;
+const DAY: i64 = HOUR * 24;
+const WEEK: i64 = DAY * 7;
+const MONTH: i64 = DAY * 30;
+const YEAR: i64 = DAY * 365;
+
+/// The calendar interval between the ticks of the [`ScaleTime`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TimeInterval {
+    Second(u32),
+    Minute(u32),
+    Hour(u32),
+    Day(u32),
+    /// A week, starts from Monday.
+    Week,
+    Month(u32),
+    Year(u32),
+}
+
+/// The intervals for the ticks, the longer intervals are `Year(n)` with the `n` of 1, 2, 5 steps.
+const TICK_INTERVALS: [TimeInterval; 18] = [
+    TimeInterval::Second(1),
+    TimeInterval::Second(5),
+    TimeInterval::Second(15),
+    TimeInterval::Second(30),
+    TimeInterval::Minute(1),
+    TimeInterval::Minute(5),
+    TimeInterval::Minute(15),
+    TimeInterval::Minute(30),
+    TimeInterval::Hour(1),
+    TimeInterval::Hour(3),
+    TimeInterval::Hour(6),
+    TimeInterval::Hour(12),
+    TimeInterval::Day(1),
+    TimeInterval::Day(2),
+    TimeInterval::Week,
+    TimeInterval::Month(1),
+    TimeInterval::Month(3),
+    TimeInterval::Year(1),
+];
+
+impl TimeInterval {
+    /// The approximate duration of the interval in milliseconds.
+    pub fn duration_millis(&self) -> i64 {
+        match *self {
+            Self::Second(n) => SECOND * n as i64,
+            Self::Minute(n) => MINUTE * n as i64,
+            Self::Hour(n) => HOUR * n as i64,
+            Self::Day(n) => DAY * n as i64,
+            Self::Week => WEEK,
+            Self::Month(n) => MONTH * n as i64,
+            Self::Year(n) => YEAR * n as i64,
+        }
+    }
+
+    /// Return the interval to have about `count` ticks in the `span` milliseconds.
+    pub fn for_span(span: i64, count: usize) -> Self {
+        let target = span as f64 / count.max(1) as f64;
+        if target > YEAR as f64 {
+            return Self::Year(nice_step(target / YEAR as f64));
+        }
+
+        let ix = TICK_INTERVALS
+            .iter()
+            .position(|interval| interval.duration_millis() as f64 >= target)
+            .unwrap_or(TICK_INTERVALS.len() - 1);
+        if ix == 0 {
+            return TICK_INTERVALS[0];
+        }
+
+        // Choose the closer one in ratio.
+        let (prev, next) = (TICK_INTERVALS[ix - 1], TICK_INTERVALS[ix]);
+        let (prev_ms, next_ms) = (prev.duration_millis() as f64, next.duration_millis() as f64);
+        if target / prev_ms < next_ms / target {
+            prev
+        } else {
+            next
+        }
+    }
+
+    /// Round down the time to the start of the interval, e.g.: `Hour(3)` rounds 05:20 to 03:00.
+    pub fn floor(&self, time: NaiveDateTime) -> Option<NaiveDateTime> {
+        let date = time.date();
+        let (hour, minute, second) = (time.hour(), time.minute(), time.second());
+
+        match *self {
+            Self::Second(n) => date.and_hms_opt(hour, minute, second - second % n),
+            Self::Minute(n) => date.and_hms_opt(hour, minute - minute % n, 0),
+            Self::Hour(n) => date.and_hms_opt(hour - hour % n, 0, 0),
+            Self::Day(n) => date
+                .checked_sub_days(Days::new((date.day0() % n) as u64))?
+                .and_hms_opt(0, 0, 0),
+            Self::Week => date
+                .checked_sub_days(Days::new(date.weekday().num_days_from_monday() as u64))?
+                .and_hms_opt(0, 0, 0),
+            Self::Month(n) => {
+                NaiveDate::from_ymd_opt(date.year(), date.month0() - date.month0() % n + 1, 1)?
+                    .and_hms_opt(0, 0, 0)
+            }
+            Self::Year(n) => {
+                NaiveDate::from_ymd_opt(date.year() - date.year().rem_euclid(n as i32), 1, 1)?
+                    .and_hms_opt(0, 0, 0)
+            }
+        }
+    }
+
+    /// Add the interval to the time.
+    pub fn offset(&self, time: NaiveDateTime) -> Option<NaiveDateTime> {
+        match *self {
+            Self::Second(n) => time.checked_add_signed(TimeDelta::seconds(n as i64)),
+            Self::Minute(n) => time.checked_add_signed(TimeDelta::minutes(n as i64)),
+            Self::Hour(n) => time.checked_add_signed(TimeDelta::hours(n as i64)),
+            Self::Day(n) => time.checked_add_signed(TimeDelta::days(n as i64)),
+            Self::Week => time.checked_add_signed(TimeDelta::weeks(1)),
+            Self::Month(n) => time.checked_add_months(Months::new(n)),
+            Self::Year(n) => time.checked_add_months(Months::new(n * 12)),
+        }
+    }
+}
+
+/// Return the step of 1, 2, 5 times of the power of 10 that is nearest to `value`, at least 1.
+fn nice_step(value: f64) -> u32 {
+    let power = 10f64.powf(value.log10().floor());
+    let step = match value / power {
+        f if f < 1.5 => 1.,
+        f if f < 3.5 => 2.,
+        f if f < 7.5 => 5.,
+        _ => 10.,
+    };
+    (step * power).max(1.) as u32
+}
+
+#[derive(Clone)]
+pub struct ScaleTime<Tz: TimeZone> {
+    /// The timestamps in milliseconds of the domain, keep the original order.
+    domain: Vec<i64>,
+    domain_min: Option<DateTime<Tz>>,
+    domain_max: Option<DateTime<Tz>>,
+    range_min: f64,
+    range_diff: f64,
+}
+
+impl<Tz> ScaleTime<Tz>
+where
+    Tz: TimeZone,
+    Tz::Offset: Display,
+{
+    pub fn new(domain: Vec<DateTime<Tz>>, range: Vec<f64>) -> Self {
+        let (domain_min, domain_max) = domain
+            .iter()
+            .minmax()
+            .into_option()
+            .map_or((None, None), |(min, max)| {
+                (Some(min.clone()), Some(max.clone()))
+            });
+
+        let (range_min, range_max) = range
+            .iter()
+            .minmax()
+            .into_option()
+            .map_or((0., 0.), |(min, max)| (*min, *max));
+
+        Self {
+            domain: domain.iter().map(|v| v.timestamp_millis()).collect(),
+            domain_min,
+            domain_max,
+            range_min,
+            range_diff: range_max - range_min,
+        }
+    }
+
+    fn domain_millis(&self) -> Option<(i64, i64)> {
+        Some((
+            self.domain_min.as_ref()?.timestamp_millis(),
+            self.domain_max.as_ref()?.timestamp_millis(),
+        ))
+    }
+
+    /// Return the interval of the ticks to have about `count` ticks.
+    pub fn interval(&self, count: usize) -> TimeInterval {
+        let span = self.domain_millis().map_or(0, |(min, max)| max - min);
+        TimeInterval::for_span(span, count)
+    }
+
+    /// Get about `count` ticks at the calendar intervals, e.g.: every 15 minutes, every month.
+    ///
+    /// The ticks are aligned in the time zone of the domain.
+    pub fn ticks(&self, count: usize) -> Vec<DateTime<Tz>> {
+        let (Some(min), Some(max)) = (self.domain_min.as_ref(), self.domain_max.as_ref()) else {
+            return vec![];
+        };
+
+        let interval = self.interval(count);
+        let tz = min.timezone();
+        let (start, end) = (min.naive_local(), max.naive_local());
+
+        let mut ticks = vec![];
+        let Some(mut time) = interval.floor(start) else {
+            return ticks;
+        };
+        while time <= end {
+            if time >= start {
+                // The local time may not exist in the DST gap.
+                if let Some(tick) = tz.from_local_datetime(&time).earliest() {
+                    ticks.push(tick);
+                }
+            }
+
+            // Floor again to keep aligned, e.g.: `Day(2)` restarts from the 1st day of each month.
+            let Some(next) = interval.offset(time).and_then(|next| interval.floor(next)) else {
+                break;
+            };
+            if next <= time {
+                break;
+            }
+            time = next;
+        }
+        ticks
+    }
+
+    /// Format the tick by its precision with the current locale.
+    ///
+    /// Like the multi-scale format of d3, the start of a year is formatted as the year,
+    /// the start of a month as the month, the start of a day as the date, and so on.
+    pub fn format_tick(&self, value: &DateTime<Tz>) -> SharedString {
+        format_time(value, &crate::locale())
+    }
+}
+
+/// Format the time by its precision in the locale.
+fn format_time<Tz>(value: &DateTime<Tz>, locale: &str) -> SharedString
+where
+    Tz: TimeZone,
+    Tz::Offset: Display,
+{
+    let time = value.naive_local();
+    let format = if time.second() != 0 || time.nanosecond() != 0 {
+        "%H:%M:%S".into()
+    } else if time.hour() != 0 || time.minute() != 0 {
+        "%H:%M".into()
+    } else if time.day() != 1 {
+        t!("Plot.time.day", locale = locale)
+    } else if time.month() != 1 {
+        t!("Plot.time.month", locale = locale)
+    } else {
+        "%Y".into()
+    };
+
+    value
+        .format_localized(&format, chrono_locale(locale))
+        .to_string()
+        .into()
+}
+
+/// Convert the locale (e.g.: `zh-CN`, `it`) to the locale of chrono for the month names.
+fn chrono_locale(locale: &str) -> Locale {
+    let name = locale.replace('-', "_");
+    Locale::try_from(name.as_str())
+        .or_else(|_| {
+            // The language only locale, e.g.: `it` to `it_IT`.
+            let name = format!("{}_{}", name, name.to_uppercase());
+            Locale::try_from(name.as_str())
+        })
+        .unwrap_or(if name.starts_with("en") {
+            Locale::en_US
+        } else {
+            Locale::POSIX
+        })
+}
+
+impl<Tz> Scale<DateTime<Tz>> for ScaleTime<Tz>
+where
+    Tz: TimeZone,
+    Tz::Offset: Display,
+{
+    fn tick(&self, value: &DateTime<Tz>) -> Option<f64> {
+        let (min, max) = self.domain_millis()?;
+        if min == max {
+            return None;
+        }
+
+        let ratio = (value.timestamp_millis() - min) as f64 / (max - min) as f64;
+        Some(ratio * self.range_diff + self.range_min)
+    }
+
+    fn least_index(&self, tick: f64) -> usize {
+        let Some((min, max)) = self.domain_millis() else {
+            return 0;
+        };
+        if self.range_diff == 0. {
+            return 0;
+        }
+
+        // The nearest value of the tick.
+        let value = min as f64 + (tick - self.range_min) / self.range_diff * (max - min) as f64;
+        self.domain
+            .iter()
+            .position_min_by(|a, b| {
+                (**a as f64 - value)
+                    .abs()
+                    .total_cmp(&(**b as f64 - value).abs())
+            })
+            .unwrap_or(0)
+    }
+
+    fn axis_ticks(&self, count: usize) -> Vec<(f64, SharedString)> {
+        self.ticks(count)
+            .iter()
+            .filter_map(|value| Some((self.tick(value)?, self.format_tick(value))))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{NaiveDate, TimeZone, Utc};
+
+    use super::*;
+
+    fn utc(y: i32, m: u32, d: u32, h: u32, min: u32, s: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(y, m, d, h, min, s).unwrap()
+    }
+
+    #[test]
+    fn test_scale_time() {
+        let scale = ScaleTime::new(
+            vec![utc(2024, 1, 1, 0, 0, 0), utc(2024, 1, 3, 0, 0, 0)],
+            vec![0., 100.],
+        );
+        assert_eq!(scale.tick(&utc(2024, 1, 1, 0, 0, 0)), Some(0.));
+        assert_eq!(scale.tick(&utc(2024, 1, 2, 0, 0, 0)), Some(50.));
+        assert_eq!(scale.tick(&utc(2024, 1, 3, 0, 0, 0)), Some(100.));
+        assert_eq!(scale.least_index(30.), 0);
+        assert_eq!(scale.least_index(80.), 1);
+
+        let scale = ScaleTime::<Utc>::new(vec![], vec![0., 100.]);
+        assert_eq!(scale.tick(&utc(2024, 1, 1, 0, 0, 0)), None);
+        assert!(scale.ticks(5).is_empty());
+    }
+
+    #[test]
+    fn test_time_interval() {
+        assert_eq!(
+            TimeInterval::for_span(10 * SECOND, 10),
+            TimeInterval::Second(1)
+        );
+        assert_eq!(TimeInterval::for_span(HOUR, 4), TimeInterval::Minute(15));
+        assert_eq!(TimeInterval::for_span(DAY, 8), TimeInterval::Hour(3));
+        assert_eq!(TimeInterval::for_span(YEAR, 12), TimeInterval::Month(1));
+        assert_eq!(TimeInterval::for_span(YEAR * 20, 10), TimeInterval::Year(2));
+        assert_eq!(
+            TimeInterval::for_span(YEAR * 400, 10),
+            TimeInterval::Year(50)
+        );
+
+        let time = NaiveDate::from_ymd_opt(2024, 5, 18)
+            .unwrap()
+            .and_hms_opt(5, 20, 42)
+            .unwrap();
+        let floor = |interval: TimeInterval| interval.floor(time).unwrap().to_string();
+        assert_eq!(floor(TimeInterval::Second(15)), "2024-05-18 05:20:30");
+        assert_eq!(floor(TimeInterval::Hour(3)), "2024-05-18 03:00:00");
+        assert_eq!(floor(TimeInterval::Day(2)), "2024-05-17 00:00:00");
+        // 2024-05-18 is Saturday.
+        assert_eq!(floor(TimeInterval::Week), "2024-05-13 00:00:00");
+        assert_eq!(floor(TimeInterval::Month(3)), "2024-04-01 00:00:00");
+        assert_eq!(floor(TimeInterval::Year(5)), "2020-01-01 00:00:00");
+    }
+
+    #[test]
+    fn test_scale_time_ticks() {
+        let scale = ScaleTime::new(
+            vec![utc(2024, 1, 1, 0, 7, 0), utc(2024, 1, 1, 1, 0, 0)],
+            vec![0., 100.],
+        );
+        assert_eq!(scale.interval(4), TimeInterval::Minute(15));
+        assert_eq!(
+            scale.ticks(4),
+            vec![
+                utc(2024, 1, 1, 0, 15, 0),
+                utc(2024, 1, 1, 0, 30, 0),
+                utc(2024, 1, 1, 0, 45, 0),
+                utc(2024, 1, 1, 1, 0, 0),
+            ]
+        );
+
+        // The months have different days.
+        let scale = ScaleTime::new(
+            vec![utc(2023, 11, 15, 0, 0, 0), utc(2024, 3, 20, 0, 0, 0)],
+            vec![0., 100.],
+        );
+        assert_eq!(scale.interval(4), TimeInterval::Month(1));
+        assert_eq!(
+            scale.ticks(4),
+            vec![
+                utc(2023, 12, 1, 0, 0, 0),
+                utc(2024, 1, 1, 0, 0, 0),
+                utc(2024, 2, 1, 0, 0, 0),
+                utc(2024, 3, 1, 0, 0, 0),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_format_time() {
+        assert_eq!(format_time(&utc(2024, 1, 1, 0, 0, 0), "en"), "2024");
+        assert_eq!(format_time(&utc(2024, 3, 1, 0, 0, 0), "en"), "Mar");
+        assert_eq!(format_time(&utc(2024, 3, 5, 0, 0, 0), "en"), "Mar 5");
+        assert_eq!(format_time(&utc(2024, 3, 5, 14, 30, 0), "en"), "14:30");
+        assert_eq!(format_time(&utc(2024, 3, 5, 14, 30, 15), "en"), "14:30:15");
+        assert_eq!(format_time(&utc(2024, 3, 5, 0, 0, 0), "zh-CN"), "3月5日");
+    }
+}
@@ -1,11 +1,17 @@
 mod arc;
 mod area;
 mod bar;
+mod bin;
+mod downsample;
 mod line;
 mod pie;
+mod stack;
 
-pub use arc::Arc;
+pub use arc::{Arc, ArcData};
 pub use area::Area;
 pub use bar::Bar;
+pub use bin::{Bin, BinData};
+pub use downsample::Downsample;
 pub use line::Line;
 pub use pie::Pie;
+pub use stack::{Stack, StackOffset, StackPoint, StackSeries};
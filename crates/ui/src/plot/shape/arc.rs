@@ -1,8 +1,13 @@
 // @reference: https://d3js.org/d3-shape/arc
 
-use std::{f64::consts::PI, fmt::Debug};
+use std::{
+    f64::consts::{PI, TAU},
+    fmt::Debug,
+};
 
-use gpui::{point, px, Bounds, Hsla, Path, PathBuilder, Pixels, Point, Window};
+use gpui::{point, px, Bounds, Hsla, Pixels, Point};
+
+use crate::plot::{Canvas, PlotPath};
 
 const EPSILON: f64 = 1e-12;
 const HALF_PI: f64 = PI / 2.;
@@ -67,7 +72,19 @@ impl Arc {
         point(r * a.cos(), r * a.sin())
     }
 
-    fn path<T>(&self, arc: &ArcData<T>, bounds: &Bounds<Pixels>) -> Option<Path<Pixels>> {
+    /// Return true if the point is in the Arc, the point is relative to the center.
+    pub fn contains<T>(&self, arc: &ArcData<T>, point: Point<f64>) -> bool {
+        let r = point.x.hypot(point.y);
+        if r < self.inner_radius.max(0.) || r > self.outer_radius.max(0.) {
+            return false;
+        }
+
+        // The angle starts from 12 o'clock and goes clockwise, the same as the angles of the Arc.
+        let angle = (point.y.atan2(point.x) + HALF_PI - arc.start_angle).rem_euclid(TAU);
+        angle < arc.end_angle - arc.start_angle
+    }
+
+    fn path<T>(&self, arc: &ArcData<T>, bounds: &Bounds<Pixels>) -> Option<PlotPath> {
         let start_angle = arc.start_angle - HALF_PI;
         let end_angle = arc.end_angle - HALF_PI;
         let pad_angle = arc.pad_angle;
@@ -120,7 +137,7 @@ impl Arc {
         let x11 = center_x + r1 * a1_outer.cos();
         let y11 = center_y + r1 * a1_outer.sin();
 
-        let mut builder = PathBuilder::fill();
+        let mut builder = PlotPath::fill();
 
         // Move to the start point of the outer arc.
         builder.move_to(point(px(x01 as f32), px(y01 as f32)));
@@ -157,7 +174,7 @@ impl Arc {
             builder.line_to(point(px(center_x as f32), px(center_y as f32)));
         }
 
-        builder.build().ok()
+        Some(builder)
     }
 
     /// Paint the Arc.
@@ -166,11 +183,11 @@ impl Arc {
         arc: &ArcData<T>,
         color: impl Into<Hsla>,
         bounds: &Bounds<Pixels>,
-        window: &mut Window,
+        canvas: &mut Canvas,
     ) {
-        let path = self.path(arc, bounds);
-        if let Some(path) = path {
-            window.paint_path(path, color.into());
+        let color: Hsla = color.into();
+        if let Some(path) = self.path(arc, bounds) {
+            canvas.paint_path(path, color);
         }
     }
 }
@@ -214,4 +231,34 @@ mod tests {
         assert_eq!(centroid.x, expected_radius * expected_angle.cos());
         assert_eq!(centroid.y, expected_radius * expected_angle.sin());
     }
+
+    #[test]
+    fn test_arc_contains() {
+        let arc = Arc::new().inner_radius(5.).outer_radius(20.);
+        let arc_data = ArcData {
+            data: &(),
+            index: 0,
+            value: 1.,
+            start_angle: 0.,
+            end_angle: PI,
+            pad_angle: 0.,
+        };
+
+        assert!(arc.contains(&arc_data, point(10., 0.)));
+        assert!(arc.contains(&arc_data, point(1., 10.)));
+        assert!(arc.contains(&arc_data, point(1., -10.)));
+        assert!(!arc.contains(&arc_data, point(-10., 0.)));
+        assert!(!arc.contains(&arc_data, point(-1., -10.)));
+        assert!(!arc.contains(&arc_data, point(1., 0.)));
+        assert!(!arc.contains(&arc_data, point(30., 0.)));
+
+        let arc_data = ArcData {
+            start_angle: PI * 1.5,
+            end_angle: PI * 2.5,
+            ..arc_data
+        };
+        assert!(arc.contains(&arc_data, point(1., -10.)));
+        assert!(arc.contains(&arc_data, point(-1., -10.)));
+        assert!(!arc.contains(&arc_data, point(0., 10.)));
+    }
 }
@@ -1,17 +1,17 @@
 // @reference: https://d3js.org/d3-shape/area
 
-use gpui::{px, Background, Bounds, Path, PathBuilder, Pixels, Point, Window};
+use gpui::{px, Bounds, Pixels, Point};
 
-use crate::plot::{origin_point, StrokeStyle};
+use crate::plot::{origin_point, Canvas, Paint, PlotPath, StrokeStyle};
 
 #[allow(clippy::type_complexity)]
 pub struct Area<T> {
     data: Vec<T>,
     x: Box<dyn Fn(&T) -> Option<f64>>,
-    y0: Option<f64>,
+    y0: Box<dyn Fn(&T) -> Option<f64>>,
     y1: Box<dyn Fn(&T) -> Option<f64>>,
-    fill: Background,
-    stroke: Background,
+    fill: Paint,
+    stroke: Paint,
     stroke_style: StrokeStyle,
 }
 
@@ -20,7 +20,7 @@ impl<T> Default for Area<T> {
         Self {
             data: Vec::new(),
             x: Box::new(|_| None),
-            y0: None,
+            y0: Box::new(|_| None),
             y1: Box::new(|_| None),
             fill: Default::default(),
             stroke: Default::default(),
@@ -54,7 +54,16 @@ impl<T> Area<T> {
 
     /// Set the y0 of the Area.
     pub fn y0(mut self, y0: f64) -> Self {
-        self.y0 = Some(y0);
+        self.y0 = Box::new(move |_| Some(y0));
+        self
+    }
+
+    /// Set the y0 of the Area for each data, e.g.: The top of the previous series in a stacked area.
+    pub fn y0_fn<F>(mut self, y0: F) -> Self
+    where
+        F: Fn(&T) -> Option<f64> + 'static,
+    {
+        self.y0 = Box::new(y0);
         self
     }
 
@@ -68,13 +77,13 @@ impl<T> Area<T> {
     }
 
     /// Set the fill color of the Area.
-    pub fn fill(mut self, fill: impl Into<Background>) -> Self {
+    pub fn fill(mut self, fill: impl Into<Paint>) -> Self {
         self.fill = fill.into();
         self
     }
 
     /// Set the stroke color of the Area.
-    pub fn stroke(mut self, stroke: impl Into<Background>) -> Self {
+    pub fn stroke(mut self, stroke: impl Into<Paint>) -> Self {
         self.stroke = stroke.into();
         self
     }
@@ -85,12 +94,13 @@ impl<T> Area<T> {
         self
     }
 
-    fn path(&self, bounds: &Bounds<Pixels>) -> (Option<Path<Pixels>>, Option<Path<Pixels>>) {
+    fn path(&self, bounds: &Bounds<Pixels>) -> (Option<PlotPath>, Option<PlotPath>) {
         let origin = bounds.origin;
-        let mut area_builder = PathBuilder::fill();
-        let mut line_builder = PathBuilder::stroke(px(1.));
+        let mut area_builder = PlotPath::fill();
+        let mut line_builder = PlotPath::stroke(px(1.));
 
         let mut points = vec![];
+        let mut base_points = vec![];
 
         for v in self.data.iter() {
             let x_tick = (self.x)(v);
@@ -100,6 +110,10 @@ impl<T> Area<T> {
                 let pos = origin_point(px(x as f32), px(y as f32), origin);
 
                 points.push(pos);
+
+                if let Some(y0) = (self.y0)(v) {
+                    base_points.push(origin_point(px(x as f32), px(y0 as f32), origin));
+                }
             }
         }
 
@@ -107,67 +121,65 @@ impl<T> Area<T> {
             return (None, None);
         }
 
+        area_builder.move_to(points[0]);
+        line_builder.move_to(points[0]);
         if points.len() == 1 {
-            area_builder.move_to(points[0]);
-            line_builder.move_to(points[0]);
-            return (area_builder.build().ok(), line_builder.build().ok());
+            return (Some(area_builder), Some(line_builder));
         }
 
-        match self.stroke_style {
-            StrokeStyle::Natural => {
-                area_builder.move_to(points[0]);
-                line_builder.move_to(points[0]);
-                let n = points.len();
-                for i in 0..n - 1 {
-                    let p0 = if i == 0 { points[0] } else { points[i - 1] };
-                    let p1 = points[i];
-                    let p2 = points[i + 1];
-                    let p3 = if i + 2 < n {
-                        points[i + 2]
-                    } else {
-                        points[n - 1]
-                    };
-
-                    // Catmull-Rom to Bezier
-                    let c1 = Point::new(p1.x + (p2.x - p0.x) / 6.0, p1.y + (p2.y - p0.y) / 6.0);
-                    let c2 = Point::new(p2.x - (p3.x - p1.x) / 6.0, p2.y - (p3.y - p1.y) / 6.0);
-
-                    area_builder.cubic_bezier_to(p2, c1, c2);
-                    line_builder.cubic_bezier_to(p2, c1, c2);
-                }
-            }
-            StrokeStyle::Linear => {
-                area_builder.move_to(points[0]);
-                line_builder.move_to(points[0]);
-                for p in &points[1..] {
-                    area_builder.line_to(*p);
-                    line_builder.line_to(*p);
-                }
-            }
-        }
+        curve_to(&mut area_builder, &points, self.stroke_style);
+        curve_to(&mut line_builder, &points, self.stroke_style);
 
-        // Close path
-        if let Some(last) = self.data.last() {
-            let x_tick = (self.x)(last);
-            if let (Some(x), Some(y)) = (x_tick, self.y0) {
-                area_builder.line_to(origin_point(px(x as f32), px(y as f32), bounds.origin));
-                area_builder.line_to(origin_point(px(0.), px(y as f32), bounds.origin));
-                area_builder.close();
-            }
+        // Close path along the base line backwards.
+        if base_points.len() == points.len() {
+            base_points.reverse();
+            area_builder.line_to(base_points[0]);
+            curve_to(&mut area_builder, &base_points, self.stroke_style);
+            area_builder.close();
         }
 
-        (area_builder.build().ok(), line_builder.build().ok())
+        (Some(area_builder), Some(line_builder))
     }
 
     /// Paint the Area.
-    pub fn paint(&self, bounds: &Bounds<Pixels>, window: &mut Window) {
+    pub fn paint(&self, bounds: &Bounds<Pixels>, canvas: &mut Canvas) {
         let (area, line) = self.path(bounds);
 
         if let Some(area) = area {
-            window.paint_path(area, self.fill);
+            canvas.paint_path(area, self.fill);
         }
         if let Some(line) = line {
-            window.paint_path(line, self.stroke);
+            canvas.paint_path(line, self.stroke);
+        }
+    }
+}
+
+/// Draw the curve through the points, the builder is at the first point.
+fn curve_to(builder: &mut PlotPath, points: &[Point<Pixels>], stroke_style: StrokeStyle) {
+    match stroke_style {
+        StrokeStyle::Natural => {
+            let n = points.len();
+            for i in 0..n.saturating_sub(1) {
+                let p0 = if i == 0 { points[0] } else { points[i - 1] };
+                let p1 = points[i];
+                let p2 = points[i + 1];
+                let p3 = if i + 2 < n {
+                    points[i + 2]
+                } else {
+                    points[n - 1]
+                };
+
+                // Catmull-Rom to Bezier
+                let c1 = Point::new(p1.x + (p2.x - p0.x) / 6.0, p1.y + (p2.y - p0.y) / 6.0);
+                let c2 = Point::new(p2.x - (p3.x - p1.x) / 6.0, p2.y - (p3.y - p1.y) / 6.0);
+
+                builder.cubic_bezier_to(p2, c1, c2);
+            }
+        }
+        StrokeStyle::Linear => {
+            for p in points.iter().skip(1) {
+                builder.line_to(*p);
+            }
         }
     }
 }
@@ -1,8 +1,8 @@
-use gpui::{fill, point, px, App, Bounds, Hsla, PaintQuad, Pixels, Point, Window};
+use gpui::{point, px, App, Bounds, Hsla, Pixels, Point};
 
 use crate::plot::{
     label::{Label, Text, TEXT_GAP, TEXT_HEIGHT},
-    origin_point,
+    origin_point, Canvas, Quad,
 };
 
 #[allow(clippy::type_complexity)]
@@ -10,7 +10,7 @@ pub struct Bar<T> {
     data: Vec<T>,
     x: Box<dyn Fn(&T) -> Option<f64>>,
     band_width: f64,
-    y0: f64,
+    y0: Box<dyn Fn(&T) -> Option<f64>>,
     y1: Box<dyn Fn(&T) -> Option<f64>>,
     fill: Box<dyn Fn(&T) -> Hsla>,
     label: Option<Box<dyn Fn(&T, Point<Pixels>) -> Text>>,
@@ -22,7 +22,7 @@ impl<T> Default for Bar<T> {
             data: Vec::new(),
             x: Box::new(|_| None),
             band_width: 0.,
-            y0: 0.,
+            y0: Box::new(|_| Some(0.)),
             y1: Box::new(|_| None),
             fill: Box::new(|_| gpui::black()),
             label: None,
@@ -61,7 +61,16 @@ impl<T> Bar<T> {
 
     /// Set the y0 of the Bar.
     pub fn y0(mut self, y0: f64) -> Self {
-        self.y0 = y0;
+        self.y0 = Box::new(move |_| Some(y0));
+        self
+    }
+
+    /// Set the y0 of the Bar for each data, e.g.: The top of the previous series in a stacked bar.
+    pub fn y0_fn<F>(mut self, y0: F) -> Self
+    where
+        F: Fn(&T) -> Option<f64> + 'static,
+    {
+        self.y0 = Box::new(y0);
         self
     }
 
@@ -93,20 +102,21 @@ impl<T> Bar<T> {
         self
     }
 
-    fn path(&self, bounds: &Bounds<Pixels>) -> (Vec<PaintQuad>, Label) {
+    fn path(&self, bounds: &Bounds<Pixels>) -> (Vec<Quad>, Label) {
         let origin = bounds.origin;
         let mut graph = vec![];
         let mut labels = vec![];
 
         for v in &self.data {
             let x_tick = (self.x)(v);
+            let y0 = (self.y0)(v);
             let y_tick = (self.y1)(v);
 
-            if let (Some(x_tick), Some(y_tick)) = (x_tick, y_tick) {
-                let is_negative = y_tick > self.y0;
+            if let (Some(x_tick), Some(y0), Some(y_tick)) = (x_tick, y0, y_tick) {
+                let is_negative = y_tick > y0;
                 let (p1, p2) = if is_negative {
                     (
-                        origin_point(px(x_tick as f32), px(self.y0 as f32), origin),
+                        origin_point(px(x_tick as f32), px(y0 as f32), origin),
                         origin_point(
                             px((x_tick + self.band_width) as f32),
                             px(y_tick as f32),
@@ -118,7 +128,7 @@ impl<T> Bar<T> {
                         origin_point(px(x_tick as f32), px(y_tick as f32), origin),
                         origin_point(
                             px((x_tick + self.band_width) as f32),
-                            px(self.y0 as f32),
+                            px(y0 as f32),
                             origin,
                         ),
                     )
@@ -126,7 +136,7 @@ impl<T> Bar<T> {
 
                 let color = (self.fill)(v);
 
-                graph.push(fill(Bounds::from_corners(p1, p2), color));
+                graph.push(Quad::fill(Bounds::from_corners(p1, p2), color));
 
                 if let Some(label) = &self.label {
                     labels.push(label(
@@ -148,11 +158,11 @@ impl<T> Bar<T> {
     }
 
     /// Paint the Bar.
-    pub fn paint(&self, bounds: &Bounds<Pixels>, window: &mut Window, cx: &mut App) {
+    pub fn paint(&self, bounds: &Bounds<Pixels>, canvas: &mut Canvas, cx: &mut App) {
         let (graph, labels) = self.path(bounds);
         for quad in graph {
-            window.paint_quad(quad);
+            canvas.paint_quad(quad);
         }
-        labels.paint(bounds, window, cx);
+        labels.paint(bounds, canvas, cx);
     }
 }
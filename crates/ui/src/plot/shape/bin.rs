@@ -0,0 +1,158 @@
+// @reference: https://d3js.org/d3-array/bin
+
+use crate::plot::scale::ScaleLinear;
+
+/// A bin of the data, the values in `[x0, x1)`, the last bin includes `x1`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct BinData {
+    pub x0: f64,
+    pub x1: f64,
+    /// The indices of the data in the bin.
+    pub indices: Vec<usize>,
+}
+
+impl BinData {
+    /// Get the count of the data in the bin.
+    pub fn len(&self) -> usize {
+        self.indices.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.indices.is_empty()
+    }
+}
+
+pub struct Bin<T> {
+    value: Box<dyn Fn(&T) -> Option<f64>>,
+    thresholds: Option<usize>,
+}
+
+impl<T> Default for Bin<T> {
+    fn default() -> Self {
+        Self {
+            value: Box::new(|_| None),
+            thresholds: None,
+        }
+    }
+}
+
+impl<T> Bin<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the value of the Bin, the missing and non-finite values are ignored.
+    pub fn value<F>(mut self, value: F) -> Self
+    where
+        F: Fn(&T) -> Option<f64> + 'static,
+    {
+        self.value = Box::new(value);
+        self
+    }
+
+    /// Set about the count of the bins, default is by Sturges' formula.
+    ///
+    /// The bins are split at the nice round values, so the count may be different.
+    pub fn thresholds(mut self, count: usize) -> Self {
+        self.thresholds = Some(count);
+        self
+    }
+
+    /// Get the bins of the data, sorted by x.
+    pub fn bins(&self, data: &[T]) -> Vec<BinData> {
+        let values = data
+            .iter()
+            .enumerate()
+            .filter_map(|(ix, d)| Some((ix, (self.value)(d).filter(|v| v.is_finite())?)))
+            .collect::<Vec<_>>();
+        if values.is_empty() {
+            return vec![];
+        }
+
+        // Sturges' formula: ceil(log2(n)) + 1.
+        let count = self
+            .thresholds
+            .unwrap_or_else(|| (values.len() as f64).log2().ceil() as usize + 1)
+            .max(1);
+        let scale = ScaleLinear::new(values.iter().map(|(_, v)| *v).collect(), vec![]).nice(count);
+        let thresholds = scale.ticks(count);
+
+        // All the values are the same.
+        if thresholds.len() < 2 {
+            let x = values[0].1;
+            return vec![BinData {
+                x0: x,
+                x1: x,
+                indices: values.into_iter().map(|(ix, _)| ix).collect(),
+            }];
+        }
+
+        let mut bins = thresholds
+            .windows(2)
+            .map(|w| BinData {
+                x0: w[0],
+                x1: w[1],
+                indices: vec![],
+            })
+            .collect::<Vec<_>>();
+        let last = bins.len() - 1;
+        for (ix, value) in values {
+            let bin = thresholds
+                .partition_point(|t| *t <= value)
+                .saturating_sub(1)
+                .min(last);
+            bins[bin].indices.push(ix);
+        }
+        bins
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bin() {
+        let data = vec![0.5, 1.5, 1.7, 2.2, 4.0, f64::NAN];
+        let bins = Bin::new()
+            .value(|v: &f64| Some(*v))
+            .thresholds(4)
+            .bins(&data);
+        assert_eq!(
+            bins.iter().map(|b| (b.x0, b.x1)).collect::<Vec<_>>(),
+            vec![(0., 1.), (1., 2.), (2., 3.), (3., 4.)]
+        );
+        assert_eq!(
+            bins.iter().map(|b| b.indices.clone()).collect::<Vec<_>>(),
+            vec![vec![0], vec![1, 2], vec![3], vec![4]]
+        );
+    }
+
+    #[test]
+    fn test_bin_sturges() {
+        let data = (0..100).map(|i| i as f64).collect::<Vec<_>>();
+        let bins = Bin::new().value(|v: &f64| Some(*v)).bins(&data);
+        assert_eq!(bins.len(), 10);
+        assert_eq!(bins[0].x0, 0.);
+        assert_eq!(bins[9].x1, 100.);
+        assert_eq!(bins.iter().map(|b| b.len()).sum::<usize>(), 100);
+        assert!(bins.iter().all(|b| b.len() == 10));
+    }
+
+    #[test]
+    fn test_bin_same_values() {
+        let data = vec![3., 3., 3.];
+        let bins = Bin::new().value(|v: &f64| Some(*v)).bins(&data);
+        assert_eq!(
+            bins,
+            vec![BinData {
+                x0: 3.,
+                x1: 3.,
+                indices: vec![0, 1, 2]
+            }]
+        );
+
+        let bins = Bin::new().value(|v: &f64| Some(*v)).bins(&[]);
+        assert!(bins.is_empty());
+    }
+}
@@ -0,0 +1,157 @@
+// @reference: https://skemman.is/handle/1946/15343
+
+use gpui::Point;
+
+/// The downsampling of the points, to keep the paint cost bounded for the large data.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Downsample {
+    /// Keep all the points.
+    #[default]
+    None,
+    /// Keep about the count of points by Largest-Triangle-Three-Buckets.
+    Lttb(usize),
+    /// Keep the first, min, max and last points in each pixel column, e.g.: To keep the spikes.
+    MinMax,
+}
+
+impl Downsample {
+    /// Downsample the points, the points are sorted by x.
+    pub fn points(&self, points: Vec<Point<f64>>) -> Vec<Point<f64>> {
+        match self {
+            Self::None => points,
+            Self::Lttb(threshold) => lttb(&points, *threshold),
+            Self::MinMax => min_max(&points),
+        }
+    }
+}
+
+/// Downsample the points to `threshold` points by Largest-Triangle-Three-Buckets.
+fn lttb(points: &[Point<f64>], threshold: usize) -> Vec<Point<f64>> {
+    let len = points.len();
+    if threshold >= len || threshold < 3 {
+        return points.to_vec();
+    }
+
+    // The first and the last points are kept, the others are split into the buckets.
+    let every = (len - 2) as f64 / (threshold - 2) as f64;
+    let mut sampled = Vec::with_capacity(threshold);
+    let mut a = 0;
+    sampled.push(points[0]);
+
+    for i in 0..threshold - 2 {
+        // The average point of the next bucket.
+        let avg_start = ((i + 1) as f64 * every) as usize + 1;
+        let avg_end = (((i + 2) as f64 * every) as usize + 1).min(len);
+        let next = &points[avg_start..avg_end];
+        let avg_x = next.iter().map(|p| p.x).sum::<f64>() / next.len() as f64;
+        let avg_y = next.iter().map(|p| p.y).sum::<f64>() / next.len() as f64;
+
+        // The point of the current bucket with the largest triangle.
+        let start = (i as f64 * every) as usize + 1;
+        let end = ((i + 1) as f64 * every) as usize + 1;
+        let pa = points[a];
+        let mut max_area = -1.;
+        for (j, p) in points.iter().enumerate().take(end).skip(start) {
+            let area = ((pa.x - avg_x) * (p.y - pa.y) - (pa.x - p.x) * (avg_y - pa.y)).abs();
+            if area > max_area {
+                max_area = area;
+                a = j;
+            }
+        }
+        sampled.push(points[a]);
+    }
+
+    sampled.push(points[len - 1]);
+    sampled
+}
+
+/// Downsample the points to the first, min, max and last points in each pixel column.
+fn min_max(points: &[Point<f64>]) -> Vec<Point<f64>> {
+    let mut sampled = Vec::new();
+    let mut start = 0;
+
+    while start < points.len() {
+        let column = points[start].x.floor();
+        let end = points[start..]
+            .iter()
+            .position(|p| p.x.floor() != column)
+            .map_or(points.len(), |len| start + len);
+
+        let column_points = &points[start..end];
+        if column_points.len() <= 4 {
+            sampled.extend_from_slice(column_points);
+        } else {
+            let (mut min, mut max) = (0, 0);
+            for (i, p) in column_points.iter().enumerate() {
+                if p.y < column_points[min].y {
+                    min = i;
+                }
+                if p.y > column_points[max].y {
+                    max = i;
+                }
+            }
+
+            let mut indices = [0, min, max, column_points.len() - 1];
+            indices.sort_unstable();
+            let mut last = None;
+            for i in indices {
+                if last != Some(i) {
+                    sampled.push(column_points[i]);
+                    last = Some(i);
+                }
+            }
+        }
+
+        start = end;
+    }
+
+    sampled
+}
+
+#[cfg(test)]
+mod tests {
+    use gpui::point;
+
+    use super::*;
+
+    #[test]
+    fn test_lttb() {
+        let points = (0..1000)
+            .map(|i| point(i as f64, (i as f64 / 10.).sin()))
+            .collect::<Vec<_>>();
+        let sampled = lttb(&points, 100);
+        assert_eq!(sampled.len(), 100);
+        assert_eq!(sampled[0], points[0]);
+        assert_eq!(sampled[99], points[999]);
+        assert!(sampled.windows(2).all(|w| w[0].x < w[1].x));
+
+        assert_eq!(lttb(&points[..10], 100).len(), 10);
+        assert_eq!(lttb(&points, 2).len(), 1000);
+    }
+
+    #[test]
+    fn test_min_max() {
+        let mut points = (0..1000)
+            .map(|i| point(i as f64 / 100., 1.))
+            .collect::<Vec<_>>();
+        points[555].y = 10.;
+        points[556].y = -10.;
+
+        let sampled = min_max(&points);
+        assert_eq!(sampled.len(), 10 * 2 + 2);
+        assert_eq!(sampled[0], points[0]);
+        assert_eq!(sampled[sampled.len() - 1], points[999]);
+        assert!(sampled.contains(&points[555]));
+        assert!(sampled.contains(&points[556]));
+        assert!(sampled.windows(2).all(|w| w[0].x < w[1].x));
+
+        let points = vec![point(0., 1.), point(1., 2.), point(2., 3.)];
+        assert_eq!(min_max(&points), points);
+    }
+
+    #[test]
+    fn test_downsample_none() {
+        let points = vec![point(0., 1.), point(0.1, 2.), point(0.2, 3.)];
+        assert_eq!(Downsample::None.points(points.clone()), points);
+    }
+}
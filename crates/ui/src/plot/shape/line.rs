@@ -1,24 +1,24 @@
 // @reference: https://d3js.org/d3-shape/line
 
-use gpui::{
-    px, quad, size, Background, BorderStyle, Bounds, Hsla, PaintQuad, Path, PathBuilder, Pixels,
-    Point, Window,
-};
+use gpui::{point, px, size, Bounds, Hsla, Pixels, Point};
 
-use crate::plot::{origin_point, StrokeStyle};
+use crate::plot::{origin_point, Canvas, Paint, PlotPath, Quad, StrokeStyle};
+
+use super::Downsample;
 
 #[allow(clippy::type_complexity)]
 pub struct Line<T> {
     data: Vec<T>,
     x: Box<dyn Fn(&T) -> Option<f64>>,
     y: Box<dyn Fn(&T) -> Option<f64>>,
-    stroke: Background,
+    stroke: Paint,
     stroke_width: Pixels,
     stroke_style: StrokeStyle,
     dot: bool,
     dot_size: Pixels,
     dot_fill_color: Hsla,
     dot_stroke_color: Option<Hsla>,
+    downsample: Downsample,
 }
 
 impl<T> Default for Line<T> {
@@ -34,6 +34,7 @@ impl<T> Default for Line<T> {
             dot_size: px(4.),
             dot_fill_color: gpui::transparent_black(),
             dot_stroke_color: None,
+            downsample: Downsample::None,
         }
     }
 }
@@ -71,7 +72,7 @@ impl<T> Line<T> {
     }
 
     /// Set the stroke color of the Line.
-    pub fn stroke(mut self, stroke: impl Into<Background>) -> Self {
+    pub fn stroke(mut self, stroke: impl Into<Paint>) -> Self {
         self.stroke = stroke.into();
         self
     }
@@ -112,43 +113,49 @@ impl<T> Line<T> {
         self
     }
 
+    /// Set the downsampling of the Line, the data is sorted by x.
+    pub fn downsample(mut self, downsample: Downsample) -> Self {
+        self.downsample = downsample;
+        self
+    }
+
     /// Paint the dots on the Line.
-    fn paint_dot(&self, dot: Point<Pixels>) -> PaintQuad {
-        quad(
+    fn paint_dot(&self, dot: Point<Pixels>) -> Quad {
+        Quad::fill(
             gpui::bounds(dot, size(self.dot_size, self.dot_size)),
-            self.dot_size / 2.,
             self.dot_fill_color,
-            px(1.),
-            self.dot_stroke_color.unwrap_or(self.dot_fill_color),
-            BorderStyle::default(),
         )
+        .corner_radii(self.dot_size / 2.)
+        .border_widths(px(1.))
+        .border_color(self.dot_stroke_color.unwrap_or(self.dot_fill_color))
     }
 
-    fn path(&self, bounds: &Bounds<Pixels>) -> (Option<Path<Pixels>>, Vec<PaintQuad>) {
+    fn path(&self, bounds: &Bounds<Pixels>) -> (Option<PlotPath>, Vec<Quad>) {
         let origin = bounds.origin;
-        let mut builder = PathBuilder::stroke(self.stroke_width);
+        let mut builder = PlotPath::stroke(self.stroke_width);
         let mut dots = vec![];
         let mut paint_dots = vec![];
 
-        for v in self.data.iter() {
-            let x_tick = (self.x)(v);
-            let y_tick = (self.y)(v);
-
-            if let (Some(x), Some(y)) = (x_tick, y_tick) {
-                let pos = origin_point(px(x as f32), px(y as f32), origin);
-
-                if self.dot {
-                    let dot_radius = self.dot_size.to_f64() / 2.;
-                    let dot_pos = origin_point(
-                        px((x - dot_radius) as f32),
-                        px((y - dot_radius) as f32),
-                        origin,
-                    );
-                    paint_dots.push(self.paint_dot(dot_pos));
-                }
-
-                dots.push(pos);
+        let points = self
+            .data
+            .iter()
+            .filter_map(|v| Some(point((self.x)(v)?, (self.y)(v)?)))
+            .collect();
+
+        for Point { x, y } in self.downsample.points(points) {
+            let pos = origin_point(px(x as f32), px(y as f32), origin);
+
+            if self.dot {
+                let dot_radius = self.dot_size.to_f64() / 2.;
+                let dot_pos = origin_point(
+                    px((x - dot_radius) as f32),
+                    px((y - dot_radius) as f32),
+                    origin,
+                );
+                paint_dots.push(self.paint_dot(dot_pos));
             }
+
+            dots.push(pos);
         }
 
         if dots.is_empty() {
@@ -157,7 +164,7 @@ impl<T> Line<T> {
 
         if dots.len() == 1 {
             builder.move_to(dots[0]);
-            return (builder.build().ok(), paint_dots);
+            return (Some(builder), paint_dots);
         }
 
         match self.stroke_style {
@@ -185,17 +192,17 @@ impl<T> Line<T> {
             }
         }
 
-        (builder.build().ok(), paint_dots)
+        (Some(builder), paint_dots)
     }
 
     /// Paint the Line.
-    pub fn paint(&self, bounds: &Bounds<Pixels>, window: &mut Window) {
+    pub fn paint(&self, bounds: &Bounds<Pixels>, canvas: &mut Canvas) {
         let (path, dots) = self.path(bounds);
         if let Some(path) = path {
-            window.paint_path(path, self.stroke);
+            canvas.paint_path(path, self.stroke);
         }
         for dot in dots {
-            window.paint_quad(dot);
+            canvas.paint_quad(dot);
         }
     }
 }
@@ -229,4 +236,20 @@ mod tests {
         let (_, dots) = line_with_dots.path(&bounds);
         assert_eq!(dots.len(), 3);
     }
+
+    #[test]
+    fn test_line_downsample() {
+        let data = (0..1000).collect::<Vec<_>>();
+        let line = Line::new()
+            .data(data)
+            .x(|v| Some(*v as f64 / 100.))
+            .y(|v| Some((*v % 7) as f64))
+            .dot()
+            .downsample(Downsample::MinMax);
+
+        let bounds = Bounds::new(point(px(0.), px(0.)), size(px(100.), px(100.)));
+        let (path, dots) = line.path(&bounds);
+        assert!(path.is_some());
+        assert!(dots.len() > 10 && dots.len() <= 10 * 4);
+    }
 }
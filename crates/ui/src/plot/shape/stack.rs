@@ -0,0 +1,159 @@
+// @reference: https://d3js.org/d3-shape/stack
+
+/// The offset of the Stack.
+#[derive(Clone, Copy, Default, PartialEq, Eq)]
+pub enum StackOffset {
+    /// Stack each value on the sum of the previous values.
+    #[default]
+    None,
+    /// Stack the positive values above 0 and the negative values below 0, e.g.: For the stacked bars.
+    Diverging,
+}
+
+pub struct StackPoint<'a, T> {
+    pub data: &'a T,
+    pub index: usize,
+    pub y0: f64,
+    pub y1: f64,
+}
+
+pub struct StackSeries<'a, T> {
+    /// The index of the key of the series.
+    pub key: usize,
+    pub points: Vec<StackPoint<'a, T>>,
+}
+
+#[allow(clippy::type_complexity)]
+pub struct Stack<T> {
+    keys: Vec<Box<dyn Fn(&T) -> Option<f64>>>,
+    offset: StackOffset,
+}
+
+impl<T> Default for Stack<T> {
+    fn default() -> Self {
+        Self {
+            keys: vec![],
+            offset: StackOffset::default(),
+        }
+    }
+}
+
+impl<T> Stack<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a key of the Stack, which gets the value of the series.
+    ///
+    /// The series are stacked in the order of the keys, the missing values are treated as 0.
+    pub fn key<F>(mut self, key: F) -> Self
+    where
+        F: Fn(&T) -> Option<f64> + 'static,
+    {
+        self.keys.push(Box::new(key));
+        self
+    }
+
+    /// Set the offset of the Stack.
+    pub fn offset(mut self, offset: StackOffset) -> Self {
+        self.offset = offset;
+        self
+    }
+
+    /// Get the stacked series of the data, one series for each key.
+    pub fn series<'a>(&self, data: &'a [T]) -> Vec<StackSeries<'a, T>> {
+        let mut series = (0..self.keys.len())
+            .map(|key| StackSeries {
+                key,
+                points: Vec::with_capacity(data.len()),
+            })
+            .collect::<Vec<_>>();
+
+        for (index, d) in data.iter().enumerate() {
+            let mut positive = 0.;
+            let mut negative = 0.;
+
+            for (key, value) in self.keys.iter().enumerate() {
+                let value = value(d).filter(|v| v.is_finite()).unwrap_or_default();
+                let (y0, y1) = match self.offset {
+                    StackOffset::None => {
+                        let y0 = positive;
+                        positive += value;
+                        (y0, positive)
+                    }
+                    StackOffset::Diverging if value < 0. => {
+                        let y1 = negative;
+                        negative += value;
+                        (negative, y1)
+                    }
+                    StackOffset::Diverging => {
+                        let y0 = positive;
+                        positive += value;
+                        (y0, positive)
+                    }
+                };
+
+                series[key].points.push(StackPoint {
+                    data: d,
+                    index,
+                    y0,
+                    y1,
+                });
+            }
+        }
+
+        series
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bounds(series: &[StackSeries<'_, (f64, f64)>]) -> Vec<Vec<(f64, f64)>> {
+        series
+            .iter()
+            .map(|s| s.points.iter().map(|p| (p.y0, p.y1)).collect())
+            .collect()
+    }
+
+    #[test]
+    fn test_stack() {
+        let data = vec![(1., 2.), (3., 4.), (5., f64::NAN)];
+        let stack = Stack::new()
+            .key(|d: &(f64, f64)| Some(d.0))
+            .key(|d| Some(d.1));
+        let series = stack.series(&data);
+
+        assert_eq!(series.len(), 2);
+        assert_eq!(series[1].key, 1);
+        assert_eq!(series[1].points[2].index, 2);
+        assert_eq!(
+            bounds(&series),
+            vec![
+                vec![(0., 1.), (0., 3.), (0., 5.)],
+                vec![(1., 3.), (3., 7.), (5., 5.)],
+            ]
+        );
+    }
+
+    #[test]
+    fn test_stack_diverging() {
+        let data = vec![(1., -2.), (-3., -4.)];
+        let stack = Stack::new()
+            .key(|d: &(f64, f64)| Some(d.0))
+            .key(|d| Some(d.1))
+            .key(|_| Some(2.))
+            .offset(StackOffset::Diverging);
+        let series = stack.series(&data);
+
+        assert_eq!(
+            bounds(&series),
+            vec![
+                vec![(0., 1.), (-3., 0.)],
+                vec![(-2., 0.), (-7., -3.)],
+                vec![(1., 3.), (0., 2.)],
+            ]
+        );
+    }
+}
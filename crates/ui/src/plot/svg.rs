@@ -0,0 +1,384 @@
+use std::fmt::{self, Display, Write};
+
+use anyhow::anyhow;
+use gpui::{px, Bounds, FontWeight, Hsla, Pixels, Point, Rgba, SharedString, Size, TextAlign};
+
+use crate::svg_img::OPTIONS;
+
+use super::{Paint, PlotPath, Quad};
+
+/// The average width of the characters in `em`, to measure the text without the fonts.
+const CHAR_WIDTH: f64 = 0.6;
+
+/// Format the number in SVG, rounded to 2 decimal places.
+pub(super) fn number(value: f64) -> String {
+    // Add `0.` to avoid `-0`.
+    format!("{}", (value * 100.).round() / 100. + 0.)
+}
+
+/// Return the color in hex and the opacity.
+fn color(color: Hsla) -> (String, f32) {
+    let Rgba { r, g, b, a } = Rgba::from(color);
+    let hex = |v: f32| (v.clamp(0., 1.) * 255.).round() as u8;
+    (format!("#{:02x}{:02x}{:02x}", hex(r), hex(g), hex(b)), a)
+}
+
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// An SVG document to export the plot, the plot is painted in it by the [`Canvas::Svg`](super::Canvas::Svg).
+pub struct SvgDocument {
+    size: Size<Pixels>,
+    font_family: SharedString,
+    body: String,
+    /// The count of the defined clip paths and gradients, to make the ids.
+    defs: usize,
+}
+
+impl SvgDocument {
+    pub fn new(size: Size<Pixels>, font_family: impl Into<SharedString>) -> Self {
+        Self {
+            size,
+            font_family: font_family.into(),
+            body: String::new(),
+            defs: 0,
+        }
+    }
+
+    fn next_id(&mut self, prefix: &str) -> String {
+        self.defs += 1;
+        format!("{}-{}", prefix, self.defs)
+    }
+
+    /// Write the attribute of the paint, e.g.: `fill="#ff0000" fill-opacity="0.5"`.
+    fn paint(&mut self, name: &str, paint: Paint) -> String {
+        match paint {
+            Paint::Color(c) => {
+                let (hex, opacity) = color(c);
+                if opacity <= 0. {
+                    format!(r#"{}="none""#, name)
+                } else if opacity >= 1. {
+                    format!(r#"{}="{}""#, name, hex)
+                } else {
+                    format!(
+                        r#"{}="{}" {}-opacity="{}""#,
+                        name,
+                        hex,
+                        name,
+                        number(opacity as f64)
+                    )
+                }
+            }
+            Paint::LinearGradient { angle, from, to } => {
+                // The angle is the same as CSS, `0.` is to the top and `90.` is to the right.
+                let id = self.next_id("gradient");
+                let (sin, cos) = (angle as f64).to_radians().sin_cos();
+                let _ = write!(
+                    self.body,
+                    r#"<linearGradient id="{}" x1="{}" y1="{}" x2="{}" y2="{}">"#,
+                    id,
+                    number(0.5 - sin / 2.),
+                    number(0.5 + cos / 2.),
+                    number(0.5 + sin / 2.),
+                    number(0.5 - cos / 2.),
+                );
+                for stop in [from, to] {
+                    let (hex, opacity) = color(stop.color);
+                    let _ = write!(
+                        self.body,
+                        r#"<stop offset="{}" stop-color="{}" stop-opacity="{}"/>"#,
+                        number(stop.percentage as f64),
+                        hex,
+                        number(opacity as f64),
+                    );
+                }
+                self.body.push_str("</linearGradient>\n");
+                format!(r#"{}="url(#{})""#, name, id)
+            }
+        }
+    }
+
+    /// Write the Quad as a `<rect>`, the border is inside the bounds.
+    pub(super) fn quad(&mut self, quad: &Quad) {
+        let border = quad.border_widths.to_f64().max(0.);
+        let bg_opacity = color(quad.background).1;
+        if bg_opacity <= 0. && (border == 0. || color(quad.border_color).1 <= 0.) {
+            return;
+        }
+
+        // The stroke of SVG is at the center of the edges, inset it by the half border.
+        let inset = border / 2.;
+        let origin = quad.bounds.origin;
+        let size = quad.bounds.size;
+        let width = (size.width.to_f64() - border).max(0.);
+        let height = (size.height.to_f64() - border).max(0.);
+        let radius = (quad.corner_radii.to_f64() - inset)
+            .min(width.min(height) / 2.)
+            .max(0.);
+
+        let fill = self.paint("fill", Paint::Color(quad.background));
+        let _ = write!(
+            self.body,
+            r#"<rect x="{}" y="{}" width="{}" height="{}""#,
+            number(origin.x.to_f64() + inset),
+            number(origin.y.to_f64() + inset),
+            number(width),
+            number(height),
+        );
+        if radius > 0. {
+            let _ = write!(self.body, r#" rx="{}""#, number(radius));
+        }
+        let _ = write!(self.body, " {}", fill);
+        if border > 0. {
+            let stroke = self.paint("stroke", Paint::Color(quad.border_color));
+            let _ = write!(
+                self.body,
+                r#" {} stroke-width="{}""#,
+                stroke,
+                number(border)
+            );
+        }
+        self.body.push_str("/>\n");
+    }
+
+    /// Write the path as a `<path>`, filled or stroked by the paint.
+    pub(super) fn path(&mut self, path: &PlotPath, paint: Paint) {
+        let data = path.svg_data();
+        if data.is_empty() {
+            return;
+        }
+
+        let attrs = match path.stroke_width {
+            Some(width) => {
+                let mut attrs = format!(
+                    r#"fill="none" {} stroke-width="{}""#,
+                    self.paint("stroke", paint),
+                    number(width.to_f64())
+                );
+                if let Some(dash_array) = &path.dash_array {
+                    let dash_array = dash_array
+                        .iter()
+                        .map(|v| number(v.to_f64()))
+                        .collect::<Vec<_>>()
+                        .join(" ");
+                    let _ = write!(attrs, r#" stroke-dasharray="{}""#, dash_array);
+                }
+                attrs
+            }
+            None => self.paint("fill", paint),
+        };
+        let _ = writeln!(self.body, r#"<path d="{}" {}/>"#, data, attrs);
+    }
+
+    /// Write the text as a `<text>`, the `origin` is the top of the text.
+    pub(super) fn text(
+        &mut self,
+        text: &str,
+        origin: Point<Pixels>,
+        font_size: Pixels,
+        font_weight: FontWeight,
+        align: TextAlign,
+        text_color: Hsla,
+    ) {
+        let anchor = match align {
+            TextAlign::Left => "start",
+            TextAlign::Right => "end",
+            _ => "middle",
+        };
+        let fill = self.paint("fill", Paint::Color(text_color));
+        let _ = writeln!(
+            self.body,
+            r#"<text x="{}" y="{}" font-family="{}" font-size="{}" font-weight="{}" text-anchor="{}" dominant-baseline="central" {}>{}</text>"#,
+            number(origin.x.to_f64()),
+            number(origin.y.to_f64() + font_size.to_f64() / 2.),
+            escape(&self.font_family),
+            number(font_size.to_f64()),
+            font_weight.0,
+            anchor,
+            fill,
+            escape(text),
+        );
+    }
+
+    /// Measure the width of the text, it's estimated by the average width of the characters.
+    pub(super) fn text_width(&self, text: &str, font_size: Pixels) -> Pixels {
+        px((text.chars().count() as f64 * font_size.to_f64() * CHAR_WIDTH) as f32)
+    }
+
+    /// Clip the content in the bounds until [`Self::pop_clip`].
+    pub(super) fn push_clip(&mut self, bounds: Bounds<Pixels>) {
+        let id = self.next_id("clip");
+        let _ = writeln!(
+            self.body,
+            r#"<clipPath id="{}"><rect x="{}" y="{}" width="{}" height="{}"/></clipPath>"#,
+            id,
+            number(bounds.origin.x.to_f64()),
+            number(bounds.origin.y.to_f64()),
+            number(bounds.size.width.to_f64()),
+            number(bounds.size.height.to_f64()),
+        );
+        let _ = writeln!(self.body, r#"<g clip-path="url(#{})">"#, id);
+    }
+
+    pub(super) fn pop_clip(&mut self) {
+        self.body.push_str("</g>\n");
+    }
+}
+
+impl Display for SvgDocument {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (width, height) = (
+            number(self.size.width.to_f64()),
+            number(self.size.height.to_f64()),
+        );
+        writeln!(
+            f,
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="{}" height="{}" viewBox="0 0 {} {}">"#,
+            width, height, width, height
+        )?;
+        f.write_str(&self.body)?;
+        f.write_str("</svg>\n")
+    }
+}
+
+/// Rasterize the SVG to PNG, the `scale` is the scale factor of the pixels.
+pub(super) fn render_png(svg: &str, scale: f32) -> anyhow::Result<Vec<u8>> {
+    let tree = usvg::Tree::from_data(svg.as_bytes(), &OPTIONS)?;
+    let size = tree.size();
+    let mut pixmap = resvg::tiny_skia::Pixmap::new(
+        (size.width() * scale).ceil() as u32,
+        (size.height() * scale).ceil() as u32,
+    )
+    .ok_or_else(|| anyhow!("invalid size of the svg: {:?}", size))?;
+
+    let transform = resvg::tiny_skia::Transform::from_scale(scale, scale);
+    resvg::render(&tree, transform, &mut pixmap.as_mut());
+    Ok(pixmap.encode_png()?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use gpui::{hsla, linear_color_stop, point, size};
+
+    fn document() -> SvgDocument {
+        SvgDocument::new(size(px(100.), px(50.)), "Inter")
+    }
+
+    #[test]
+    fn test_number() {
+        assert_eq!(number(1.), "1");
+        assert_eq!(number(1.006), "1.01");
+        assert_eq!(number(-0.001), "0");
+        assert_eq!(number(12.5), "12.5");
+    }
+
+    #[test]
+    fn test_quad() {
+        let mut svg = document();
+        let bounds = Bounds::new(point(px(10.), px(10.)), size(px(20.), px(10.)));
+        svg.quad(&Quad::fill(bounds, hsla(0., 1., 0.5, 1.)));
+        svg.quad(
+            &Quad::fill(bounds, hsla(0., 0., 0., 0.))
+                .border_widths(px(2.))
+                .border_color(hsla(0., 0., 0., 0.5))
+                .corner_radii(px(4.)),
+        );
+        svg.quad(&Quad::fill(bounds, hsla(0., 0., 0., 0.)));
+
+        assert_eq!(
+            svg.body,
+            concat!(
+                "<rect x=\"10\" y=\"10\" width=\"20\" height=\"10\" fill=\"#ff0000\"/>\n",
+                "<rect x=\"11\" y=\"11\" width=\"18\" height=\"8\" rx=\"3\" fill=\"none\" ",
+                "stroke=\"#000000\" stroke-opacity=\"0.5\" stroke-width=\"2\"/>\n",
+            )
+        );
+    }
+
+    #[test]
+    fn test_path() {
+        let mut svg = document();
+        let mut line = PlotPath::stroke(px(1.)).dash_array(&[px(4.), px(2.)]);
+        line.move_to(point(px(0.), px(0.)));
+        line.line_to(point(px(100.), px(0.)));
+        svg.path(&line, Paint::Color(hsla(0., 0., 1., 1.)));
+        svg.path(&PlotPath::fill(), Paint::Color(hsla(0., 0., 1., 1.)));
+
+        assert_eq!(
+            svg.body,
+            "<path d=\"M0 0 L100 0\" fill=\"none\" stroke=\"#ffffff\" stroke-width=\"1\" stroke-dasharray=\"4 2\"/>\n"
+        );
+
+        let mut svg = document();
+        let mut area = PlotPath::fill();
+        area.add_polygon(
+            &[
+                point(px(0.), px(0.)),
+                point(px(10.), px(10.)),
+                point(px(0.), px(10.)),
+            ],
+            true,
+        );
+        svg.path(
+            &area,
+            Paint::LinearGradient {
+                angle: 0.,
+                from: linear_color_stop(hsla(0., 0., 0., 1.), 1.),
+                to: linear_color_stop(hsla(0., 0., 1., 0.5), 0.),
+            },
+        );
+        assert_eq!(
+            svg.body,
+            concat!(
+                "<linearGradient id=\"gradient-1\" x1=\"0.5\" y1=\"1\" x2=\"0.5\" y2=\"0\">",
+                "<stop offset=\"1\" stop-color=\"#000000\" stop-opacity=\"1\"/>",
+                "<stop offset=\"0\" stop-color=\"#ffffff\" stop-opacity=\"0.5\"/>",
+                "</linearGradient>\n",
+                "<path d=\"M0 0 L10 10 L0 10 Z\" fill=\"url(#gradient-1)\"/>\n",
+            )
+        );
+    }
+
+    #[test]
+    fn test_text_and_clip() {
+        let mut svg = document();
+        svg.push_clip(Bounds::new(point(px(0.), px(0.)), size(px(50.), px(40.))));
+        svg.text(
+            "A & B",
+            point(px(50.), px(20.)),
+            px(10.),
+            FontWeight::NORMAL,
+            TextAlign::Center,
+            hsla(0., 0., 0., 1.),
+        );
+        svg.pop_clip();
+
+        assert_eq!(
+            svg.to_string(),
+            concat!(
+                "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"100\" height=\"50\" viewBox=\"0 0 100 50\">\n",
+                "<clipPath id=\"clip-1\"><rect x=\"0\" y=\"0\" width=\"50\" height=\"40\"/></clipPath>\n",
+                "<g clip-path=\"url(#clip-1)\">\n",
+                "<text x=\"50\" y=\"25\" font-family=\"Inter\" font-size=\"10\" font-weight=\"400\" ",
+                "text-anchor=\"middle\" dominant-baseline=\"central\" fill=\"#000000\">A &amp; B</text>\n",
+                "</g>\n",
+                "</svg>\n",
+            )
+        );
+        assert_eq!(svg.text_width("abc", px(10.)), px(18.));
+    }
+}
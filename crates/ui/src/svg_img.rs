@@ -17,7 +17,7 @@ use image::ImageBuffer;
 
 const SCALE: f32 = 2.;
 
-static OPTIONS: LazyLock<usvg::Options> = LazyLock::new(|| {
+pub(crate) static OPTIONS: LazyLock<usvg::Options> = LazyLock::new(|| {
     let mut options = usvg::Options::default();
     options.fontdb_mut().load_system_fonts();
     options
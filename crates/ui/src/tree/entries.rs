@@ -0,0 +1,241 @@
+use std::{collections::HashSet, hash::Hash};
+
+/// A visible node of the Tree, the expanded nodes are flattened into a list of entries.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TreeEntry<Id> {
+    /// The id of the node.
+    pub id: Id,
+    /// The depth of the node, 0 for the root nodes.
+    pub depth: usize,
+    /// The entry index of the parent node, None for the root nodes.
+    pub parent: Option<usize>,
+    /// Whether the node may have children.
+    pub has_children: bool,
+    /// Whether the node is expanded.
+    pub expanded: bool,
+    /// Whether the node is expanded but the children are not loaded yet.
+    pub loading: bool,
+}
+
+/// The position to drop the dragged nodes, relative to the target node.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DropPosition {
+    /// Insert before the target node, as a sibling.
+    Before,
+    /// Insert into the target node, as a child.
+    Inside,
+    /// Insert after the target node, as a sibling.
+    After,
+}
+
+impl DropPosition {
+    /// Return the drop position by the mouse offset `y` in a row of `height`.
+    ///
+    /// The top and bottom quarter of the row are `Before` and `After`,
+    /// the middle is `Inside` if the target can have children.
+    pub(crate) fn from_offset(y: f32, height: f32, can_contain: bool) -> Self {
+        if can_contain {
+            if y < height * 0.25 {
+                Self::Before
+            } else if y > height * 0.75 {
+                Self::After
+            } else {
+                Self::Inside
+            }
+        } else if y < height * 0.5 {
+            Self::Before
+        } else {
+            Self::After
+        }
+    }
+}
+
+/// Flatten the expanded nodes of the tree into entries in display order.
+///
+/// - `children` returns the children of the node (or the root nodes for None),
+///   None means the children are not loaded yet.
+/// - `has_children` returns whether the node may have children.
+pub(crate) fn flatten_entries<Id, C, H>(
+    expanded: &HashSet<Id>,
+    children: C,
+    has_children: H,
+) -> Vec<TreeEntry<Id>>
+where
+    Id: Clone + Eq + Hash,
+    C: Fn(Option<&Id>) -> Option<Vec<Id>>,
+    H: Fn(&Id) -> bool,
+{
+    let mut entries = vec![];
+    // The stack of (parent entry index, depth, reversed children)
+    let mut stack = vec![(None, 0, reversed(children(None).unwrap_or_default()))];
+
+    while let Some((parent, depth, nodes)) = stack.last_mut() {
+        let Some(id) = nodes.pop() else {
+            stack.pop();
+            continue;
+        };
+        let (parent, depth) = (*parent, *depth);
+
+        let has_children = has_children(&id);
+        let is_expanded = has_children && expanded.contains(&id);
+        let node_children = if is_expanded {
+            children(Some(&id))
+        } else {
+            None
+        };
+        let ix = entries.len();
+        entries.push(TreeEntry {
+            id,
+            depth,
+            parent,
+            has_children,
+            expanded: is_expanded,
+            loading: is_expanded && node_children.is_none(),
+        });
+
+        if let Some(node_children) = node_children {
+            stack.push((Some(ix), depth + 1, reversed(node_children)));
+        }
+    }
+
+    entries
+}
+
+fn reversed<T>(mut items: Vec<T>) -> Vec<T> {
+    items.reverse();
+    items
+}
+
+/// Find the next entry (start from `start`, wrap around) whose label starts with the `query`, case-insensitive.
+///
+/// The labels are only requested until the entry is found.
+pub(crate) fn find_by_prefix<S: AsRef<str>>(
+    mut labels: impl FnMut(usize) -> Option<S>,
+    len: usize,
+    start: usize,
+    query: &str,
+) -> Option<usize> {
+    if query.is_empty() || len == 0 {
+        return None;
+    }
+
+    (0..len)
+        .map(|offset| (start + offset) % len)
+        .find(|&ix| labels(ix).is_some_and(|label| starts_with_ignore_case(label.as_ref(), query)))
+}
+
+fn starts_with_ignore_case(text: &str, prefix: &str) -> bool {
+    let mut text = text.chars().flat_map(char::to_lowercase);
+    prefix
+        .chars()
+        .flat_map(char::to_lowercase)
+        .all(|c| text.next() == Some(c))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::{HashMap, HashSet};
+
+    use super::{find_by_prefix, flatten_entries, DropPosition};
+
+    fn tree() -> HashMap<Option<&'static str>, Vec<&'static str>> {
+        HashMap::from([
+            (None, vec!["a", "b", "c"]),
+            (Some("a"), vec!["a1", "a2"]),
+            (Some("a1"), vec!["a1x"]),
+            (Some("c"), vec![]),
+        ])
+    }
+
+    #[test]
+    fn test_flatten_entries() {
+        let tree = tree();
+        let children = |id: Option<&&str>| tree.get(&id.copied()).cloned();
+        // "b" may have children, but they are not loaded.
+        let has_children = |id: &&str| tree.contains_key(&Some(*id)) || *id == "b";
+
+        let entries = flatten_entries(&HashSet::new(), children, has_children);
+        assert_eq!(
+            entries.iter().map(|e| e.id).collect::<Vec<_>>(),
+            vec!["a", "b", "c"]
+        );
+        assert!(entries.iter().all(|e| e.depth == 0 && !e.expanded));
+
+        let expanded = HashSet::from(["a", "a1", "b", "a1x"]);
+        let entries = flatten_entries(&expanded, children, has_children);
+        assert_eq!(
+            entries
+                .iter()
+                .map(|e| (e.id, e.depth, e.parent))
+                .collect::<Vec<_>>(),
+            vec![
+                ("a", 0, None),
+                ("a1", 1, Some(0)),
+                ("a1x", 2, Some(1)),
+                ("a2", 1, Some(0)),
+                ("b", 0, None),
+                ("c", 0, None),
+            ]
+        );
+        // The leaf node is never expanded.
+        assert!(!entries[2].expanded);
+        assert!(entries[4].expanded && entries[4].loading);
+        assert!(entries[5].has_children && !entries[5].expanded);
+
+        // Collapsed parent hides the expanded children.
+        let expanded = HashSet::from(["a1"]);
+        let entries = flatten_entries(&expanded, children, has_children);
+        assert_eq!(entries.len(), 3);
+    }
+
+    #[test]
+    fn test_find_by_prefix() {
+        let labels = ["Apple", "banana", "Blueberry", "cherry"];
+        let get = |ix: usize| labels.get(ix).copied();
+
+        assert_eq!(find_by_prefix(get, labels.len(), 0, "b"), Some(1));
+        assert_eq!(find_by_prefix(get, labels.len(), 2, "b"), Some(2));
+        assert_eq!(find_by_prefix(get, labels.len(), 3, "a"), Some(0));
+        assert_eq!(find_by_prefix(get, labels.len(), 0, "BLU"), Some(2));
+        assert_eq!(find_by_prefix(get, labels.len(), 0, "x"), None);
+        assert_eq!(find_by_prefix(get, labels.len(), 0, ""), None);
+
+        // The labels after the found entry are not requested.
+        let mut requested = vec![];
+        let found = find_by_prefix(
+            |ix| {
+                requested.push(ix);
+                labels.get(ix).map(|label| label.to_string())
+            },
+            labels.len(),
+            1,
+            "blue",
+        );
+        assert_eq!(found, Some(2));
+        assert_eq!(requested, vec![1, 2]);
+    }
+
+    #[test]
+    fn test_drop_position() {
+        assert_eq!(
+            DropPosition::from_offset(2., 20., true),
+            DropPosition::Before
+        );
+        assert_eq!(
+            DropPosition::from_offset(10., 20., true),
+            DropPosition::Inside
+        );
+        assert_eq!(
+            DropPosition::from_offset(18., 20., true),
+            DropPosition::After
+        );
+        assert_eq!(
+            DropPosition::from_offset(9., 20., false),
+            DropPosition::Before
+        );
+        assert_eq!(
+            DropPosition::from_offset(11., 20., false),
+            DropPosition::After
+        );
+    }
+}
@@ -0,0 +1,5 @@
+mod entries;
+mod tree;
+
+pub use entries::{DropPosition, TreeEntry};
+pub use tree::*;
@@ -0,0 +1,1002 @@
+use std::{
+    collections::{HashMap, HashSet},
+    hash::Hash,
+    ops::Range,
+    time::{Duration, Instant},
+};
+
+use gpui::{
+    actions, div, prelude::FluentBuilder as _, px, uniform_list, App, AppContext as _, Context,
+    DragMoveEvent, ElementId, Entity, EntityId, EventEmitter, FocusHandle, Focusable,
+    InteractiveElement, IntoElement, KeyBinding, KeyDownEvent, ListSizingBehavior, MouseButton,
+    MouseDownEvent, ParentElement, Pixels, Render, ScrollStrategy, SharedString,
+    StatefulInteractiveElement as _, Styled, Subscription, Task, UniformListScrollHandle, Window,
+};
+
+use crate::{
+    actions::{Cancel, Confirm, SelectAll, SelectNext, SelectPrev, SelectToNext, SelectToPrev},
+    h_flex,
+    indicator::Indicator,
+    input::{InputEvent, InputState, TextInput},
+    scroll::{Scrollbar, ScrollbarState},
+    v_flex, ActiveTheme, Icon, IconName, Sizable as _,
+};
+
+use super::entries::{find_by_prefix, flatten_entries, DropPosition, TreeEntry};
+
+actions!(tree, [Expand, Collapse, Rename]);
+
+const CONTEXT: &str = "Tree";
+/// The type-to-find query will be reset after this duration without typing.
+const FIND_TIMEOUT: Duration = Duration::from_millis(1000);
+
+pub fn init(cx: &mut App) {
+    let context = Some(CONTEXT);
+    cx.bind_keys([
+        KeyBinding::new("escape", Cancel, context),
+        KeyBinding::new("enter", Confirm { secondary: false }, context),
+        KeyBinding::new("secondary-enter", Confirm { secondary: true }, context),
+        KeyBinding::new("up", SelectPrev, context),
+        KeyBinding::new("down", SelectNext, context),
+        KeyBinding::new("shift-up", SelectToPrev, context),
+        KeyBinding::new("shift-down", SelectToNext, context),
+        KeyBinding::new("secondary-a", SelectAll, context),
+        KeyBinding::new("left", Collapse, context),
+        KeyBinding::new("right", Expand, context),
+        KeyBinding::new("f2", Rename, context),
+    ]);
+}
+
+#[derive(Clone)]
+pub enum TreeEvent<Id> {
+    /// The selected nodes changed, in display order.
+    Select(Vec<Id>),
+    /// Double click on the node or pressed Enter.
+    Confirm(Id),
+    /// The node is expanded.
+    Expand(Id),
+    /// The node is collapsed.
+    Collapse(Id),
+}
+
+/// A delegate for the Tree.
+///
+/// The nodes are identified by `Id`, the Tree only keeps the ids of the visible nodes,
+/// so the delegate is free to load the children lazily.
+#[allow(unused)]
+pub trait TreeDelegate: Sized + 'static {
+    type Id: Clone + Eq + Hash + 'static;
+    type Item: IntoElement;
+
+    /// Return the children ids of the node, or the root nodes when `parent` is None.
+    ///
+    /// Return None if the children are not loaded yet,
+    /// then `load_children` will be called when the node is expanded.
+    fn children(&self, parent: Option<&Self::Id>, cx: &App) -> Option<Vec<Self::Id>>;
+
+    /// Return true if the node may have children, the node can be expanded.
+    fn has_children(&self, id: &Self::Id, cx: &App) -> bool;
+
+    /// Load the children of the node, this is called when a node is expanded
+    /// and `children` returns None.
+    ///
+    /// The Tree will be refreshed after the task is finished.
+    fn load_children(
+        &mut self,
+        id: &Self::Id,
+        window: &mut Window,
+        cx: &mut Context<Tree<Self>>,
+    ) -> Task<()> {
+        Task::ready(())
+    }
+
+    /// Return the label of the node, used by type-to-find and as the initial text of rename.
+    fn label(&self, id: &Self::Id, cx: &App) -> SharedString;
+
+    /// Render the content of the node, the indent and the expand icon are rendered by the Tree.
+    fn render_item(
+        &self,
+        entry: &TreeEntry<Self::Id>,
+        selected: bool,
+        window: &mut Window,
+        cx: &mut Context<Tree<Self>>,
+    ) -> Option<Self::Item>;
+
+    /// Set the selected nodes, in display order.
+    fn set_selected(
+        &mut self,
+        ids: &[Self::Id],
+        window: &mut Window,
+        cx: &mut Context<Tree<Self>>,
+    ) {
+    }
+
+    /// Double click on the node or pressed Enter.
+    fn confirm(
+        &mut self,
+        id: &Self::Id,
+        secondary: bool,
+        window: &mut Window,
+        cx: &mut Context<Tree<Self>>,
+    ) {
+    }
+
+    /// Return true if the node can be renamed by `F2`, default is false.
+    fn can_rename(&self, id: &Self::Id, cx: &App) -> bool {
+        false
+    }
+
+    /// Rename the node with the new label, called when pressed Enter in the rename input.
+    fn rename(
+        &mut self,
+        id: &Self::Id,
+        label: SharedString,
+        window: &mut Window,
+        cx: &mut Context<Tree<Self>>,
+    ) {
+    }
+
+    /// Return true if the node can be dragged, default is false.
+    fn can_drag(&self, id: &Self::Id, cx: &App) -> bool {
+        false
+    }
+
+    /// Return true if the dragged nodes can be dropped at the position of the target node.
+    fn can_drop(
+        &self,
+        ids: &[Self::Id],
+        target: &Self::Id,
+        position: DropPosition,
+        cx: &App,
+    ) -> bool {
+        true
+    }
+
+    /// Move the dragged nodes to the position of the target node.
+    ///
+    /// The delegate should update the data, then the Tree will be refreshed.
+    fn on_drop(
+        &mut self,
+        ids: Vec<Self::Id>,
+        target: &Self::Id,
+        position: DropPosition,
+        window: &mut Window,
+        cx: &mut Context<Tree<Self>>,
+    ) {
+    }
+}
+
+/// The drag payload of the Tree nodes.
+#[derive(Clone)]
+pub(crate) struct DragTreeItems {
+    entity_id: EntityId,
+    ix: usize,
+    label: SharedString,
+    count: usize,
+}
+
+impl Render for DragTreeItems {
+    fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        h_flex()
+            .gap_2()
+            .px_3()
+            .py_1()
+            .text_sm()
+            .bg(cx.theme().popover)
+            .text_color(cx.theme().popover_foreground)
+            .border_1()
+            .border_color(cx.theme().border)
+            .rounded(cx.theme().radius)
+            .shadow_md()
+            .opacity(0.9)
+            .child(self.label.clone())
+            .when(self.count > 1, |this| {
+                this.child(
+                    div()
+                        .text_xs()
+                        .text_color(cx.theme().muted_foreground)
+                        .child(format!("+{}", self.count - 1)),
+                )
+            })
+    }
+}
+
+struct RenameState<Id> {
+    id: Id,
+    input: Entity<InputState>,
+    _subscription: Subscription,
+}
+
+/// A Tree view with virtualized rendering, can handle a large number of nodes.
+pub struct Tree<D: TreeDelegate> {
+    focus_handle: FocusHandle,
+    delegate: D,
+    entries: Vec<TreeEntry<D::Id>>,
+    /// The entry index of each visible node, rebuilt with the entries.
+    entry_indices: HashMap<D::Id, usize>,
+    expanded: HashSet<D::Id>,
+    selected: HashSet<D::Id>,
+    /// The node that was last moved to, the keyboard navigation starts from here.
+    cursor: Option<D::Id>,
+    /// The node where the range selection starts.
+    anchor: Option<D::Id>,
+    multi_selectable: bool,
+    indent: Pixels,
+    scroll_handle: UniformListScrollHandle,
+    scroll_state: ScrollbarState,
+    find_query: String,
+    last_find_at: Option<Instant>,
+    drop_target: Option<(usize, DropPosition)>,
+    renaming: Option<RenameState<D::Id>>,
+    _load_tasks: HashMap<D::Id, Task<()>>,
+}
+
+impl<D> Tree<D>
+where
+    D: TreeDelegate,
+{
+    pub fn new(delegate: D, _: &mut Window, cx: &mut Context<Self>) -> Self {
+        let mut this = Self {
+            focus_handle: cx.focus_handle(),
+            delegate,
+            entries: vec![],
+            entry_indices: HashMap::new(),
+            expanded: HashSet::new(),
+            selected: HashSet::new(),
+            cursor: None,
+            anchor: None,
+            multi_selectable: false,
+            indent: px(16.),
+            scroll_handle: UniformListScrollHandle::new(),
+            scroll_state: ScrollbarState::default(),
+            find_query: String::new(),
+            last_find_at: None,
+            drop_target: None,
+            renaming: None,
+            _load_tasks: HashMap::new(),
+        };
+        this.refresh(cx);
+        this
+    }
+
+    /// Sets whether the tree can select multiple nodes, default is false.
+    pub fn multi_selectable(mut self, multi_selectable: bool) -> Self {
+        self.multi_selectable = multi_selectable;
+        self
+    }
+
+    /// Set the indent width of each level, default is 16px.
+    pub fn indent(mut self, indent: impl Into<Pixels>) -> Self {
+        self.indent = indent.into();
+        self
+    }
+
+    pub fn delegate(&self) -> &D {
+        &self.delegate
+    }
+
+    pub fn delegate_mut(&mut self) -> &mut D {
+        &mut self.delegate
+    }
+
+    /// Returns the visible entries of the tree.
+    pub fn entries(&self) -> &[TreeEntry<D::Id>] {
+        &self.entries
+    }
+
+    /// Reload the visible entries from the delegate, call this after the data changed.
+    pub fn refresh(&mut self, cx: &mut Context<Self>) {
+        let delegate = &self.delegate;
+        let app: &App = cx;
+        self.entries = flatten_entries(
+            &self.expanded,
+            |parent| delegate.children(parent, app),
+            |id| delegate.has_children(id, app),
+        );
+        self.entry_indices = self
+            .entries
+            .iter()
+            .enumerate()
+            .map(|(ix, entry)| (entry.id.clone(), ix))
+            .collect();
+        cx.notify();
+    }
+
+    fn index_of(&self, id: &D::Id) -> Option<usize> {
+        self.entry_indices.get(id).copied()
+    }
+
+    fn cursor_index(&self) -> Option<usize> {
+        self.cursor.as_ref().and_then(|id| self.index_of(id))
+    }
+
+    pub fn is_expanded(&self, id: &D::Id) -> bool {
+        self.expanded.contains(id)
+    }
+
+    /// Expand the node, the children will be loaded if they are not loaded yet.
+    pub fn expand(&mut self, id: &D::Id, window: &mut Window, cx: &mut Context<Self>) {
+        if !self.delegate.has_children(id, cx) || !self.expanded.insert(id.clone()) {
+            return;
+        }
+
+        if self.delegate.children(Some(id), cx).is_none() {
+            let load = self.delegate.load_children(id, window, cx);
+            let id = id.clone();
+            let task = cx.spawn_in(window, {
+                let id = id.clone();
+                async move |this, cx| {
+                    load.await;
+                    _ = this.update(cx, |this, cx| {
+                        this._load_tasks.remove(&id);
+                        this.refresh(cx);
+                    });
+                }
+            });
+            self._load_tasks.insert(id, task);
+        }
+
+        cx.emit(TreeEvent::Expand(id.clone()));
+        self.refresh(cx);
+    }
+
+    /// Collapse the node.
+    pub fn collapse(&mut self, id: &D::Id, _: &mut Window, cx: &mut Context<Self>) {
+        if !self.expanded.remove(id) {
+            return;
+        }
+
+        cx.emit(TreeEvent::Collapse(id.clone()));
+        self.refresh(cx);
+    }
+
+    /// Toggle the expand state of the node.
+    pub fn toggle_expand(&mut self, id: &D::Id, window: &mut Window, cx: &mut Context<Self>) {
+        if self.is_expanded(id) {
+            self.collapse(id, window, cx);
+        } else {
+            self.expand(id, window, cx);
+        }
+    }
+
+    /// Returns the selected node ids, in display order.
+    pub fn selected_ids(&self) -> Vec<D::Id> {
+        self.entries
+            .iter()
+            .filter(|entry| self.selected.contains(&entry.id))
+            .map(|entry| entry.id.clone())
+            .collect()
+    }
+
+    /// Select the node and scroll to it, the other nodes will be deselected.
+    pub fn set_selected_id(
+        &mut self,
+        id: Option<D::Id>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.selected.clear();
+        self.selected.extend(id.clone());
+        self.anchor = id.clone();
+        self.cursor = id;
+        self.scroll_to_cursor();
+        self.update_selection(window, cx);
+    }
+
+    /// Scroll to the node at the given entry index.
+    pub fn scroll_to_item(&mut self, ix: usize, _: &mut Window, cx: &mut Context<Self>) {
+        self.scroll_handle.scroll_to_item(ix, ScrollStrategy::Top);
+        cx.notify();
+    }
+
+    fn scroll_to_cursor(&mut self) {
+        if let Some(ix) = self.cursor_index() {
+            self.scroll_handle.scroll_to_item(ix, ScrollStrategy::Top);
+        }
+    }
+
+    fn update_selection(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let ids = self.selected_ids();
+        self.delegate.set_selected(&ids, window, cx);
+        cx.emit(TreeEvent::Select(ids));
+        cx.notify();
+    }
+
+    fn select_index(&mut self, ix: usize, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(entry) = self.entries.get(ix) else {
+            return;
+        };
+
+        self.set_selected_id(Some(entry.id.clone()), window, cx);
+    }
+
+    /// Select the range from the anchor to the entry in multi-selection mode.
+    fn select_index_to(&mut self, ix: usize, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(entry) = self.entries.get(ix) else {
+            return;
+        };
+
+        let anchor_ix = self
+            .anchor
+            .as_ref()
+            .and_then(|id| self.index_of(id))
+            .unwrap_or(ix);
+        let range = anchor_ix.min(ix)..=anchor_ix.max(ix);
+
+        self.cursor = Some(entry.id.clone());
+        self.selected = self.entries[range]
+            .iter()
+            .map(|entry| entry.id.clone())
+            .collect();
+        self.scroll_to_cursor();
+        self.update_selection(window, cx);
+    }
+
+    fn toggle_index(&mut self, ix: usize, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(entry) = self.entries.get(ix) else {
+            return;
+        };
+
+        let id = entry.id.clone();
+        if !self.selected.remove(&id) {
+            self.selected.insert(id.clone());
+        }
+        self.anchor = Some(id.clone());
+        self.cursor = Some(id);
+        self.update_selection(window, cx);
+    }
+
+    fn on_action_select_prev(
+        &mut self,
+        _: &SelectPrev,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if self.entries.is_empty() {
+            return;
+        }
+
+        let ix = match self.cursor_index() {
+            Some(ix) if ix > 0 => ix - 1,
+            Some(_) => self.entries.len() - 1,
+            None => 0,
+        };
+        self.select_index(ix, window, cx);
+    }
+
+    fn on_action_select_next(
+        &mut self,
+        _: &SelectNext,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if self.entries.is_empty() {
+            return;
+        }
+
+        let ix = match self.cursor_index() {
+            Some(ix) if ix + 1 < self.entries.len() => ix + 1,
+            _ => 0,
+        };
+        self.select_index(ix, window, cx);
+    }
+
+    fn on_action_select_to_prev(
+        &mut self,
+        _: &SelectToPrev,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        match self.cursor_index() {
+            Some(ix) if self.multi_selectable => {
+                self.select_index_to(ix.saturating_sub(1), window, cx)
+            }
+            _ => self.on_action_select_prev(&SelectPrev, window, cx),
+        }
+    }
+
+    fn on_action_select_to_next(
+        &mut self,
+        _: &SelectToNext,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        match self.cursor_index() {
+            Some(ix) if self.multi_selectable => {
+                let ix = (ix + 1).min(self.entries.len().saturating_sub(1));
+                self.select_index_to(ix, window, cx)
+            }
+            _ => self.on_action_select_next(&SelectNext, window, cx),
+        }
+    }
+
+    fn on_action_select_all(&mut self, _: &SelectAll, window: &mut Window, cx: &mut Context<Self>) {
+        if !self.multi_selectable {
+            cx.propagate();
+            return;
+        }
+
+        self.selected = self.entries.iter().map(|entry| entry.id.clone()).collect();
+        self.update_selection(window, cx);
+    }
+
+    fn on_action_expand(&mut self, _: &Expand, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(ix) = self.cursor_index() else {
+            return;
+        };
+
+        let entry = &self.entries[ix];
+        if entry.has_children && !entry.expanded {
+            let id = entry.id.clone();
+            self.expand(&id, window, cx);
+        } else if entry.expanded
+            && self
+                .entries
+                .get(ix + 1)
+                .is_some_and(|next| next.parent == Some(ix))
+        {
+            // Move to the first child.
+            self.select_index(ix + 1, window, cx);
+        }
+    }
+
+    fn on_action_collapse(&mut self, _: &Collapse, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(ix) = self.cursor_index() else {
+            return;
+        };
+
+        let entry = &self.entries[ix];
+        if entry.expanded {
+            let id = entry.id.clone();
+            self.collapse(&id, window, cx);
+        } else if let Some(parent) = entry.parent {
+            // Move to the parent.
+            self.select_index(parent, window, cx);
+        }
+    }
+
+    fn on_action_confirm(
+        &mut self,
+        confirm: &Confirm,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(id) = self.cursor.clone() else {
+            return;
+        };
+
+        self.delegate.confirm(&id, confirm.secondary, window, cx);
+        cx.emit(TreeEvent::Confirm(id));
+        cx.notify();
+    }
+
+    fn on_action_cancel(&mut self, _: &Cancel, window: &mut Window, cx: &mut Context<Self>) {
+        if self.renaming.take().is_some() {
+            self.focus_handle.focus(window);
+            cx.notify();
+            return;
+        }
+
+        if self.selected.is_empty() {
+            cx.propagate();
+            return;
+        }
+
+        self.set_selected_id(None, window, cx);
+    }
+
+    fn on_action_rename(&mut self, _: &Rename, window: &mut Window, cx: &mut Context<Self>) {
+        if let Some(id) = self.cursor.clone() {
+            self.start_rename(id, window, cx);
+        }
+    }
+
+    /// Start to rename the node with an inline input, if the delegate allows.
+    pub fn start_rename(&mut self, id: D::Id, window: &mut Window, cx: &mut Context<Self>) {
+        if !self.delegate.can_rename(&id, cx) {
+            return;
+        }
+
+        let label = self.delegate.label(&id, cx);
+        let input = cx.new(|cx| InputState::new(window, cx).default_value(label));
+        let _subscription = cx.subscribe_in(&input, window, Self::on_rename_input_event);
+        input.update(cx, |input, cx| input.focus(window, cx));
+
+        self.renaming = Some(RenameState {
+            id,
+            input,
+            _subscription,
+        });
+        cx.notify();
+    }
+
+    fn on_rename_input_event(
+        &mut self,
+        input: &Entity<InputState>,
+        event: &InputEvent,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        match event {
+            InputEvent::PressEnter { .. } => {
+                let Some(state) = self.renaming.take() else {
+                    return;
+                };
+
+                let label = input.read(cx).value().clone();
+                if !label.trim().is_empty() {
+                    self.delegate.rename(&state.id, label, window, cx);
+                    self.refresh(cx);
+                }
+                self.focus_handle.focus(window);
+            }
+            InputEvent::Blur => {
+                self.renaming = None;
+                cx.notify();
+            }
+            _ => {}
+        }
+    }
+
+    /// Select the node by typing the prefix of its label.
+    fn on_key_down(&mut self, event: &KeyDownEvent, window: &mut Window, cx: &mut Context<Self>) {
+        let modifiers = &event.keystroke.modifiers;
+        if self.renaming.is_some() || modifiers.control || modifiers.platform || modifiers.alt {
+            return;
+        }
+        let Some(text) = event.keystroke.key_char.as_ref() else {
+            return;
+        };
+        if text.chars().any(|c| c.is_control()) || text.trim().is_empty() {
+            return;
+        }
+
+        let now = Instant::now();
+        if self
+            .last_find_at
+            .is_none_or(|last| now.duration_since(last) > FIND_TIMEOUT)
+        {
+            self.find_query.clear();
+        }
+        self.last_find_at = Some(now);
+        self.find_query.push_str(text);
+
+        // Typing a new query finds the next node, continue typing refines the current node.
+        let cursor = self.cursor_index();
+        let start = match cursor {
+            Some(ix) if self.find_query.chars().count() == 1 => ix + 1,
+            Some(ix) => ix,
+            None => 0,
+        };
+        // The labels are looked up from the cursor until found, to avoid collecting all of them.
+        if let Some(ix) = find_by_prefix(
+            |ix| {
+                self.entries
+                    .get(ix)
+                    .map(|entry| self.delegate.label(&entry.id, cx))
+            },
+            self.entries.len(),
+            start,
+            &self.find_query,
+        ) {
+            self.select_index(ix, window, cx);
+        }
+        cx.stop_propagation();
+    }
+
+    fn on_item_mouse_down(
+        &mut self,
+        ix: usize,
+        event: &MouseDownEvent,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.focus_handle.focus(window);
+
+        if self.multi_selectable && event.modifiers.shift {
+            self.select_index_to(ix, window, cx);
+            return;
+        }
+        if self.multi_selectable && event.modifiers.secondary() {
+            self.toggle_index(ix, window, cx);
+            return;
+        }
+
+        // Keep the selection if click on a selected node, so that it can be dragged.
+        let id = self.entries[ix].id.clone();
+        if !(self.selected.contains(&id) && self.selected.len() > 1) {
+            self.select_index(ix, window, cx);
+        }
+
+        if event.click_count == 2 {
+            if self.entries[ix].has_children {
+                self.toggle_expand(&id, window, cx);
+            }
+            self.cursor = Some(id);
+            self.on_action_confirm(
+                &Confirm {
+                    secondary: event.modifiers.secondary(),
+                },
+                window,
+                cx,
+            );
+        }
+    }
+
+    /// Return the dragged node ids, all the selected nodes if the dragged node is selected.
+    fn dragged_ids(&self, ix: usize) -> Vec<D::Id> {
+        let Some(entry) = self.entries.get(ix) else {
+            return vec![];
+        };
+
+        if self.selected.contains(&entry.id) {
+            self.selected_ids()
+        } else {
+            vec![entry.id.clone()]
+        }
+    }
+
+    /// Return true if the entry is the node or a descendant of the node at `ancestor`.
+    fn is_descendant_or_self(&self, mut ix: usize, ancestor: &D::Id) -> bool {
+        loop {
+            if &self.entries[ix].id == ancestor {
+                return true;
+            }
+            match self.entries[ix].parent {
+                Some(parent) => ix = parent,
+                None => return false,
+            }
+        }
+    }
+
+    fn on_item_drag_move(
+        &mut self,
+        ix: usize,
+        event: &DragMoveEvent<DragTreeItems>,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if !event.bounds.contains(&event.event.position) {
+            return;
+        }
+
+        let drag = event.drag(cx);
+        if drag.entity_id != cx.entity_id() {
+            return;
+        }
+
+        let ids = self.dragged_ids(drag.ix);
+        let target = &self.entries[ix];
+        let position = DropPosition::from_offset(
+            (event.event.position.y - event.bounds.top()).0,
+            event.bounds.size.height.0,
+            target.has_children,
+        );
+
+        let drop_target = if ids.iter().any(|id| self.is_descendant_or_self(ix, id))
+            || !self.delegate.can_drop(&ids, &target.id, position, cx)
+        {
+            None
+        } else {
+            Some((ix, position))
+        };
+
+        if self.drop_target != drop_target {
+            self.drop_target = drop_target;
+            cx.notify();
+        }
+    }
+
+    fn on_item_drop(&mut self, drag: &DragTreeItems, window: &mut Window, cx: &mut Context<Self>) {
+        let Some((ix, position)) = self.drop_target.take() else {
+            return;
+        };
+        if drag.entity_id != cx.entity_id() {
+            return;
+        }
+
+        let ids = self.dragged_ids(drag.ix);
+        let target = self.entries[ix].id.clone();
+        if position == DropPosition::Inside {
+            self.expanded.insert(target.clone());
+        }
+        self.delegate.on_drop(ids, &target, position, window, cx);
+        self.refresh(cx);
+    }
+
+    fn render_entry(
+        &mut self,
+        ix: usize,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> impl IntoElement {
+        let entry = self.entries[ix].clone();
+        let selected = self.selected.contains(&entry.id);
+        let is_cursor = self.cursor.as_ref() == Some(&entry.id);
+        let focused = self.focus_handle.is_focused(window);
+        let drop_position = self
+            .drop_target
+            .and_then(|(target_ix, position)| (target_ix == ix).then_some(position));
+        let draggable = self.delegate.can_drag(&entry.id, cx);
+        let renaming_input = self
+            .renaming
+            .as_ref()
+            .filter(|state| state.id == entry.id)
+            .map(|state| state.input.clone());
+        let drag = DragTreeItems {
+            entity_id: cx.entity_id(),
+            ix,
+            label: self.delegate.label(&entry.id, cx),
+            count: if selected { self.selected.len() } else { 1 },
+        };
+        let id = entry.id.clone();
+
+        h_flex()
+            .id(ElementId::from(("tree-item", ix)))
+            .w_full()
+            .pl(self.indent * entry.depth as f32)
+            .pr_2()
+            .gap_1()
+            .relative()
+            .when(selected, |this| this.bg(cx.theme().list_active))
+            .when(!selected, |this| {
+                this.hover(|this| this.bg(cx.theme().list_hover))
+            })
+            .when(drop_position == Some(DropPosition::Inside), |this| {
+                this.bg(cx.theme().drop_target)
+            })
+            .when(is_cursor && focused, |this| {
+                this.child(
+                    div()
+                        .absolute()
+                        .top_0()
+                        .left_0()
+                        .right_0()
+                        .bottom_0()
+                        .border_1()
+                        .border_color(cx.theme().list_active_border),
+                )
+            })
+            .when_some(
+                drop_position.filter(|position| *position != DropPosition::Inside),
+                |this, position| {
+                    // The drop indicator line between the nodes.
+                    this.child(
+                        div()
+                            .absolute()
+                            .left(self.indent * entry.depth as f32)
+                            .right_0()
+                            .h(px(2.))
+                            .bg(cx.theme().drag_border)
+                            .map(|this| match position {
+                                DropPosition::Before => this.top_0(),
+                                _ => this.bottom_0(),
+                            }),
+                    )
+                },
+            )
+            .child(
+                div()
+                    .id("disclosure")
+                    .flex_none()
+                    .size_4()
+                    .flex()
+                    .items_center()
+                    .justify_center()
+                    .text_color(cx.theme().muted_foreground)
+                    .when(entry.loading, |this| this.child(Indicator::new().xsmall()))
+                    .when(entry.has_children && !entry.loading, |this| {
+                        this.child(
+                            Icon::new(if entry.expanded {
+                                IconName::ChevronDown
+                            } else {
+                                IconName::ChevronRight
+                            })
+                            .xsmall(),
+                        )
+                        .on_mouse_down(
+                            MouseButton::Left,
+                            cx.listener({
+                                let id = id.clone();
+                                move |this, _, window, cx| {
+                                    cx.stop_propagation();
+                                    this.toggle_expand(&id, window, cx);
+                                }
+                            }),
+                        )
+                    }),
+            )
+            .child(
+                div()
+                    .flex_1()
+                    .overflow_hidden()
+                    .map(|this| match renaming_input {
+                        Some(input) => this.child(TextInput::new(&input).xsmall()),
+                        None => {
+                            this.children(self.delegate.render_item(&entry, selected, window, cx))
+                        }
+                    }),
+            )
+            .on_mouse_down(
+                MouseButton::Left,
+                cx.listener(move |this, ev: &MouseDownEvent, window, cx| {
+                    this.on_item_mouse_down(ix, ev, window, cx);
+                }),
+            )
+            .when(draggable, |this| {
+                this.on_drag(drag, |drag, _, _, cx| {
+                    cx.stop_propagation();
+                    cx.new(|_| drag.clone())
+                })
+            })
+            .on_drag_move(cx.listener(
+                move |this, ev: &DragMoveEvent<DragTreeItems>, window, cx| {
+                    this.on_item_drag_move(ix, ev, window, cx);
+                },
+            ))
+            .on_drop(cx.listener(|this, drag: &DragTreeItems, window, cx| {
+                this.on_item_drop(drag, window, cx);
+            }))
+    }
+}
+
+impl<D> Focusable for Tree<D>
+where
+    D: TreeDelegate,
+{
+    fn focus_handle(&self, _: &App) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl<D> EventEmitter<TreeEvent<D::Id>> for Tree<D> where D: TreeDelegate {}
+
+impl<D> Render for Tree<D>
+where
+    D: TreeDelegate,
+{
+    fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        if !cx.has_active_drag() {
+            self.drop_target = None;
+        }
+
+        let entries_count = self.entries.len();
+
+        v_flex()
+            .key_context(CONTEXT)
+            .id("tree")
+            .track_focus(&self.focus_handle)
+            .size_full()
+            .relative()
+            .overflow_hidden()
+            .on_action(cx.listener(Self::on_action_select_prev))
+            .on_action(cx.listener(Self::on_action_select_next))
+            .on_action(cx.listener(Self::on_action_select_to_prev))
+            .on_action(cx.listener(Self::on_action_select_to_next))
+            .on_action(cx.listener(Self::on_action_select_all))
+            .on_action(cx.listener(Self::on_action_expand))
+            .on_action(cx.listener(Self::on_action_collapse))
+            .on_action(cx.listener(Self::on_action_confirm))
+            .on_action(cx.listener(Self::on_action_cancel))
+            .on_action(cx.listener(Self::on_action_rename))
+            .on_key_down(cx.listener(Self::on_key_down))
+            .child(
+                uniform_list(
+                    "tree-entries",
+                    entries_count,
+                    cx.processor(move |tree, visible_range: Range<usize>, window, cx| {
+                        visible_range
+                            .map(|ix| tree.render_entry(ix, window, cx))
+                            .collect::<Vec<_>>()
+                    }),
+                )
+                .flex_grow()
+                .with_sizing_behavior(ListSizingBehavior::Auto)
+                .track_scroll(self.scroll_handle.clone()),
+            )
+            .child(Scrollbar::uniform_scroll(
+                &self.scroll_state,
+                &self.scroll_handle,
+            ))
+    }
+}
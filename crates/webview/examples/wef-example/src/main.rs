@@ -16,40 +16,51 @@ use gpui_webview::{
 };
 use serde::Serialize;
 
+fn func_registry(cx: &App) -> FuncRegistry {
+    let background_executor = cx.background_executor().clone();
+
+    FuncRegistry::builder()
+        .with_spawner(move |fut| {
+            background_executor.spawn(fut).detach();
+        })
+        .register("toUppercase", |value: String| value.to_uppercase())
+        .register("addInt", |a: i32, b: i32| a + b)
+        .register("parseInt", |value: String| value.parse::<i32>())
+        .register_async("sleep", |millis: u64| async move {
+            Timer::after(Duration::from_millis(millis)).await;
+            "ok"
+        })
+        .register_stream("countdown", |from: u32| {
+            futures_util::stream::unfold(from, |n| async move {
+                Timer::after(Duration::from_secs(1)).await;
+                n.checked_sub(1).map(|n| (n, n))
+            })
+        })
+        .register("emitMessage", |frame: Frame| {
+            #[derive(Debug, Serialize)]
+            struct Message {
+                event: String,
+                data: String,
+            }
+
+            frame.emit(Message {
+                event: "custom".to_string(),
+                data: "ok".to_string(),
+            });
+        })
+        .on_event("ping", |frame, message: String| {
+            frame.emit_event("pong", message);
+        })
+        .build()
+}
+
 struct Main {
     address_state: Entity<InputState>,
     webview: Entity<WebView>,
 }
 
 impl Main {
-    fn new(window: &mut Window, cx: &mut App) -> Entity<Self> {
-        let background_executor = cx.background_executor().clone();
-
-        let func_registry = FuncRegistry::builder()
-            .with_spawner(move |fut| {
-                background_executor.spawn(fut).detach();
-            })
-            .register("toUppercase", |value: String| value.to_uppercase())
-            .register("addInt", |a: i32, b: i32| a + b)
-            .register("parseInt", |value: String| value.parse::<i32>())
-            .register_async("sleep", |millis: u64| async move {
-                Timer::after(Duration::from_millis(millis)).await;
-                "ok"
-            })
-            .register("emit", |frame: Frame| {
-                #[derive(Debug, Serialize)]
-                struct Message {
-                    event: String,
-                    data: String,
-                }
-
-                frame.emit(Message {
-                    event: "custom".to_string(),
-                    data: "ok".to_string(),
-                });
-            })
-            .build();
-
+    fn new(func_registry: FuncRegistry, window: &mut Window, cx: &mut App) -> Entity<Self> {
         cx.new(|cx| {
             let url = "https://www.google.com";
 
@@ -118,6 +129,19 @@ fn run() {
 
         gpui_component::init(cx);
 
+        let func_registry = func_registry(cx);
+        match func_registry.export_types_from_env() {
+            Ok(true) => {
+                cx.quit();
+                return;
+            }
+            Ok(false) => {}
+            Err(err) => {
+                eprintln!("Failed to export the types: {}", err);
+                std::process::exit(1);
+            }
+        }
+
         let bounds = Bounds::centered(None, size(px(500.), px(500.0)), cx);
         cx.open_window(
             WindowOptions {
@@ -125,7 +149,7 @@ fn run() {
                 ..Default::default()
             },
             |window, cx| {
-                let main = Main::new(window, cx);
+                let main = Main::new(func_registry, window, cx);
                 cx.new(|cx| Root::new(main.into(), window, cx))
             },
         )
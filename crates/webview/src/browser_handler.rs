@@ -8,7 +8,7 @@ use gpui_component::{
 };
 use wef::{
     BrowserHandler, ContextMenuParams, CursorInfo, CursorType, DirtyRects, Frame, ImageBuffer,
-    JsDialogCallback, JsDialogType, LogSeverity, LogicalUnit, PaintElementType, Point, Rect,
+    JsDialogCallback, JsDialogType, LogSeverity, LogicalUnit, PaintElementType, Point, Rect, Value,
 };
 
 use crate::{
@@ -216,6 +216,18 @@ impl BrowserHandler for WebViewHandler {
         }
     }
 
+    fn on_js_event(&mut self, frame: Frame, name: &str, payload: &Value) {
+        if let Some(entity) = self.entity.upgrade() {
+            _ = self.cx.update_entity(&entity, |_, cx| {
+                cx.emit(JsEvent {
+                    frame,
+                    name: name.to_string(),
+                    payload: payload.clone(),
+                });
+            });
+        }
+    }
+
     fn on_cursor_changed(
         &mut self,
         cursor_type: CursorType,
@@ -1,6 +1,6 @@
 //! Events for the WebView.
 
-use wef::{Frame, LogSeverity};
+use wef::{Frame, LogSeverity, Value};
 
 /// Emitted when the browser is created.
 #[derive(Debug)]
@@ -98,3 +98,15 @@ pub struct LoadErrorEvent {
     /// The uRL that failed to load.
     pub failed_url: String,
 }
+
+/// Emitted when JavaScript emits a named event with `jsBridge.emit(name,
+/// payload)`.
+#[derive(Debug)]
+pub struct JsEvent {
+    /// The frame object.
+    pub frame: Frame,
+    /// The name of the event.
+    pub name: String,
+    /// The payload of the event.
+    pub payload: Value,
+}
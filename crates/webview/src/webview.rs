@@ -346,5 +346,6 @@ impl_emiter!(
     LoadingStateChangedEvent,
     LoadStartEvent,
     LoadEndEvent,
-    LoadErrorEvent
+    LoadErrorEvent,
+    JsEvent
 );
@@ -62,6 +62,7 @@ struct BrowserCallbacks {
                        const char* default_prompt_text,
                        CefRefPtr<CefJSDialogCallback>* callback);
   void (*on_query)(
-      void* userdata, void* frame, const char* payload,
+      void* userdata, void* frame, int64_t query_id, const char* payload,
       CefRefPtr<CefMessageRouterBrowserSide::Handler::Callback>* callback);
+  void (*on_query_canceled)(void* userdata, int64_t query_id);
 };
\ No newline at end of file
@@ -480,8 +480,18 @@ bool WefClient::OnQuery(
 
   state_->callbacks_target.call(
       [&](const BrowserCallbacks& callbacks, void* userdata) {
-        return callbacks.on_query(userdata, new WefFrame{frame},
+        return callbacks.on_query(userdata, new WefFrame{frame}, query_id,
                                   request_str.c_str(), callback_ptr);
       });
   return true;
 }
+
+void WefClient::OnQueryCanceled(CefRefPtr<CefBrowser> browser,
+                                CefRefPtr<CefFrame> frame, int64_t query_id) {
+  DCHECK(CefCurrentlyOn(TID_UI));
+
+  state_->callbacks_target.call(
+      [&](const BrowserCallbacks& callbacks, void* userdata) {
+        callbacks.on_query_canceled(userdata, query_id);
+      });
+}
@@ -288,4 +288,6 @@ class WefClient : public CefClient,
                int64_t query_id, const CefString& request, bool persistent,
                CefRefPtr<CefMessageRouterBrowserSide::Handler::Callback>
                    callback) override;
+  void OnQueryCanceled(CefRefPtr<CefBrowser> browser,
+                       CefRefPtr<CefFrame> frame, int64_t query_id) override;
 };
@@ -7,6 +7,10 @@
 extern "C" {
 void wef_frame_destroy(WefFrame* frame) { delete frame; }
 
+WefFrame* wef_frame_clone(WefFrame* frame) {
+  return new WefFrame{frame->frame};
+}
+
 bool wef_frame_is_valid(WefFrame* frame) { return frame->frame->IsValid(); }
 
 bool wef_frame_is_main(WefFrame* frame) { return frame->frame->IsMain(); }
@@ -13,8 +13,8 @@ void wef_query_callback_success(
 
 void wef_query_callback_failure(
     CefRefPtr<CefMessageRouterBrowserSide::Handler::Callback>* callback,
-    const char* error) {
-  (*callback)->Failure(-1, error);
+    int error_code, const char* error) {
+  (*callback)->Failure(error_code, error);
 }
 
 void wef_query_callback_destroy(
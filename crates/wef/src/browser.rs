@@ -1,13 +1,19 @@
-use std::{ffi::CString, fmt};
+use std::{
+    ffi::CString,
+    fmt,
+    sync::{Mutex, Weak},
+};
 
 use crate::{
     BrowserBuilder, Frame, KeyCode, KeyModifier, LogicalUnit, MouseButton, PhysicalUnit, Point,
-    Size, ffi::*,
+    Size, ffi::*, js_eval::PendingEvals,
 };
 
 /// A browser instance.
 pub struct Browser {
     pub(crate) wef_browser: *mut wef_browser_t,
+    /// The pending evaluations of the frames, owned by the browser state.
+    pub(crate) pending_evals: Weak<Mutex<PendingEvals>>,
 }
 
 impl fmt::Debug for Browser {
@@ -158,27 +164,27 @@ impl Browser {
     /// Returns the main (top-level) frame for the browser.
     pub fn main_frame(&self) -> Option<Frame> {
         let frame = unsafe { wef_browser_get_main_frame(self.wef_browser) };
-        (!frame.is_null()).then_some(Frame(frame))
+        (!frame.is_null()).then_some(Frame(frame, self.pending_evals.clone()))
     }
 
     /// Returns the focused frame for the browser.
     pub fn focused_frame(&self) -> Option<Frame> {
         let frame = unsafe { wef_browser_get_focused_frame(self.wef_browser) };
-        (!frame.is_null()).then_some(Frame(frame))
+        (!frame.is_null()).then_some(Frame(frame, self.pending_evals.clone()))
     }
 
     /// Returns a frame by its name.
     pub fn frame_by_name(&self, name: &str) -> Option<Frame> {
         let c_name = CString::new(name).unwrap();
         let frame = unsafe { wef_browser_get_frame_by_name(self.wef_browser, c_name.as_ptr()) };
-        (!frame.is_null()).then_some(Frame(frame))
+        (!frame.is_null()).then_some(Frame(frame, self.pending_evals.clone()))
     }
 
     /// Returns a frame by its identifier.
     pub fn frame_by_identifier(&self, id: &str) -> Option<Frame> {
         let c_id = CString::new(id).unwrap();
         let frame = unsafe { wef_browser_get_frame_by_identifier(self.wef_browser, c_id.as_ptr()) };
-        (!frame.is_null()).then_some(Frame(frame))
+        (!frame.is_null()).then_some(Frame(frame, self.pending_evals.clone()))
     }
 
     /// Returns `true` if the browser's audio is muted.
@@ -12,7 +12,7 @@ use crate::{
     ContextMenuParams, ContextMenuTypeFlags, CursorType, DirtyRects, FileDialogCallback,
     FileDialogMode, Frame, JsDialogCallback, JsDialogType, LogicalUnit, Point, Rect, Size,
     builder::BrowserState, cursor::CursorInfo, ffi::*, file_dialog::AcceptFilter,
-    query::QueryCallback,
+    js_eval::JsException, query::QueryCallback,
 };
 
 /// A type alias for the image buffer.
@@ -217,6 +217,10 @@ pub trait BrowserHandler {
     ) -> bool {
         false
     }
+    /// Called when JavaScript emits a named event with
+    /// `jsBridge.emit(name, payload)`, after the handlers registered with
+    /// [`FuncRegistryBuilder::on_event`](crate::FuncRegistryBuilder::on_event).
+    fn on_js_event(&mut self, frame: Frame, name: &str, payload: &Value) {}
 }
 
 impl BrowserHandler for () {}
@@ -281,7 +285,7 @@ pub(crate) extern "C" fn on_address_changed<T: BrowserHandler>(
 ) {
     unsafe {
         let state = &mut *(userdata as *mut BrowserState<T>);
-        let frame = Frame(frame);
+        let frame = state.frame(frame);
         let url = CStr::from_ptr(url).to_string_lossy();
         state.handler.on_address_changed(frame, &url);
     }
@@ -427,7 +431,7 @@ pub(crate) extern "C" fn on_load_start<T: BrowserHandler>(
 ) {
     unsafe {
         let state = &mut *(userdata as *mut BrowserState<T>);
-        let frame = Frame(frame);
+        let frame = state.frame(frame);
         state.handler.on_load_start(frame);
     }
 }
@@ -438,7 +442,7 @@ pub(crate) extern "C" fn on_load_end<T: BrowserHandler>(
 ) {
     unsafe {
         let state = &mut *(userdata as *mut BrowserState<T>);
-        let frame = Frame(frame);
+        let frame = state.frame(frame);
         state.handler.on_load_end(frame);
     }
 }
@@ -451,7 +455,7 @@ pub(crate) extern "C" fn on_load_error<T: BrowserHandler>(
 ) {
     unsafe {
         let state = &mut *(userdata as *mut BrowserState<T>);
-        let frame = Frame(frame);
+        let frame = state.frame(frame);
         let error_text = CStr::from_ptr(error_text).to_string_lossy();
         let failed_url = CStr::from_ptr(failed_url).to_string_lossy();
         state.handler.on_load_error(frame, &error_text, &failed_url);
@@ -544,7 +548,7 @@ pub(crate) extern "C" fn on_context_menu<T: BrowserHandler>(
 ) {
     unsafe {
         let state = &mut *(userdata as *mut BrowserState<T>);
-        let frame = Frame(frame);
+        let frame = state.frame(frame);
         let link_url = (!(*params).link_url.is_null())
             .then(|| CStr::from_ptr((*params).link_url).to_string_lossy());
         let unfiltered_link_url = (!(*params).unfiltered_link_url.is_null())
@@ -636,19 +640,35 @@ pub(crate) extern "C" fn on_js_dialog<T: BrowserHandler>(
 pub(crate) extern "C" fn on_query<T: BrowserHandler>(
     userdata: *mut c_void,
     frame: *mut wef_frame_t,
+    query_id: i64,
     query: *const c_char,
     callback: *mut wef_query_callback_t,
 ) {
     #[derive(Debug, Deserialize)]
-    struct Request {
-        method: String,
-        args: Vec<Value>,
+    #[serde(untagged)]
+    enum Request {
+        Call {
+            method: String,
+            args: Vec<Value>,
+        },
+        Event {
+            event: String,
+            #[serde(default)]
+            payload: Value,
+        },
+        EvalResult {
+            #[serde(rename = "evalId")]
+            eval_id: u64,
+            #[serde(default)]
+            result: Value,
+            #[serde(default)]
+            error: Option<JsException>,
+        },
     }
 
-    let frame = Frame(frame);
-
     unsafe {
         let state = &mut *(userdata as *mut BrowserState<T>);
+        let frame = state.frame(frame);
         let Some(request) = CStr::from_ptr(query)
             .to_str()
             .ok()
@@ -657,11 +677,43 @@ pub(crate) extern "C" fn on_query<T: BrowserHandler>(
             return;
         };
 
-        state.func_registry.call(
-            frame,
-            &request.method,
-            request.args,
-            QueryCallback::new(callback),
-        )
+        match request {
+            Request::Call { method, args } => state.func_registry.call(
+                frame,
+                &method,
+                args,
+                QueryCallback::new(callback),
+                &state.pending_calls,
+                query_id,
+            ),
+            Request::Event { event, payload } => {
+                let res = state.func_registry.emit_event(&frame, &event, &payload);
+                state.handler.on_js_event(frame, &event, &payload);
+                QueryCallback::new(callback).result(res.map(|_| Value::Null));
+            }
+            Request::EvalResult {
+                eval_id,
+                result,
+                error,
+            } => {
+                state.pending_evals.lock().unwrap().resolve(
+                    eval_id,
+                    frame.identifier().as_deref(),
+                    result,
+                    error,
+                );
+                QueryCallback::new(callback).result(Ok(Value::Null));
+            }
+        }
+    }
+}
+
+pub(crate) extern "C" fn on_query_canceled<T: BrowserHandler>(
+    userdata: *mut c_void,
+    query_id: i64,
+) {
+    unsafe {
+        let state = &mut *(userdata as *mut BrowserState<T>);
+        state.pending_calls.cancel(query_id);
     }
 }
@@ -1,8 +1,14 @@
-use std::ffi::{CString, c_void};
+use std::{
+    ffi::{CString, c_void},
+    sync::{Arc, Mutex},
+};
 
 use raw_window_handle::RawWindowHandle;
 
-use crate::{Browser, BrowserHandler, FuncRegistry, ffi::*};
+use crate::{
+    Browser, BrowserHandler, Frame, FuncRegistry, ffi::*, func_registry::PendingCalls,
+    js_eval::PendingEvals,
+};
 
 /// A builder for creating a browser instance.
 pub struct BrowserBuilder<T> {
@@ -34,6 +40,15 @@ impl BrowserBuilder<()> {
 pub(crate) struct BrowserState<T> {
     pub(crate) handler: T,
     pub(crate) func_registry: FuncRegistry,
+    pub(crate) pending_calls: PendingCalls,
+    pub(crate) pending_evals: Arc<Mutex<PendingEvals>>,
+}
+
+impl<T> BrowserState<T> {
+    /// Wraps the frame of the browser, to evaluate JavaScript in it.
+    pub(crate) fn frame(&self, frame: *mut wef_frame_t) -> Frame {
+        Frame(frame, Arc::downgrade(&self.pending_evals))
+    }
 }
 
 impl<T> BrowserBuilder<T>
@@ -146,10 +161,15 @@ where
             on_find_result: crate::browser_handler::on_find_result::<T>,
             on_js_dialog: crate::browser_handler::on_js_dialog::<T>,
             on_query: crate::browser_handler::on_query::<T>,
+            on_query_canceled: crate::browser_handler::on_query_canceled::<T>,
         };
+        let pending_evals = Arc::new(Mutex::new(PendingEvals::default()));
+        let weak_pending_evals = Arc::downgrade(&pending_evals);
         let handler = Box::into_raw(Box::new(BrowserState {
             handler: self.handler,
             func_registry: self.func_registry.clone(),
+            pending_calls: PendingCalls::default(),
+            pending_evals,
         }));
         let parent_window_handle: *const c_void = match self.parent {
             Some(RawWindowHandle::Win32(handle)) => handle.hwnd.get() as *const c_void,
@@ -180,6 +200,7 @@ where
         unsafe {
             Browser {
                 wef_browser: wef_browser_create(&settings),
+                pending_evals: weak_pending_evals,
             }
         }
     }
@@ -110,7 +110,8 @@ pub(crate) struct CBrowserCallbacks {
         *mut wef_js_dialog_callback_t,
     ) -> bool,
     pub(crate) on_query:
-        extern "C" fn(*mut c_void, *mut wef_frame_t, *const c_char, *mut wef_query_callback_t),
+        extern "C" fn(*mut c_void, *mut wef_frame_t, i64, *const c_char, *mut wef_query_callback_t),
+    pub(crate) on_query_canceled: extern "C" fn(*mut c_void, i64),
 }
 
 #[inline]
@@ -249,6 +250,8 @@ unsafe extern "C" {
 
     pub(crate) unsafe fn wef_frame_destroy(frame: *mut wef_frame_t);
 
+    pub(crate) unsafe fn wef_frame_clone(frame: *mut wef_frame_t) -> *mut wef_frame_t;
+
     pub(crate) unsafe fn wef_frame_is_valid(frame: *mut wef_frame_t) -> bool;
 
     pub(crate) unsafe fn wef_frame_is_main(frame: *mut wef_frame_t) -> bool;
@@ -330,6 +333,7 @@ unsafe extern "C" {
 
     pub(crate) unsafe fn wef_query_callback_failure(
         callback: *mut wef_query_callback_t,
+        error_code: i32,
         error: *const c_char,
     );
 
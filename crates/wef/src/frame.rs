@@ -1,14 +1,24 @@
 use std::{
     ffi::{CStr, c_char, c_void},
     fmt,
+    sync::{Arc, Mutex, Weak},
+    time::Duration,
 };
 
-use serde::Serialize;
+use futures_util::future::{Either, select};
+use serde::{Serialize, de::DeserializeOwned};
 
-use crate::ffi::*;
+use crate::{
+    ffi::*,
+    js_eval::{DEFAULT_EVAL_TIMEOUT, JsError, PendingEvalGuard, PendingEvals},
+};
 
 /// A frame in the browser window.
-pub struct Frame(pub(crate) *mut wef_frame_t);
+pub struct Frame(
+    pub(crate) *mut wef_frame_t,
+    /// The pending evaluations of the browser of the frame.
+    pub(crate) Weak<Mutex<PendingEvals>>,
+);
 
 unsafe impl Send for Frame {}
 unsafe impl Sync for Frame {}
@@ -29,6 +39,12 @@ impl Drop for Frame {
     }
 }
 
+impl Clone for Frame {
+    fn clone(&self) -> Self {
+        Frame(unsafe { wef_frame_clone(self.0) }, self.1.clone())
+    }
+}
+
 impl Frame {
     /// Returns `true` if this object is currently attached to a valid frame.
     pub fn is_valid(&self) -> bool {
@@ -79,7 +95,7 @@ impl Frame {
     pub fn parent(&self) -> Option<Frame> {
         let frame = unsafe { wef_frame_parent(self.0) };
         if !frame.is_null() {
-            Some(Frame(frame))
+            Some(Frame(frame, self.1.clone()))
         } else {
             None
         }
@@ -134,6 +150,69 @@ impl Frame {
         unsafe { wef_frame_execute_javascript(self.0, c_script.as_ptr()) };
     }
 
+    /// Evaluates the JavaScript in this frame and returns the result.
+    ///
+    /// The script is evaluated with the global `eval`, so the result is the
+    /// value of the last expression, and the promise is awaited. The result is
+    /// serialized to JSON and deserialized to `T`.
+    ///
+    /// Returns [`JsError::Exception`] if the script throws,
+    /// [`JsError::Timeout`] if the result is not returned within
+    /// [`DEFAULT_EVAL_TIMEOUT`], [`JsError::Cancelled`] if the browser is
+    /// closed, or [`JsError::InvalidFrame`] if the frame doesn't exist.
+    ///
+    /// ```rust, ignore
+    /// let title: String = frame.eval("document.title").await?;
+    /// ```
+    pub fn eval<T>(&self, script: &str) -> impl Future<Output = Result<T, JsError>> + Send + 'static
+    where
+        T: DeserializeOwned,
+    {
+        self.eval_with_timeout(script, DEFAULT_EVAL_TIMEOUT)
+    }
+
+    /// Evaluates the JavaScript in this frame and returns the result, with the
+    /// given timeout.
+    ///
+    /// See [`Frame::eval`].
+    pub fn eval_with_timeout<T>(
+        &self,
+        script: &str,
+        timeout: Duration,
+    ) -> impl Future<Output = Result<T, JsError>> + Send + 'static
+    where
+        T: DeserializeOwned,
+    {
+        let registered = match (self.identifier(), self.1.upgrade()) {
+            (None, _) => Err(JsError::InvalidFrame),
+            (_, None) => Err(JsError::Cancelled),
+            (Some(frame_id), Some(evals)) => {
+                let (id, rx) = evals.lock().unwrap().register(Some(frame_id));
+                self.execute_javascript(&format!(
+                    "window.jsBridge.__internal.evalScript({}, {})",
+                    id,
+                    serde_json::to_string(script).unwrap()
+                ));
+                let guard = PendingEvalGuard {
+                    evals: Arc::downgrade(&evals),
+                    id,
+                };
+                Ok((guard, rx))
+            }
+        };
+
+        async move {
+            let (_guard, rx) = registered?;
+            let delay = futures_timer::Delay::new(timeout);
+            let value = match select(rx, delay).await {
+                Either::Left((Ok(res), _)) => res?,
+                Either::Left((Err(_), _)) => return Err(JsError::Cancelled),
+                Either::Right(_) => return Err(JsError::Timeout(timeout)),
+            };
+            Ok(serde_json::from_value(value)?)
+        }
+    }
+
     /// Emits a message to the JavaScript side.
     pub fn emit(&self, message: impl Serialize) {
         let Ok(message) = serde_json::to_string(&message) else {
@@ -141,6 +220,20 @@ impl Frame {
         };
         self.execute_javascript(&format!("window.jsBridge.__internal.emit({})", message));
     }
+
+    /// Emits a named event to the JavaScript listeners registered with
+    /// `jsBridge.on(name, callback)`.
+    pub fn emit_event(&self, name: &str, payload: impl Serialize) {
+        let (Ok(name), Ok(payload)) =
+            (serde_json::to_string(name), serde_json::to_string(&payload))
+        else {
+            return;
+        };
+        self.execute_javascript(&format!(
+            "window.jsBridge.__internal.emitEvent({}, {})",
+            name, payload
+        ));
+    }
 }
 
 extern "C" fn get_string_callback(output: *mut c_void, value: *const c_char) {
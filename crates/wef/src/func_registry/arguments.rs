@@ -0,0 +1,147 @@
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+
+use crate::func_registry::CallFunctionError;
+
+/// The arguments of a function call from JavaScript.
+///
+/// The trailing arguments can be omitted if they can be deserialized from
+/// `null`, e.g.: `Option<T>`.
+pub(crate) struct Arguments {
+    args: std::vec::IntoIter<Value>,
+    index: usize,
+    expected: usize,
+    actual: usize,
+}
+
+impl Arguments {
+    /// Creates the arguments, returns an error if there are more arguments
+    /// than expected.
+    pub(crate) fn new(args: Vec<Value>, expected: usize) -> Result<Self, CallFunctionError> {
+        if args.len() > expected {
+            return Err(CallFunctionError::InvalidNumberOfArguments {
+                expected,
+                actual: args.len(),
+            });
+        }
+
+        Ok(Self {
+            actual: args.len(),
+            args: args.into_iter(),
+            index: 0,
+            expected,
+        })
+    }
+
+    /// Deserializes the next argument, the error names the argument and the
+    /// path of the offending field.
+    pub(crate) fn next<T: DeserializeOwned>(&mut self) -> Result<T, CallFunctionError> {
+        let index = self.index;
+        self.index += 1;
+
+        match self.args.next() {
+            Some(value) => serde_path_to_error::deserialize(value).map_err(|err| {
+                let path = err.path().to_string();
+                let error = err.into_inner().to_string();
+                CallFunctionError::InvalidArgument {
+                    arg_name: format!("arg{}", index),
+                    error: if path == "." {
+                        error
+                    } else {
+                        format!("field `{}`: {}", path, error)
+                    },
+                }
+            }),
+            None => serde_json::from_value(Value::Null).map_err(|_| {
+                CallFunctionError::InvalidNumberOfArguments {
+                    expected: self.expected,
+                    actual: self.actual,
+                }
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde::Deserialize;
+    use serde_json::json;
+
+    use super::*;
+
+    #[derive(Debug, Deserialize, PartialEq)]
+    struct User {
+        name: String,
+        address: Address,
+    }
+
+    #[derive(Debug, Deserialize, PartialEq)]
+    struct Address {
+        zip: u32,
+    }
+
+    #[test]
+    fn test_optional_arguments() {
+        let mut args = Arguments::new(vec![json!(1)], 3).unwrap();
+        assert_eq!(args.next::<i32>().unwrap(), 1);
+        assert_eq!(args.next::<Option<i32>>().unwrap(), None);
+        assert_eq!(args.next::<Option<String>>().unwrap(), None);
+
+        let mut args = Arguments::new(vec![json!(1)], 2).unwrap();
+        assert_eq!(args.next::<i32>().unwrap(), 1);
+        assert!(matches!(
+            args.next::<i32>(),
+            Err(CallFunctionError::InvalidNumberOfArguments {
+                expected: 2,
+                actual: 1
+            })
+        ));
+
+        assert!(matches!(
+            Arguments::new(vec![json!(1), json!(2)], 1),
+            Err(CallFunctionError::InvalidNumberOfArguments {
+                expected: 1,
+                actual: 2
+            })
+        ));
+    }
+
+    #[test]
+    fn test_object_argument() {
+        let mut args = Arguments::new(
+            vec![json!(true), json!({ "name": "a", "address": { "zip": 1 } })],
+            2,
+        )
+        .unwrap();
+        assert!(args.next::<bool>().unwrap());
+        assert_eq!(
+            args.next::<User>().unwrap(),
+            User {
+                name: "a".to_string(),
+                address: Address { zip: 1 }
+            }
+        );
+    }
+
+    #[test]
+    fn test_argument_errors() {
+        let error = |value: Value| {
+            let mut args = Arguments::new(vec![json!(1), value], 2).unwrap();
+            args.next::<i32>().unwrap();
+            args.next::<User>().unwrap_err().to_string()
+        };
+
+        assert_eq!(
+            error(json!({ "name": "a", "address": { "zip": "x" } })),
+            "Invalid argument arg1: field `address.zip`: invalid type: string \"x\", expected u32"
+        );
+        assert_eq!(
+            error(json!({ "address": { "zip": 1 } })),
+            "Invalid argument arg1: missing field `name`"
+        );
+        assert_eq!(
+            error(json!({ "name": "a", "address": {} })),
+            "Invalid argument arg1: field `address`: missing field `zip`"
+        );
+    }
+}
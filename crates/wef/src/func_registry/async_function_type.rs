@@ -1,9 +1,12 @@
+use schemars::{JsonSchema, SchemaGenerator};
 use serde::de::DeserializeOwned;
 use serde_json::Value;
 
 use crate::{
     Frame,
-    func_registry::{CallFunctionError, into_result::IntoFunctionResult},
+    func_registry::{
+        CallFunctionError, FunctionSignature, arguments::Arguments, into_result::IntoFunctionResult,
+    },
 };
 
 /// Represents a async function type that can be called from JavaScript.
@@ -11,6 +14,10 @@ pub trait AsyncFunctionType<S, R> {
     /// Number of arguments.
     const NUM_ARGUMENTS: usize;
 
+    /// Returns the JSON schemas of the arguments and the result, the [`Frame`]
+    /// argument is not included.
+    fn signature(generator: &mut SchemaGenerator) -> FunctionSignature;
+
     /// Calls the function with the given arguments.
     fn call(
         &self,
@@ -26,40 +33,31 @@ macro_rules! impl_async_function_types {
             F: Fn($($name),*) -> Fut + Clone + Send + Sync + 'static,
             Fut: Future<Output = Ret> + Send + 'static,
             Ret: IntoFunctionResult<R> + Send,
-            $($name: DeserializeOwned + Send,)*
+            R: JsonSchema,
+            $($name: DeserializeOwned + JsonSchema + Send,)*
         {
             const NUM_ARGUMENTS: usize = tuple_len::tuple_len!(($($name,)*));
 
+            fn signature(generator: &mut SchemaGenerator) -> FunctionSignature {
+                FunctionSignature {
+                    arguments: vec![$(generator.subschema_for::<$name>()),*],
+                    result: generator.subschema_for::<R>(),
+                }
+            }
+
             fn call(&self, _frame: Frame, args: Vec<Value>) -> impl Future<Output = Result<Value, CallFunctionError>> + Send + 'static {
                 let f = self.clone();
                 async move {
-                    let expected_args = tuple_len::tuple_len!(($($name,)*));
-
-                    if args.len() != expected_args {
-                        return Err(CallFunctionError::InvalidNumberOfArguments {
-                            expected: expected_args,
-                            actual: args.len(),
-                        });
-                    }
-
-                    let mut args = args;
-                    args.reverse();
+                    #[allow(unused_mut, unused_variables)]
+                    let mut args = Arguments::new(args, tuple_len::tuple_len!(($($name,)*)))?;
 
                     $(
                         #[allow(non_snake_case)]
-                        let $name: $name = serde_json::from_value(args.pop().unwrap()).map_err(|e| {
-                            CallFunctionError::InvalidArgument {
-                                arg_name: "A1".to_string(),
-                                error: e.to_string(),
-                            }
-                        })?;
+                        let $name: $name = args.next()?;
                     )*
 
 
-                    match (f($($name),*).await).into_function_result() {
-                        Ok(value) => Ok(serde_json::to_value(value).unwrap()),
-                        Err(e) => Err(CallFunctionError::Other(e.to_string())),
-                    }
+                    f($($name),*).await.into_function_result()
                 }
             }
         }
@@ -69,40 +67,31 @@ macro_rules! impl_async_function_types {
             F: Fn(Frame, $($name),*) -> Fut + Clone + Send + Sync + 'static,
             Fut: Future<Output = Ret> + Send + 'static,
             Ret: IntoFunctionResult<R> + Send,
-            $($name: DeserializeOwned + Send,)*
+            R: JsonSchema,
+            $($name: DeserializeOwned + JsonSchema + Send,)*
         {
             const NUM_ARGUMENTS: usize = tuple_len::tuple_len!(($($name,)*));
 
+            fn signature(generator: &mut SchemaGenerator) -> FunctionSignature {
+                FunctionSignature {
+                    arguments: vec![$(generator.subschema_for::<$name>()),*],
+                    result: generator.subschema_for::<R>(),
+                }
+            }
+
             fn call(&self, frame: Frame, args: Vec<Value>) -> impl Future<Output = Result<Value, CallFunctionError>> + Send + 'static {
                 let f = self.clone();
                 async move {
-                    let expected_args = tuple_len::tuple_len!(($($name,)*));
-
-                    if args.len() != expected_args {
-                        return Err(CallFunctionError::InvalidNumberOfArguments {
-                            expected: expected_args,
-                            actual: args.len(),
-                        });
-                    }
-
-                    let mut args = args;
-                    args.reverse();
+                    #[allow(unused_mut, unused_variables)]
+                    let mut args = Arguments::new(args, tuple_len::tuple_len!(($($name,)*)))?;
 
                     $(
                         #[allow(non_snake_case)]
-                        let $name: $name = serde_json::from_value(args.pop().unwrap()).map_err(|e| {
-                            CallFunctionError::InvalidArgument {
-                                arg_name: "A1".to_string(),
-                                error: e.to_string(),
-                            }
-                        })?;
+                        let $name: $name = args.next()?;
                     )*
 
 
-                    match (f(frame, $($name),*).await).into_function_result() {
-                        Ok(value) => Ok(serde_json::to_value(value).unwrap()),
-                        Err(e) => Err(CallFunctionError::Other(e.to_string())),
-                    }
+                    f(frame, $($name),*).await.into_function_result()
                 }
             }
         }
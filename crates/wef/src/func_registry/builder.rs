@@ -1,31 +1,102 @@
 use std::{collections::HashMap, sync::Arc};
 
 use futures_util::future::BoxFuture;
+use schemars::JsonSchema;
+use serde::de::DeserializeOwned;
 
 use crate::{
-    AsyncFunctionType, FuncRegistry, FunctionType,
-    func_registry::dyn_wrapper::{DynAsyncFunctionWrapper, DynFunctionType, DynFunctionWrapper},
+    AsyncFunctionType, Frame, FuncRegistry, FunctionType, StreamFunctionType,
+    func_registry::{
+        dyn_wrapper::{
+            DynAsyncFunctionWrapper, DynFunctionType, DynFunctionWrapper, DynStreamFunctionWrapper,
+        },
+        event_handler::{DynEventHandler, EventHandler},
+    },
 };
 
+/// The names of the built-in members of `window.jsBridge`, see `inject.js`.
+const RESERVED_NAMES: &[&str] = &[
+    "addEventListener",
+    "removeEventListener",
+    "on",
+    "off",
+    "emit",
+    "__internal",
+];
+
+/// Panics if the function name, or its top-level namespace, is a built-in
+/// member of `window.jsBridge`.
+fn check_name(name: &str) {
+    let root = name.split('.').next().unwrap_or(name);
+    assert!(
+        !RESERVED_NAMES.contains(&root),
+        "the function name `{}` is reserved by `window.jsBridge`",
+        name
+    );
+}
+
+/// Panics if a function name is also the namespace of another function, e.g.:
+/// `fs` and `fs.readFile`, the namespace and the function would overwrite each
+/// other in `window.jsBridge`.
+fn check_namespaces<V>(functions: &HashMap<String, V>) {
+    let mut names = functions.keys().collect::<Vec<_>>();
+    names.sort();
+    for name in names {
+        let namespace = name
+            .match_indices('.')
+            .map(|(i, _)| &name[..i])
+            .find(|namespace| functions.contains_key(*namespace));
+        if let Some(namespace) = namespace {
+            panic!(
+                "the function name `{}` is also the namespace of the function `{}`",
+                namespace, name
+            );
+        }
+    }
+}
+
 /// A builder for creating a function registry.
 #[derive(Default)]
 pub struct FuncRegistryBuilder {
     functions: HashMap<String, Box<dyn DynFunctionType>>,
+    events: HashMap<String, Vec<Box<dyn DynEventHandler>>>,
 }
 
 impl FuncRegistryBuilder {
     /// Registers a function with the given name.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the name is reserved by `window.jsBridge`, see
+    /// [`FuncRegistry`].
     pub fn register<F, S, R>(mut self, name: &str, func: F) -> Self
     where
         F: FunctionType<S, R> + Send + Sync + 'static,
         S: Send + Sync + 'static,
         R: Send + Sync + 'static,
     {
+        check_name(name);
         self.functions
             .insert(name.to_string(), Box::new(DynFunctionWrapper::new(func)));
         self
     }
 
+    /// Registers a handler of the event with the given name, which is emitted
+    /// from JavaScript with `jsBridge.emit(name, payload)`.
+    ///
+    /// Multiple handlers can be registered for the same event.
+    pub fn on_event<F, T>(mut self, name: &str, handler: F) -> Self
+    where
+        F: Fn(Frame, T) + Send + Sync + 'static,
+        T: DeserializeOwned + JsonSchema + 'static,
+    {
+        self.events
+            .entry(name.to_string())
+            .or_default()
+            .push(Box::new(EventHandler::new(handler)));
+        self
+    }
+
     /// Consumes the builder and returns a new [`AsyncFuncRegistryBuilder`].
     pub fn with_spawner<S, R>(self, spawner: S) -> AsyncFuncRegistryBuilder
     where
@@ -33,6 +104,7 @@ impl FuncRegistryBuilder {
     {
         AsyncFuncRegistryBuilder {
             functions: self.functions,
+            events: self.events,
             spawner: Arc::new(move |fut| {
                 spawner(fut);
             }),
@@ -40,9 +112,16 @@ impl FuncRegistryBuilder {
     }
 
     /// Builds the [`FuncRegistry`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if a function name is also the namespace of another function,
+    /// e.g.: `fs` and `fs.readFile`.
     pub fn build(self) -> FuncRegistry {
+        check_namespaces(&self.functions);
         FuncRegistry {
             functions: Arc::new(self.functions),
+            events: Arc::new(self.events),
             spawner: None,
         }
     }
@@ -51,29 +130,58 @@ impl FuncRegistryBuilder {
 /// A builder for creating an function registry with async functions.
 pub struct AsyncFuncRegistryBuilder {
     functions: HashMap<String, Box<dyn DynFunctionType>>,
+    events: HashMap<String, Vec<Box<dyn DynEventHandler>>>,
     spawner: Arc<dyn Fn(BoxFuture<'static, ()>) + Send + Sync>,
 }
 
 impl AsyncFuncRegistryBuilder {
     /// Registers a function with the given name.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the name is reserved by `window.jsBridge`, see
+    /// [`FuncRegistry`].
     pub fn register<F, S, R>(mut self, name: &str, func: F) -> Self
     where
         F: FunctionType<S, R> + Send + Sync + 'static,
         S: Send + Sync + 'static,
         R: Send + Sync + 'static,
     {
+        check_name(name);
         self.functions
             .insert(name.to_string(), Box::new(DynFunctionWrapper::new(func)));
         self
     }
 
+    /// Registers a handler of the event with the given name, which is emitted
+    /// from JavaScript with `jsBridge.emit(name, payload)`.
+    ///
+    /// Multiple handlers can be registered for the same event.
+    pub fn on_event<F, T>(mut self, name: &str, handler: F) -> Self
+    where
+        F: Fn(Frame, T) + Send + Sync + 'static,
+        T: DeserializeOwned + JsonSchema + 'static,
+    {
+        self.events
+            .entry(name.to_string())
+            .or_default()
+            .push(Box::new(EventHandler::new(handler)));
+        self
+    }
+
     /// Registers a async function with the given name.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the name is reserved by `window.jsBridge`, see
+    /// [`FuncRegistry`].
     pub fn register_async<F, S, R>(mut self, name: &str, func: F) -> Self
     where
         F: AsyncFunctionType<S, R> + Send + Sync + 'static,
         S: Send + Sync + 'static,
         R: Send + Sync + 'static,
     {
+        check_name(name);
         self.functions.insert(
             name.to_string(),
             Box::new(DynAsyncFunctionWrapper::new(func)),
@@ -81,11 +189,102 @@ impl AsyncFuncRegistryBuilder {
         self
     }
 
+    /// Registers a function with the given name that returns a stream of
+    /// items.
+    ///
+    /// In JavaScript, the function returns an async iterator of the items, the
+    /// stream is dropped when the iteration is stopped or the call is aborted.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the name is reserved by `window.jsBridge`, see
+    /// [`FuncRegistry`].
+    pub fn register_stream<F, S, R>(mut self, name: &str, func: F) -> Self
+    where
+        F: StreamFunctionType<S, R> + Send + Sync + 'static,
+        S: Send + Sync + 'static,
+        R: Send + Sync + 'static,
+    {
+        check_name(name);
+        self.functions.insert(
+            name.to_string(),
+            Box::new(DynStreamFunctionWrapper::new(func)),
+        );
+        self
+    }
+
     /// Builds the [`FuncRegistry`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if a function name is also the namespace of another function,
+    /// e.g.: `fs` and `fs.readFile`.
     pub fn build(self) -> FuncRegistry {
+        check_namespaces(&self.functions);
         FuncRegistry {
             functions: Arc::new(self.functions),
+            events: Arc::new(self.events),
             spawner: Some(self.spawner),
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_register() {
+        let registry = FuncRegistry::builder()
+            .register("emitter", || ())
+            .register("events.emit", || ())
+            .build();
+        assert!(registry.functions.contains_key("emitter"));
+        assert!(registry.functions.contains_key("events.emit"));
+    }
+
+    #[test]
+    fn test_build_namespaces() {
+        let registry = FuncRegistry::builder()
+            .register("fs.readFile", || ())
+            .register("fs.watch.start", || ())
+            .register("fsx", || ())
+            .build();
+        assert_eq!(registry.functions.len(), 3);
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "the function name `fs` is also the namespace of the function `fs.readFile`"
+    )]
+    fn test_build_function_and_namespace() {
+        FuncRegistry::builder()
+            .register("fs.readFile", || ())
+            .register("fs", || ())
+            .build();
+    }
+
+    #[test]
+    #[should_panic(expected = "the function name `fs.watch` is also the namespace")]
+    fn test_build_function_and_nested_namespace() {
+        FuncRegistry::builder()
+            .with_spawner(|_| ())
+            .register("fs.watch", || ())
+            .register_async("fs.watch.start", || async {})
+            .build();
+    }
+
+    #[test]
+    #[should_panic(expected = "the function name `emit` is reserved")]
+    fn test_register_reserved_name() {
+        FuncRegistry::builder().register("emit", || ());
+    }
+
+    #[test]
+    #[should_panic(expected = "the function name `on.click` is reserved")]
+    fn test_register_reserved_namespace() {
+        FuncRegistry::builder()
+            .with_spawner(|_| ())
+            .register_async("on.click", || async {});
+    }
+}
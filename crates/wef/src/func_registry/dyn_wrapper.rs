@@ -1,13 +1,24 @@
 use std::marker::PhantomData;
 
-use futures_util::future::BoxFuture;
+use futures_util::{StreamExt, future::BoxFuture};
+use schemars::SchemaGenerator;
 use serde_json::Value;
 
-use crate::{AsyncFunctionType, Frame, FunctionType, query::QueryCallback};
+use crate::{
+    AsyncFunctionType, Frame, FunctionType, StreamFunctionType, func_registry::FunctionSignature,
+    query::QueryCallback,
+};
 
 pub(crate) trait DynFunctionType: Send + Sync {
     fn num_arguments(&self) -> usize;
 
+    fn signature(&self, generator: &mut SchemaGenerator) -> FunctionSignature;
+
+    /// Returns `true` if the function returns a stream of items.
+    fn is_stream(&self) -> bool {
+        false
+    }
+
     fn call(
         &self,
         spawner: Option<&(dyn Fn(BoxFuture<'static, ()>) + Send + Sync)>,
@@ -43,6 +54,11 @@ where
         F::NUM_ARGUMENTS
     }
 
+    #[inline]
+    fn signature(&self, generator: &mut SchemaGenerator) -> FunctionSignature {
+        F::signature(generator)
+    }
+
     #[inline]
     fn call(
         &self,
@@ -81,6 +97,11 @@ where
         F::NUM_ARGUMENTS
     }
 
+    #[inline]
+    fn signature(&self, generator: &mut SchemaGenerator) -> FunctionSignature {
+        F::signature(generator)
+    }
+
     #[inline]
     fn call(
         &self,
@@ -94,3 +115,70 @@ where
         spawner(Box::pin(async move { callback.result(fut.await) }));
     }
 }
+
+pub(crate) struct DynStreamFunctionWrapper<F, S, R> {
+    func: F,
+    _mark: PhantomData<(S, R)>,
+}
+
+impl<F, S, R> DynStreamFunctionWrapper<F, S, R> {
+    #[inline]
+    pub(crate) fn new(func: F) -> Self {
+        Self {
+            func,
+            _mark: PhantomData,
+        }
+    }
+}
+
+impl<F, S, R> DynFunctionType for DynStreamFunctionWrapper<F, S, R>
+where
+    F: StreamFunctionType<S, R> + Send + Sync,
+    S: Send + Sync,
+    R: Send + Sync,
+{
+    #[inline]
+    fn num_arguments(&self) -> usize {
+        F::NUM_ARGUMENTS
+    }
+
+    #[inline]
+    fn signature(&self, generator: &mut SchemaGenerator) -> FunctionSignature {
+        F::signature(generator)
+    }
+
+    #[inline]
+    fn is_stream(&self) -> bool {
+        true
+    }
+
+    fn call(
+        &self,
+        spawner: Option<&(dyn Fn(BoxFuture<'static, ()>) + Send + Sync)>,
+        frame: Frame,
+        args: Vec<Value>,
+        callback: QueryCallback,
+    ) {
+        let spawner = spawner.expect("BUG: spawner is None");
+        let stream = match self.func.call(frame, args) {
+            Ok(stream) => stream,
+            Err(err) => {
+                callback.result(Err(err));
+                return;
+            }
+        };
+        spawner(Box::pin(async move {
+            let mut stream = std::pin::pin!(stream);
+            while let Some(item) = stream.next().await {
+                match item {
+                    Ok(value) => callback.send(value),
+                    Err(err) => {
+                        callback.result(Err(err));
+                        return;
+                    }
+                }
+            }
+            callback.done();
+        }));
+    }
+}
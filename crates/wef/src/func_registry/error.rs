@@ -1,3 +1,8 @@
+use std::fmt;
+
+use serde::Serialize;
+use serde_json::{Value, json};
+
 /// Error type for function calls.
 #[derive(Debug, thiserror::Error)]
 pub enum CallFunctionError {
@@ -20,7 +25,209 @@ pub enum CallFunctionError {
     /// Function not found.
     #[error("Function not found: {0}")]
     NotFound(String),
+    /// The error returned by the function.
+    #[error("{}", .0.message)]
+    Function(FunctionError),
     /// Other errors.
     #[error("{0}")]
     Other(String),
 }
+
+impl CallFunctionError {
+    /// Returns the error code sent to JavaScript.
+    pub fn code(&self) -> &str {
+        match self {
+            CallFunctionError::InvalidNumberOfArguments { .. } => "INVALID_NUMBER_OF_ARGUMENTS",
+            CallFunctionError::InvalidArgument { .. } => "INVALID_ARGUMENT",
+            CallFunctionError::NotFound(_) => "NOT_FOUND",
+            CallFunctionError::Function(err) => &err.code,
+            CallFunctionError::Other(_) => FunctionError::DEFAULT_CODE,
+        }
+    }
+
+    /// Returns the additional data of the error sent to JavaScript.
+    pub fn data(&self) -> Value {
+        match self {
+            CallFunctionError::InvalidNumberOfArguments { expected, actual } => {
+                json!({ "expected": expected, "actual": actual })
+            }
+            CallFunctionError::InvalidArgument { arg_name, error } => {
+                json!({ "argument": arg_name, "error": error })
+            }
+            CallFunctionError::NotFound(name) => json!({ "name": name }),
+            CallFunctionError::Function(err) => err.data.clone(),
+            CallFunctionError::Other(_) => Value::Null,
+        }
+    }
+
+    /// Returns the JSON object `{ code, message, data }` sent to JavaScript.
+    pub(crate) fn to_json(&self) -> Value {
+        json!({
+            "code": self.code(),
+            "message": self.to_string(),
+            "data": self.data(),
+        })
+    }
+}
+
+/// A structured error sent to JavaScript, the promise of the call is rejected
+/// with a `JsBridgeError` that has the same fields.
+///
+/// All the [`std::error::Error`] types can be converted to `FunctionError` with
+/// the [`FunctionError::DEFAULT_CODE`] code, so the `?` operator can be used in
+/// the functions that return `Result<T, FunctionError>`.
+///
+/// ```rust, no_run
+/// use wef::{FuncRegistry, FunctionError};
+///
+/// let registry = FuncRegistry::builder()
+///     .register("withdraw", |amount: String| {
+///         let amount: u32 = amount.parse()?;
+///         if amount > 100 {
+///             return Err(FunctionError::new("INSUFFICIENT_FUNDS", "Insufficient funds")
+///                 .with_data(100));
+///         }
+///         Ok(amount)
+///     })
+///     .build();
+/// ```
+///
+/// Implement `From<T> for FunctionError` to send the custom error types to
+/// JavaScript, the error itself can be the data of the error.
+///
+/// ```rust
+/// use serde::Serialize;
+/// use wef::FunctionError;
+///
+/// #[derive(Serialize)]
+/// #[serde(tag = "kind")]
+/// enum FsError {
+///     NotFound { path: String },
+///     PermissionDenied { path: String },
+/// }
+///
+/// impl From<FsError> for FunctionError {
+///     fn from(err: FsError) -> Self {
+///         match &err {
+///             FsError::NotFound { path } => {
+///                 FunctionError::new("FS_NOT_FOUND", format!("{} not found", path))
+///             }
+///             FsError::PermissionDenied { path } => {
+///                 FunctionError::new("FS_PERMISSION_DENIED", format!("{} is denied", path))
+///             }
+///         }
+///         .with_data(err)
+///     }
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct FunctionError {
+    /// The error code, e.g.: `NOT_FOUND`.
+    pub code: String,
+    /// The error message.
+    pub message: String,
+    /// The additional data of the error.
+    pub data: Value,
+}
+
+impl fmt::Display for FunctionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.code, self.message)
+    }
+}
+
+impl<E: std::error::Error> From<E> for FunctionError {
+    fn from(err: E) -> Self {
+        FunctionError::new(FunctionError::DEFAULT_CODE, err.to_string())
+    }
+}
+
+impl FunctionError {
+    /// The code of the errors that don't have a specific code.
+    pub const DEFAULT_CODE: &str = "ERROR";
+
+    /// Creates a new `FunctionError` with the code and the message.
+    pub fn new(code: impl Into<String>, message: impl Into<String>) -> Self {
+        Self {
+            code: code.into(),
+            message: message.into(),
+            data: Value::Null,
+        }
+    }
+
+    /// Sets the additional data of the error.
+    pub fn with_data(mut self, data: impl Serialize) -> Self {
+        self.data = serde_json::to_value(data).unwrap_or_default();
+        self
+    }
+}
+
+/// Converts the error returned by a function to a [`FunctionError`].
+///
+/// _`FunctionError` doesn't implement `Serialize` and `std::error::Error`, so
+/// `Result<T, FunctionError>` is not ambiguous with the serializable results._
+pub(crate) trait IntoFunctionError {
+    fn into_function_error(self) -> FunctionError;
+}
+
+impl<E: std::error::Error> IntoFunctionError for E {
+    fn into_function_error(self) -> FunctionError {
+        self.into()
+    }
+}
+
+impl IntoFunctionError for FunctionError {
+    #[inline]
+    fn into_function_error(self) -> FunctionError {
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::func_registry::into_result::IntoFunctionResult;
+
+    #[test]
+    fn test_call_function_error() {
+        assert_eq!(
+            CallFunctionError::NotFound("add".to_string()).to_json(),
+            json!({
+                "code": "NOT_FOUND",
+                "message": "Function not found: add",
+                "data": { "name": "add" }
+            })
+        );
+        assert_eq!(
+            CallFunctionError::InvalidArgument {
+                arg_name: "arg0".to_string(),
+                error: "missing field `x`".to_string(),
+            }
+            .to_json(),
+            json!({
+                "code": "INVALID_ARGUMENT",
+                "message": "Invalid argument arg0: missing field `x`",
+                "data": { "argument": "arg0", "error": "missing field `x`" }
+            })
+        );
+    }
+
+    #[test]
+    fn test_function_result_error() {
+        let err = IntoFunctionResult::<i32>::into_function_result("x".parse::<i32>()).unwrap_err();
+        assert_eq!(
+            err.to_json(),
+            json!({ "code": "ERROR", "message": "invalid digit found in string", "data": null })
+        );
+
+        let res: Result<i32, FunctionError> =
+            Err(FunctionError::new("INSUFFICIENT_FUNDS", "Insufficient funds").with_data(100));
+        let err = res.into_function_result().unwrap_err();
+        assert_eq!(err.code(), "INSUFFICIENT_FUNDS");
+        assert_eq!(err.to_string(), "Insufficient funds");
+        assert_eq!(
+            err.to_json(),
+            json!({ "code": "INSUFFICIENT_FUNDS", "message": "Insufficient funds", "data": 100 })
+        );
+    }
+}
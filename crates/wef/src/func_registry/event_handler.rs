@@ -0,0 +1,49 @@
+use std::marker::PhantomData;
+
+use schemars::{JsonSchema, Schema, SchemaGenerator};
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+
+use crate::{Frame, func_registry::CallFunctionError};
+
+pub(crate) trait DynEventHandler: Send + Sync {
+    fn payload_schema(&self, generator: &mut SchemaGenerator) -> Schema;
+
+    fn call(&self, frame: Frame, payload: Value) -> Result<(), CallFunctionError>;
+}
+
+pub(crate) struct EventHandler<F, T> {
+    func: F,
+    _mark: PhantomData<fn(T)>,
+}
+
+impl<F, T> EventHandler<F, T> {
+    #[inline]
+    pub(crate) fn new(func: F) -> Self {
+        Self {
+            func,
+            _mark: PhantomData,
+        }
+    }
+}
+
+impl<F, T> DynEventHandler for EventHandler<F, T>
+where
+    F: Fn(Frame, T) + Send + Sync,
+    T: DeserializeOwned + JsonSchema,
+{
+    #[inline]
+    fn payload_schema(&self, generator: &mut SchemaGenerator) -> Schema {
+        generator.subschema_for::<T>()
+    }
+
+    fn call(&self, frame: Frame, payload: Value) -> Result<(), CallFunctionError> {
+        let payload =
+            serde_json::from_value(payload).map_err(|e| CallFunctionError::InvalidArgument {
+                arg_name: "payload".to_string(),
+                error: e.to_string(),
+            })?;
+        (self.func)(frame, payload);
+        Ok(())
+    }
+}
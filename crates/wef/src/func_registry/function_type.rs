@@ -1,9 +1,12 @@
+use schemars::{JsonSchema, SchemaGenerator};
 use serde::de::DeserializeOwned;
 use serde_json::Value;
 
 use crate::{
     Frame,
-    func_registry::{CallFunctionError, into_result::IntoFunctionResult},
+    func_registry::{
+        CallFunctionError, FunctionSignature, arguments::Arguments, into_result::IntoFunctionResult,
+    },
 };
 
 /// Represents a function type that can be called from JavaScript.
@@ -11,6 +14,10 @@ pub trait FunctionType<S, R> {
     /// Number of arguments.
     const NUM_ARGUMENTS: usize;
 
+    /// Returns the JSON schemas of the arguments and the result, the [`Frame`]
+    /// argument is not included.
+    fn signature(generator: &mut SchemaGenerator) -> FunctionSignature;
+
     /// Calls the function with the given arguments.
     fn call(&self, frame: Frame, args: Vec<Value>) -> Result<Value, CallFunctionError>;
 }
@@ -21,39 +28,29 @@ macro_rules! impl_function_types {
         where
             F: Fn($($name),*) -> Ret,
             Ret: IntoFunctionResult<R>,
-            $($name: DeserializeOwned,)*
+            R: JsonSchema,
+            $($name: DeserializeOwned + JsonSchema,)*
         {
             const NUM_ARGUMENTS: usize = tuple_len::tuple_len!(($($name,)*));
 
-            fn call(&self, _frame: Frame, args: Vec<Value>) -> Result<Value, CallFunctionError> {
-                let expected_args = tuple_len::tuple_len!(($($name,)*));
-
-                if args.len() != expected_args {
-                    return Err(CallFunctionError::InvalidNumberOfArguments {
-                        expected: expected_args,
-                        actual: args.len(),
-                    });
+            fn signature(generator: &mut SchemaGenerator) -> FunctionSignature {
+                FunctionSignature {
+                    arguments: vec![$(generator.subschema_for::<$name>()),*],
+                    result: generator.subschema_for::<R>(),
                 }
+            }
 
-                let mut args = args;
-                args.reverse();
+            fn call(&self, _frame: Frame, args: Vec<Value>) -> Result<Value, CallFunctionError> {
+                #[allow(unused_mut, unused_variables)]
+                let mut args = Arguments::new(args, tuple_len::tuple_len!(($($name,)*)))?;
 
                 $(
                     #[allow(non_snake_case)]
-                    let $name: $name = serde_json::from_value(args.pop().unwrap()).map_err(|e| {
-                        CallFunctionError::InvalidArgument {
-                            arg_name: "A1".to_string(),
-                            error: e.to_string(),
-                        }
-                    })?;
+                    let $name: $name = args.next()?;
                 )*
 
 
-                let result = IntoFunctionResult::into_function_result(self($($name),*));
-                match result {
-                    Ok(value) => Ok(serde_json::to_value(value).unwrap()),
-                    Err(e) => Err(CallFunctionError::Other(e.to_string())),
-                }
+                IntoFunctionResult::into_function_result(self($($name),*))
             }
         }
 
@@ -61,39 +58,29 @@ macro_rules! impl_function_types {
         where
             F: Fn(Frame, $($name),*) -> Ret,
             Ret: IntoFunctionResult<R>,
-            $($name: DeserializeOwned,)*
+            R: JsonSchema,
+            $($name: DeserializeOwned + JsonSchema,)*
         {
             const NUM_ARGUMENTS: usize = tuple_len::tuple_len!(($($name,)*));
 
-            fn call(&self, frame: Frame, args: Vec<Value>) -> Result<Value, CallFunctionError> {
-                let expected_args = tuple_len::tuple_len!(($($name,)*));
-
-                if args.len() != expected_args {
-                    return Err(CallFunctionError::InvalidNumberOfArguments {
-                        expected: expected_args,
-                        actual: args.len(),
-                    });
+            fn signature(generator: &mut SchemaGenerator) -> FunctionSignature {
+                FunctionSignature {
+                    arguments: vec![$(generator.subschema_for::<$name>()),*],
+                    result: generator.subschema_for::<R>(),
                 }
+            }
 
-                let mut args = args;
-                args.reverse();
+            fn call(&self, frame: Frame, args: Vec<Value>) -> Result<Value, CallFunctionError> {
+                #[allow(unused_mut, unused_variables)]
+                let mut args = Arguments::new(args, tuple_len::tuple_len!(($($name,)*)))?;
 
                 $(
                     #[allow(non_snake_case)]
-                    let $name: $name = serde_json::from_value(args.pop().unwrap()).map_err(|e| {
-                        CallFunctionError::InvalidArgument {
-                            arg_name: "A1".to_string(),
-                            error: e.to_string(),
-                        }
-                    })?;
+                    let $name: $name = args.next()?;
                 )*
 
 
-                let result = IntoFunctionResult::into_function_result(self(frame, $($name),*));
-                match result {
-                    Ok(value) => Ok(serde_json::to_value(value).unwrap()),
-                    Err(e) => Err(CallFunctionError::Other(e.to_string())),
-                }
+                IntoFunctionResult::into_function_result(self(frame, $($name),*))
             }
         }
     };
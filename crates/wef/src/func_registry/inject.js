@@ -1,28 +1,171 @@
+window.JsBridgeError = class JsBridgeError extends Error {
+  constructor({ code, message, data }) {
+    super(message);
+    this.name = "JsBridgeError";
+    this.code = code;
+    this.data = data === undefined ? null : data;
+  }
+};
+
 window.jsBridge = {
   __internal: {
-    call(method, args) {
-      var request = {
-        method: method,
-        args,
-      };
+    error(error_message) {
+      try {
+        const error = JSON.parse(error_message);
+        if (error && typeof error.code === "string") {
+          return new window.JsBridgeError(error);
+        }
+      } catch (_) {}
+      return new window.JsBridgeError({ code: "ERROR", message: error_message });
+    },
+    query(request, options) {
+      const signal = options && options.signal;
       return new Promise((resolve, reject) => {
-        window.cefQuery({
+        if (signal && signal.aborted) {
+          reject(signal.reason);
+          return;
+        }
+        const onAbort = () => {
+          window.cefQueryCancel(id);
+          reject(signal.reason);
+        };
+        const id = window.cefQuery({
           request: JSON.stringify(request),
           persistent: false,
           onSuccess: function (response) {
+            if (signal) signal.removeEventListener("abort", onAbort);
             resolve(JSON.parse(response));
           },
-          onFailure: (error_code, error_message) => reject(error_message),
+          onFailure: (error_code, error_message) => {
+            if (signal) signal.removeEventListener("abort", onAbort);
+            reject(this.error(error_message));
+          },
         });
+        if (signal) signal.addEventListener("abort", onAbort);
       });
     },
+    call(method, args, options) {
+      return this.query(
+        {
+          method: method,
+          args,
+        },
+        options
+      );
+    },
+    stream(method, args, options) {
+      const signal = options && options.signal;
+      const items = [];
+      const waiters = [];
+      let id = null;
+      let finished = false;
+      let error = null;
+
+      const settle = () => {
+        while (waiters.length > 0 && (items.length > 0 || finished)) {
+          const waiter = waiters.shift();
+          if (items.length > 0) {
+            waiter.resolve({ value: items.shift(), done: false });
+          } else if (error !== null) {
+            // Only the first request after the error rejects, the later ones are done.
+            waiter.reject(error);
+            error = null;
+          } else {
+            waiter.resolve({ value: undefined, done: true });
+          }
+        }
+      };
+      const finish = (reason) => {
+        if (finished) return;
+        finished = true;
+        error = reason;
+        if (signal) signal.removeEventListener("abort", onAbort);
+        settle();
+      };
+      const cancel = () => {
+        if (id !== null && !finished) {
+          window.cefQueryCancel(id);
+        }
+      };
+      const onAbort = () => {
+        cancel();
+        items.length = 0;
+        finish(signal.reason);
+      };
+      // The query starts when the first item is requested.
+      const start = () => {
+        if (signal && signal.aborted) {
+          onAbort();
+          return;
+        }
+        id = window.cefQuery({
+          request: JSON.stringify({ method: method, args }),
+          persistent: true,
+          onSuccess: function (response) {
+            if (finished) return;
+            items.push(JSON.parse(response));
+            settle();
+          },
+          onFailure: (error_code, error_message) => {
+            finish(error_code === 0 ? null : this.error(error_message));
+          },
+        });
+        if (signal) signal.addEventListener("abort", onAbort);
+      };
+
+      return {
+        [Symbol.asyncIterator]() {
+          return this;
+        },
+        next() {
+          if (id === null && !finished) start();
+          return new Promise((resolve, reject) => {
+            waiters.push({ resolve, reject });
+            settle();
+          });
+        },
+        return(value) {
+          cancel();
+          items.length = 0;
+          error = null;
+          finish(null);
+          return Promise.resolve({ value, done: true });
+        },
+      };
+    },
+    evalScript(id, script) {
+      new Promise((resolve) => resolve((0, eval)(script)))
+        .then((value) => JSON.parse(JSON.stringify(value) ?? "null"))
+        .then(
+          (result) => this.query({ evalId: id, result }),
+          (error) => {
+            const isError = error instanceof Error;
+            return this.query({
+              evalId: id,
+              error: {
+                message: isError ? error.message : String(error),
+                stack: isError && error.stack ? String(error.stack) : null,
+              },
+            });
+          }
+        );
+    },
     nextEventListenerId: 0,
     eventListeners: {},
+    namedEventListeners: {},
     emit(message) {
       for (const id in this.eventListeners) {
         this.eventListeners[id](message);
       }
     },
+    emitEvent(name, payload) {
+      for (const id in this.namedEventListeners) {
+        const listener = this.namedEventListeners[id];
+        if (listener.name === name) {
+          listener.callback(payload);
+        }
+      }
+    },
   },
   addEventListener(callback) {
     const id = this.__internal.nextEventListenerId++;
@@ -32,4 +175,20 @@ window.jsBridge = {
   removeEventListener(id) {
     delete this.__internal.eventListeners[id];
   },
+  on(name, callback) {
+    const id = this.__internal.nextEventListenerId++;
+    this.__internal.namedEventListeners[id] = { name, callback };
+    return id;
+  },
+  off(id) {
+    delete this.__internal.namedEventListeners[id];
+  },
+  emit(name, payload) {
+    return this.__internal
+      .query({
+        event: name,
+        payload: payload === undefined ? null : payload,
+      })
+      .then(() => undefined);
+  },
 };
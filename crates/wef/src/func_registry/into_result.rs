@@ -1,9 +1,7 @@
-use std::error::Error;
-
 use serde::Serialize;
 use serde_json::Value;
 
-use crate::func_registry::CallFunctionError;
+use crate::func_registry::{CallFunctionError, IntoFunctionError};
 
 pub(crate) trait IntoFunctionResult<T> {
     fn into_function_result(self) -> Result<Value, CallFunctionError>;
@@ -12,11 +10,11 @@ pub(crate) trait IntoFunctionResult<T> {
 impl<T, E> IntoFunctionResult<T> for Result<T, E>
 where
     T: Serialize,
-    E: Error,
+    E: IntoFunctionError,
 {
     fn into_function_result(self) -> Result<Value, CallFunctionError> {
         self.map(|value| serde_json::to_value(value).unwrap())
-            .map_err(|err| CallFunctionError::Other(err.to_string()))
+            .map_err(|err| CallFunctionError::Function(err.into_function_error()))
     }
 }
 
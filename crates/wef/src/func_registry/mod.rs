@@ -1,13 +1,24 @@
+mod arguments;
 mod async_function_type;
 mod builder;
 mod dyn_wrapper;
 mod error;
+mod event_handler;
 mod function_type;
 mod into_result;
+mod pending;
 mod registry;
+mod signature;
+mod stream_function_type;
+mod typescript;
 
 pub use async_function_type::AsyncFunctionType;
 pub use builder::{AsyncFuncRegistryBuilder, FuncRegistryBuilder};
-pub use error::CallFunctionError;
+pub use error::{CallFunctionError, FunctionError};
 pub use function_type::FunctionType;
 pub use registry::FuncRegistry;
+pub use signature::FunctionSignature;
+pub use stream_function_type::StreamFunctionType;
+
+pub(crate) use error::IntoFunctionError;
+pub(crate) use pending::PendingCalls;
@@ -0,0 +1,123 @@
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+};
+
+use futures_util::future::{AbortHandle, Abortable, BoxFuture};
+
+/// The running async calls of a browser, which can be cancelled from
+/// JavaScript by the query id.
+#[derive(Default, Clone)]
+pub(crate) struct PendingCalls(Arc<Mutex<HashMap<i64, AbortHandle>>>);
+
+impl PendingCalls {
+    /// Wraps the future of the query to be aborted by [`PendingCalls::cancel`],
+    /// the query is removed when the future is finished.
+    pub(crate) fn track(
+        &self,
+        query_id: i64,
+        fut: BoxFuture<'static, ()>,
+    ) -> BoxFuture<'static, ()> {
+        let (handle, registration) = AbortHandle::new_pair();
+        self.0.lock().unwrap().insert(query_id, handle);
+
+        let calls = self.clone();
+        Box::pin(async move {
+            _ = Abortable::new(fut, registration).await;
+            calls.0.lock().unwrap().remove(&query_id);
+        })
+    }
+
+    /// Aborts the future of the query, it's dropped without sending the
+    /// result.
+    pub(crate) fn cancel(&self, query_id: i64) {
+        if let Some(handle) = self.0.lock().unwrap().remove(&query_id) {
+            handle.abort();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        sync::atomic::{AtomicBool, Ordering},
+        task::{Context, Poll},
+    };
+
+    use futures_util::task::noop_waker_ref;
+
+    use super::*;
+
+    fn poll(fut: &mut BoxFuture<'static, ()>) -> Poll<()> {
+        fut.as_mut()
+            .poll(&mut Context::from_waker(noop_waker_ref()))
+    }
+
+    fn is_pending(calls: &PendingCalls, query_id: i64) -> bool {
+        calls.0.lock().unwrap().contains_key(&query_id)
+    }
+
+    /// Sets the flag when the future is dropped.
+    struct DropFlag(Arc<AtomicBool>);
+
+    impl Drop for DropFlag {
+        fn drop(&mut self) {
+            self.0.store(true, Ordering::SeqCst);
+        }
+    }
+
+    fn never_finished(dropped: &Arc<AtomicBool>) -> BoxFuture<'static, ()> {
+        let flag = DropFlag(dropped.clone());
+        Box::pin(async move {
+            let _flag = flag;
+            futures_util::future::pending::<()>().await
+        })
+    }
+
+    #[test]
+    fn test_track_finished() {
+        let calls = PendingCalls::default();
+        let mut fut = calls.track(1, Box::pin(async {}));
+        assert!(is_pending(&calls, 1));
+
+        assert_eq!(poll(&mut fut), Poll::Ready(()));
+        assert!(!is_pending(&calls, 1));
+    }
+
+    #[test]
+    fn test_cancel() {
+        let calls = PendingCalls::default();
+        let dropped = Arc::new(AtomicBool::new(false));
+        let mut fut1 = calls.track(1, never_finished(&dropped));
+        let mut fut2 = calls.track(2, never_finished(&Arc::default()));
+
+        assert_eq!(poll(&mut fut1), Poll::Pending);
+        assert_eq!(poll(&mut fut2), Poll::Pending);
+
+        calls.cancel(1);
+        assert!(!is_pending(&calls, 1));
+        assert!(is_pending(&calls, 2));
+
+        // The aborted future finishes and drops the inner future.
+        assert_eq!(poll(&mut fut1), Poll::Ready(()));
+        assert!(dropped.load(Ordering::SeqCst));
+        assert_eq!(poll(&mut fut2), Poll::Pending);
+
+        // Cancelling an unknown or finished query does nothing.
+        calls.cancel(1);
+        calls.cancel(3);
+        assert!(is_pending(&calls, 2));
+    }
+
+    #[test]
+    fn test_cancel_before_polled() {
+        let calls = PendingCalls::default();
+        let dropped = Arc::new(AtomicBool::new(false));
+        let mut fut = calls.track(1, never_finished(&dropped));
+
+        calls.cancel(1);
+        assert_eq!(poll(&mut fut), Poll::Ready(()));
+        assert!(dropped.load(Ordering::SeqCst));
+        assert!(!is_pending(&calls, 1));
+    }
+}
@@ -1,15 +1,27 @@
 use std::{collections::HashMap, sync::Arc};
 
 use futures_util::future::BoxFuture;
-use serde_json::Value;
+use schemars::generate::SchemaSettings;
+use serde_json::{Map, Value, json};
 
 use crate::{
     Frame, FuncRegistryBuilder,
-    func_registry::{CallFunctionError, dyn_wrapper::DynFunctionType},
+    func_registry::{
+        CallFunctionError, PendingCalls,
+        dyn_wrapper::DynFunctionType,
+        event_handler::DynEventHandler,
+        typescript::{self, BridgeSchema, FunctionSchema},
+    },
     query::QueryCallback,
 };
 
-/// A registry for functions that can be called from JavaScript.
+/// The environment variable of the path to write the TypeScript declarations.
+const EXPORT_TYPES_ENV: &str = "WEF_EXPORT_TYPES";
+/// The environment variable of the path to write the JSON Schema manifest.
+const EXPORT_SCHEMA_ENV: &str = "WEF_EXPORT_SCHEMA";
+
+/// A registry for functions that can be called from JavaScript, and the
+/// handlers of the events emitted from JavaScript.
 ///
 /// To create a new `FuncRegistry`, use the [`FuncRegistry::builder`] method to
 /// create a `FuncRegistryBuilder`, register your functions, and then call
@@ -39,6 +51,37 @@ use crate::{
 /// jsBridge.sub(5, 3); // Returns 2
 /// ```
 ///
+/// # Arguments
+///
+/// A struct argument is deserialized from a JavaScript object, and the
+/// trailing `Option<T>` arguments can be omitted. If an argument can't be
+/// deserialized, the error names the argument and the path of the offending
+/// field, e.g.: ``Invalid argument arg0: field `address.zip`: invalid type``.
+///
+/// The functions with dotted names are registered in the nested namespaces, a
+/// namespace can't be the name of a function at the same time.
+///
+/// ```rust, no_run
+/// use serde::Deserialize;
+/// use wef::FuncRegistry;
+///
+/// #[derive(Deserialize, schemars::JsonSchema)]
+/// struct ReadOptions {
+///     encoding: String,
+/// }
+///
+/// let registry = FuncRegistry::builder()
+///     .register("fs.readFile", |path: String, options: Option<ReadOptions>| {
+///         std::fs::read_to_string(path)
+///     })
+///     .build();
+/// ```
+///
+/// ```javascript
+/// jsBridge.fs.readFile("a.txt");
+/// jsBridge.fs.readFile("a.txt", { encoding: "utf-8" });
+/// ```
+///
 /// # Asynchronous Functions
 ///
 /// You can also register asynchronous functions. Call
@@ -61,10 +104,86 @@ use crate::{
 ///     .build();
 /// ```
 ///
+/// The async functions can be cancelled from JavaScript with an `AbortSignal`,
+/// the future is dropped when the signal is aborted or the page navigates
+/// away.
+///
+/// ```javascript
+/// const controller = new AbortController();
+/// jsBridge.sleep(1000, { signal: controller.signal });
+/// controller.abort(); // The future of `sleep` is dropped
+/// ```
+///
+/// # Streams
+///
+/// Register the functions that return a [`futures_util::Stream`] with
+/// `AsyncFuncRegistryBuilder::register_stream`, they return async iterators in
+/// JavaScript.
+///
+/// ```rust, ignore
+/// use wef::FuncRegistry;
+///
+/// let registry = FuncRegistry::builder()
+///     .with_spawner(tokio::spawn)
+///     .register_stream("count", |n: u32| futures_util::stream::iter(0..n))
+///     .build();
+/// ```
+///
+/// ```javascript
+/// for await (const i of jsBridge.count(3)) {
+///     console.log(i); // 0, 1, 2
+/// }
+/// ```
+///
+/// The stream is dropped when the loop is stopped with `break`, or the call is
+/// aborted.
+///
+/// # Events
+///
+/// Register the handlers of the named events emitted from JavaScript with
+/// [`FuncRegistryBuilder::on_event`], and emit the named events to JavaScript
+/// with [`Frame::emit_event`].
+///
+/// ```rust, no_run
+/// use wef::FuncRegistry;
+///
+/// let registry = FuncRegistry::builder()
+///     .on_event("clicked", |frame, id: u32| {
+///         frame.emit_event("selected", id);
+///     })
+///     .build();
+/// ```
+///
+/// ```javascript
+/// const id = jsBridge.on("selected", (id) => console.log(id));
+/// jsBridge.emit("clicked", 1);
+/// jsBridge.off(id); // Unsubscribe the listener
+/// ```
+///
+/// # Reserved Names
+///
+/// The names `emit`, `on`, `off`, `addEventListener`, `removeEventListener`
+/// and `__internal` are the built-in members of `window.jsBridge`, the
+/// functions with these names or in these namespaces can't be registered.
+///
+/// # TypeScript Declarations
+///
+/// The types of the arguments and the results must implement
+/// [`schemars::JsonSchema`], so that the registry can generate the TypeScript
+/// declarations of `window.jsBridge` with
+/// [`FuncRegistry::typescript_declarations`], and the JSON Schema manifest with
+/// [`FuncRegistry::json_schema`].
+///
+/// Use `cargo wef types` to write the declarations into the web project, it
+/// runs the application with the `WEF_EXPORT_TYPES` environment variable, and
+/// the application exports the types by
+/// [`FuncRegistry::export_types_from_env`].
+///
 /// _You can clone the `FuncRegistry` and use it in multiple browsers._
 #[derive(Default, Clone)]
 pub struct FuncRegistry {
     pub(crate) functions: Arc<HashMap<String, Box<dyn DynFunctionType>>>,
+    pub(crate) events: Arc<HashMap<String, Vec<Box<dyn DynEventHandler>>>>,
     pub(crate) spawner: Option<Arc<dyn Fn(BoxFuture<'static, ()>) + Send + Sync>>,
 }
 
@@ -75,27 +194,188 @@ impl FuncRegistry {
         FuncRegistryBuilder::default()
     }
 
-    pub(crate) fn call(&self, frame: Frame, name: &str, args: Vec<Value>, callback: QueryCallback) {
+    /// Returns the TypeScript declarations of `window.jsBridge` with all the
+    /// registered functions and events.
+    pub fn typescript_declarations(&self) -> String {
+        typescript::declarations(&self.schema())
+    }
+
+    /// Returns the JSON Schema manifest of all the registered functions, with
+    /// the schemas of the arguments and the result of each function, and the
+    /// schema of the payload of each event.
+    pub fn json_schema(&self) -> Value {
+        let BridgeSchema {
+            functions,
+            events,
+            definitions,
+        } = self.schema();
+        let functions = functions
+            .into_iter()
+            .map(|function| {
+                let mut schema = json!({
+                    "arguments": function.signature.arguments,
+                    "result": function.signature.result,
+                });
+                if function.stream {
+                    schema["stream"] = Value::Bool(true);
+                }
+                (function.name, schema)
+            })
+            .collect::<Map<_, _>>();
+        let events = events
+            .into_iter()
+            .map(|(name, schema)| (name, schema.to_value()))
+            .collect::<Map<_, _>>();
+
+        json!({
+            "$schema": "https://json-schema.org/draft/2020-12/schema",
+            "functions": functions,
+            "events": events,
+            "$defs": definitions,
+        })
+    }
+
+    /// Returns the signatures of the functions and the payload schemas of the
+    /// events sorted by name, with the schema definitions referenced by them.
+    fn schema(&self) -> BridgeSchema {
+        let mut generator = SchemaSettings::draft2020_12().into_generator();
+        let mut functions = self
+            .functions
+            .iter()
+            .map(|(name, func)| FunctionSchema {
+                name: name.clone(),
+                signature: func.signature(&mut generator),
+                stream: func.is_stream(),
+            })
+            .collect::<Vec<_>>();
+        functions.sort_by(|a, b| a.name.cmp(&b.name));
+
+        // The handlers of the same event should have the same payload type, so
+        // the first one is used.
+        let mut events = self
+            .events
+            .iter()
+            .filter_map(|(name, handlers)| {
+                let schema = handlers.first()?.payload_schema(&mut generator);
+                Some((name.clone(), schema))
+            })
+            .collect::<Vec<_>>();
+        events.sort_by(|(a, _), (b, _)| a.cmp(b));
+
+        BridgeSchema {
+            functions,
+            events,
+            definitions: generator.take_definitions(false),
+        }
+    }
+
+    /// Writes the TypeScript declarations and the JSON Schema manifest to the
+    /// paths of the `WEF_EXPORT_TYPES` and `WEF_EXPORT_SCHEMA` environment
+    /// variables, if any of them is set, see `cargo wef types`.
+    ///
+    /// Call it in the application right after building the registry, it does
+    /// nothing and returns `false` if none of the environment variables is set.
+    /// If it returns `true`, the application should exit, so that `cargo wef
+    /// types` can continue.
+    pub fn export_types_from_env(&self) -> std::io::Result<bool> {
+        let types_path = std::env::var_os(EXPORT_TYPES_ENV);
+        let schema_path = std::env::var_os(EXPORT_SCHEMA_ENV);
+        if types_path.is_none() && schema_path.is_none() {
+            return Ok(false);
+        }
+
+        if let Some(path) = types_path {
+            std::fs::write(&path, self.typescript_declarations())?;
+        }
+        if let Some(path) = schema_path {
+            let schema = serde_json::to_string_pretty(&self.json_schema())?;
+            std::fs::write(&path, schema)?;
+        }
+        Ok(true)
+    }
+
+    /// Calls the handlers of the event emitted from JavaScript, returns the
+    /// first error if the payload can't be deserialized by any of them.
+    pub(crate) fn emit_event(
+        &self,
+        frame: &Frame,
+        name: &str,
+        payload: &Value,
+    ) -> Result<(), CallFunctionError> {
+        let mut res = Ok(());
+        for handler in self.events.get(name).into_iter().flatten() {
+            res = res.and(handler.call(frame.clone(), payload.clone()));
+        }
+        res
+    }
+
+    pub(crate) fn call(
+        &self,
+        frame: Frame,
+        name: &str,
+        args: Vec<Value>,
+        callback: QueryCallback,
+        pending_calls: &PendingCalls,
+        query_id: i64,
+    ) {
         let Some(func) = self.functions.get(name) else {
             callback.result(Err(CallFunctionError::NotFound(name.to_string())));
             return;
         };
-        func.call(self.spawner.as_deref(), frame, args, callback)
+
+        // The spawned futures are tracked by the query id, so they can be
+        // cancelled from JavaScript.
+        let spawner = self.spawner.clone().map(|spawner| {
+            let pending_calls = pending_calls.clone();
+            move |fut: BoxFuture<'static, ()>| spawner(pending_calls.track(query_id, fut))
+        });
+        func.call(
+            spawner
+                .as_ref()
+                .map(|spawner| spawner as &(dyn Fn(BoxFuture<'static, ()>) + Send + Sync)),
+            frame,
+            args,
+            callback,
+        )
     }
 
     pub(crate) fn javascript(&self) -> String {
         let mut code = include_str!("inject.js").to_string();
 
-        for (name, func) in &*self.functions {
+        // Sorted by the name, so the generated code doesn't depend on the order
+        // of the map.
+        let mut functions = self.functions.iter().collect::<Vec<_>>();
+        functions.sort_by(|(a, _), (b, _)| a.cmp(b));
+
+        for (name, func) in functions {
+            // The dotted names are registered in the nested namespaces, e.g.:
+            // `jsBridge.fs.readFile`.
+            let mut path = "window.jsBridge".to_string();
+            let mut segments = name.split('.').peekable();
+            while let Some(segment) = segments.next() {
+                path += &format!("[{}]", Value::from(segment));
+                if segments.peek().is_some() {
+                    code += &format!("{path} = {path} || {{}};", path = path);
+                }
+            }
+
             let args = (0..func.num_arguments())
                 .map(|i| format!("arg{}", i))
                 .collect::<Vec<_>>()
                 .join(",");
+            let method = if func.is_stream() { "stream" } else { "call" };
             code += &format!(
-                r#"window.jsBridge.{name} = function({args}) {{
-                return window.jsBridge.__internal.call("{name}", [{args}]);
+                r#"{path} = function({params}) {{
+                return window.jsBridge.__internal.{method}({name}, [{args}], options);
             }};"#,
-                name = name,
+                path = path,
+                name = Value::from(name.as_str()),
+                params = if args.is_empty() {
+                    "options".to_string()
+                } else {
+                    format!("{},options", args)
+                },
+                method = method,
                 args = args
             );
         }
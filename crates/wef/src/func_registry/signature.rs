@@ -0,0 +1,10 @@
+use schemars::Schema;
+
+/// The JSON schemas of the arguments and the result of a registered function.
+#[derive(Debug, Clone)]
+pub struct FunctionSignature {
+    /// The schemas of the arguments in order.
+    pub arguments: Vec<Schema>,
+    /// The schema of the result, the error of a `Result` is not included.
+    pub result: Schema,
+}
@@ -0,0 +1,114 @@
+use futures_util::{Stream, StreamExt};
+use schemars::{JsonSchema, SchemaGenerator};
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+
+use crate::{
+    Frame,
+    func_registry::{
+        CallFunctionError, FunctionSignature, arguments::Arguments, into_result::IntoFunctionResult,
+    },
+};
+
+/// Represents a function type that returns a stream of items to JavaScript.
+pub trait StreamFunctionType<S, R> {
+    /// Number of arguments.
+    const NUM_ARGUMENTS: usize;
+
+    /// Returns the JSON schemas of the arguments and the items, the [`Frame`]
+    /// argument is not included.
+    fn signature(generator: &mut SchemaGenerator) -> FunctionSignature;
+
+    /// Calls the function with the given arguments, and returns the stream of
+    /// the items.
+    fn call(
+        &self,
+        frame: Frame,
+        args: Vec<Value>,
+    ) -> Result<
+        impl Stream<Item = Result<Value, CallFunctionError>> + Send + 'static,
+        CallFunctionError,
+    >;
+}
+
+macro_rules! impl_stream_function_types {
+    ($($name:ident),*) => {
+        impl<F, $($name,)* R, St, Ret> StreamFunctionType<($($name,)*), R> for F
+        where
+            F: Fn($($name),*) -> St + Send + Sync + 'static,
+            St: Stream<Item = Ret> + Send + 'static,
+            Ret: IntoFunctionResult<R> + Send,
+            R: JsonSchema,
+            $($name: DeserializeOwned + JsonSchema + Send,)*
+        {
+            const NUM_ARGUMENTS: usize = tuple_len::tuple_len!(($($name,)*));
+
+            fn signature(generator: &mut SchemaGenerator) -> FunctionSignature {
+                FunctionSignature {
+                    arguments: vec![$(generator.subschema_for::<$name>()),*],
+                    result: generator.subschema_for::<R>(),
+                }
+            }
+
+            fn call(
+                &self,
+                _frame: Frame,
+                args: Vec<Value>,
+            ) -> Result<impl Stream<Item = Result<Value, CallFunctionError>> + Send + 'static, CallFunctionError> {
+                #[allow(unused_mut, unused_variables)]
+                let mut args = Arguments::new(args, tuple_len::tuple_len!(($($name,)*)))?;
+
+                $(
+                    #[allow(non_snake_case)]
+                    let $name: $name = args.next()?;
+                )*
+
+                Ok(self($($name),*).map(|item| item.into_function_result()))
+            }
+        }
+
+        impl<F, $($name,)* R, St, Ret> StreamFunctionType<(Frame, $($name,)*), R> for F
+        where
+            F: Fn(Frame, $($name),*) -> St + Send + Sync + 'static,
+            St: Stream<Item = Ret> + Send + 'static,
+            Ret: IntoFunctionResult<R> + Send,
+            R: JsonSchema,
+            $($name: DeserializeOwned + JsonSchema + Send,)*
+        {
+            const NUM_ARGUMENTS: usize = tuple_len::tuple_len!(($($name,)*));
+
+            fn signature(generator: &mut SchemaGenerator) -> FunctionSignature {
+                FunctionSignature {
+                    arguments: vec![$(generator.subschema_for::<$name>()),*],
+                    result: generator.subschema_for::<R>(),
+                }
+            }
+
+            fn call(
+                &self,
+                frame: Frame,
+                args: Vec<Value>,
+            ) -> Result<impl Stream<Item = Result<Value, CallFunctionError>> + Send + 'static, CallFunctionError> {
+                #[allow(unused_mut, unused_variables)]
+                let mut args = Arguments::new(args, tuple_len::tuple_len!(($($name,)*)))?;
+
+                $(
+                    #[allow(non_snake_case)]
+                    let $name: $name = args.next()?;
+                )*
+
+                Ok(self(frame, $($name),*).map(|item| item.into_function_result()))
+            }
+        }
+    };
+}
+
+impl_stream_function_types!();
+impl_stream_function_types!(A1);
+impl_stream_function_types!(A1, A2);
+impl_stream_function_types!(A1, A2, A3);
+impl_stream_function_types!(A1, A2, A3, A4);
+impl_stream_function_types!(A1, A2, A3, A4, A5);
+impl_stream_function_types!(A1, A2, A3, A4, A5, A6);
+impl_stream_function_types!(A1, A2, A3, A4, A5, A6, A7);
+impl_stream_function_types!(A1, A2, A3, A4, A5, A6, A7, A8);
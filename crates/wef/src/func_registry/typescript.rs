@@ -0,0 +1,554 @@
+use schemars::Schema;
+use serde_json::{Map, Value};
+
+use crate::func_registry::FunctionSignature;
+
+/// The schema of a registered function.
+pub(crate) struct FunctionSchema {
+    /// The name of the function.
+    pub(crate) name: String,
+    /// The signature of the function, the result is the type of the items if
+    /// it's a stream function.
+    pub(crate) signature: FunctionSignature,
+    /// Whether the function returns a stream of items.
+    pub(crate) stream: bool,
+}
+
+/// The schemas of the functions and the events of a registry.
+pub(crate) struct BridgeSchema {
+    /// The functions.
+    pub(crate) functions: Vec<FunctionSchema>,
+    /// The payload schemas of the events emitted from JavaScript.
+    pub(crate) events: Vec<(String, Schema)>,
+    /// The schema definitions referenced by the functions and the events.
+    pub(crate) definitions: Map<String, Value>,
+}
+
+/// Generates the TypeScript declarations of `window.jsBridge` from the schemas
+/// of the functions, the events and the shared schema definitions.
+pub(crate) fn declarations(bridge: &BridgeSchema) -> String {
+    let mut code = String::from("// This file is generated by wef, do not edit it manually.\n\n");
+
+    for (name, schema) in sorted(&bridge.definitions) {
+        write_doc(&mut code, schema, "");
+        let name = type_name(name);
+        match interface_properties(schema) {
+            Some(properties) => {
+                code += &format!("export interface {} {{\n", name);
+                write_properties(&mut code, properties, schema);
+                code += "}\n\n";
+            }
+            None => code += &format!("export type {} = {};\n\n", name, ts_type(schema)),
+        }
+    }
+
+    code += "export interface CallOptions {\n";
+    code += "  /** Aborts the call, the function is cancelled in Rust. */\n";
+    code += "  signal?: AbortSignal;\n";
+    code += "}\n\n";
+
+    code += "export interface JsBridge {\n";
+    let mut functions = bridge
+        .functions
+        .iter()
+        .map(|function| (function.name.split('.').collect::<Vec<_>>(), function))
+        .collect::<Vec<_>>();
+    functions.sort_by(|(a, _), (b, _)| a.cmp(b));
+    write_functions(&mut code, &functions, 0);
+    code += "  addEventListener(callback: (message: any) => void): number;\n";
+    code += "  removeEventListener(id: number): void;\n";
+    code += "  on(name: string, callback: (payload: any) => void): number;\n";
+    code += "  off(id: number): void;\n";
+    for (name, payload) in &bridge.events {
+        code += &format!(
+            "  emit(name: {}, payload: {}): Promise<void>;\n",
+            Value::from(name.as_str()),
+            ts_type(payload.as_value())
+        );
+    }
+    code += "  emit(name: string, payload?: any): Promise<void>;\n";
+    code += "}\n\n";
+
+    code += "declare global {\n";
+    code += "  /** The error of the rejected calls. */\n";
+    code += "  class JsBridgeError extends Error {\n";
+    code += "    /** The error code, e.g.: `NOT_FOUND`. */\n";
+    code += "    readonly code: string;\n";
+    code += "    /** The additional data of the error. */\n";
+    code += "    readonly data: any;\n";
+    code += "  }\n";
+    code += "  var jsBridge: JsBridge;\n";
+    code += "}\n";
+    code
+}
+
+/// Writes the methods of the functions, the functions with dotted names are
+/// grouped into the nested namespaces, e.g.: `fs.readFile`.
+fn write_functions(code: &mut String, functions: &[(Vec<&str>, &FunctionSchema)], depth: usize) {
+    let indent = "  ".repeat(depth + 1);
+    let mut functions = functions;
+
+    while let Some(((path, function), rest)) = functions.split_first() {
+        if path.len() == depth + 1 {
+            write_method(code, path[depth], function, &indent);
+            functions = rest;
+            continue;
+        }
+
+        // The functions are sorted by the path, so the functions in the same
+        // namespace are adjacent.
+        let namespace = path[depth];
+        let len = functions
+            .iter()
+            .take_while(|(path, _)| path.len() > depth + 1 && path[depth] == namespace)
+            .count();
+        *code += &format!("{}{}: {{\n", indent, property_name(namespace));
+        write_functions(code, &functions[..len], depth + 1);
+        *code += &format!("{}}};\n", indent);
+        functions = &functions[len..];
+    }
+}
+
+fn write_method(code: &mut String, name: &str, function: &FunctionSchema, indent: &str) {
+    let arguments = &function.signature.arguments;
+    // The trailing arguments that accept `null` can be omitted.
+    let required = arguments
+        .iter()
+        .rposition(|schema| !accepts_null(schema.as_value()))
+        .map_or(0, |i| i + 1);
+    let mut args = arguments
+        .iter()
+        .enumerate()
+        .map(|(i, schema)| {
+            format!(
+                "arg{}{}: {}",
+                i,
+                if i < required { "" } else { "?" },
+                ts_type(schema.as_value())
+            )
+        })
+        .collect::<Vec<_>>();
+    args.push("options?: CallOptions".to_string());
+    *code += &format!(
+        "{}{}({}): {}<{}>;\n",
+        indent,
+        property_name(name),
+        args.join(", "),
+        if function.stream {
+            "AsyncIterable"
+        } else {
+            "Promise"
+        },
+        ts_type(function.signature.result.as_value())
+    );
+}
+
+/// Returns `true` if `null` is valid for the JSON schema, e.g.: `Option<T>`.
+fn accepts_null(schema: &Value) -> bool {
+    let object = match schema {
+        Value::Object(object) => object,
+        Value::Bool(value) => return *value,
+        _ => return false,
+    };
+
+    if let Some(value) = object.get("const") {
+        return value.is_null();
+    }
+    if let Some(values) = object.get("enum").and_then(Value::as_array) {
+        return values.iter().any(Value::is_null);
+    }
+    for key in ["anyOf", "oneOf"] {
+        if let Some(schemas) = object.get(key).and_then(Value::as_array) {
+            return schemas.iter().any(accepts_null);
+        }
+    }
+    match object.get("type") {
+        Some(Value::String(ty)) => ty == "null",
+        Some(Value::Array(types)) => types.iter().any(|ty| ty == "null"),
+        _ => false,
+    }
+}
+
+/// Converts the JSON schema to a TypeScript type.
+fn ts_type(schema: &Value) -> String {
+    let object = match schema {
+        Value::Object(object) => object,
+        Value::Bool(false) => return "never".to_string(),
+        _ => return "any".to_string(),
+    };
+
+    if let Some(reference) = object.get("$ref").and_then(Value::as_str) {
+        return type_name(reference.rsplit('/').next().unwrap_or(reference));
+    }
+    if let Some(value) = object.get("const") {
+        return value.to_string();
+    }
+    if let Some(values) = object.get("enum").and_then(Value::as_array) {
+        return union(values.iter().map(Value::to_string));
+    }
+    for key in ["anyOf", "oneOf"] {
+        if let Some(schemas) = object.get(key).and_then(Value::as_array) {
+            return union(schemas.iter().map(ts_type));
+        }
+    }
+    if let Some(schemas) = object.get("allOf").and_then(Value::as_array) {
+        return schemas
+            .iter()
+            .map(|schema| parenthesize(ts_type(schema)))
+            .collect::<Vec<_>>()
+            .join(" & ");
+    }
+
+    match object.get("type") {
+        Some(Value::String(ty)) => primitive_type(ty, object),
+        Some(Value::Array(types)) => union(
+            types
+                .iter()
+                .filter_map(Value::as_str)
+                .map(|ty| primitive_type(ty, object)),
+        ),
+        _ if object.contains_key("properties") => primitive_type("object", object),
+        _ => "any".to_string(),
+    }
+}
+
+fn primitive_type(ty: &str, object: &Map<String, Value>) -> String {
+    match ty {
+        "string" => "string".to_string(),
+        "number" | "integer" => "number".to_string(),
+        "boolean" => "boolean".to_string(),
+        "null" => "null".to_string(),
+        "array" => {
+            if let Some(items) = object.get("prefixItems").and_then(Value::as_array) {
+                let items = items.iter().map(ts_type).collect::<Vec<_>>();
+                format!("[{}]", items.join(", "))
+            } else if let Some(items) = object.get("items") {
+                format!("{}[]", parenthesize(ts_type(items)))
+            } else {
+                "any[]".to_string()
+            }
+        }
+        "object" => {
+            if let Some(properties) = object.get("properties").and_then(Value::as_object) {
+                let required = required(object);
+                let properties = sorted(properties)
+                    .into_iter()
+                    .map(|(name, schema)| {
+                        format!(
+                            "{}{}: {}",
+                            property_name(name),
+                            if required.contains(&name.as_str()) {
+                                ""
+                            } else {
+                                "?"
+                            },
+                            ts_type(schema)
+                        )
+                    })
+                    .collect::<Vec<_>>();
+                format!("{{ {} }}", properties.join("; "))
+            } else {
+                match object.get("additionalProperties") {
+                    Some(schema @ Value::Object(_)) => {
+                        format!("Record<string, {}>", ts_type(schema))
+                    }
+                    _ => "Record<string, any>".to_string(),
+                }
+            }
+        }
+        _ => "any".to_string(),
+    }
+}
+
+/// Returns the properties of the schema if it's a plain object that can be
+/// declared as an interface.
+fn interface_properties(schema: &Value) -> Option<&Map<String, Value>> {
+    let object = schema.as_object()?;
+    if ["$ref", "const", "enum", "anyOf", "oneOf", "allOf"]
+        .iter()
+        .any(|key| object.contains_key(*key))
+    {
+        return None;
+    }
+    if object
+        .get("type")
+        .is_some_and(|ty| ty.as_str() != Some("object"))
+    {
+        return None;
+    }
+    object.get("properties")?.as_object()
+}
+
+fn write_properties(code: &mut String, properties: &Map<String, Value>, schema: &Value) {
+    let required = schema.as_object().map(required).unwrap_or_default();
+    for (name, schema) in sorted(properties) {
+        write_doc(code, schema, "  ");
+        *code += &format!(
+            "  {}{}: {};\n",
+            property_name(name),
+            if required.contains(&name.as_str()) {
+                ""
+            } else {
+                "?"
+            },
+            ts_type(schema)
+        );
+    }
+}
+
+fn write_doc(code: &mut String, schema: &Value, indent: &str) {
+    let Some(description) = schema.get("description").and_then(Value::as_str) else {
+        return;
+    };
+    let lines = description.lines().collect::<Vec<_>>();
+    if let [line] = lines.as_slice() {
+        *code += &format!("{}/** {} */\n", indent, line);
+    } else {
+        *code += &format!("{}/**\n", indent);
+        for line in lines {
+            *code += &format!("{} * {}\n", indent, line);
+        }
+        *code += &format!("{} */\n", indent);
+    }
+}
+
+fn required(object: &Map<String, Value>) -> Vec<&str> {
+    object
+        .get("required")
+        .and_then(Value::as_array)
+        .map(|names| names.iter().filter_map(Value::as_str).collect())
+        .unwrap_or_default()
+}
+
+/// Returns the entries of the map sorted by the keys, the order of
+/// [`serde_json::Map`] depends on whether its `preserve_order` feature is
+/// enabled in the dependency graph.
+fn sorted(map: &Map<String, Value>) -> Vec<(&String, &Value)> {
+    let mut entries = map.iter().collect::<Vec<_>>();
+    entries.sort_by(|(a, _), (b, _)| a.cmp(b));
+    entries
+}
+
+fn union(types: impl Iterator<Item = String>) -> String {
+    let mut members = Vec::<String>::new();
+    for ty in types {
+        if !members.contains(&ty) {
+            members.push(ty);
+        }
+    }
+    if members.is_empty() {
+        "never".to_string()
+    } else {
+        members.join(" | ")
+    }
+}
+
+/// Wraps the union and intersection types in parentheses, e.g.: The items of an array.
+fn parenthesize(ty: String) -> String {
+    if ty.contains(" | ") || ty.contains(" & ") {
+        format!("({})", ty)
+    } else {
+        ty
+    }
+}
+
+fn is_identifier(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_' || c == '$')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '$')
+}
+
+fn property_name(name: &str) -> String {
+    if is_identifier(name) {
+        name.to_string()
+    } else {
+        Value::from(name).to_string()
+    }
+}
+
+/// Converts the name of the schema definition to a valid type name.
+fn type_name(name: &str) -> String {
+    let name = name.replace("~1", "/").replace("~0", "~");
+    name.chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '_' || c == '$' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_ts_type() {
+        assert_eq!(
+            ts_type(&json!({ "type": "integer", "format": "int32" })),
+            "number"
+        );
+        assert_eq!(
+            ts_type(&json!({ "type": ["string", "null"] })),
+            "string | null"
+        );
+        assert_eq!(
+            ts_type(&json!({ "type": "array", "items": { "type": ["number", "null"] } })),
+            "(number | null)[]"
+        );
+        assert_eq!(
+            ts_type(&json!({
+                "type": "array",
+                "prefixItems": [{ "type": "string" }, { "type": "boolean" }]
+            })),
+            "[string, boolean]"
+        );
+        assert_eq!(
+            ts_type(&json!({ "type": "object", "additionalProperties": { "type": "number" } })),
+            "Record<string, number>"
+        );
+        assert_eq!(
+            ts_type(&json!({ "type": "string", "enum": ["a", "b"] })),
+            r#""a" | "b""#
+        );
+        assert_eq!(
+            ts_type(&json!({ "anyOf": [{ "$ref": "#/$defs/Point" }, { "type": "null" }] })),
+            "Point | null"
+        );
+        assert_eq!(
+            ts_type(&json!({
+                "type": "object",
+                "properties": { "x": { "type": "number" }, "my-y": { "type": "number" } },
+                "required": ["x"]
+            })),
+            r#"{ "my-y"?: number; x: number }"#
+        );
+        assert_eq!(ts_type(&json!(true)), "any");
+    }
+
+    #[test]
+    fn test_accepts_null() {
+        assert!(accepts_null(&json!({ "type": ["integer", "null"] })));
+        assert!(accepts_null(&json!({
+            "anyOf": [{ "$ref": "#/$defs/Point" }, { "type": "null" }]
+        })));
+        assert!(accepts_null(&json!(true)));
+        assert!(!accepts_null(&json!({ "type": "integer" })));
+        assert!(!accepts_null(&json!({ "$ref": "#/$defs/Point" })));
+    }
+
+    #[test]
+    fn test_declarations() {
+        let functions = vec![
+            FunctionSchema {
+                name: "move".to_string(),
+                signature: FunctionSignature {
+                    arguments: vec![
+                        json!({ "$ref": "#/$defs/Point" }).try_into().unwrap(),
+                        json!({ "type": "integer" }).try_into().unwrap(),
+                    ],
+                    result: json!({ "type": "null" }).try_into().unwrap(),
+                },
+                stream: false,
+            },
+            FunctionSchema {
+                name: "fs.readFile".to_string(),
+                signature: FunctionSignature {
+                    arguments: vec![
+                        json!({ "type": "string" }).try_into().unwrap(),
+                        json!({ "type": ["string", "null"] }).try_into().unwrap(),
+                    ],
+                    result: json!({ "type": "string" }).try_into().unwrap(),
+                },
+                stream: false,
+            },
+            FunctionSchema {
+                name: "fs.watch.start".to_string(),
+                signature: FunctionSignature {
+                    arguments: vec![json!({ "type": "string" }).try_into().unwrap()],
+                    result: json!({ "type": "null" }).try_into().unwrap(),
+                },
+                stream: false,
+            },
+            FunctionSchema {
+                name: "ticks".to_string(),
+                signature: FunctionSignature {
+                    arguments: vec![],
+                    result: json!({ "type": "integer" }).try_into().unwrap(),
+                },
+                stream: true,
+            },
+        ];
+        let definitions = json!({
+            "Point": {
+                "description": "A point.",
+                "type": "object",
+                "properties": { "x": { "type": "number" }, "y": { "type": "number" } },
+                "required": ["x", "y"]
+            },
+            "Color": { "type": "string", "enum": ["red", "green"] }
+        });
+
+        let events = vec![(
+            "clicked".to_string(),
+            json!({ "type": "integer" }).try_into().unwrap(),
+        )];
+
+        assert_eq!(
+            declarations(&BridgeSchema {
+                functions,
+                events,
+                definitions: definitions.as_object().unwrap().clone(),
+            }),
+            r#"// This file is generated by wef, do not edit it manually.
+
+export type Color = "red" | "green";
+
+/** A point. */
+export interface Point {
+  x: number;
+  y: number;
+}
+
+export interface CallOptions {
+  /** Aborts the call, the function is cancelled in Rust. */
+  signal?: AbortSignal;
+}
+
+export interface JsBridge {
+  fs: {
+    readFile(arg0: string, arg1?: string | null, options?: CallOptions): Promise<string>;
+    watch: {
+      start(arg0: string, options?: CallOptions): Promise<null>;
+    };
+  };
+  move(arg0: Point, arg1: number, options?: CallOptions): Promise<null>;
+  ticks(options?: CallOptions): AsyncIterable<number>;
+  addEventListener(callback: (message: any) => void): number;
+  removeEventListener(id: number): void;
+  on(name: string, callback: (payload: any) => void): number;
+  off(id: number): void;
+  emit(name: "clicked", payload: number): Promise<void>;
+  emit(name: string, payload?: any): Promise<void>;
+}
+
+declare global {
+  /** The error of the rejected calls. */
+  class JsBridgeError extends Error {
+    /** The error code, e.g.: `NOT_FOUND`. */
+    readonly code: string;
+    /** The additional data of the error. */
+    readonly data: any;
+  }
+  var jsBridge: JsBridge;
+}
+"#
+        );
+    }
+}
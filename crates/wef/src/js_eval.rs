@@ -0,0 +1,238 @@
+use std::{
+    collections::HashMap,
+    sync::{Mutex, Weak},
+    time::Duration,
+};
+
+use futures_channel::oneshot;
+use serde::Deserialize;
+use serde_json::Value;
+
+/// The default timeout of [`Frame::eval`](crate::Frame::eval).
+pub const DEFAULT_EVAL_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Error type of evaluating JavaScript with [`Frame::eval`](crate::Frame::eval).
+#[derive(Debug, thiserror::Error)]
+pub enum JsError {
+    /// The script threw an exception or returned a rejected promise.
+    #[error("{message}")]
+    Exception {
+        /// The message of the exception.
+        message: String,
+        /// The stack trace of the exception, if it's an `Error` object.
+        stack: Option<String>,
+    },
+    /// The result was not returned in time, e.g. the page navigated away
+    /// before the script finished.
+    #[error("JavaScript evaluation timed out after {0:?}")]
+    Timeout(Duration),
+    /// The evaluation was cancelled before the result was returned, e.g. the
+    /// browser was closed.
+    #[error("JavaScript evaluation was cancelled")]
+    Cancelled,
+    /// The frame has no identifier, e.g. it doesn't exist yet, so the result
+    /// can't be returned from it.
+    #[error("the frame to evaluate JavaScript in does not exist")]
+    InvalidFrame,
+    /// The result can't be deserialized to the expected type.
+    #[error("failed to deserialize the result of JavaScript evaluation: {0}")]
+    Deserialize(#[from] serde_json::Error),
+}
+
+/// The exception reported by `jsBridge.__internal.evalScript`.
+#[derive(Debug, Deserialize)]
+pub(crate) struct JsException {
+    message: String,
+    #[serde(default)]
+    stack: Option<String>,
+}
+
+type EvalSender = oneshot::Sender<Result<Value, JsError>>;
+
+/// An evaluation waiting for the result from the frame.
+struct PendingEval {
+    /// The identifier of the evaluated frame, only this frame can report the
+    /// result.
+    frame_id: Option<String>,
+    sender: EvalSender,
+}
+
+/// The pending evaluations of a browser, the results are reported from
+/// JavaScript by `cefQuery`.
+///
+/// The browser state owns it, the frames keep a weak reference, so the
+/// pending evaluations are cancelled when the browser is closed.
+#[derive(Default)]
+pub(crate) struct PendingEvals {
+    next_id: u64,
+    evals: HashMap<u64, PendingEval>,
+}
+
+impl PendingEvals {
+    /// Creates a pending evaluation of the frame, the result is sent to the
+    /// receiver by [`PendingEvals::resolve`].
+    pub(crate) fn register(
+        &mut self,
+        frame_id: Option<String>,
+    ) -> (u64, oneshot::Receiver<Result<Value, JsError>>) {
+        self.next_id += 1;
+        let (sender, receiver) = oneshot::channel();
+        self.evals
+            .insert(self.next_id, PendingEval { frame_id, sender });
+        (self.next_id, receiver)
+    }
+
+    /// Removes the pending evaluation, e.g. when it's timed out.
+    pub(crate) fn unregister(&mut self, id: u64) {
+        self.evals.remove(&id);
+    }
+
+    /// Sends the result reported from JavaScript to the pending evaluation.
+    ///
+    /// The result is ignored if it's not reported by the evaluated frame.
+    pub(crate) fn resolve(
+        &mut self,
+        id: u64,
+        frame_id: Option<&str>,
+        result: Value,
+        error: Option<JsException>,
+    ) {
+        let is_evaluated_frame = self
+            .evals
+            .get(&id)
+            .is_some_and(|eval| frame_id.is_some() && eval.frame_id.as_deref() == frame_id);
+        if !is_evaluated_frame {
+            return;
+        }
+
+        let Some(eval) = self.evals.remove(&id) else {
+            return;
+        };
+        _ = eval.sender.send(match error {
+            Some(JsException { message, stack }) => Err(JsError::Exception { message, stack }),
+            None => Ok(result),
+        });
+    }
+}
+
+/// Unregisters the pending evaluation when it's dropped, so the evaluation
+/// that is timed out or dropped before the result is returned doesn't stay in
+/// the [`PendingEvals`].
+pub(crate) struct PendingEvalGuard {
+    pub(crate) evals: Weak<Mutex<PendingEvals>>,
+    pub(crate) id: u64,
+}
+
+impl Drop for PendingEvalGuard {
+    fn drop(&mut self) {
+        let Some(evals) = self.evals.upgrade() else {
+            return;
+        };
+        if let Ok(mut evals) = evals.lock() {
+            evals.unregister(self.id);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use serde_json::json;
+
+    use super::*;
+
+    fn frame(id: &str) -> Option<String> {
+        Some(id.to_string())
+    }
+
+    #[test]
+    fn test_resolve() {
+        let mut evals = PendingEvals::default();
+        let (id1, mut rx1) = evals.register(frame("1"));
+        let (id2, mut rx2) = evals.register(frame("2"));
+        assert_ne!(id1, id2);
+
+        evals.resolve(id1, Some("1"), json!(42), None);
+        assert!(matches!(rx1.try_recv(), Ok(Some(Ok(value))) if value == json!(42)));
+
+        evals.resolve(
+            id2,
+            Some("2"),
+            Value::Null,
+            Some(JsException {
+                message: "boom".to_string(),
+                stack: None,
+            }),
+        );
+        assert!(matches!(
+            rx2.try_recv(),
+            Ok(Some(Err(JsError::Exception { message, .. }))) if message == "boom"
+        ));
+
+        // The resolved evaluation is removed.
+        assert!(evals.evals.is_empty());
+        evals.resolve(id1, Some("1"), json!(0), None);
+    }
+
+    #[test]
+    fn test_resolve_from_other_frame() {
+        let mut evals = PendingEvals::default();
+        let (id, mut rx) = evals.register(frame("1"));
+
+        evals.resolve(id, Some("2"), json!("forged"), None);
+        evals.resolve(id, None, json!("forged"), None);
+        assert!(matches!(rx.try_recv(), Ok(None)));
+
+        evals.resolve(id, Some("1"), json!("ok"), None);
+        assert!(matches!(rx.try_recv(), Ok(Some(Ok(value))) if value == json!("ok")));
+
+        // The frame that doesn't exist can't be resolved by any frame.
+        let (id, mut rx) = evals.register(None);
+        evals.resolve(id, None, json!("forged"), None);
+        assert!(matches!(rx.try_recv(), Ok(None)));
+    }
+
+    #[test]
+    fn test_unregister() {
+        let mut evals = PendingEvals::default();
+        let (id, mut rx) = evals.register(frame("1"));
+
+        evals.unregister(id);
+        assert!(rx.try_recv().is_err());
+        evals.resolve(id, Some("1"), json!(1), None);
+        evals.unregister(id);
+    }
+
+    #[test]
+    fn test_guard() {
+        let evals = Arc::new(Mutex::new(PendingEvals::default()));
+        let (id, mut rx) = evals.lock().unwrap().register(frame("1"));
+        let guard = PendingEvalGuard {
+            evals: Arc::downgrade(&evals),
+            id,
+        };
+
+        drop(guard);
+        assert!(evals.lock().unwrap().evals.is_empty());
+        assert!(rx.try_recv().is_err());
+
+        // The browser is closed before the guard is dropped.
+        let (id, _rx) = evals.lock().unwrap().register(frame("1"));
+        let guard = PendingEvalGuard {
+            evals: Arc::downgrade(&evals),
+            id,
+        };
+        drop(evals);
+        drop(guard);
+    }
+
+    #[test]
+    fn test_drop() {
+        let mut evals = PendingEvals::default();
+        let (_, mut rx) = evals.register(frame("1"));
+
+        drop(evals);
+        assert!(rx.try_recv().is_err());
+    }
+}
@@ -18,6 +18,7 @@ mod func_registry;
 mod geom;
 mod input;
 mod js_dialog;
+mod js_eval;
 mod query;
 #[cfg(target_os = "macos")]
 mod sandbox_context;
@@ -42,11 +43,12 @@ pub use frame::Frame;
 pub use framework_loader::FrameworkLoader;
 pub use func_registry::{
     AsyncFuncRegistryBuilder, AsyncFunctionType, CallFunctionError, FuncRegistry,
-    FuncRegistryBuilder, FunctionType,
+    FuncRegistryBuilder, FunctionError, FunctionSignature, FunctionType, StreamFunctionType,
 };
 pub use geom::{Point, Rect, Size};
 pub use input::{KeyCode, KeyModifier, MouseButton};
 pub use js_dialog::{JsDialogCallback, JsDialogType};
+pub use js_eval::{DEFAULT_EVAL_TIMEOUT, JsError};
 #[cfg(target_os = "macos")]
 pub use sandbox_context::SandboxContext;
 pub use serde_json::Value;
@@ -27,13 +27,27 @@ impl QueryCallback {
         }
     }
 
-    fn success(self, resp: Value) {
+    /// Sends an item of the stream, the persistent query is kept until
+    /// [`QueryCallback::done`] is called.
+    pub(crate) fn send(&self, resp: Value) {
         let resp = CString::new(serde_json::to_string(&resp).unwrap_or_default()).unwrap();
         unsafe { wef_query_callback_success(self.0, resp.as_ptr()) }
     }
 
+    /// Finishes the stream of the persistent query, with the error code `0`.
+    pub(crate) fn done(self) {
+        unsafe { wef_query_callback_failure(self.0, 0, c"".as_ptr()) }
+    }
+
+    fn success(self, resp: Value) {
+        self.send(resp);
+    }
+
+    /// Sends the error as a JSON object `{ code, message, data }`, with the
+    /// error code `-1`.
     fn failure(self, err: CallFunctionError) {
-        let err = CString::new(err.to_string()).unwrap();
-        unsafe { wef_query_callback_failure(self.0, err.as_ptr()) }
+        let err = err.to_json().to_string();
+        let err = CString::new(err).unwrap();
+        unsafe { wef_query_callback_failure(self.0, -1, err.as_ptr()) }
     }
 }
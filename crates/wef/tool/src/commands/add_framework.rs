@@ -2,24 +2,32 @@ use std::path::PathBuf;
 
 use anyhow::Result;
 
-#[allow(unused_variables)]
+use crate::internal::CefBuildsPlatform;
+
 pub(crate) fn add_framework(
     app_path: PathBuf,
     release: bool,
     force: bool,
     wef_version: Option<String>,
     wef_path: Option<PathBuf>,
+    platform: CefBuildsPlatform,
 ) -> Result<()> {
     let cef_root = crate::internal::find_cef_root();
-    crate::internal::add_cef_framework(&cef_root, &app_path, release, force)?;
+    crate::internal::add_cef_framework(&cef_root, &app_path, release, force, platform)?;
 
-    #[cfg(target_os = "macos")]
-    crate::internal::add_helper(
-        &app_path,
-        wef_version.as_deref(),
-        wef_path.as_deref(),
-        release,
-        force,
-    )?;
+    if platform.os() == Some("macos") {
+        // The helper processes are compiled for the host.
+        anyhow::ensure!(
+            std::env::consts::OS == "macos",
+            "the helper processes of MacOS apps can only be built on MacOS"
+        );
+        crate::internal::add_helper(
+            &app_path,
+            wef_version.as_deref(),
+            wef_path.as_deref(),
+            release,
+            force,
+        )?;
+    }
     Ok(())
 }
@@ -1,7 +1,7 @@
 use std::{
     ffi::OsStr,
     fs::File,
-    io::BufWriter,
+    io::{BufWriter, Write},
     path::{Path, PathBuf},
     process::Command,
 };
@@ -9,10 +9,11 @@ use std::{
 use anyhow::{Context, Result};
 use askama::Template;
 use cargo_metadata::{Metadata, MetadataCommand};
+use clap::ValueEnum;
 use icns::IconFamily;
 use image::GenericImageView;
 
-use crate::internal::{InfoPlist, add_cef_framework, add_helper};
+use crate::internal::{CefBuildsPlatform, DesktopEntry, InfoPlist, add_cef_framework, add_helper};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 enum BinaryKind {
@@ -170,6 +171,13 @@ fn create_plist(binary_info: &BinaryInfo, bundle_type: Option<&str>) -> Result<I
     Ok(plist)
 }
 
+fn is_retina(path: &Path) -> bool {
+    path.file_stem()
+        .and_then(OsStr::to_str)
+        .map(|stem| stem.ends_with("@2x"))
+        .unwrap_or(false)
+}
+
 fn create_icns_file(
     package_path: &Path,
     resources_dir: &Path,
@@ -233,13 +241,6 @@ fn create_icns_file(
         }
     }
 
-    fn is_retina(path: &Path) -> bool {
-        path.file_stem()
-            .and_then(OsStr::to_str)
-            .map(|stem| stem.ends_with("@2x"))
-            .unwrap_or(false)
-    }
-
     let mut images_to_resize: Vec<(image::DynamicImage, u32, u32)> = vec![];
     for icon_path in &plist.icons {
         let icon_path = package_path.join(icon_path);
@@ -308,12 +309,279 @@ fn bundle_macos_app(
         .with_context(|| format!("create file at {}", plist_path.display()))?;
 
     println!("Add CEF Framework...");
-    add_cef_framework(cef_root, &app_path, release, false)?;
+    add_cef_framework(cef_root, &app_path, release, false, CefBuildsPlatform::Auto)?;
     println!("Add Helper Processes...");
     add_helper(&app_path, wef_version, wef_path, release, false)?;
     Ok(macos_path.join(filename))
 }
 
+/// The standard sizes of the icons in the hicolor theme.
+const LINUX_ICON_SIZES: &[u32] = &[16, 22, 24, 32, 48, 64, 96, 128, 192, 256, 512];
+
+/// Creates the PNG icons of the hicolor theme from the bundle icons, returns
+/// the path of the largest one.
+fn create_linux_icons(
+    package_path: &Path,
+    icons_path: &Path,
+    plist: &InfoPlist,
+) -> Result<Option<PathBuf>> {
+    let mut largest: Option<(u32, PathBuf)> = None;
+
+    for icon_path in &plist.icons {
+        let icon_path = package_path.join(icon_path);
+        if icon_path.extension() == Some(OsStr::new("icns")) {
+            println!("Skip ICNS icon {}", icon_path.display());
+            continue;
+        }
+
+        let icon = image::open(&icon_path)
+            .with_context(|| format!("load image {}", icon_path.display()))?;
+        let (w, h) = icon.dimensions();
+        let density = if is_retina(&icon_path) { 2 } else { 1 };
+        let Some(size) = LINUX_ICON_SIZES
+            .iter()
+            .copied()
+            .rev()
+            .find(|size| size * density <= w.min(h))
+        else {
+            println!("Skip icon {}, it's too small", icon_path.display());
+            continue;
+        };
+        let pixel_size = size * density;
+        let icon = if (w, h) != (pixel_size, pixel_size) {
+            icon.resize_exact(pixel_size, pixel_size, image::imageops::Lanczos3)
+        } else {
+            icon
+        };
+
+        let size_dir = if density > 1 {
+            format!("{size}x{size}@{density}", size = size, density = density)
+        } else {
+            format!("{size}x{size}", size = size)
+        };
+        let apps_path = icons_path.join(size_dir).join("apps");
+        std::fs::create_dir_all(&apps_path)?;
+        let target_path = apps_path.join(format!("{}.png", plist.identifier));
+        icon.save(&target_path)
+            .with_context(|| format!("write icon {}", target_path.display()))?;
+
+        if largest
+            .as_ref()
+            .is_none_or(|(largest_size, _)| pixel_size > *largest_size)
+        {
+            largest = Some((pixel_size, target_path));
+        }
+    }
+
+    Ok(largest.map(|(_, path)| path))
+}
+
+/// Creates an AppImage-style directory, the CEF framework is added to the
+/// `usr/lib/<name>` directory by the caller, which is returned.
+///
+/// ```text
+/// <name>.AppDir
+/// ├── AppRun                         <= the launcher
+/// ├── <identifier>.desktop
+/// ├── <identifier>.png
+/// ├── .DirIcon
+/// └── usr
+///     ├── lib/<name>/<name>          <= the binary and the CEF framework
+///     └── share
+///         ├── applications/<identifier>.desktop
+///         └── icons/hicolor/<size>x<size>/apps/<identifier>.png
+/// ```
+fn create_linux_app_dir(
+    exec_path: &Path,
+    package_path: &Path,
+    app_path: &Path,
+    plist: &InfoPlist,
+) -> Result<PathBuf> {
+    let filename = exec_path.file_name().unwrap().to_string_lossy().to_string();
+    // Remove the previous build, so the stale icons and files are not bundled.
+    if app_path.exists() {
+        std::fs::remove_dir_all(app_path)
+            .with_context(|| format!("remove {}", app_path.display()))?;
+    }
+    let lib_path = app_path.join("usr").join("lib").join(&filename);
+    std::fs::create_dir_all(&lib_path).context("create app directory")?;
+
+    let binary_path = lib_path.join(&filename);
+    std::fs::copy(exec_path, &binary_path).context("copy binary to app directory")?;
+
+    // Load `libcef.so` next to the binary, wherever the directory is moved to.
+    match Command::new("patchelf")
+        .arg("--set-rpath")
+        .arg("$ORIGIN")
+        .arg(&binary_path)
+        .status()
+    {
+        Ok(status) if status.success() => {}
+        Ok(_) => println!("Failed to set rpath, the launcher sets LD_LIBRARY_PATH instead"),
+        Err(_) => println!("patchelf is not found, the launcher sets LD_LIBRARY_PATH instead"),
+    }
+
+    let launcher_path = app_path.join("AppRun");
+    std::fs::write(
+        &launcher_path,
+        format!(
+            r#"#!/bin/sh
+HERE="$(dirname "$(readlink -f "$0")")"
+export LD_LIBRARY_PATH="$HERE/usr/lib/{name}${{LD_LIBRARY_PATH:+:$LD_LIBRARY_PATH}}"
+exec "$HERE/usr/lib/{name}/{name}" "$@"
+"#,
+            name = filename
+        ),
+    )
+    .with_context(|| format!("create launcher at {}", launcher_path.display()))?;
+    set_executable(&launcher_path)?;
+
+    println!("Create icons...");
+    let share_path = app_path.join("usr").join("share");
+    let icon = create_linux_icons(
+        package_path,
+        &share_path.join("icons").join("hicolor"),
+        plist,
+    )
+    .context("create icons")?;
+    if let Some(icon_path) = &icon {
+        for target_path in [
+            app_path.join(format!("{}.png", plist.identifier)),
+            app_path.join(".DirIcon"),
+        ] {
+            std::fs::copy(icon_path, &target_path).with_context(|| {
+                format!("copy {} to {}", icon_path.display(), target_path.display())
+            })?;
+        }
+    }
+
+    // `Exec` is the binary name as required by `appimagetool`, it's launched by `AppRun` in the
+    // AppImage. The binary is not in the PATH, so the entry installed from the extracted
+    // directory needs `Exec` pointing to `AppRun`.
+    let desktop_entry =
+        DesktopEntry::new(plist, &filename, icon.map(|_| plist.identifier.clone())).render()?;
+    let applications_path = share_path.join("applications");
+    std::fs::create_dir_all(&applications_path)?;
+    for desktop_path in [
+        app_path.join(format!("{}.desktop", plist.identifier)),
+        applications_path.join(format!("{}.desktop", plist.identifier)),
+    ] {
+        std::fs::write(&desktop_path, &desktop_entry)
+            .with_context(|| format!("create file at {}", desktop_path.display()))?;
+    }
+
+    Ok(lib_path)
+}
+
+#[cfg(unix)]
+fn set_executable(path: &Path) -> Result<()> {
+    use std::os::unix::fs::PermissionsExt;
+
+    std::fs::set_permissions(path, std::fs::Permissions::from_mode(0o755))
+        .with_context(|| format!("set permissions of {}", path.display()))
+}
+
+#[cfg(not(unix))]
+fn set_executable(_path: &Path) -> Result<()> {
+    Ok(())
+}
+
+fn create_tar_gz(app_path: &Path, root_name: &str, output_path: &Path) -> Result<()> {
+    let file = BufWriter::new(File::create(output_path)?);
+    let mut builder = tar::Builder::new(flate2::write::GzEncoder::new(
+        file,
+        flate2::Compression::default(),
+    ));
+    builder.follow_symlinks(false);
+    builder.append_dir_all(root_name, app_path)?;
+    builder.into_inner()?.finish()?.flush()?;
+    Ok(())
+}
+
+/// Map the Rust architecture name to the one expected by appimagetool.
+fn appimage_arch(arch: &str) -> &str {
+    match arch {
+        "x86" => "i686",
+        "arm" => "armhf",
+        _ => arch,
+    }
+}
+
+fn create_app_image(app_path: &Path, output_path: &Path) -> Result<()> {
+    match Command::new("appimagetool")
+        .arg(app_path)
+        .arg(output_path)
+        .env("ARCH", appimage_arch(std::env::consts::ARCH))
+        .status()
+    {
+        Ok(status) => anyhow::ensure!(status.success(), "failed to create AppImage"),
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => anyhow::bail!(
+            "appimagetool is not found, install it from https://github.com/AppImage/appimagetool"
+        ),
+        Err(err) => return Err(err).context("run appimagetool"),
+    }
+    Ok(())
+}
+
+fn bundle_linux_app(
+    exec_path: &Path,
+    binary_info: BinaryInfo,
+    cef_root: &Path,
+    release: bool,
+    bundle_type: Option<&str>,
+    format: LinuxBundleFormat,
+) -> Result<PathBuf> {
+    let filename = exec_path.file_name().unwrap().to_string_lossy().to_string();
+    let target_dir = exec_path.parent().unwrap();
+    let app_path = target_dir.join(format!("{}.AppDir", filename));
+
+    let plist = create_plist(&binary_info, bundle_type)?;
+    let lib_path = create_linux_app_dir(exec_path, &binary_info.package_path, &app_path, &plist)?;
+
+    println!("Add CEF Framework...");
+    add_cef_framework(cef_root, &lib_path, release, false, CefBuildsPlatform::Auto)?;
+
+    let output_name = format!(
+        "{}-{}-{}",
+        filename,
+        plist
+            .bundle_short_version
+            .as_deref()
+            .unwrap_or(&binary_info.version),
+        std::env::consts::ARCH
+    );
+    match format {
+        LinuxBundleFormat::Dir => {}
+        LinuxBundleFormat::TarGz => {
+            println!("Create tar.gz archive...");
+            let output_path = target_dir.join(format!("{}.tar.gz", output_name));
+            create_tar_gz(&app_path, &output_name, &output_path)
+                .with_context(|| format!("create archive {}", output_path.display()))?;
+            println!("Created {}", output_path.display());
+        }
+        LinuxBundleFormat::AppImage => {
+            println!("Create AppImage...");
+            let output_path = target_dir.join(format!("{}.AppImage", output_name));
+            create_app_image(&app_path, &output_path)?;
+            println!("Created {}", output_path.display());
+        }
+    }
+
+    Ok(app_path.join("AppRun"))
+}
+
+/// The output format of the Linux application bundle.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub(crate) enum LinuxBundleFormat {
+    /// A relocatable AppImage-style directory
+    Dir,
+    /// A `.tar.gz` archive of the directory
+    TarGz,
+    /// An AppImage created with `appimagetool`
+    AppImage,
+}
+
+#[allow(clippy::too_many_arguments)]
 pub(crate) fn build(
     package: Option<String>,
     bin: Option<String>,
@@ -322,6 +590,7 @@ pub(crate) fn build(
     wef_version: Option<&str>,
     wef_path: Option<&Path>,
     bundle_type: Option<&str>,
+    linux_bundle: Option<LinuxBundleFormat>,
 ) -> Result<PathBuf> {
     let cef_root = crate::internal::find_cef_root();
     println!("Using CEF_ROOT: {}", cef_root.display());
@@ -356,8 +625,15 @@ pub(crate) fn build(
         .target_directory
         .join(if release { "release" } else { "debug" });
 
-    match std::env::consts::OS {
-        "macos" => {
+    if std::env::consts::OS != "linux" {
+        anyhow::ensure!(
+            linux_bundle.is_none(),
+            "linux-bundle argument is used only on Linux"
+        );
+    }
+
+    match (std::env::consts::OS, linux_bundle) {
+        ("macos", _) => {
             let (exec_path, binary_info) = execute_path(
                 &metadata,
                 target_dir.as_std_path(),
@@ -375,13 +651,36 @@ pub(crate) fn build(
                 bundle_type,
             )
         }
-        "windows" | "linux" => {
+        ("linux", Some(format)) => {
+            let (exec_path, binary_info) = execute_path(
+                &metadata,
+                target_dir.as_std_path(),
+                package.as_deref(),
+                bin.as_deref(),
+                example.as_deref(),
+            )?;
+            bundle_linux_app(
+                &exec_path,
+                binary_info,
+                &cef_root,
+                release,
+                bundle_type,
+                format,
+            )
+        }
+        ("windows" | "linux", _) => {
             anyhow::ensure!(
                 bundle_type.is_none(),
-                "bundle-type argument is used only on macOS"
+                "bundle-type argument is used only on macOS or with linux-bundle"
             );
 
-            add_cef_framework(&cef_root, target_dir.as_std_path(), release, false)?;
+            add_cef_framework(
+                &cef_root,
+                target_dir.as_std_path(),
+                release,
+                false,
+                CefBuildsPlatform::Auto,
+            )?;
             execute_path(
                 &metadata,
                 target_dir.as_std_path(),
@@ -710,4 +1009,82 @@ mod tests {
             })
         );
     }
+
+    #[test]
+    fn linux_app_dir() {
+        let dir = tempfile::tempdir().unwrap();
+        let exec_path = dir.path().join("app");
+        std::fs::write(&exec_path, "binary").unwrap();
+        let icons_path = dir.path().join("icons");
+        std::fs::create_dir_all(&icons_path).unwrap();
+        image::RgbaImage::new(300, 300)
+            .save(icons_path.join("icon.png"))
+            .unwrap();
+        image::RgbaImage::new(64, 64)
+            .save(icons_path.join("icon@2x.png"))
+            .unwrap();
+
+        let mut plist = InfoPlist::new("test-app", "io.github.wef.test-app");
+        plist.icons = vec![
+            "icons/icon.png".to_string(),
+            "icons/icon@2x.png".to_string(),
+        ];
+        let app_path = dir.path().join("app.AppDir");
+        // The files of the previous build are removed.
+        std::fs::create_dir_all(&app_path).unwrap();
+        std::fs::write(app_path.join("stale.desktop"), "stale").unwrap();
+        let lib_path = create_linux_app_dir(&exec_path, dir.path(), &app_path, &plist).unwrap();
+        assert!(!app_path.join("stale.desktop").exists());
+
+        assert_eq!(lib_path, app_path.join("usr/lib/app"));
+        assert_eq!(
+            std::fs::read_to_string(lib_path.join("app")).unwrap(),
+            "binary"
+        );
+        assert!(
+            std::fs::read_to_string(app_path.join("AppRun"))
+                .unwrap()
+                .contains(r#"exec "$HERE/usr/lib/app/app" "$@""#)
+        );
+
+        let hicolor_path = app_path.join("usr/share/icons/hicolor");
+        assert_eq!(
+            image::open(hicolor_path.join("256x256/apps/io.github.wef.test-app.png"))
+                .unwrap()
+                .dimensions(),
+            (256, 256)
+        );
+        assert!(
+            hicolor_path
+                .join("32x32@2/apps/io.github.wef.test-app.png")
+                .exists()
+        );
+        assert_eq!(
+            image::open(app_path.join("io.github.wef.test-app.png"))
+                .unwrap()
+                .dimensions(),
+            (256, 256)
+        );
+        assert!(app_path.join(".DirIcon").exists());
+
+        let desktop_entry =
+            std::fs::read_to_string(app_path.join("io.github.wef.test-app.desktop")).unwrap();
+        assert!(desktop_entry.contains("Exec=app %U"));
+        assert!(desktop_entry.contains("Icon=io.github.wef.test-app"));
+        assert_eq!(
+            std::fs::read_to_string(
+                app_path.join("usr/share/applications/io.github.wef.test-app.desktop")
+            )
+            .unwrap(),
+            desktop_entry
+        );
+    }
+
+    #[test]
+    fn appimage_arch_name() {
+        assert_eq!(appimage_arch("x86_64"), "x86_64");
+        assert_eq!(appimage_arch("aarch64"), "aarch64");
+        assert_eq!(appimage_arch("arm"), "armhf");
+        assert_eq!(appimage_arch("x86"), "i686");
+    }
 }
@@ -0,0 +1,100 @@
+use std::path::PathBuf;
+
+use anyhow::{Context, Result};
+use indicatif::HumanBytes;
+use serde::Serialize;
+
+#[derive(Debug, Serialize)]
+struct RemovedCefVersion {
+    version: String,
+    size: u64,
+}
+
+#[derive(Debug, Serialize)]
+struct Clean {
+    cache_dir: PathBuf,
+    removed: Vec<RemovedCefVersion>,
+    freed: u64,
+}
+
+/// Removes the downloaded CEF versions, if no versions are specified, removes
+/// all the versions except the one installed in `CEF_ROOT`.
+///
+/// Only the directories listed by `cargo wef list` are removed, and the ones
+/// containing files not written by `cargo wef init` are refused.
+pub(crate) fn clean(
+    cache_dir: Option<PathBuf>,
+    versions: Vec<String>,
+    all: bool,
+    json: bool,
+) -> Result<()> {
+    let cache_dir = cache_dir.unwrap_or_else(crate::internal::default_cache_dir);
+    let cached = crate::internal::cached_cef_versions(&cache_dir)?;
+
+    for version in &versions {
+        anyhow::ensure!(
+            cached.iter().any(|cached| &cached.version == version),
+            "CEF {} is not downloaded to {}",
+            version,
+            cache_dir.display()
+        );
+    }
+
+    let cef_root = crate::internal::find_cef_root();
+    let installed_version = crate::internal::read_cef_version(&cef_root).ok();
+    anyhow::ensure!(
+        all || !versions.is_empty() || installed_version.is_some(),
+        "cannot read the CEF version installed in {}, specify the versions to remove or use `--all`",
+        cef_root.display()
+    );
+
+    let cached = cached
+        .into_iter()
+        .filter(|cached| {
+            if !versions.is_empty() {
+                versions.contains(&cached.version)
+            } else {
+                all || installed_version.as_deref() != Some(&cached.version)
+            }
+        })
+        .collect::<Vec<_>>();
+    for cached in &cached {
+        anyhow::ensure!(
+            cached.other_files.is_empty(),
+            "refuse to remove {}, it contains files not downloaded by `cargo wef init`: {}",
+            cached.path.display(),
+            cached.other_files.join(", ")
+        );
+    }
+
+    let mut removed = vec![];
+    for cached in cached {
+        std::fs::remove_dir_all(&cached.path)
+            .with_context(|| format!("remove {}", cached.path.display()))?;
+        removed.push(RemovedCefVersion {
+            version: cached.version,
+            size: cached.size,
+        });
+    }
+
+    let clean = Clean {
+        cache_dir,
+        freed: removed.iter().map(|removed| removed.size).sum(),
+        removed,
+    };
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&clean)?);
+        return Ok(());
+    }
+
+    if clean.removed.is_empty() {
+        println!("Nothing to remove in {}", clean.cache_dir.display());
+        return Ok(());
+    }
+    for removed in &clean.removed {
+        println!("Removed {} ({})", removed.version, HumanBytes(removed.size));
+    }
+    println!("Freed {}", HumanBytes(clean.freed));
+    Ok(())
+}
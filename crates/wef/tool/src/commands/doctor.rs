@@ -0,0 +1,478 @@
+use std::{
+    fmt,
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+use anyhow::Result;
+use serde::Serialize;
+
+use crate::internal::{
+    CefBuildsPlatform, DEFAULT_CEF_VERSION, cef_framework_files, find_helper_apps,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+enum Status {
+    Ok,
+    Warning,
+    Error,
+}
+
+impl fmt::Display for Status {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Status::Ok => write!(f, "ok"),
+            Status::Warning => write!(f, "warning"),
+            Status::Error => write!(f, "error"),
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct Check {
+    name: &'static str,
+    status: Status,
+    message: String,
+}
+
+impl Check {
+    fn new(name: &'static str, status: Status, message: impl Into<String>) -> Self {
+        Self {
+            name,
+            status,
+            message: message.into(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct Report {
+    cef_root: PathBuf,
+    installed_version: Option<String>,
+    expected_version: &'static str,
+    checks: Vec<Check>,
+}
+
+pub(crate) fn doctor(
+    app_path: Option<PathBuf>,
+    platform: CefBuildsPlatform,
+    json: bool,
+) -> Result<()> {
+    anyhow::ensure!(
+        platform.os().is_some(),
+        "unsupported platform: {}/{}, specify the platform with `--platform`",
+        std::env::consts::OS,
+        std::env::consts::ARCH
+    );
+
+    let cef_root = crate::internal::find_cef_root();
+    let installed_version = crate::internal::read_cef_version(&cef_root).ok();
+
+    let mut checks = vec![check_cef_root(&cef_root)];
+    if cef_root.exists() {
+        checks.push(check_version(&cef_root));
+        checks.extend(check_files(&cef_root, platform));
+        checks.extend(check_shared_libraries(&cef_root, platform));
+    }
+    if let Some(app_path) = &app_path {
+        checks.push(check_app(app_path, platform));
+    }
+
+    let report = Report {
+        cef_root,
+        installed_version,
+        expected_version: DEFAULT_CEF_VERSION,
+        checks,
+    };
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&report)?);
+    } else {
+        println!("CEF_ROOT: {}", report.cef_root.display());
+        for check in &report.checks {
+            println!("[{}] {}: {}", check.status, check.name, check.message);
+        }
+    }
+
+    let errors = report
+        .checks
+        .iter()
+        .filter(|check| check.status == Status::Error)
+        .count();
+    anyhow::ensure!(errors == 0, "found {} problem(s)", errors);
+    Ok(())
+}
+
+fn check_cef_root(cef_root: &Path) -> Check {
+    let source = if std::env::var_os("CEF_ROOT").is_some() {
+        "the CEF_ROOT environment variable"
+    } else {
+        "the default path, set the CEF_ROOT environment variable to change it"
+    };
+
+    if cef_root.is_dir() {
+        Check::new(
+            "cef_root",
+            Status::Ok,
+            format!("{} is resolved from {}", cef_root.display(), source),
+        )
+    } else {
+        Check::new(
+            "cef_root",
+            Status::Error,
+            format!(
+                "{} resolved from {} does not exist, run `cargo wef init` to download CEF",
+                cef_root.display(),
+                source
+            ),
+        )
+    }
+}
+
+fn check_version(cef_root: &Path) -> Check {
+    match crate::internal::read_cef_version(cef_root) {
+        Ok(version) if version == DEFAULT_CEF_VERSION => {
+            Check::new("cef_version", Status::Ok, version)
+        }
+        Ok(version) => Check::new(
+            "cef_version",
+            Status::Warning,
+            format!(
+                "{} is installed, but cargo-wef is tested with {}, run `cargo wef init --force` to install it",
+                version, DEFAULT_CEF_VERSION
+            ),
+        ),
+        Err(err) => Check::new("cef_version", Status::Error, format!("{:#}", err)),
+    }
+}
+
+/// Checks the files to build the Wef applications and to add the CEF framework
+/// to them.
+fn check_files(cef_root: &Path, platform: CefBuildsPlatform) -> Vec<Check> {
+    let (files, resources) = cef_framework_files(platform);
+    let mut checks = vec![];
+
+    let missing = missing_files(cef_root, &["include", "libcef_dll"]);
+    checks.push(if missing.is_empty() {
+        Check::new(
+            "headers",
+            Status::Ok,
+            "the headers and the wrapper are found",
+        )
+    } else {
+        Check::new(
+            "headers",
+            Status::Error,
+            format!("missing {}, required to build wef", missing.join(", ")),
+        )
+    });
+
+    // The `Debug` directory is not in the minimal distributions, so it's only
+    // required by the debug builds.
+    for (name, profile, status) in [
+        ("release_files", "Release", Status::Error),
+        ("debug_files", "Debug", Status::Warning),
+    ] {
+        let missing = missing_files(&cef_root.join(profile), files);
+        checks.push(if missing.is_empty() {
+            Check::new(
+                name,
+                Status::Ok,
+                format!("all the files in {} are found", profile),
+            )
+        } else {
+            Check::new(
+                name,
+                status,
+                format!("missing {} in {}", missing.join(", "), profile),
+            )
+        });
+    }
+
+    if !resources.is_empty() {
+        let missing = missing_files(&cef_root.join("Resources"), resources);
+        checks.push(if missing.is_empty() {
+            Check::new("resources", Status::Ok, "all the resources are found")
+        } else {
+            Check::new(
+                "resources",
+                Status::Error,
+                format!("missing {} in Resources", missing.join(", ")),
+            )
+        });
+    }
+
+    checks
+}
+
+/// Checks the system libraries required by `libcef.so` on Linux, the CEF
+/// builds for the other targets can't be checked on the host.
+fn check_shared_libraries(cef_root: &Path, platform: CefBuildsPlatform) -> Option<Check> {
+    if platform.os() != Some("linux") || !platform.is_host() {
+        return None;
+    }
+
+    let libcef_path = cef_root.join("Release").join("libcef.so");
+    if !libcef_path.exists() {
+        return None;
+    }
+
+    Some(match Command::new("ldd").arg(&libcef_path).output() {
+        Ok(output) if output.status.success() => {
+            let missing = missing_libraries(&String::from_utf8_lossy(&output.stdout));
+            if missing.is_empty() {
+                Check::new(
+                    "shared_libraries",
+                    Status::Ok,
+                    "all the shared libraries of libcef.so are found",
+                )
+            } else {
+                Check::new(
+                    "shared_libraries",
+                    Status::Error,
+                    format!(
+                        "missing {} required by libcef.so, install them with the package manager",
+                        missing.join(", ")
+                    ),
+                )
+            }
+        }
+        Ok(output) => Check::new(
+            "shared_libraries",
+            Status::Warning,
+            format!(
+                "failed to run ldd: {}",
+                String::from_utf8_lossy(&output.stderr).trim()
+            ),
+        ),
+        Err(_) => Check::new(
+            "shared_libraries",
+            Status::Warning,
+            "ldd is not found, skip checking the shared libraries",
+        ),
+    })
+}
+
+/// Checks the CEF framework and the helper processes in the application built
+/// by `cargo wef build`.
+fn check_app(app_path: &Path, platform: CefBuildsPlatform) -> Check {
+    if !app_path.exists() {
+        return Check::new(
+            "app",
+            Status::Error,
+            format!("{} does not exist", app_path.display()),
+        );
+    }
+
+    let (files, resources) = cef_framework_files(platform);
+    let mut missing = vec![];
+
+    if platform.os() == Some("macos") {
+        let frameworks_path = app_path.join("Contents").join("Frameworks");
+        missing.extend(
+            missing_files(&frameworks_path, files)
+                .into_iter()
+                .map(|file| format!("Contents/Frameworks/{}", file)),
+        );
+        match find_helper_apps(app_path) {
+            Ok(helper_paths) => missing.extend(
+                helper_paths
+                    .into_iter()
+                    .filter(|path| !path.exists())
+                    .map(|path| path.file_name().unwrap().to_string_lossy().to_string()),
+            ),
+            Err(err) => return Check::new("app", Status::Error, format!("{:#}", err)),
+        }
+    } else {
+        let dir = find_app_dir(app_path);
+        missing.extend(missing_files(&dir, files));
+        missing.extend(missing_files(&dir, resources));
+    }
+
+    if missing.is_empty() {
+        Check::new(
+            "app",
+            Status::Ok,
+            format!("{} contains the CEF framework", app_path.display()),
+        )
+    } else {
+        Check::new(
+            "app",
+            Status::Error,
+            format!(
+                "missing {} in {}, build it with `cargo wef build` or run `cargo wef add-framework`",
+                missing.join(", "),
+                app_path.display()
+            ),
+        )
+    }
+}
+
+/// Returns the directory of the binary, the app path can be the binary, the
+/// directory of the binary, or the directory created with `--linux-bundle`.
+fn find_app_dir(app_path: &Path) -> PathBuf {
+    if app_path.is_file() {
+        return app_path.parent().unwrap_or(app_path).to_path_buf();
+    }
+
+    let lib_path = app_path.join("usr").join("lib");
+    if app_path.join("AppRun").exists() {
+        let entry = std::fs::read_dir(&lib_path)
+            .into_iter()
+            .flatten()
+            .flatten()
+            .find(|entry| entry.path().is_dir());
+        if let Some(entry) = entry {
+            return entry.path();
+        }
+    }
+
+    app_path.to_path_buf()
+}
+
+fn missing_files(dir: &Path, files: &[&str]) -> Vec<String> {
+    files
+        .iter()
+        .filter(|file| !dir.join(file).exists())
+        .map(|file| file.to_string())
+        .collect()
+}
+
+/// Returns the libraries that are `not found` in the output of `ldd`.
+fn missing_libraries(ldd_output: &str) -> Vec<String> {
+    ldd_output
+        .lines()
+        .filter(|line| line.contains("not found"))
+        .filter_map(|line| line.split_whitespace().next())
+        .map(ToString::to_string)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_cef_root(version: &str) -> tempfile::TempDir {
+        let dir = tempfile::tempdir().unwrap();
+        let (files, resources) = cef_framework_files(CefBuildsPlatform::Auto);
+        std::fs::create_dir_all(dir.path().join("include")).unwrap();
+        std::fs::create_dir_all(dir.path().join("libcef_dll")).unwrap();
+        std::fs::write(
+            dir.path().join("include").join("cef_version.h"),
+            format!(
+                "#ifndef CEF_INCLUDE_CEF_VERSION_H_\n#define CEF_VERSION \"{}\"\n#define CEF_VERSION_MAJOR 137\n",
+                version
+            ),
+        )
+        .unwrap();
+        for (parent, files) in [("Release", files), ("Resources", resources)] {
+            let parent = dir.path().join(parent);
+            std::fs::create_dir_all(&parent).unwrap();
+            for file in files {
+                std::fs::write(parent.join(file), "").unwrap();
+            }
+        }
+        dir
+    }
+
+    fn statuses(checks: &[Check]) -> Vec<(&'static str, Status)> {
+        checks
+            .iter()
+            .map(|check| (check.name, check.status))
+            .collect()
+    }
+
+    #[test]
+    fn version() {
+        let cef_root = create_cef_root(DEFAULT_CEF_VERSION);
+        assert_eq!(
+            crate::internal::read_cef_version(cef_root.path()).unwrap(),
+            DEFAULT_CEF_VERSION
+        );
+        assert_eq!(check_version(cef_root.path()).status, Status::Ok);
+
+        let cef_root = create_cef_root("120.1.10+g3ce3184+chromium-120.0.6099.129");
+        let check = check_version(cef_root.path());
+        assert_eq!(check.status, Status::Warning);
+        assert!(check.message.starts_with("120.1.10+g3ce3184"));
+
+        let cef_root = tempfile::tempdir().unwrap();
+        assert_eq!(check_version(cef_root.path()).status, Status::Error);
+    }
+
+    #[test]
+    fn files() {
+        let cef_root = create_cef_root(DEFAULT_CEF_VERSION);
+        let mut expected = vec![
+            ("headers", Status::Ok),
+            ("release_files", Status::Ok),
+            ("debug_files", Status::Warning),
+        ];
+        if !cef_framework_files(CefBuildsPlatform::Auto).1.is_empty() {
+            expected.push(("resources", Status::Ok));
+        }
+        assert_eq!(
+            statuses(&check_files(cef_root.path(), CefBuildsPlatform::Auto)),
+            expected
+        );
+
+        let (files, _) = cef_framework_files(CefBuildsPlatform::Auto);
+        std::fs::remove_file(cef_root.path().join("Release").join(files[0])).unwrap();
+        std::fs::remove_dir(cef_root.path().join("libcef_dll")).unwrap();
+        let checks = check_files(cef_root.path(), CefBuildsPlatform::Auto);
+        assert_eq!(checks[0].status, Status::Error);
+        assert_eq!(
+            checks[0].message,
+            "missing libcef_dll, required to build wef"
+        );
+        assert_eq!(checks[1].status, Status::Error);
+        assert_eq!(
+            checks[1].message,
+            format!("missing {} in Release", files[0])
+        );
+    }
+
+    #[test]
+    fn cross_target_files() {
+        let cef_root = tempfile::tempdir().unwrap();
+        let release_path = cef_root.path().join("Release");
+        std::fs::create_dir_all(&release_path).unwrap();
+        for file in cef_framework_files(CefBuildsPlatform::Windows_arm64).0 {
+            std::fs::write(release_path.join(file), "").unwrap();
+        }
+
+        let checks = check_files(cef_root.path(), CefBuildsPlatform::Windows_arm64);
+        assert_eq!(checks[1].name, "release_files");
+        assert_eq!(checks[1].status, Status::Ok);
+
+        let checks = check_files(cef_root.path(), CefBuildsPlatform::Linux_arm64);
+        assert_eq!(checks[1].status, Status::Error);
+        assert!(checks[1].message.contains("libcef.so"));
+        assert!(
+            check_shared_libraries(cef_root.path(), CefBuildsPlatform::Windows_arm64).is_none()
+        );
+    }
+
+    #[test]
+    fn app_dir() {
+        let dir = tempfile::tempdir().unwrap();
+        let lib_path = dir.path().join("app.AppDir/usr/lib/app");
+        std::fs::create_dir_all(&lib_path).unwrap();
+        std::fs::write(dir.path().join("app.AppDir/AppRun"), "").unwrap();
+        std::fs::write(lib_path.join("app"), "").unwrap();
+
+        assert_eq!(find_app_dir(&dir.path().join("app.AppDir")), lib_path);
+        assert_eq!(find_app_dir(&lib_path.join("app")), lib_path);
+        assert_eq!(find_app_dir(&lib_path), lib_path);
+    }
+
+    #[test]
+    fn ldd_output() {
+        let output = "\tlinux-vdso.so.1 (0x00007ffd)\n\
+            \tlibnss3.so => not found\n\
+            \tlibglib-2.0.so.0 => /lib/x86_64-linux-gnu/libglib-2.0.so.0 (0x00007f)\n\
+            \tlibatk-1.0.so.0 => not found\n";
+        assert_eq!(missing_libraries(output), ["libnss3.so", "libatk-1.0.so.0"]);
+    }
+}
@@ -1,9 +1,11 @@
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use anyhow::Result;
 use indicatif::{ProgressBar, ProgressStyle};
 
-use crate::internal::{CefBuildsPlatform, DownloadCefCallback};
+use crate::internal::{
+    CefBuildsPlatform, CefSource, ChecksumAlgorithm, DownloadCefCallback, DownloadCefOptions,
+};
 
 #[derive(Debug, Default)]
 struct CmdDownloadCallback {
@@ -11,18 +13,33 @@ struct CmdDownloadCallback {
     extract_progress: Option<ProgressBar>,
 }
 
+#[allow(clippy::too_many_arguments)]
 pub(crate) fn init(
     path: Option<PathBuf>,
     version: String,
     platform: CefBuildsPlatform,
     force: bool,
+    mirror: String,
+    from_archive: Option<PathBuf>,
+    cache_dir: Option<PathBuf>,
+    verify: bool,
 ) -> Result<()> {
     let path = path.unwrap_or_else(|| dirs::home_dir().expect("get home directory").join(".cef"));
+    let cache_dir = cache_dir.unwrap_or_else(crate::internal::default_cache_dir);
+    let source = match &from_archive {
+        Some(archive_path) => CefSource::Archive(archive_path),
+        None => CefSource::Mirror(&mirror),
+    };
     crate::internal::download_cef(
         &path,
-        &version,
-        platform,
-        force,
+        DownloadCefOptions {
+            version: &version,
+            platform,
+            source,
+            cache_dir: &cache_dir,
+            force,
+            verify,
+        },
         CmdDownloadCallback::default(),
     )?;
     println!("Set environment variable CEF_ROOT={}", path.display());
@@ -46,6 +63,10 @@ fn create_extract_progress_bar() -> ProgressBar {
 }
 
 impl DownloadCefCallback for CmdDownloadCallback {
+    fn cached(&mut self, path: &Path) {
+        println!("Using cached archive {}", path.display());
+    }
+
     fn download_start(&mut self, total_size: u64) {
         self.download_progress = Some(create_download_progress_bar());
         if let Some(pb) = &self.download_progress {
@@ -66,6 +87,17 @@ impl DownloadCefCallback for CmdDownloadCallback {
         }
     }
 
+    fn verified(&mut self, algorithm: ChecksumAlgorithm) {
+        println!("Verified {} checksum", algorithm.extension());
+    }
+
+    fn unverified(&mut self, path: &Path) {
+        eprintln!(
+            "Warning: no checksum file found next to {}, the archive is not verified",
+            path.display()
+        );
+    }
+
     fn extract_start(&mut self) {
         self.extract_progress = Some(create_extract_progress_bar());
     }
@@ -0,0 +1,81 @@
+use std::path::PathBuf;
+
+use anyhow::Result;
+use indicatif::HumanBytes;
+use serde::Serialize;
+
+use crate::internal::{CachedCefVersion, DEFAULT_CEF_VERSION};
+
+#[derive(Debug, Serialize)]
+struct ListedCefVersion {
+    #[serde(flatten)]
+    cached: CachedCefVersion,
+    installed: bool,
+    default: bool,
+}
+
+#[derive(Debug, Serialize)]
+struct List {
+    cache_dir: PathBuf,
+    cef_root: PathBuf,
+    installed_version: Option<String>,
+    versions: Vec<ListedCefVersion>,
+}
+
+pub(crate) fn list(cache_dir: Option<PathBuf>, json: bool) -> Result<()> {
+    let cache_dir = cache_dir.unwrap_or_else(crate::internal::default_cache_dir);
+    let cef_root = crate::internal::find_cef_root();
+    let installed_version = crate::internal::read_cef_version(&cef_root).ok();
+
+    let versions = crate::internal::cached_cef_versions(&cache_dir)?
+        .into_iter()
+        .map(|cached| ListedCefVersion {
+            installed: installed_version.as_deref() == Some(&cached.version),
+            default: cached.version == DEFAULT_CEF_VERSION,
+            cached,
+        })
+        .collect::<Vec<_>>();
+    let list = List {
+        cache_dir,
+        cef_root,
+        installed_version,
+        versions,
+    };
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&list)?);
+        return Ok(());
+    }
+
+    match &list.installed_version {
+        Some(version) => println!("Installed: {} ({})", version, list.cef_root.display()),
+        None => println!("Installed: none ({})", list.cef_root.display()),
+    }
+    println!("Cache directory: {}", list.cache_dir.display());
+    if list.versions.is_empty() {
+        println!("No downloaded CEF versions");
+    }
+    for version in &list.versions {
+        let mut tags = vec![];
+        if version.installed {
+            tags.push("installed");
+        }
+        if version.default {
+            tags.push("default");
+        }
+        if version.cached.partial {
+            tags.push("partial download");
+        }
+        println!(
+            "  {} {}{}",
+            version.cached.version,
+            HumanBytes(version.cached.size),
+            if tags.is_empty() {
+                String::new()
+            } else {
+                format!(" ({})", tags.join(", "))
+            }
+        );
+    }
+    Ok(())
+}
@@ -1,9 +1,17 @@
 mod add_framework;
 mod build;
+mod clean;
+mod doctor;
 mod init;
+mod list;
 mod run;
+mod types;
 
 pub(crate) use add_framework::add_framework;
-pub(crate) use build::build;
+pub(crate) use build::{LinuxBundleFormat, build};
+pub(crate) use clean::clean;
+pub(crate) use doctor::doctor;
 pub(crate) use init::init;
+pub(crate) use list::list;
 pub(crate) use run::run;
+pub(crate) use types::types;
@@ -11,8 +11,16 @@ pub(crate) fn run(
     wef_path: Option<&Path>,
     args: Vec<String>,
 ) -> Result<()> {
-    let exec_path =
-        crate::commands::build(package, bin, example, release, wef_version, wef_path, None)?;
+    let exec_path = crate::commands::build(
+        package,
+        bin,
+        example,
+        release,
+        wef_version,
+        wef_path,
+        None,
+        None,
+    )?;
     Command::new(&exec_path).args(args).status()?;
     Ok(())
 }
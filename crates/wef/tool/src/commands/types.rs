@@ -0,0 +1,71 @@
+use std::{
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+use anyhow::{Context, Result};
+
+/// The file name of the declarations if the output path is a directory.
+const DEFAULT_FILE_NAME: &str = "jsbridge.d.ts";
+
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn types(
+    package: Option<String>,
+    bin: Option<String>,
+    example: Option<String>,
+    release: bool,
+    wef_version: Option<&str>,
+    wef_path: Option<&Path>,
+    out: PathBuf,
+    schema: Option<PathBuf>,
+) -> Result<()> {
+    let out = if out.is_dir() {
+        out.join(DEFAULT_FILE_NAME)
+    } else {
+        out
+    };
+    let out = std::path::absolute(&out)?;
+    let schema = schema.map(std::path::absolute).transpose()?;
+
+    for path in std::iter::once(&out).chain(&schema) {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)
+                .with_context(|| format!("create directory {}", parent.display()))?;
+        }
+        // Remove the previous output to check if the application exported the types.
+        if path.exists() {
+            std::fs::remove_file(path).with_context(|| format!("remove {}", path.display()))?;
+        }
+    }
+
+    let exec_path = crate::commands::build(
+        package,
+        bin,
+        example,
+        release,
+        wef_version,
+        wef_path,
+        None,
+        None,
+    )?;
+
+    let mut command = Command::new(&exec_path);
+    command.env("WEF_EXPORT_TYPES", &out);
+    if let Some(schema) = &schema {
+        command.env("WEF_EXPORT_SCHEMA", schema);
+    }
+    anyhow::ensure!(
+        command.status()?.success(),
+        "failed to export the types from the application"
+    );
+    anyhow::ensure!(
+        out.exists(),
+        "the application exited without exporting the types, call `FuncRegistry::export_types_from_env` and exit if it returns `true`"
+    );
+
+    println!("Write TypeScript declarations to {}", out.display());
+    if let Some(schema) = &schema {
+        println!("Write JSON Schema manifest to {}", schema.display());
+    }
+    Ok(())
+}
@@ -2,18 +2,100 @@ use std::path::Path;
 
 use anyhow::{Context, Result};
 
+use crate::internal::CefBuildsPlatform;
+
+/// The CEF framework in the `Release` or `Debug` directory of CEF on macOS.
+const MACOS_FRAMEWORK: &str = "Chromium Embedded Framework.framework";
+
+/// The files copied from the `Release` or `Debug` directory of CEF on Windows.
+const WINDOWS_FILES: &[&str] = &[
+    "chrome_elf.dll",
+    "libcef.dll",
+    "libEGL.dll",
+    "libGLESv2.dll",
+    "v8_context_snapshot.bin",
+];
+
+/// The files copied from the `Release` or `Debug` directory of CEF on Windows
+/// if they exist, they are not in all the builds, e.g.: the ARM builds.
+const WINDOWS_OPTIONAL_FILES: &[&str] = &[
+    "d3dcompiler_47.dll",
+    "dxcompiler.dll",
+    "dxil.dll",
+    "vk_swiftshader.dll",
+    "vk_swiftshader_icd.json",
+    "vulkan-1.dll",
+];
+
+/// The files copied from the `Release` or `Debug` directory of CEF on Linux.
+const LINUX_FILES: &[&str] = &[
+    "libcef.so",
+    "libEGL.so",
+    "libGLESv2.so",
+    "v8_context_snapshot.bin",
+];
+
+/// The files copied from the `Release` or `Debug` directory of CEF on Linux if
+/// they exist, they are not in all the builds, e.g.: the ARM builds.
+const LINUX_OPTIONAL_FILES: &[&str] = &[
+    "libvk_swiftshader.so",
+    "libvulkan.so.1",
+    "vk_swiftshader_icd.json",
+];
+
+/// The files copied from the `Resources` directory of CEF on Windows and
+/// Linux.
+const RESOURCES: &[&str] = &[
+    "chrome_100_percent.pak",
+    "chrome_200_percent.pak",
+    "icudtl.dat",
+    "resources.pak",
+    "locales",
+];
+
+/// Returns the required files of the CEF framework in the `Release` or `Debug`
+/// directory, and the files in the `Resources` directory, that are added to the
+/// application on the platform.
+pub(crate) fn cef_framework_files(
+    platform: CefBuildsPlatform,
+) -> (&'static [&'static str], &'static [&'static str]) {
+    match platform.os() {
+        Some("macos") => (&[MACOS_FRAMEWORK], &[]),
+        Some("windows") => (WINDOWS_FILES, RESOURCES),
+        _ => (LINUX_FILES, RESOURCES),
+    }
+}
+
+/// Adds the CEF framework in `cef_root` to the application, the `platform` can
+/// be different from the host to assemble the application for another target,
+/// and `cef_root` must contain the CEF build of the platform.
 pub(crate) fn add_cef_framework(
     cef_root: &Path,
     app_path: &Path,
     release: bool,
     force: bool,
+    platform: CefBuildsPlatform,
 ) -> Result<()> {
-    match std::env::consts::OS {
-        "macos" => add_cef_framework_macos(cef_root, app_path, release, force),
-        "windows" => add_cef_framework_windows(cef_root, app_path, release, force),
-        "linux" => add_cef_framework_linux(cef_root, app_path, release, force),
+    match platform.os() {
+        Some("macos") => add_cef_framework_macos(cef_root, app_path, release, force),
+        Some("windows") => add_cef_framework_files(
+            cef_root,
+            app_path,
+            release,
+            force,
+            WINDOWS_FILES,
+            WINDOWS_OPTIONAL_FILES,
+        ),
+        Some("linux") => add_cef_framework_files(
+            cef_root,
+            app_path,
+            release,
+            force,
+            LINUX_FILES,
+            LINUX_OPTIONAL_FILES,
+        ),
         _ => {
-            anyhow::bail!("Unsupported platform: {}", std::env::consts::OS);
+            anyhow::bail!("Unsupported platform: {:?}", platform);
         }
     }
 }
@@ -43,13 +125,9 @@ fn add_cef_framework_macos(
     // copy CEF framework
     let cef_framework_path = cef_root
         .join(if !release { "Debug" } else { "Release" })
-        .join("Chromium Embedded Framework.framework");
+        .join(MACOS_FRAMEWORK);
 
-    if !force
-        && frameworks_path
-            .join("Chromium Embedded Framework.framework")
-            .exists()
-    {
+    if !force && frameworks_path.join(MACOS_FRAMEWORK).exists() {
         return Ok(());
     }
 
@@ -75,116 +153,34 @@ fn add_cef_framework_macos(
     Ok(())
 }
 
-fn add_cef_framework_windows(
-    cef_root: &Path,
-    app_path: &Path,
-    release: bool,
-    force: bool,
-) -> Result<()> {
-    let files = [
-        "chrome_elf.dll",
-        "d3dcompiler_47.dll",
-        "dxcompiler.dll",
-        "dxil.dll",
-        "libcef.dll",
-        "libEGL.dll",
-        "libGLESv2.dll",
-        "v8_context_snapshot.bin",
-        "vk_swiftshader.dll",
-        "vk_swiftshader_icd.json",
-        "vulkan-1.dll",
-    ];
-
-    let resources = [
-        "chrome_100_percent.pak",
-        "chrome_200_percent.pak",
-        "icudtl.dat",
-        "resources.pak",
-        "locales",
-    ];
-
-    if !force
-        && files
-            .iter()
-            .all(|filename| app_path.join(filename).exists())
-        && resources
-            .iter()
-            .all(|filename| app_path.join(filename).exists())
-    {
-        return Ok(());
-    }
-
-    for filename in files {
-        let src_path = cef_root
-            .join(if !release { "Debug" } else { "Release" })
-            .join(filename);
-        let dst_path = app_path.join(filename);
-        std::fs::copy(src_path, dst_path)
-            .with_context(|| format!("copy {} to {}", filename, app_path.display()))?;
-    }
-
-    let resources_src_path = cef_root.join("Resources");
-    fs_extra::dir::copy(
-        &resources_src_path,
-        app_path,
-        &fs_extra::dir::CopyOptions {
-            overwrite: true,
-            skip_exist: false,
-            copy_inside: false,
-            content_only: true,
-            ..Default::default()
-        },
-    )
-    .with_context(|| {
-        format!(
-            "copy CEF Resources from {} to {}",
-            resources_src_path.display(),
-            app_path.display()
-        )
-    })?;
-
-    Ok(())
-}
-
-fn add_cef_framework_linux(
+/// Copies the files of the CEF framework next to the binary on Windows and
+/// Linux.
+fn add_cef_framework_files(
     cef_root: &Path,
     app_path: &Path,
     release: bool,
     force: bool,
+    files: &[&str],
+    optional_files: &[&str],
 ) -> Result<()> {
-    let files = [
-        "libcef.so",
-        "libEGL.so",
-        "libGLESv2.so",
-        "libvk_swiftshader.so",
-        "libvulkan.so.1",
-        "v8_context_snapshot.bin",
-        "vk_swiftshader_icd.json",
-    ];
-
-    let resources = [
-        "chrome_100_percent.pak",
-        "chrome_200_percent.pak",
-        "icudtl.dat",
-        "resources.pak",
-        "locales",
-    ];
-
     if !force
         && files
             .iter()
             .all(|filename| app_path.join(filename).exists())
-        && resources
+        && RESOURCES
             .iter()
             .all(|filename| app_path.join(filename).exists())
     {
         return Ok(());
     }
 
-    for filename in files {
+    for filename in files.iter().chain(optional_files) {
         let src_path = cef_root
             .join(if !release { "Debug" } else { "Release" })
             .join(filename);
+        if optional_files.contains(filename) && !src_path.exists() {
+            continue;
+        }
         let dst_path = app_path.join(filename);
         std::fs::copy(src_path, dst_path)
             .with_context(|| format!("copy {} to {}", filename, app_path.display()))?;
@@ -1,4 +1,8 @@
-use std::{fs::File, path::Path, process::Command};
+use std::{
+    fs::File,
+    path::{Path, PathBuf},
+    process::Command,
+};
 
 use anyhow::{Context, Result};
 use askama::Template;
@@ -267,6 +271,26 @@ fn create_helper_app(
     Ok(())
 }
 
+fn helper_app_paths(app_path: &Path, bundle_info: &BundleInfo) -> Vec<PathBuf> {
+    HelperKind::ALL
+        .iter()
+        .map(|kind| {
+            app_path.join("Contents").join("Frameworks").join(format!(
+                "{}.app",
+                kind.bundle_name(&bundle_info.bundle_name)
+            ))
+        })
+        .collect()
+}
+
+/// Returns the paths of the helper apps that should be in the MacOS app.
+pub(crate) fn find_helper_apps(app_path: &Path) -> Result<Vec<PathBuf>> {
+    let info_path = app_path.join("Contents").join("Info.plist");
+    let bundle_info = read_bundle_info(&info_path)
+        .with_context(|| format!("read bundle info from {}", info_path.display()))?;
+    Ok(helper_app_paths(app_path, &bundle_info))
+}
+
 pub(crate) fn add_helper(
     app_path: &Path,
     wef_version: Option<&str>,
@@ -286,13 +310,9 @@ pub(crate) fn add_helper(
         .with_context(|| format!("read bundle info from {}", info_path.display()))?;
 
     if !force
-        && HelperKind::ALL.iter().all(|kind| {
-            let helper_path = app_path.join("Contents").join("Frameworks").join(format!(
-                "{}.app",
-                kind.bundle_name(&bundle_info.bundle_name)
-            ));
-            helper_path.exists()
-        })
+        && helper_app_paths(app_path, &bundle_info)
+            .iter()
+            .all(|helper_path| helper_path.exists())
     {
         return Ok(());
     }
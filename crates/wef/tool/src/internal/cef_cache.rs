@@ -0,0 +1,180 @@
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use serde::Serialize;
+
+/// The marker file written to the version directories created by `cargo wef
+/// init`, only the directories with the marker or the CEF archives are
+/// treated as cached versions.
+pub(crate) const CACHE_MARKER: &str = ".wef-cef-cache";
+
+/// Returns the default directory of the downloaded CEF archives.
+pub(crate) fn default_cache_dir() -> PathBuf {
+    dirs::cache_dir()
+        .expect("get cache directory")
+        .join("wef")
+        .join("cef")
+}
+
+/// A CEF version downloaded to the cache directory by `cargo wef init`.
+#[derive(Debug, Serialize)]
+pub(crate) struct CachedCefVersion {
+    pub(crate) version: String,
+    pub(crate) path: PathBuf,
+    /// The total size of the files in bytes.
+    pub(crate) size: u64,
+    /// The file names of the complete archives.
+    pub(crate) archives: Vec<String>,
+    /// Whether there is an interrupted download.
+    pub(crate) partial: bool,
+    /// The files that are not written by `cargo wef init`.
+    #[serde(skip)]
+    pub(crate) other_files: Vec<String>,
+}
+
+/// Returns the CEF versions in the cache directory sorted by the version
+/// numbers.
+///
+/// The subdirectories without the [`CACHE_MARKER`], the archives or the
+/// interrupted downloads are ignored.
+pub(crate) fn cached_cef_versions(cache_dir: &Path) -> Result<Vec<CachedCefVersion>> {
+    if !cache_dir.exists() {
+        return Ok(vec![]);
+    }
+
+    let mut versions = vec![];
+    for entry in std::fs::read_dir(cache_dir)
+        .with_context(|| format!("read cache directory {}", cache_dir.display()))?
+    {
+        let entry = entry?;
+        if !entry.file_type()?.is_dir() {
+            continue;
+        }
+
+        let mut version = CachedCefVersion {
+            version: entry.file_name().to_string_lossy().to_string(),
+            path: entry.path(),
+            size: 0,
+            archives: vec![],
+            partial: false,
+            other_files: vec![],
+        };
+        let mut marked = false;
+        for file in std::fs::read_dir(entry.path())? {
+            let file = file?;
+            let name = file.file_name().to_string_lossy().to_string();
+            if !file.file_type()?.is_file() {
+                version.other_files.push(name);
+                continue;
+            }
+
+            version.size += file.metadata()?.len();
+            if name == CACHE_MARKER {
+                marked = true;
+            } else if !name.starts_with("cef_binary_") {
+                version.other_files.push(name);
+            } else if name.ends_with(".tar.bz2.part") {
+                version.partial = true;
+            } else if name.ends_with(".tar.bz2") {
+                version.archives.push(name);
+            } else if !name.ends_with(".tar.bz2.sha1") && !name.ends_with(".tar.bz2.sha256") {
+                version.other_files.push(name);
+            }
+        }
+        if !marked && version.archives.is_empty() && !version.partial {
+            continue;
+        }
+        version.archives.sort();
+        version.other_files.sort();
+        versions.push(version);
+    }
+
+    versions.sort_by(|a, b| {
+        version_numbers(&a.version)
+            .cmp(&version_numbers(&b.version))
+            .then_with(|| a.version.cmp(&b.version))
+    });
+    Ok(versions)
+}
+
+/// Returns the numeric components of the version before the build metadata,
+/// e.g.: `[137, 0, 10]` for `137.0.10+g7e14fe1+chromium-137.0.7151.69`, the
+/// non-numeric components are `None`.
+fn version_numbers(version: &str) -> Vec<Option<u64>> {
+    version
+        .split('+')
+        .next()
+        .unwrap_or_default()
+        .split('.')
+        .map(|number| number.parse().ok())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use super::*;
+
+    #[test]
+    fn only_cef_directories() {
+        let dir = tempfile::tempdir().unwrap();
+        let create = |version: &str, files: &[&str]| {
+            let path = dir.path().join(version);
+            fs::create_dir_all(&path).unwrap();
+            for file in files {
+                fs::write(path.join(file), "data").unwrap();
+            }
+        };
+        create(
+            "1.0.0",
+            &[
+                "cef_binary_1.0.0_linux64.tar.bz2",
+                "cef_binary_1.0.0_linux64.tar.bz2.sha256",
+            ],
+        );
+        create("2.0.0", &["cef_binary_2.0.0_linux64.tar.bz2.part"]);
+        create("3.0.0", &[CACHE_MARKER]);
+        create("4.0.0", &[CACHE_MARKER, "notes.txt"]);
+        create("Documents", &["notes.txt"]);
+        create("empty", &[]);
+        fs::write(dir.path().join("file.txt"), "data").unwrap();
+
+        let versions = cached_cef_versions(dir.path()).unwrap();
+        assert_eq!(
+            versions
+                .iter()
+                .map(|version| version.version.as_str())
+                .collect::<Vec<_>>(),
+            ["1.0.0", "2.0.0", "3.0.0", "4.0.0"]
+        );
+        assert_eq!(versions[0].archives, ["cef_binary_1.0.0_linux64.tar.bz2"]);
+        assert!(versions[0].other_files.is_empty());
+        assert!(versions[1].partial && versions[1].archives.is_empty());
+        assert!(versions[2].other_files.is_empty());
+        assert_eq!(versions[3].other_files, ["notes.txt"]);
+    }
+
+    #[test]
+    fn sort_by_version_numbers() {
+        let dir = tempfile::tempdir().unwrap();
+        for version in [
+            "137.0.10+g7e14fe1+chromium-137.0.7151.69",
+            "99.2.15+g71e9523+chromium-99.0.4844.84",
+            "137.0.9+g1a2b3c4+chromium-137.0.7151.56",
+        ] {
+            let path = dir.path().join(version);
+            fs::create_dir_all(&path).unwrap();
+            fs::write(path.join(CACHE_MARKER), version).unwrap();
+        }
+
+        let versions = cached_cef_versions(dir.path()).unwrap();
+        assert_eq!(
+            versions
+                .iter()
+                .map(|version| version.version.split('+').next().unwrap())
+                .collect::<Vec<_>>(),
+            ["99.2.15", "137.0.9", "137.0.10"]
+        );
+    }
+}
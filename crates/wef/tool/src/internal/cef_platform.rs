@@ -5,43 +5,126 @@ use clap::ValueEnum;
 pub enum CefBuildsPlatform {
     Auto,
     Windows_x64,
+    Windows_arm64,
     Macos_x64,
     Macos_arm64,
     Linux_x64,
+    Linux_arm64,
+    Linux_arm,
 }
 
 pub(crate) const DEFAULT_CEF_VERSION: &str = "137.0.10+g7e14fe1+chromium-137.0.7151.69";
 
+/// The official CDN of the CEF builds.
+pub(crate) const DEFAULT_CEF_MIRROR: &str = "https://cef-builds.spotifycdn.com";
+
 impl CefBuildsPlatform {
-    fn arch(&self) -> Option<&'static str> {
+    /// Returns the platform of the host if it's `Auto`, or `None` if the host
+    /// is not supported by the CEF builds.
+    pub(crate) fn resolve(&self) -> Option<CefBuildsPlatform> {
         match self {
             CefBuildsPlatform::Auto => match (std::env::consts::OS, std::env::consts::ARCH) {
-                ("windows", "x86_64") => CefBuildsPlatform::Windows_x64.arch(),
-                ("macos", "x86_64") => CefBuildsPlatform::Macos_x64.arch(),
-                ("macos", "aarch64") => CefBuildsPlatform::Macos_arm64.arch(),
-                ("linux", "x86_64") => CefBuildsPlatform::Linux_x64.arch(),
+                ("windows", "x86_64") => Some(CefBuildsPlatform::Windows_x64),
+                ("windows", "aarch64") => Some(CefBuildsPlatform::Windows_arm64),
+                ("macos", "x86_64") => Some(CefBuildsPlatform::Macos_x64),
+                ("macos", "aarch64") => Some(CefBuildsPlatform::Macos_arm64),
+                ("linux", "x86_64") => Some(CefBuildsPlatform::Linux_x64),
+                ("linux", "aarch64") => Some(CefBuildsPlatform::Linux_arm64),
+                ("linux", "arm") => Some(CefBuildsPlatform::Linux_arm),
                 _ => None,
             },
+            platform => Some(*platform),
+        }
+    }
+
+    /// Returns the operating system of the platform, in the same form as
+    /// [`std::env::consts::OS`].
+    pub(crate) fn os(&self) -> Option<&'static str> {
+        match self.resolve()? {
+            CefBuildsPlatform::Auto => None,
+            CefBuildsPlatform::Windows_x64 | CefBuildsPlatform::Windows_arm64 => Some("windows"),
+            CefBuildsPlatform::Macos_x64 | CefBuildsPlatform::Macos_arm64 => Some("macos"),
+            CefBuildsPlatform::Linux_x64
+            | CefBuildsPlatform::Linux_arm64
+            | CefBuildsPlatform::Linux_arm => Some("linux"),
+        }
+    }
+
+    /// Returns whether the platform is the host.
+    pub(crate) fn is_host(&self) -> bool {
+        self.resolve() == CefBuildsPlatform::Auto.resolve()
+    }
+
+    fn arch(&self) -> Option<&'static str> {
+        match self.resolve()? {
+            CefBuildsPlatform::Auto => None,
             CefBuildsPlatform::Windows_x64 => Some("windows64"),
+            CefBuildsPlatform::Windows_arm64 => Some("windowsarm64"),
             CefBuildsPlatform::Macos_x64 => Some("macosx64"),
             CefBuildsPlatform::Macos_arm64 => Some("macosarm64"),
             CefBuildsPlatform::Linux_x64 => Some("linux64"),
+            CefBuildsPlatform::Linux_arm64 => Some("linuxarm64"),
+            CefBuildsPlatform::Linux_arm => Some("linuxarm"),
         }
     }
 
-    pub(crate) fn download_url(&self, version: &str) -> Option<String> {
+    /// Returns the file name of the archive, e.g.:
+    /// `cef_binary_{version}_linux64.tar.bz2`.
+    pub(crate) fn archive_name(&self, version: &str) -> Option<String> {
         Some(format!(
-            "https://cef-builds.spotifycdn.com/cef_binary_{version}_{arch}.tar.bz2",
+            "cef_binary_{version}_{arch}.tar.bz2",
             version = version,
             arch = self.arch()?
         ))
     }
 
-    pub(crate) fn root_dir_name(&self, version: &str) -> Option<String> {
+    /// Returns the download URL of the archive from the mirror, e.g.:
+    /// [`DEFAULT_CEF_MIRROR`].
+    pub(crate) fn download_url(&self, mirror: &str, version: &str) -> Option<String> {
         Some(format!(
-            "cef_binary_{version}_{arch}",
-            version = version,
-            arch = self.arch()?
+            "{mirror}/{name}",
+            mirror = mirror.trim_end_matches('/'),
+            name = self.archive_name(version)?
         ))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn archive_name() {
+        for (platform, os, arch) in [
+            (CefBuildsPlatform::Windows_x64, "windows", "windows64"),
+            (CefBuildsPlatform::Windows_arm64, "windows", "windowsarm64"),
+            (CefBuildsPlatform::Macos_x64, "macos", "macosx64"),
+            (CefBuildsPlatform::Macos_arm64, "macos", "macosarm64"),
+            (CefBuildsPlatform::Linux_x64, "linux", "linux64"),
+            (CefBuildsPlatform::Linux_arm64, "linux", "linuxarm64"),
+            (CefBuildsPlatform::Linux_arm, "linux", "linuxarm"),
+        ] {
+            assert_eq!(platform.os(), Some(os));
+            assert_eq!(
+                platform.archive_name("1.0").unwrap(),
+                format!("cef_binary_1.0_{}.tar.bz2", arch)
+            );
+        }
+    }
+
+    #[test]
+    fn value_names() {
+        assert_eq!(
+            CefBuildsPlatform::from_str("linux-arm64", false).unwrap(),
+            CefBuildsPlatform::Linux_arm64
+        );
+        assert_eq!(
+            CefBuildsPlatform::from_str("windows-arm64", false).unwrap(),
+            CefBuildsPlatform::Windows_arm64
+        );
+        assert_eq!(
+            CefBuildsPlatform::from_str("linux-arm", false).unwrap(),
+            CefBuildsPlatform::Linux_arm
+        );
+    }
+}
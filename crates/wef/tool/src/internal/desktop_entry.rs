@@ -0,0 +1,153 @@
+use askama::Template;
+
+use crate::internal::InfoPlist;
+
+/// ```askama
+/// [Desktop Entry]
+/// Type=Application
+/// Version=1.0
+/// Name={{ name }}
+/// Exec={{ exec }} %U
+/// {% if let Some(icon) = icon -%}
+/// Icon={{ icon }}
+/// {% endif -%}
+/// Categories={{ category }};
+/// {% if !url_schemes.is_empty() -%}
+/// MimeType={% for scheme in url_schemes %}x-scheme-handler/{{ scheme }};{% endfor %}
+/// {% endif -%}
+/// Terminal=false
+/// {% if let Some(version) = version -%}
+/// X-AppImage-Version={{ version }}
+/// {% endif -%}
+/// ```
+#[derive(Debug, Template)]
+#[template(ext = "txt", in_doc = true)]
+pub(crate) struct DesktopEntry {
+    pub(crate) name: String,
+    pub(crate) exec: String,
+    pub(crate) icon: Option<String>,
+    pub(crate) category: &'static str,
+    pub(crate) url_schemes: Vec<String>,
+    pub(crate) version: Option<String>,
+}
+
+impl DesktopEntry {
+    /// Creates the desktop entry from the same bundle settings as the macOS
+    /// `Info.plist`.
+    pub(crate) fn new(plist: &InfoPlist, exec: impl Into<String>, icon: Option<String>) -> Self {
+        Self {
+            name: plist
+                .display_name
+                .clone()
+                .unwrap_or_else(|| plist.name.clone()),
+            exec: exec.into(),
+            icon,
+            category: plist
+                .category
+                .as_deref()
+                .map(desktop_category)
+                .unwrap_or("Utility"),
+            url_schemes: plist.url_schemes.clone(),
+            version: plist.bundle_short_version.clone(),
+        }
+    }
+}
+
+/// Maps the macOS application category, e.g.:
+/// `public.app-category.developer-tools`, to a freedesktop main category.
+fn desktop_category(category: &str) -> &'static str {
+    const MAIN_CATEGORIES: &[&str] = &[
+        "AudioVideo",
+        "Audio",
+        "Video",
+        "Development",
+        "Education",
+        "Game",
+        "Graphics",
+        "Network",
+        "Office",
+        "Science",
+        "Settings",
+        "System",
+        "Utility",
+    ];
+
+    if let Some(category) = MAIN_CATEGORIES.iter().find(|c| **c == category) {
+        return category;
+    }
+
+    let category = category.trim_start_matches("public.app-category.");
+    match category {
+        "developer-tools" => "Development",
+        "education" | "reference" => "Education",
+        "games" => "Game",
+        _ if category.ends_with("-games") => "Game",
+        "graphics-design" | "photography" => "Graphics",
+        "music" | "video" | "entertainment" => "AudioVideo",
+        "business" | "finance" | "productivity" => "Office",
+        "news" | "social-networking" => "Network",
+        _ => "Utility",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn render() {
+        let mut plist = InfoPlist::new("test-app", "io.github.wef.test-app");
+        plist.display_name = Some("Test App".to_string());
+        plist.category = Some("public.app-category.developer-tools".to_string());
+        plist.url_schemes = vec!["wef".to_string(), "wef-test".to_string()];
+        plist.bundle_short_version = Some("0.3.0".to_string());
+
+        assert_eq!(
+            DesktopEntry::new(
+                &plist,
+                "test-app",
+                Some("io.github.wef.test-app".to_string())
+            )
+            .render()
+            .unwrap(),
+            "[Desktop Entry]
+Type=Application
+Version=1.0
+Name=Test App
+Exec=test-app %U
+Icon=io.github.wef.test-app
+Categories=Development;
+MimeType=x-scheme-handler/wef;x-scheme-handler/wef-test;
+Terminal=false
+X-AppImage-Version=0.3.0
+"
+        );
+
+        assert_eq!(
+            DesktopEntry::new(&InfoPlist::new("app", "io.github.wef.app"), "app", None)
+                .render()
+                .unwrap(),
+            "[Desktop Entry]
+Type=Application
+Version=1.0
+Name=app
+Exec=app %U
+Categories=Utility;
+Terminal=false
+"
+        );
+    }
+
+    #[test]
+    fn category() {
+        assert_eq!(desktop_category("Utility"), "Utility");
+        assert_eq!(desktop_category("Network"), "Network");
+        assert_eq!(desktop_category("public.app-category.games"), "Game");
+        assert_eq!(desktop_category("public.app-category.puzzle-games"), "Game");
+        assert_eq!(
+            desktop_category("public.app-category.productivity"),
+            "Office"
+        );
+        assert_eq!(desktop_category("public.app-category.weather"), "Utility");
+    }
+}
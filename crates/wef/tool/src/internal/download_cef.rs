@@ -1,75 +1,359 @@
 use std::{
-    fs::{self, File},
+    fs::{self, File, OpenOptions},
     io::{Read, Write},
-    path::Path,
+    path::{Component, Path, PathBuf},
 };
 
 use anyhow::{Context, Result};
-use reqwest::blocking::Client;
+use reqwest::{
+    StatusCode,
+    blocking::Client,
+    header::{CONTENT_LENGTH, RANGE},
+};
+use sha1::Sha1;
+use sha2::{Digest, Sha256};
 use tar::EntryType;
 
-use crate::internal::CefBuildsPlatform;
+use crate::internal::{CACHE_MARKER, CefBuildsPlatform, read_cef_version};
+
+/// Where to get the CEF archive from.
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum CefSource<'a> {
+    /// Download from the mirror, e.g.: [`DEFAULT_CEF_MIRROR`](crate::internal::DEFAULT_CEF_MIRROR).
+    Mirror(&'a str),
+    /// Install from a local archive.
+    Archive(&'a Path),
+}
+
+/// The options of [`download_cef`].
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct DownloadCefOptions<'a> {
+    pub(crate) version: &'a str,
+    pub(crate) platform: CefBuildsPlatform,
+    pub(crate) source: CefSource<'a>,
+    /// The shared directory of the downloaded archives, the archives are
+    /// stored in the subdirectories named by the version.
+    pub(crate) cache_dir: &'a Path,
+    /// Download and extract even if the target directory or the cached archive
+    /// already exists.
+    pub(crate) force: bool,
+    /// Verify the archive with the published checksum files.
+    pub(crate) verify: bool,
+}
+
+/// The file written to the extracted CEF directory, it records the name of the
+/// archive, e.g.: `cef_binary_{version}_linux64.tar.bz2`.
+const INSTALLED_MARKER: &str = ".wef-cef-archive";
 
+/// The algorithms of the checksum sidecar files, in order of preference.
+const CHECKSUM_ALGORITHMS: &[ChecksumAlgorithm] =
+    &[ChecksumAlgorithm::Sha256, ChecksumAlgorithm::Sha1];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ChecksumAlgorithm {
+    Sha1,
+    Sha256,
+}
+
+impl ChecksumAlgorithm {
+    /// Returns the extension of the sidecar file, e.g.: `sha256`.
+    pub(crate) fn extension(&self) -> &'static str {
+        match self {
+            ChecksumAlgorithm::Sha1 => "sha1",
+            ChecksumAlgorithm::Sha256 => "sha256",
+        }
+    }
+
+    fn digest_file(&self, path: &Path) -> Result<String> {
+        let mut file = File::open(path).with_context(|| format!("open file {}", path.display()))?;
+        match self {
+            ChecksumAlgorithm::Sha1 => digest_reader::<Sha1>(&mut file),
+            ChecksumAlgorithm::Sha256 => digest_reader::<Sha256>(&mut file),
+        }
+        .with_context(|| format!("read file {}", path.display()))
+    }
+}
+
+/// The expected checksum of an archive.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Checksum {
+    algorithm: ChecksumAlgorithm,
+    hash: String,
+}
+
+impl Checksum {
+    /// Parses the content of a sidecar file, the hash can be followed by the
+    /// file name, e.g.: `<hash>  cef_binary.tar.bz2`.
+    fn parse(algorithm: ChecksumAlgorithm, content: &str) -> Result<Self> {
+        let hash = content
+            .split_whitespace()
+            .next()
+            .unwrap_or_default()
+            .to_ascii_lowercase();
+        let len = match algorithm {
+            ChecksumAlgorithm::Sha1 => 40,
+            ChecksumAlgorithm::Sha256 => 64,
+        };
+        anyhow::ensure!(
+            hash.len() == len && hash.chars().all(|c| c.is_ascii_hexdigit()),
+            "invalid {} checksum: {:?}",
+            algorithm.extension(),
+            content.trim()
+        );
+        Ok(Self { algorithm, hash })
+    }
+
+    /// Reads the sidecar file next to the archive, e.g.: `<archive>.sha256`.
+    fn read_sidecar(archive_path: &Path) -> Result<Option<Self>> {
+        for algorithm in CHECKSUM_ALGORITHMS {
+            let path = sidecar_path(archive_path, *algorithm);
+            if path.exists() {
+                let content = fs::read_to_string(&path)
+                    .with_context(|| format!("read checksum file {}", path.display()))?;
+                return Self::parse(*algorithm, &content).map(Some);
+            }
+        }
+        Ok(None)
+    }
+
+    fn verify(&self, path: &Path) -> Result<()> {
+        let actual = self.algorithm.digest_file(path)?;
+        anyhow::ensure!(
+            actual == self.hash,
+            "{} checksum mismatch for {}: expected {}, got {}",
+            self.algorithm.extension(),
+            path.display(),
+            self.hash,
+            actual
+        );
+        Ok(())
+    }
+}
+
+/// Downloads the CEF archive, or uses the cached or the local archive, then
+/// extracts it to the `path`.
 pub(crate) fn download_cef(
     path: &Path,
-    version: &str,
-    platform: CefBuildsPlatform,
-    force: bool,
+    options: DownloadCefOptions,
     mut callback: impl DownloadCefCallback,
 ) -> Result<()> {
-    if !force && path.exists() {
+    let unsupported = || anyhow::anyhow!("unsupported platform: {:?}", options.platform);
+    let name = options
+        .platform
+        .archive_name(options.version)
+        .ok_or_else(unsupported)?;
+    if !options.force && path.exists() {
+        let installed = installed_archive_name(path);
+        anyhow::ensure!(
+            installed.as_deref() == Some(name.as_str()),
+            "{} already contains {}, but {} is requested, use `--force` to replace it or install to another directory",
+            path.display(),
+            installed
+                .as_deref()
+                .map(|name| name.trim_end_matches(".tar.bz2"))
+                .unwrap_or("an unknown CEF"),
+            name.trim_end_matches(".tar.bz2")
+        );
         return Ok(());
     }
 
-    let url = platform
-        .download_url(version)
-        .ok_or_else(|| anyhow::anyhow!("unsupported platform: {:?}", platform))?;
-
-    // Download with progress
-    let client = Client::new();
-    let response = client.get(&url).send().context("download CEF")?;
-
-    let total_size = response.content_length().unwrap_or(0);
-
-    let tmpdir_path = tempfile::tempdir().context("create temporary directory")?;
-    let archive_path = tmpdir_path.path().join("cef.tar.bz2");
-
-    callback.download_start(total_size);
-    download_file(&url, &archive_path, &mut callback)?;
-    callback.download_end();
+    let archive_path = match options.source {
+        CefSource::Archive(archive_path) => {
+            match Checksum::read_sidecar(archive_path)? {
+                Some(checksum) if options.verify => {
+                    checksum.verify(archive_path)?;
+                    callback.verified(checksum.algorithm);
+                }
+                None if options.verify => callback.unverified(archive_path),
+                _ => {}
+            }
+            archive_path.to_path_buf()
+        }
+        CefSource::Mirror(mirror) => fetch_archive(mirror, &options, &mut callback)?,
+    };
 
     // Create the target directory if it doesn't exist
     fs::create_dir_all(path).context("create target directory")?;
 
     // Extract with progress
     callback.extract_start();
-    extract_archive(
-        &archive_path,
-        path,
-        &platform.root_dir_name(version).unwrap(),
-        &mut callback,
-    )
-    .context("extract CEF archive")?;
+    extract_archive(&archive_path, path, &mut callback).context("extract CEF archive")?;
+    let marker_path = path.join(INSTALLED_MARKER);
+    fs::write(&marker_path, &name)
+        .with_context(|| format!("write install marker {}", marker_path.display()))?;
     callback.extract_end();
 
     Ok(())
 }
 
-fn download_file(url: &str, path: &Path, callback: &mut impl DownloadCefCallback) -> Result<()> {
+/// Returns the name of the archive the CEF in `path` is extracted from.
+///
+/// The CEF installed before the [`INSTALLED_MARKER`] is written is assumed to
+/// be extracted for the host.
+fn installed_archive_name(path: &Path) -> Option<String> {
+    match fs::read_to_string(path.join(INSTALLED_MARKER)) {
+        Ok(name) => Some(name.trim().to_string()),
+        Err(_) => CefBuildsPlatform::Auto.archive_name(&read_cef_version(path).ok()?),
+    }
+}
+
+/// Returns the path of the archive in the cache directory, downloads it from
+/// the mirror if it's not cached.
+fn fetch_archive(
+    mirror: &str,
+    options: &DownloadCefOptions,
+    callback: &mut impl DownloadCefCallback,
+) -> Result<PathBuf> {
+    let unsupported = || anyhow::anyhow!("unsupported platform: {:?}", options.platform);
+    let name = options
+        .platform
+        .archive_name(options.version)
+        .ok_or_else(unsupported)?;
+    let url = options
+        .platform
+        .download_url(mirror, options.version)
+        .ok_or_else(unsupported)?;
+
+    let cache_dir = options.cache_dir.join(options.version);
+    let archive_path = cache_dir.join(&name);
+    fs::create_dir_all(&cache_dir)
+        .with_context(|| format!("create cache directory {}", cache_dir.display()))?;
+    let marker_path = cache_dir.join(CACHE_MARKER);
+    if !marker_path.exists() {
+        fs::write(&marker_path, options.version)
+            .with_context(|| format!("write cache marker {}", marker_path.display()))?;
+    }
+
+    if !options.force && archive_path.exists() {
+        // The sidecar file is cached with the archive, so the cached archive can be
+        // verified without network.
+        match Checksum::read_sidecar(&archive_path)? {
+            Some(checksum) if options.verify => {
+                if checksum.verify(&archive_path).is_ok() {
+                    callback.cached(&archive_path);
+                    callback.verified(checksum.algorithm);
+                    return Ok(archive_path);
+                }
+            }
+            None if options.verify => {}
+            _ => {
+                callback.cached(&archive_path);
+                return Ok(archive_path);
+            }
+        }
+    }
+
     let client = Client::new();
+    let checksum = if options.verify {
+        Some(fetch_checksum(&client, &url)?)
+    } else {
+        None
+    };
+
+    let part_path = cache_dir.join(format!("{}.part", name));
+    if options.force {
+        _ = fs::remove_file(&part_path);
+    }
+    download_file(&client, &url, &part_path, callback)?;
+
+    for algorithm in CHECKSUM_ALGORITHMS {
+        _ = fs::remove_file(sidecar_path(&archive_path, *algorithm));
+    }
+    if let Some(checksum) = &checksum {
+        if let Err(err) = checksum.verify(&part_path) {
+            _ = fs::remove_file(&part_path);
+            return Err(err);
+        }
+        callback.verified(checksum.algorithm);
+    }
+
+    fs::rename(&part_path, &archive_path)
+        .with_context(|| format!("move the archive to {}", archive_path.display()))?;
+    if let Some(checksum) = &checksum {
+        let path = sidecar_path(&archive_path, checksum.algorithm);
+        fs::write(&path, &checksum.hash)
+            .with_context(|| format!("write checksum file {}", path.display()))?;
+    }
 
-    let mut response = client
-        .get(url)
+    Ok(archive_path)
+}
+
+/// Fetches the published checksum sidecar file of the archive, e.g.:
+/// `<url>.sha256` or `<url>.sha1`.
+fn fetch_checksum(client: &Client, url: &str) -> Result<Checksum> {
+    for algorithm in CHECKSUM_ALGORITHMS {
+        let checksum_url = format!("{}.{}", url, algorithm.extension());
+        let response = client
+            .get(&checksum_url)
+            .send()
+            .with_context(|| format!("download checksum from {}", checksum_url))?;
+        if response.status() == StatusCode::NOT_FOUND {
+            continue;
+        }
+        let content = response
+            .error_for_status()
+            .and_then(|resp| resp.text())
+            .with_context(|| format!("download checksum from {}", checksum_url))?;
+        return Checksum::parse(*algorithm, &content);
+    }
+
+    anyhow::bail!(
+        "no checksum file found for {}, use `--no-verify` to skip the verification",
+        url
+    )
+}
+
+/// Downloads the file to the path, resumes the download if the path already
+/// exists and the server supports range requests.
+fn download_file(
+    client: &Client,
+    url: &str,
+    path: &Path,
+    callback: &mut impl DownloadCefCallback,
+) -> Result<()> {
+    let offset = fs::metadata(path)
+        .map(|metadata| metadata.len())
+        .unwrap_or(0);
+
+    let mut request = client.get(url);
+    if offset > 0 {
+        request = request.header(RANGE, format!("bytes={}-", offset));
+    }
+    let response = request
         .send()
-        .and_then(|resp| resp.error_for_status())
+        .with_context(|| format!("download CEF from {}", url))?;
+    if offset > 0 && response.status() == StatusCode::RANGE_NOT_SATISFIABLE {
+        // The file has been completely downloaded, it will be verified by the caller.
+        return Ok(());
+    }
+    let mut response = response
+        .error_for_status()
         .with_context(|| format!("download CEF from {}", url))?;
 
-    let mut downloaded: u64 = 0;
-    let mut buffer = [0; 8192];
+    let resumed = response.status() == StatusCode::PARTIAL_CONTENT;
+    let mut downloaded = if resumed { offset } else { 0 };
+    let content_length = response
+        .headers()
+        .get(CONTENT_LENGTH)
+        .and_then(|value| value.to_str().ok()?.parse::<u64>().ok())
+        .unwrap_or(0);
 
-    let mut file = File::create(path).with_context(|| format!("create file {}", path.display()))?;
+    let mut file = OpenOptions::new()
+        .create(true)
+        .write(true)
+        .append(resumed)
+        .truncate(!resumed)
+        .open(path)
+        .with_context(|| format!("create file {}", path.display()))?;
 
-    while let Ok(bytes_read) = response.read(&mut buffer) {
+    callback.download_start(downloaded + content_length);
+    callback.download_progress(downloaded);
+
+    let mut buffer = [0; 8192];
+    loop {
+        let bytes_read = response
+            .read(&mut buffer)
+            .with_context(|| format!("download CEF from {}", url))?;
         if bytes_read == 0 {
             break;
         }
@@ -78,6 +362,7 @@ fn download_file(url: &str, path: &Path, callback: &mut impl DownloadCefCallback
         downloaded += bytes_read as u64;
         callback.download_progress(downloaded);
     }
+    callback.download_end();
 
     Ok(())
 }
@@ -85,7 +370,6 @@ fn download_file(url: &str, path: &Path, callback: &mut impl DownloadCefCallback
 fn extract_archive(
     archive_path: &Path,
     target_dir: &Path,
-    root_dir_name: &str,
     callback: &mut impl DownloadCefCallback,
 ) -> Result<()> {
     let tar_bz2 = File::open(archive_path)
@@ -103,8 +387,17 @@ fn extract_archive(
             continue;
         }
 
-        let entry_path = entry.path().unwrap().to_path_buf();
-        let filepath = target_dir.join(entry_path.strip_prefix(root_dir_name).unwrap());
+        // Strip the root directory of the archive, e.g.: `cef_binary_{version}_{arch}`.
+        let entry_path = entry.path().context("read entry path")?.to_path_buf();
+        let relative_path = entry_path
+            .components()
+            .skip(1)
+            .filter(|component| matches!(component, Component::Normal(_)))
+            .collect::<PathBuf>();
+        if relative_path.as_os_str().is_empty() {
+            continue;
+        }
+        let filepath = target_dir.join(relative_path);
         let parent_path = filepath.parent().unwrap();
         std::fs::create_dir_all(parent_path)
             .with_context(|| format!("create directory for entry {}", parent_path.display()))?;
@@ -122,17 +415,499 @@ fn extract_archive(
     Ok(())
 }
 
+fn sidecar_path(archive_path: &Path, algorithm: ChecksumAlgorithm) -> PathBuf {
+    let mut path = archive_path.as_os_str().to_os_string();
+    path.push(".");
+    path.push(algorithm.extension());
+    path.into()
+}
+
+fn digest_reader<D: Digest>(reader: &mut impl Read) -> std::io::Result<String> {
+    let mut hasher = D::new();
+    let mut buffer = [0; 8192];
+    loop {
+        let bytes_read = reader.read(&mut buffer)?;
+        if bytes_read == 0 {
+            break;
+        }
+        hasher.update(&buffer[..bytes_read]);
+    }
+    Ok(hasher
+        .finalize()
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect())
+}
+
 #[allow(unused_variables)]
 pub(crate) trait DownloadCefCallback {
+    fn cached(&mut self, path: &Path) {}
+
     fn download_start(&mut self, total: u64) {}
 
     fn download_progress(&mut self, downloaded: u64) {}
 
     fn download_end(&mut self) {}
 
+    fn verified(&mut self, algorithm: ChecksumAlgorithm) {}
+
+    fn unverified(&mut self, path: &Path) {}
+
     fn extract_start(&mut self) {}
 
     fn extract_file(&mut self, path: &str) {}
 
     fn extract_end(&mut self) {}
 }
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        collections::HashMap,
+        io::{BufRead, BufReader},
+        net::TcpListener,
+        sync::{Arc, Mutex},
+    };
+
+    use super::*;
+
+    const VERSION: &str = "1.0.0+test";
+    const PLATFORM: CefBuildsPlatform = CefBuildsPlatform::Linux_x64;
+
+    /// A local HTTP server standing in for the CEF mirror, it supports the
+    /// range requests and records the requests.
+    struct Mirror {
+        url: String,
+        files: Arc<Mutex<HashMap<String, Vec<u8>>>>,
+        requests: Arc<Mutex<Vec<String>>>,
+    }
+
+    impl Mirror {
+        fn start() -> Self {
+            let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+            let url = format!("http://{}/cef", listener.local_addr().unwrap());
+            let files = Arc::new(Mutex::new(HashMap::<String, Vec<u8>>::new()));
+            let requests = Arc::new(Mutex::new(Vec::new()));
+
+            std::thread::spawn({
+                let files = files.clone();
+                let requests = requests.clone();
+                move || {
+                    for stream in listener.incoming() {
+                        let mut stream = stream.unwrap();
+                        let mut reader = BufReader::new(stream.try_clone().unwrap());
+                        let mut request_line = String::new();
+                        reader.read_line(&mut request_line).unwrap();
+                        let path = request_line.split(' ').nth(1).unwrap().to_string();
+
+                        let mut range_start = None;
+                        loop {
+                            let mut line = String::new();
+                            reader.read_line(&mut line).unwrap();
+                            if line.trim().is_empty() {
+                                break;
+                            }
+                            if let Some(value) = line.to_ascii_lowercase().strip_prefix("range:") {
+                                let value = value.trim().trim_start_matches("bytes=");
+                                range_start = value.trim_end_matches('-').parse::<usize>().ok();
+                            }
+                        }
+
+                        requests.lock().unwrap().push(match range_start {
+                            Some(start) => format!("{} bytes={}-", path, start),
+                            None => path.clone(),
+                        });
+
+                        let (status, body) = match files.lock().unwrap().get(&path) {
+                            Some(data) => match range_start {
+                                Some(start) if start >= data.len() => {
+                                    ("416 Range Not Satisfiable", vec![])
+                                }
+                                Some(start) => ("206 Partial Content", data[start..].to_vec()),
+                                None => ("200 OK", data.clone()),
+                            },
+                            None => ("404 Not Found", vec![]),
+                        };
+                        _ = write!(
+                            stream,
+                            "HTTP/1.1 {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+                            status,
+                            body.len()
+                        );
+                        _ = stream.write_all(&body);
+                    }
+                }
+            });
+
+            Self {
+                url,
+                files,
+                requests,
+            }
+        }
+
+        fn put(&self, name: &str, data: impl Into<Vec<u8>>) {
+            self.files
+                .lock()
+                .unwrap()
+                .insert(format!("/cef/{}", name), data.into());
+        }
+
+        fn take_requests(&self) -> Vec<String> {
+            std::mem::take(&mut *self.requests.lock().unwrap())
+        }
+    }
+
+    struct NoopCallback;
+
+    impl DownloadCefCallback for NoopCallback {}
+
+    fn archive_name() -> String {
+        PLATFORM.archive_name(VERSION).unwrap()
+    }
+
+    /// Creates a `tar.bz2` archive with the root directory like the CEF builds.
+    fn create_archive() -> Vec<u8> {
+        let mut builder = tar::Builder::new(bzip2::write::BzEncoder::new(
+            Vec::new(),
+            bzip2::Compression::fast(),
+        ));
+        let root = archive_name().trim_end_matches(".tar.bz2").to_string();
+        for (path, content) in [("README.txt", "readme"), ("Release/libcef.so", "libcef")] {
+            let mut header = tar::Header::new_gnu();
+            header.set_size(content.len() as u64);
+            header.set_mode(0o644);
+            header.set_cksum();
+            builder
+                .append_data(
+                    &mut header,
+                    format!("{}/{}", root, path),
+                    content.as_bytes(),
+                )
+                .unwrap();
+        }
+        builder.into_inner().unwrap().finish().unwrap()
+    }
+
+    fn sha256(data: &[u8]) -> String {
+        digest_reader::<Sha256>(&mut &data[..]).unwrap()
+    }
+
+    fn sha1(data: &[u8]) -> String {
+        digest_reader::<Sha1>(&mut &data[..]).unwrap()
+    }
+
+    fn options<'a>(source: CefSource<'a>, cache_dir: &'a Path) -> DownloadCefOptions<'a> {
+        DownloadCefOptions {
+            version: VERSION,
+            platform: PLATFORM,
+            source,
+            cache_dir,
+            force: false,
+            verify: true,
+        }
+    }
+
+    fn assert_extracted(path: &Path) {
+        assert_eq!(
+            fs::read_to_string(path.join("README.txt")).unwrap(),
+            "readme"
+        );
+        assert_eq!(
+            fs::read_to_string(path.join("Release/libcef.so")).unwrap(),
+            "libcef"
+        );
+    }
+
+    #[test]
+    fn download_and_verify() {
+        let mirror = Mirror::start();
+        let archive = create_archive();
+        let name = archive_name();
+        mirror.put(&name, archive.clone());
+        mirror.put(
+            &format!("{}.sha256", name),
+            format!("{}  {}\n", sha256(&archive), name),
+        );
+
+        let dir = tempfile::tempdir().unwrap();
+        let cache_dir = dir.path().join("cache");
+        let target = dir.path().join("cef");
+        download_cef(
+            &target,
+            options(CefSource::Mirror(&mirror.url), &cache_dir),
+            NoopCallback,
+        )
+        .unwrap();
+
+        assert_extracted(&target);
+        assert_eq!(
+            mirror.take_requests(),
+            [format!("/cef/{}.sha256", name), format!("/cef/{}", name)]
+        );
+        let cached = cache_dir.join(VERSION).join(&name);
+        assert_eq!(fs::read(&cached).unwrap(), archive);
+        assert!(cache_dir.join(VERSION).join(CACHE_MARKER).exists());
+        assert_eq!(
+            fs::read_to_string(sidecar_path(&cached, ChecksumAlgorithm::Sha256)).unwrap(),
+            sha256(&archive)
+        );
+    }
+
+    #[test]
+    fn fallback_to_sha1() {
+        let mirror = Mirror::start();
+        let archive = create_archive();
+        let name = archive_name();
+        mirror.put(&name, archive.clone());
+        mirror.put(&format!("{}.sha1", name), sha1(&archive));
+
+        let dir = tempfile::tempdir().unwrap();
+        let target = dir.path().join("cef");
+        download_cef(
+            &target,
+            options(CefSource::Mirror(&mirror.url), dir.path()),
+            NoopCallback,
+        )
+        .unwrap();
+        assert_extracted(&target);
+    }
+
+    #[test]
+    fn checksum_mismatch() {
+        let mirror = Mirror::start();
+        let archive = create_archive();
+        let name = archive_name();
+        mirror.put(&name, archive.clone());
+        mirror.put(&format!("{}.sha256", name), sha256(b"other"));
+
+        let dir = tempfile::tempdir().unwrap();
+        let target = dir.path().join("cef");
+        let err = download_cef(
+            &target,
+            options(CefSource::Mirror(&mirror.url), dir.path()),
+            NoopCallback,
+        )
+        .unwrap_err()
+        .to_string();
+
+        assert!(err.contains("checksum mismatch"), "{}", err);
+        assert!(err.contains(&sha256(b"other")), "{}", err);
+        assert!(err.contains(&sha256(&archive)), "{}", err);
+        assert!(!target.exists());
+        assert_eq!(
+            fs::read_dir(dir.path().join(VERSION))
+                .unwrap()
+                .map(|entry| entry.unwrap().file_name())
+                .collect::<Vec<_>>(),
+            [CACHE_MARKER]
+        );
+    }
+
+    #[test]
+    fn missing_checksum() {
+        let mirror = Mirror::start();
+        let archive = create_archive();
+        mirror.put(&archive_name(), archive);
+
+        let dir = tempfile::tempdir().unwrap();
+        let target = dir.path().join("cef");
+        let err = download_cef(
+            &target,
+            options(CefSource::Mirror(&mirror.url), dir.path()),
+            NoopCallback,
+        )
+        .unwrap_err()
+        .to_string();
+        assert!(err.contains("--no-verify"), "{}", err);
+
+        download_cef(
+            &target,
+            DownloadCefOptions {
+                verify: false,
+                ..options(CefSource::Mirror(&mirror.url), dir.path())
+            },
+            NoopCallback,
+        )
+        .unwrap();
+        assert_extracted(&target);
+    }
+
+    #[test]
+    fn reuse_cache() {
+        let mirror = Mirror::start();
+        let archive = create_archive();
+        let name = archive_name();
+        mirror.put(&name, archive.clone());
+        mirror.put(&format!("{}.sha256", name), sha256(&archive));
+
+        let dir = tempfile::tempdir().unwrap();
+        let cache_dir = dir.path().join("cache");
+        for target in ["a", "b"] {
+            download_cef(
+                &dir.path().join(target),
+                options(CefSource::Mirror(&mirror.url), &cache_dir),
+                NoopCallback,
+            )
+            .unwrap();
+        }
+
+        assert_extracted(&dir.path().join("b"));
+        // The second install is verified against the cached checksum without network.
+        assert_eq!(mirror.take_requests().len(), 2);
+
+        // A corrupted cache is downloaded again.
+        fs::write(cache_dir.join(VERSION).join(&name), b"corrupted").unwrap();
+        download_cef(
+            &dir.path().join("c"),
+            options(CefSource::Mirror(&mirror.url), &cache_dir),
+            NoopCallback,
+        )
+        .unwrap();
+        assert_extracted(&dir.path().join("c"));
+        assert_eq!(mirror.take_requests().len(), 2);
+    }
+
+    #[test]
+    fn resume_download() {
+        let mirror = Mirror::start();
+        let archive = create_archive();
+        let name = archive_name();
+        mirror.put(&name, archive.clone());
+        mirror.put(&format!("{}.sha256", name), sha256(&archive));
+
+        let dir = tempfile::tempdir().unwrap();
+        let half = archive.len() / 2;
+        fs::create_dir_all(dir.path().join(VERSION)).unwrap();
+        fs::write(
+            dir.path().join(VERSION).join(format!("{}.part", name)),
+            &archive[..half],
+        )
+        .unwrap();
+
+        let target = dir.path().join("cef");
+        download_cef(
+            &target,
+            options(CefSource::Mirror(&mirror.url), dir.path()),
+            NoopCallback,
+        )
+        .unwrap();
+
+        assert_extracted(&target);
+        assert_eq!(
+            mirror.take_requests()[1],
+            format!("/cef/{} bytes={}-", name, half)
+        );
+    }
+
+    #[test]
+    fn from_archive() {
+        let archive = create_archive();
+        let dir = tempfile::tempdir().unwrap();
+        let archive_path = dir.path().join(archive_name());
+        fs::write(&archive_path, &archive).unwrap();
+
+        // Without a sidecar file
+        let target = dir.path().join("a");
+        download_cef(
+            &target,
+            options(CefSource::Archive(&archive_path), dir.path()),
+            NoopCallback,
+        )
+        .unwrap();
+        assert_extracted(&target);
+
+        // With a mismatched sidecar file
+        fs::write(
+            sidecar_path(&archive_path, ChecksumAlgorithm::Sha256),
+            sha256(b"other"),
+        )
+        .unwrap();
+        let err = download_cef(
+            &dir.path().join("b"),
+            options(CefSource::Archive(&archive_path), dir.path()),
+            NoopCallback,
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("checksum mismatch"));
+    }
+
+    #[test]
+    fn existing_installation() {
+        let archive = create_archive();
+        let dir = tempfile::tempdir().unwrap();
+        let archive_path = dir.path().join(archive_name());
+        fs::write(&archive_path, &archive).unwrap();
+
+        let target = dir.path().join("cef");
+        let options = options(CefSource::Archive(&archive_path), dir.path());
+        download_cef(&target, options, NoopCallback).unwrap();
+        assert_eq!(
+            fs::read_to_string(target.join(INSTALLED_MARKER)).unwrap(),
+            archive_name()
+        );
+
+        // The same version and platform is already installed.
+        fs::remove_file(target.join("README.txt")).unwrap();
+        download_cef(&target, options, NoopCallback).unwrap();
+        assert!(!target.join("README.txt").exists());
+
+        // Another platform is requested.
+        let other = DownloadCefOptions {
+            platform: CefBuildsPlatform::Linux_arm64,
+            ..options
+        };
+        let err = download_cef(&target, other, NoopCallback)
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("_linux64"), "{}", err);
+        assert!(err.contains("_linuxarm64"), "{}", err);
+        assert!(err.contains("--force"), "{}", err);
+
+        // Not installed by `cargo wef init`.
+        let unknown = dir.path().join("unknown");
+        fs::create_dir_all(&unknown).unwrap();
+        let err = download_cef(&unknown, options, NoopCallback)
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("an unknown CEF"), "{}", err);
+
+        download_cef(
+            &target,
+            DownloadCefOptions {
+                force: true,
+                ..options
+            },
+            NoopCallback,
+        )
+        .unwrap();
+        assert_extracted(&target);
+    }
+
+    #[test]
+    fn parse_checksum() {
+        let hash = sha256(b"");
+        assert_eq!(
+            Checksum::parse(
+                ChecksumAlgorithm::Sha256,
+                &format!("{}  cef.tar.bz2\n", hash.to_uppercase())
+            )
+            .unwrap(),
+            Checksum {
+                algorithm: ChecksumAlgorithm::Sha256,
+                hash
+            }
+        );
+        assert!(Checksum::parse(ChecksumAlgorithm::Sha1, "<html>").is_err());
+    }
+
+    #[test]
+    fn mirror_url() {
+        assert_eq!(
+            PLATFORM
+                .download_url("https://example.com/cef/", VERSION)
+                .unwrap(),
+            format!("https://example.com/cef/{}", archive_name())
+        );
+    }
+}
@@ -1,4 +1,6 @@
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
 
 pub(crate) fn find_cef_root() -> PathBuf {
     if let Ok(cef_root) = std::env::var("CEF_ROOT") {
@@ -7,3 +9,25 @@ pub(crate) fn find_cef_root() -> PathBuf {
         dirs::home_dir().expect("get home directory").join(".cef")
     }
 }
+
+/// Reads the version of the CEF installed in `cef_root` from
+/// `include/cef_version.h`.
+pub(crate) fn read_cef_version(cef_root: &Path) -> Result<String> {
+    let path = cef_root.join("include").join("cef_version.h");
+    let content =
+        std::fs::read_to_string(&path).with_context(|| format!("read {}", path.display()))?;
+    parse_cef_version(&content)
+        .with_context(|| format!("no CEF_VERSION defined in {}", path.display()))
+}
+
+fn parse_cef_version(content: &str) -> Option<String> {
+    content.lines().find_map(|line| {
+        let value = line
+            .trim()
+            .strip_prefix("#define")?
+            .trim_start()
+            .strip_prefix("CEF_VERSION")?;
+        let value = value.trim();
+        Some(value.strip_prefix('"')?.strip_suffix('"')?.to_string())
+    })
+}
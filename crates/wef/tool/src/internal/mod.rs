@@ -1,13 +1,21 @@
 mod add_cef_framework;
 mod add_helper;
+mod cef_cache;
 mod cef_platform;
+mod desktop_entry;
 mod download_cef;
 mod find_cef_root;
 mod plist;
 
-pub(crate) use add_cef_framework::add_cef_framework;
-pub(crate) use add_helper::add_helper;
-pub(crate) use cef_platform::{CefBuildsPlatform, DEFAULT_CEF_VERSION};
-pub(crate) use download_cef::{DownloadCefCallback, download_cef};
-pub(crate) use find_cef_root::find_cef_root;
+pub(crate) use add_cef_framework::{add_cef_framework, cef_framework_files};
+pub(crate) use add_helper::{add_helper, find_helper_apps};
+pub(crate) use cef_cache::{
+    CACHE_MARKER, CachedCefVersion, cached_cef_versions, default_cache_dir,
+};
+pub(crate) use cef_platform::{CefBuildsPlatform, DEFAULT_CEF_MIRROR, DEFAULT_CEF_VERSION};
+pub(crate) use desktop_entry::DesktopEntry;
+pub(crate) use download_cef::{
+    CefSource, ChecksumAlgorithm, DownloadCefCallback, DownloadCefOptions, download_cef,
+};
+pub(crate) use find_cef_root::{find_cef_root, read_cef_version};
 pub(crate) use plist::InfoPlist;
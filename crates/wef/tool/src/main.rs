@@ -5,7 +5,10 @@ use std::path::PathBuf;
 
 use clap::{Parser, Subcommand};
 
-use crate::internal::{CefBuildsPlatform, DEFAULT_CEF_VERSION};
+use crate::{
+    commands::LinuxBundleFormat,
+    internal::{CefBuildsPlatform, DEFAULT_CEF_MIRROR, DEFAULT_CEF_VERSION},
+};
 
 #[derive(Subcommand)]
 enum Commands {
@@ -17,11 +20,34 @@ enum Commands {
         #[clap(long, default_value = DEFAULT_CEF_VERSION)]
         version: String,
         /// Platform
+        ///
+        /// It can be different from the host to download CEF for another
+        /// target, e.g. `linux-arm64`
         #[clap(long, default_value = "auto")]
         platform: CefBuildsPlatform,
         /// Force download even if the file already exists
+        ///
+        /// Without it, fails if the path contains another CEF version or
+        /// platform
         #[clap(long, short, default_value_t = false)]
         force: bool,
+        /// The mirror to download CEF from
+        #[clap(long, env = "CEF_MIRROR", default_value = DEFAULT_CEF_MIRROR)]
+        mirror: String,
+        /// Install from a local CEF archive instead of downloading it
+        ///
+        /// The archive is verified if there is a `.sha256` or `.sha1` file next
+        /// to it, and the mirror is ignored
+        #[clap(long, value_name = "PATH")]
+        from_archive: Option<PathBuf>,
+        /// The directory to cache the downloaded archives
+        ///
+        /// If not specified, use `wef/cef` in the user's cache directory
+        #[clap(long, env = "WEF_CACHE_DIR")]
+        cache_dir: Option<PathBuf>,
+        /// Skip verifying the archive with the checksum file
+        #[clap(long, default_value_t = false)]
+        no_verify: bool,
     },
     /// Compile a local package and all of its dependencies
     Build {
@@ -48,6 +74,10 @@ enum Commands {
         wef_path: Option<PathBuf>,
         /// Specify the bundle type for the MacOS application
         bundle_type: Option<String>,
+        /// Create a relocatable Linux application bundle with a launcher, a
+        /// `.desktop` file and icons
+        #[clap(long, value_name = "FORMAT")]
+        linux_bundle: Option<LinuxBundleFormat>,
     },
     /// Run a binary or example of the local package
     Run {
@@ -75,10 +105,50 @@ enum Commands {
         #[arg(last = true)]
         args: Vec<String>,
     },
+    /// Generate the TypeScript declarations of the functions registered in the
+    /// JS bridge
+    Types {
+        /// Output path of the declarations, e.g. `web/src/jsbridge.d.ts`
+        ///
+        /// If it's a directory, write `jsbridge.d.ts` into it
+        #[clap(default_value = "jsbridge.d.ts")]
+        out: PathBuf,
+        /// Also write the JSON Schema manifest of the functions to the path
+        #[clap(long)]
+        schema: Option<PathBuf>,
+        /// Package to build (see `cargo help pkgid`)
+        #[clap(long, short, value_name = "SPEC")]
+        package: Option<String>,
+        /// Build only the specified binary
+        #[clap(long, value_name = "NAME")]
+        bin: Option<String>,
+        /// Build only the specified example
+        #[clap(long, value_name = "NAME")]
+        example: Option<String>,
+        /// Build artifacts in release mode, with optimizations
+        #[clap(long, short)]
+        release: bool,
+        /// Use the specified Wef version
+        ///
+        /// If not specified, use the latest version
+        #[clap(long)]
+        wef_version: Option<String>,
+        /// Specify the source code path of the local Wef library instead of the
+        /// published version
+        #[clap(long)]
+        wef_path: Option<PathBuf>,
+    },
     /// Add CEF framework to the application
     AddFramework {
         /// Target app path
         app_path: PathBuf,
+        /// The platform of the application
+        ///
+        /// Use it to assemble the application for a different target than the
+        /// host, the CEF_ROOT must contain the CEF of the platform, e.g.
+        /// downloaded with `cargo wef init --platform <PLATFORM> <PATH>`
+        #[clap(long, default_value = "auto")]
+        platform: CefBuildsPlatform,
         /// Build artifacts in release mode, with optimizations
         #[clap(long, short)]
         release: bool,
@@ -95,6 +165,49 @@ enum Commands {
         #[clap(long)]
         wef_path: Option<PathBuf>,
     },
+    /// Check the CEF installation and the application built with Wef
+    Doctor {
+        /// Also check the CEF framework and the helper processes in the
+        /// application, e.g. `target/release/app.app` or `target/release/app`
+        #[clap(long, value_name = "PATH")]
+        app: Option<PathBuf>,
+        /// The platform of the CEF in CEF_ROOT and the application
+        #[clap(long, default_value = "auto")]
+        platform: CefBuildsPlatform,
+        /// Print the report in JSON
+        #[clap(long)]
+        json: bool,
+    },
+    /// List the downloaded CEF versions
+    List {
+        /// The directory of the downloaded archives
+        ///
+        /// If not specified, use `wef/cef` in the user's cache directory
+        #[clap(long, env = "WEF_CACHE_DIR")]
+        cache_dir: Option<PathBuf>,
+        /// Print the list in JSON
+        #[clap(long)]
+        json: bool,
+    },
+    /// Remove the downloaded CEF versions
+    Clean {
+        /// The versions to remove
+        ///
+        /// If not specified, remove all the versions except the one installed
+        /// in CEF_ROOT, fails if no CEF is installed
+        versions: Vec<String>,
+        /// Remove all the versions, including the installed one
+        #[clap(long, conflicts_with = "versions")]
+        all: bool,
+        /// The directory of the downloaded archives
+        ///
+        /// If not specified, use `wef/cef` in the user's cache directory
+        #[clap(long, env = "WEF_CACHE_DIR")]
+        cache_dir: Option<PathBuf>,
+        /// Print the removed versions in JSON
+        #[clap(long)]
+        json: bool,
+    },
 }
 
 /// Wef CLI tool
@@ -126,8 +239,21 @@ fn main() {
                     version,
                     platform,
                     force,
+                    mirror,
+                    from_archive,
+                    cache_dir,
+                    no_verify,
                 },
-        } => commands::init(path, version, platform, force),
+        } => commands::init(
+            path,
+            version,
+            platform,
+            force,
+            mirror,
+            from_archive,
+            cache_dir,
+            !no_verify,
+        ),
         WefCommands::Wef {
             commands:
                 Commands::Build {
@@ -138,6 +264,7 @@ fn main() {
                     wef_version,
                     wef_path,
                     bundle_type,
+                    linux_bundle,
                 },
         } => commands::build(
             package,
@@ -147,6 +274,7 @@ fn main() {
             wef_version.as_deref(),
             wef_path.as_deref(),
             bundle_type.as_deref(),
+            linux_bundle,
         )
         .map(|_| ()),
         WefCommands::Wef {
@@ -169,6 +297,28 @@ fn main() {
             wef_path.as_deref(),
             args,
         ),
+        WefCommands::Wef {
+            commands:
+                Commands::Types {
+                    out,
+                    schema,
+                    package,
+                    bin,
+                    example,
+                    release,
+                    wef_version,
+                    wef_path,
+                },
+        } => commands::types(
+            package,
+            bin,
+            example,
+            release,
+            wef_version.as_deref(),
+            wef_path.as_deref(),
+            out,
+            schema,
+        ),
         WefCommands::Wef {
             commands:
                 Commands::AddFramework {
@@ -177,8 +327,29 @@ fn main() {
                     force,
                     wef_version,
                     wef_path,
+                    platform,
+                },
+        } => commands::add_framework(app_path, release, force, wef_version, wef_path, platform),
+        WefCommands::Wef {
+            commands:
+                Commands::Doctor {
+                    app,
+                    platform,
+                    json,
+                },
+        } => commands::doctor(app, platform, json),
+        WefCommands::Wef {
+            commands: Commands::List { cache_dir, json },
+        } => commands::list(cache_dir, json),
+        WefCommands::Wef {
+            commands:
+                Commands::Clean {
+                    versions,
+                    all,
+                    cache_dir,
+                    json,
                 },
-        } => commands::add_framework(app_path, release, force, wef_version, wef_path),
+        } => commands::clean(cache_dir, versions, all, json),
     };
 
     if let Err(err) = res {